@@ -3,16 +3,24 @@
 #![allow(clippy::redundant_closure)]
 #![allow(clippy::double_ended_iterator_last)]
 
-use std::{backtrace::Backtrace, borrow::Cow, num::NonZeroUsize, panic, path::PathBuf, sync::Arc};
+use std::{
+    backtrace::Backtrace, borrow::Cow, num::NonZeroUsize, os::unix::process::CommandExt, panic,
+    path::PathBuf, process::Command, sync::Arc
+};
 
-use clap::{Parser, command};
+use clap::{Parser, builder::PossibleValuesParser, command};
 use flexi_logger::{Age, Cleanup, Criterion, FileSpec, LogSpecBuilder, Logger, Naming};
 use hydebar_core::{
     adapters::hyprland_client::HyprlandClient,
-    config::{ConfigLoadError, ConfigManager, get_config},
-    event_bus::EventBus
+    config::{
+        ConfigLoadError, ConfigManager,
+        examples::{EXAMPLE_NAMES, example},
+        get_config
+    },
+    event_bus::EventBus,
+    utils::font
 };
-use hydebar_gui::{App, get_log_spec};
+use hydebar_gui::{App, get_log_spec, restart_requested};
 use hydebar_proto::ports::hyprland::HyprlandPort;
 use iced::Font;
 use log::{debug, error};
@@ -24,7 +32,10 @@ const ICON_FONT: &[u8] = include_bytes!("../../../assets/SymbolsNerdFont-Regular
 #[command(version, about, long_about = None)]
 struct Args {
     #[arg(short, long, value_parser = clap::value_parser!(PathBuf))]
-    config_path: Option<PathBuf>
+    config_path: Option<PathBuf>,
+    /// Print a bundled example configuration and exit
+    #[arg(long, value_name = "NAME", value_parser = PossibleValuesParser::new(EXAMPLE_NAMES))]
+    example:     Option<String>
 }
 
 #[derive(Debug)]
@@ -32,7 +43,8 @@ enum MainError {
     Logger(flexi_logger::FlexiLoggerError),
     Config(ConfigLoadError),
     Iced(iced::Error),
-    BusCapacity
+    BusCapacity,
+    Restart(std::io::Error)
 }
 
 impl std::fmt::Display for MainError {
@@ -41,7 +53,8 @@ impl std::fmt::Display for MainError {
             Self::Logger(err) => write!(f, "failed to initialize logger: {}", err),
             Self::Config(err) => write!(f, "configuration error: {}", err),
             Self::Iced(err) => write!(f, "iced runtime error: {}", err),
-            Self::BusCapacity => write!(f, "invalid event bus capacity")
+            Self::BusCapacity => write!(f, "invalid event bus capacity"),
+            Self::Restart(err) => write!(f, "failed to restart: {}", err)
         }
     }
 }
@@ -52,7 +65,8 @@ impl std::error::Error for MainError {
             Self::Logger(err) => Some(err),
             Self::Config(err) => Some(err),
             Self::Iced(err) => Some(err),
-            Self::BusCapacity => None
+            Self::BusCapacity => None,
+            Self::Restart(err) => Some(err)
         }
     }
 }
@@ -82,6 +96,12 @@ async fn main() -> Result<(), MainError> {
 
 async fn run() -> Result<(), MainError> {
     let args = Args::parse();
+
+    if let Some(source) = args.example.as_deref().and_then(example) {
+        print!("{source}");
+        return Ok(());
+    }
+
     debug!("args: {args:?}");
 
     let logger = Logger::with(
@@ -114,7 +134,7 @@ async fn run() -> Result<(), MainError> {
     logger.set_new_spec(get_log_spec(&config.log_level));
 
     let font = match config.appearance.font_name {
-        Some(ref font_name) => Font::with_name(Box::leak(font_name.clone().into_boxed_str())),
+        Some(ref font_name) => font::named(font_name),
         None => Font::DEFAULT
     };
 
@@ -143,5 +163,24 @@ async fn run() -> Result<(), MainError> {
             runtime_handle,
             bus_receiver
         )))
-        .map_err(MainError::from)
+        .map_err(MainError::from)?;
+
+    // The app, and with it the logger, has been dropped at this point, so the
+    // new process starts from a clean slate.
+    if restart_requested() {
+        return Err(MainError::Restart(reexec()));
+    }
+
+    Ok(())
+}
+
+/// Replace the current process with a fresh instance started with the same
+/// arguments. Only returns when the exec failed.
+fn reexec() -> std::io::Error {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(err) => return err
+    };
+
+    Command::new(exe).args(std::env::args_os().skip(1)).exec()
 }
@@ -1,5 +1,6 @@
 mod config;
 mod listeners;
+mod socket;
 mod sync_ops;
 mod util;
 
@@ -7,22 +8,25 @@ use std::sync::Arc;
 
 use hydebar_proto::ports::hyprland::{
     HyprlandError, HyprlandEventStream, HyprlandKeyboardEvent, HyprlandKeyboardState,
-    HyprlandMonitorInfo, HyprlandMonitorSelector, HyprlandPort, HyprlandWindowEvent,
-    HyprlandWindowInfo, HyprlandWorkspaceEvent, HyprlandWorkspaceInfo, HyprlandWorkspaceSelector,
+    HyprlandLayoutState, HyprlandLockState, HyprlandMonitorInfo, HyprlandMonitorSelector,
+    HyprlandPort, HyprlandWindowEvent, HyprlandWindowInfo, HyprlandWindowState,
+    HyprlandWorkspaceEvent, HyprlandWorkspaceInfo, HyprlandWorkspaceSelector,
     HyprlandWorkspaceSnapshot
 };
 use hyprland::{
     ctl::switch_xkb_layout::SwitchXKBLayoutCmdTypes,
-    data::{Client, Devices, Monitors, Workspace, Workspaces},
+    data::{Client, Devices, FullscreenState, Monitors, Workspace, Workspaces},
     dispatch::{Dispatch, DispatchType, MonitorIdentifier, WorkspaceIdentifierWithSpecial},
-    keyword::Keyword,
+    keyword::{Keyword, OptionValue},
     shared::{HyprData, HyprDataActive, HyprDataActiveOptional}
 };
+use log::info;
 
 pub use self::config::HyprlandClientConfig;
 use self::{
     listeners::{spawn_keyboard_listener, spawn_window_listener, spawn_workspace_listener},
-    sync_ops::execute_with_retry
+    sync_ops::execute_with_retry,
+    util::scroll_lock_led
 };
 
 const WORKSPACE_SNAPSHOT_OP: &str = "workspace_snapshot";
@@ -30,19 +34,25 @@ const ACTIVE_WINDOW_OP: &str = "active_window";
 const CHANGE_WORKSPACE_OP: &str = "change_workspace";
 const TOGGLE_SPECIAL_OP: &str = "toggle_special_workspace";
 const KEYBOARD_STATE_OP: &str = "keyboard_state";
+const LOCK_STATE_OP: &str = "lock_state";
 const SWITCH_LAYOUT_OP: &str = "switch_keyboard_layout";
+const LAYOUT_STATE_OP: &str = "layout_state";
+const SET_GAPS_OP: &str = "set_gaps";
 
 /// [`HyprlandPort`] implementation backed by the `hyprland-rs` crate.
+///
+/// Whether Hyprland runs is checked once on construction. Without it every
+/// request fails right away and event streams stay silent, so hydebar keeps
+/// working under other compositors.
 #[derive(Clone, Debug)]
 pub struct HyprlandClient {
-    config: Arc<HyprlandClientConfig>
+    config:    Arc<HyprlandClientConfig>,
+    available: bool
 }
 
 impl Default for HyprlandClient {
     fn default() -> Self {
-        Self {
-            config: Arc::new(HyprlandClientConfig::default())
-        }
+        Self::with_config(HyprlandClientConfig::default())
     }
 }
 
@@ -56,11 +66,30 @@ impl HyprlandClient {
 
     /// Construct a [`HyprlandClient`] with the provided configuration.
     pub fn with_config(config: HyprlandClientConfig) -> Self {
+        let available = socket::event_socket().is_some();
+        if !available {
+            info!(
+                target: "hydebar::hyprland",
+                "Hyprland is not running, its modules are disabled"
+            );
+        }
+
         Self {
-            config: Arc::new(config)
+            config: Arc::new(config),
+            available
         }
     }
 
+    fn not_running(operation: &'static str) -> HyprlandError {
+        HyprlandError::message(operation, "Hyprland is not running")
+    }
+
+    /// Stream that never yields, standing in for the events of an absent
+    /// Hyprland instead of a listener that keeps reconnecting.
+    fn silent<E: Send + 'static>() -> HyprlandEventStream<E> {
+        Box::pin(tokio_stream::pending())
+    }
+
     pub(crate) fn backend_error<E>(operation: &'static str, err: E) -> HyprlandError
     where
         E: std::error::Error + Send + Sync + 'static
@@ -80,13 +109,17 @@ impl HyprlandClient {
         R: Send + 'static,
         F: Fn() -> Result<R, HyprlandError> + Send + Sync + 'static
     {
+        if !self.available {
+            return Err(Self::not_running(operation));
+        }
+
         execute_with_retry(&self.config, operation, func)
     }
 
     fn spawn_window_listener(
         &self
     ) -> Result<HyprlandEventStream<HyprlandWindowEvent>, HyprlandError> {
-        spawn_window_listener(self.config.clone())
+        spawn_window_listener(self.clone(), self.config.clone())
     }
 
     fn spawn_workspace_listener(
@@ -103,19 +136,35 @@ impl HyprlandClient {
 }
 
 impl HyprlandPort for HyprlandClient {
+    fn is_available(&self) -> bool {
+        self.available
+    }
+
     fn window_events(&self) -> Result<HyprlandEventStream<HyprlandWindowEvent>, HyprlandError> {
+        if !self.available {
+            return Ok(Self::silent());
+        }
+
         self.spawn_window_listener()
     }
 
     fn workspace_events(
         &self
     ) -> Result<HyprlandEventStream<HyprlandWorkspaceEvent>, HyprlandError> {
+        if !self.available {
+            return Ok(Self::silent());
+        }
+
         self.spawn_workspace_listener()
     }
 
     fn keyboard_events(
         &self
     ) -> Result<HyprlandEventStream<HyprlandKeyboardEvent>, HyprlandError> {
+        if !self.available {
+            return Ok(Self::silent());
+        }
+
         self.spawn_keyboard_listener()
     }
 
@@ -124,9 +173,20 @@ impl HyprlandPort for HyprlandClient {
             Client::get_active()
                 .map_err(|err| HyprlandClient::backend_error(ACTIVE_WINDOW_OP, err))
                 .map(|maybe_client| {
-                    maybe_client.map(|client| HyprlandWindowInfo {
-                        title: client.title,
-                        class: client.class
+                    maybe_client.map(|client| {
+                        let state = if client.fullscreen != FullscreenState::None {
+                            HyprlandWindowState::Fullscreen
+                        } else if client.floating {
+                            HyprlandWindowState::Floating
+                        } else {
+                            HyprlandWindowState::Tiled
+                        };
+
+                        HyprlandWindowInfo {
+                            title: client.title,
+                            class: client.class,
+                            state
+                        }
                     })
                 })
         })
@@ -146,7 +206,8 @@ impl HyprlandPort for HyprlandClient {
                 .map(|monitor| HyprlandMonitorInfo {
                     id:                   i32::try_from(monitor.id).unwrap_or(i32::MAX),
                     name:                 monitor.name,
-                    special_workspace_id: Some(monitor.special_workspace.id)
+                    special_workspace_id: Some(monitor.special_workspace.id),
+                    focused:              monitor.focused
                 })
                 .collect();
 
@@ -219,10 +280,8 @@ impl HyprlandPort for HyprlandClient {
 
             let devices = Devices::get()
                 .map_err(|err| HyprlandClient::backend_error(KEYBOARD_STATE_OP, err))?;
-            let active_layout = devices
-                .keyboards
-                .iter()
-                .find(|keyboard| keyboard.main)
+            let main = devices.keyboards.iter().find(|keyboard| keyboard.main);
+            let active_layout = main
                 .map(|keyboard| keyboard.active_keymap.to_string())
                 .unwrap_or_else(|| "unknown".to_string());
 
@@ -234,10 +293,52 @@ impl HyprlandPort for HyprlandClient {
         })
     }
 
+    fn lock_state(&self) -> Result<HyprlandLockState, HyprlandError> {
+        self.execute_with_retry(LOCK_STATE_OP, || {
+            let devices =
+                Devices::get().map_err(|err| HyprlandClient::backend_error(LOCK_STATE_OP, err))?;
+            let main = devices.keyboards.iter().find(|keyboard| keyboard.main);
+
+            Ok(HyprlandLockState {
+                caps_lock:   main.is_some_and(|keyboard| keyboard.caps_lock),
+                num_lock:    main.is_some_and(|keyboard| keyboard.num_lock),
+                scroll_lock: scroll_lock_led()
+            })
+        })
+    }
+
     fn switch_keyboard_layout(&self) -> Result<(), HyprlandError> {
         self.execute_with_retry(SWITCH_LAYOUT_OP, || {
             hyprland::ctl::switch_xkb_layout::call("all", SwitchXKBLayoutCmdTypes::Next)
                 .map_err(|err| HyprlandClient::backend_error(SWITCH_LAYOUT_OP, err))
         })
     }
+
+    fn layout_state(&self) -> Result<HyprlandLayoutState, HyprlandError> {
+        self.execute_with_retry(LAYOUT_STATE_OP, || {
+            let keyword = |key: &str| {
+                Keyword::get(key)
+                    .map(|keyword| keyword.value.to_string())
+                    .map_err(|err| HyprlandClient::backend_error(LAYOUT_STATE_OP, err))
+            };
+
+            Ok(HyprlandLayoutState {
+                layout:   keyword("general:layout")?,
+                gaps_in:  keyword("general:gaps_in")?,
+                gaps_out: keyword("general:gaps_out")?
+            })
+        })
+    }
+
+    fn set_gaps(&self, gaps_in: &str, gaps_out: &str) -> Result<(), HyprlandError> {
+        let gaps_in = gaps_in.to_string();
+        let gaps_out = gaps_out.to_string();
+        self.execute_with_retry(SET_GAPS_OP, move || {
+            Keyword::set("general:gaps_in", OptionValue::String(gaps_in.clone()))
+                .and_then(|_| {
+                    Keyword::set("general:gaps_out", OptionValue::String(gaps_out.clone()))
+                })
+                .map_err(|err| HyprlandClient::backend_error(SET_GAPS_OP, err))
+        })
+    }
 }
@@ -16,7 +16,38 @@ const WINDOW_EVENTS_OP: &str = "window_events";
 const WORKSPACE_EVENTS_OP: &str = "workspace_events";
 const KEYBOARD_EVENTS_OP: &str = "keyboard_events";
 
+/// Publish [`HyprlandWindowEvent::ActiveWindowChanged`] with the state of the
+/// currently focused window.
+async fn send_active_window_changed(
+    tx: &mpsc::Sender<Result<HyprlandWindowEvent, HyprlandError>>,
+    client: &HyprlandClient
+) {
+    let state = match client.active_window() {
+        Ok(window) => window.map(|window| window.state),
+        Err(err) => {
+            warn!(
+                target: "hydebar::hyprland",
+                "failed to query active window state (operation={}, error={err})",
+                WINDOW_EVENTS_OP
+            );
+            None
+        }
+    };
+
+    if let Err(err) = tx
+        .send(Ok(HyprlandWindowEvent::ActiveWindowChanged(state)))
+        .await
+    {
+        warn!(
+            target: "hydebar::hyprland",
+            "window event receiver dropped (operation={}, error={err})",
+            WINDOW_EVENTS_OP
+        );
+    }
+}
+
 pub(crate) fn spawn_window_listener(
+    client: HyprlandClient,
     config: Arc<HyprlandClientConfig>
 ) -> Result<HyprlandEventStream<HyprlandWindowEvent>, HyprlandError> {
     let handle =
@@ -32,18 +63,31 @@ pub(crate) fn spawn_window_listener(
 
             listener.add_active_window_changed_handler({
                 let tx = tx.clone();
+                let client = client.clone();
                 move |_| {
                     let tx = tx.clone();
-                    Box::pin(async move {
-                        if let Err(err) = tx.send(Ok(HyprlandWindowEvent::ActiveWindowChanged)).await
-                        {
-                            warn!(
-                                target: "hydebar::hyprland",
-                                "window event receiver dropped (operation={}, error={err})",
-                                WINDOW_EVENTS_OP
-                            );
-                        }
-                    })
+                    let client = client.clone();
+                    Box::pin(async move { send_active_window_changed(&tx, &client).await })
+                }
+            });
+
+            listener.add_float_state_changed_handler({
+                let tx = tx.clone();
+                let client = client.clone();
+                move |_| {
+                    let tx = tx.clone();
+                    let client = client.clone();
+                    Box::pin(async move { send_active_window_changed(&tx, &client).await })
+                }
+            });
+
+            listener.add_fullscreen_state_changed_handler({
+                let tx = tx.clone();
+                let client = client.clone();
+                move |_| {
+                    let tx = tx.clone();
+                    let client = client.clone();
+                    Box::pin(async move { send_active_window_changed(&tx, &client).await })
                 }
             });
 
@@ -0,0 +1,63 @@
+use std::{
+    env,
+    path::{Path, PathBuf}
+};
+
+/// Directory older Hyprland releases keep their sockets in.
+const LEGACY_SOCKET_DIR: &str = "/tmp/hypr";
+
+/// Event socket of the Hyprland instance hydebar runs under, or `None` when
+/// no Hyprland is running.
+pub(crate) fn event_socket() -> Option<PathBuf> {
+    let runtime_dir = env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from);
+    let signature = env::var("HYPRLAND_INSTANCE_SIGNATURE").ok();
+
+    find_event_socket(runtime_dir.as_deref(), signature.as_deref())
+}
+
+/// Look for `.socket2.sock` of the instance `signature`, first under
+/// `$XDG_RUNTIME_DIR/hypr` and then in the legacy location.
+fn find_event_socket(runtime_dir: Option<&Path>, signature: Option<&str>) -> Option<PathBuf> {
+    let signature = signature.filter(|signature| !signature.is_empty())?;
+
+    runtime_dir
+        .map(|dir| dir.join("hypr"))
+        .into_iter()
+        .chain([PathBuf::from(LEGACY_SOCKET_DIR)])
+        .map(|dir| dir.join(signature).join(".socket2.sock"))
+        .find(|socket| socket.exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::find_event_socket;
+
+    #[test]
+    fn finds_socket_of_the_running_instance() {
+        let runtime = TempDir::new().expect("temp dir");
+        let instance = runtime.path().join("hypr").join("abc_123");
+        fs::create_dir_all(&instance).expect("create instance dir");
+        fs::write(instance.join(".socket2.sock"), "").expect("create socket");
+
+        assert_eq!(
+            find_event_socket(Some(runtime.path()), Some("abc_123")),
+            Some(instance.join(".socket2.sock"))
+        );
+    }
+
+    #[test]
+    fn no_socket_without_a_running_instance() {
+        let runtime = TempDir::new().expect("temp dir");
+
+        assert_eq!(find_event_socket(Some(runtime.path()), None), None);
+        assert_eq!(find_event_socket(Some(runtime.path()), Some("")), None);
+        assert_eq!(
+            find_event_socket(Some(runtime.path()), Some("hydebar-test-missing")),
+            None
+        );
+    }
+}
@@ -1,7 +1,10 @@
-use std::time::Duration;
+use std::{fs, time::Duration};
 
 use tokio::time::sleep;
 
+/// Where the kernel exposes keyboard LEDs, named `inputN::<led>`.
+const LEDS_DIR: &str = "/sys/class/leds";
+
 /// Compute the delay to wait before retrying an operation using linear backoff.
 ///
 /// The returned duration is `base_backoff * attempt` with saturating
@@ -36,6 +39,23 @@ pub(crate) async fn sleep_with_backoff(backoff: Duration) {
     sleep(backoff).await;
 }
 
+/// Whether any keyboard lights its scroll lock LED. Hyprland does not report
+/// scroll lock with the other modifier locks.
+pub(crate) fn scroll_lock_led() -> bool {
+    let Ok(entries) = fs::read_dir(LEDS_DIR) else {
+        return false;
+    };
+
+    entries.filter_map(Result::ok).any(|entry| {
+        entry
+            .file_name()
+            .to_string_lossy()
+            .ends_with("::scrolllock")
+            && fs::read_to_string(entry.path().join("brightness"))
+                .is_ok_and(|brightness| brightness.trim() != "0")
+    })
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use std::time::Duration;
@@ -1,8 +1,10 @@
 use iced::{
-    Font,
-    widget::{Text, text}
+    Element, Font,
+    widget::{Row, Text, text}
 };
 
+use crate::config::IconPosition;
+
 #[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 pub enum Icons {
     #[default]
@@ -21,6 +23,7 @@ pub enum Icons {
     Speaker1,
     Speaker2,
     Speaker3,
+    SpeakerMuted,
     Headphones0,
     Headphones1,
     Headset,
@@ -45,14 +48,18 @@ pub enum Icons {
     WifiLock3,
     WifiLock4,
     WifiLock5,
+    WifiAlert,
     Ethernet,
     Vpn,
+    WireGuard,
     Bluetooth,
     PowerSaver,
     Balanced,
     Performance,
     EyeOpened,
     EyeClosed,
+    CoffeeFilled,
+    CoffeeOutline,
     Lock,
     Power,
     Reboot,
@@ -67,16 +74,33 @@ pub enum Icons {
     Close,
     Airplane,
     Webcam,
+    Location,
+    Shield,
     SkipPrevious,
     Play,
     Pause,
     SkipNext,
+    Stop,
+    Shuffle,
+    ShuffleDisabled,
+    Repeat,
+    RepeatOnce,
+    RepeatOff,
     MusicNote,
     Drive,
     IpAddress,
     DownloadSpeed,
     UploadSpeed,
-    Copy
+    Copy,
+    CapsLock,
+    NumLock,
+    ScrollLock,
+    WindowTiled,
+    WindowFloating,
+    WindowFullscreen,
+    Bug,
+    Alert,
+    KeyboardBacklight
 }
 
 impl From<Icons> for &'static str {
@@ -97,6 +121,7 @@ impl From<Icons> for &'static str {
             Icons::Speaker1 => "󰕿",
             Icons::Speaker2 => "󰖀",
             Icons::Speaker3 => "󰕾",
+            Icons::SpeakerMuted => "󰝟",
             Icons::Headphones0 => "󰟎",
             Icons::Headphones1 => "󰋋",
             Icons::Headset => "󰋎",
@@ -121,14 +146,18 @@ impl From<Icons> for &'static str {
             Icons::WifiLock3 => "󰤤",
             Icons::WifiLock4 => "󰤧",
             Icons::WifiLock5 => "󰤪",
+            Icons::WifiAlert => "󰤩",
             Icons::Ethernet => "󰈀",
             Icons::Vpn => "󰖂",
+            Icons::WireGuard => "󰌆",
             Icons::Bluetooth => "󰂯",
             Icons::PowerSaver => "󰾆",
             Icons::Balanced => "󰾅",
             Icons::Performance => "󰓅",
             Icons::EyeOpened => "󰈈",
             Icons::EyeClosed => "󰈉",
+            Icons::CoffeeFilled => "󰅶",
+            Icons::CoffeeOutline => "󰛊",
             Icons::Lock => "󰌾",
             Icons::Power => "󰐥",
             Icons::Reboot => "󰑐",
@@ -143,16 +172,33 @@ impl From<Icons> for &'static str {
             Icons::Close => "󰅖",
             Icons::Airplane => "󰀝",
             Icons::Webcam => "",
+            Icons::Location => "󰍎",
+            Icons::Shield => "󰒘",
             Icons::SkipPrevious => "󰒮",
             Icons::Play => "󰐊",
             Icons::Pause => "󰏤",
             Icons::SkipNext => "󰒭",
+            Icons::Stop => "󰓛",
+            Icons::Shuffle => "󰒝",
+            Icons::ShuffleDisabled => "󰒞",
+            Icons::Repeat => "󰑖",
+            Icons::RepeatOnce => "󰑘",
+            Icons::RepeatOff => "󰑗",
             Icons::MusicNote => "󰎇",
             Icons::Drive => "󰋊",
             Icons::IpAddress => "󰩠",
             Icons::DownloadSpeed => "󰛴",
             Icons::UploadSpeed => "󰛶",
-            Icons::Copy => "󰆏"
+            Icons::Copy => "󰆏",
+            Icons::CapsLock => "󰘳",
+            Icons::NumLock => "󰎠",
+            Icons::ScrollLock => "󰓢",
+            Icons::WindowTiled => "󰕰",
+            Icons::WindowFloating => "󰖲",
+            Icons::WindowFullscreen => "󰊓",
+            Icons::Bug => "",
+            Icons::Alert => "󰀦",
+            Icons::KeyboardBacklight => "󰌌"
         }
     }
 }
@@ -165,3 +211,18 @@ pub fn icon<'a>(r#type: Icons) -> Text<'a> {
 pub fn icon_raw<'a>(s: String) -> Text<'a> {
     text(s).font(Font::with_name("Symbols Nerd Font"))
 }
+
+/// Row of `glyph` and `label`, ordered by `position`.
+pub fn icon_with_label<'a, M: 'a>(
+    glyph: impl Into<Element<'a, M>>,
+    label: Option<impl Into<Element<'a, M>>>,
+    position: IconPosition
+) -> Row<'a, M> {
+    let glyph = glyph.into();
+    let label = label.map(Into::into);
+
+    match position {
+        IconPosition::Before => Row::new().push(glyph).push_maybe(label),
+        IconPosition::After => Row::new().push_maybe(label).push(glyph)
+    }
+}
@@ -6,7 +6,10 @@ use std::{
 
 pub use hydebar_proto::config::*;
 
+pub mod diff;
+pub mod examples;
 pub mod manager;
+pub mod report;
 pub mod watch;
 
 use log::{info, warn};
@@ -95,6 +98,10 @@ pub(crate) enum ConfigReadError {
     Parse {
         path:   PathBuf,
         source: toml::de::Error
+    },
+    Theme {
+        path:   PathBuf,
+        reason: String
     }
 }
 
@@ -123,6 +130,17 @@ impl std::fmt::Display for ConfigReadError {
                     source
                 )
             }
+            Self::Theme {
+                path,
+                reason
+            } => {
+                write!(
+                    f,
+                    "failed to parse theme file '{}': {}",
+                    path.display(),
+                    reason
+                )
+            }
         }
     }
 }
@@ -135,7 +153,10 @@ impl std::error::Error for ConfigReadError {
             } => Some(source),
             Self::Parse {
                 source, ..
-            } => Some(source)
+            } => Some(source),
+            Self::Theme {
+                ..
+            } => None
         }
     }
 }
@@ -195,7 +216,16 @@ fn ensure_parent_exists(path: &Path) -> Result<(), ConfigLoadError> {
     Ok(())
 }
 
-pub(crate) fn read_config(path: &Path) -> Result<Config, ConfigReadError> {
+/// Config read from disk. A theme file that cannot be loaded does not fail
+/// the read: the config keeps the default appearance and the problem is
+/// reported next to it.
+#[derive(Debug)]
+pub(crate) struct LoadedConfig {
+    pub config:      Config,
+    pub theme_error: Option<ConfigReadError>
+}
+
+pub(crate) fn read_config(path: &Path) -> Result<LoadedConfig, ConfigReadError> {
     let mut content = String::new();
     File::open(path)
         .and_then(|mut file| file.read_to_string(&mut content))
@@ -204,19 +234,89 @@ pub(crate) fn read_config(path: &Path) -> Result<Config, ConfigReadError> {
             source
         })?;
 
-    toml::from_str(&content).map_err(|source| ConfigReadError::Parse {
+    let mut config: Config =
+        toml::from_str(&content).map_err(|source| ConfigReadError::Parse {
+            path: path.to_path_buf(),
+            source
+        })?;
+
+    let theme_error = match &config.theme_file {
+        Some(theme_file) => match read_theme_file(&resolve_theme_path(path, theme_file)) {
+            Ok(appearance) => {
+                config.appearance = appearance;
+                None
+            }
+            Err(err) => {
+                config.appearance = Appearance::default();
+                Some(err)
+            }
+        },
+        None => None
+    };
+
+    Ok(LoadedConfig {
+        config,
+        theme_error
+    })
+}
+
+/// Location of `theme_file`, expanding a leading `~` and resolving relative
+/// paths against the directory of the config file at `config_path`.
+fn resolve_theme_path(config_path: &Path, theme_file: &Path) -> PathBuf {
+    let theme_file = PathBuf::from(shellexpand::tilde(&theme_file.to_string_lossy()).as_ref());
+
+    match config_path.parent() {
+        Some(dir) if theme_file.is_relative() => dir.join(theme_file),
+        _ => theme_file
+    }
+}
+
+/// Appearance defined by a theme file. Files ending in `.json` are read as
+/// JSON and may hold just a preset name, anything else is read as TOML.
+fn read_theme_file(path: &Path) -> Result<Appearance, ConfigReadError> {
+    let content = fs::read_to_string(path).map_err(|source| ConfigReadError::Read {
         path: path.to_path_buf(),
         source
+    })?;
+
+    let appearance = if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str::<serde_json::Value>(&content)
+            .and_then(deserialize_theme_or_appearance)
+            .map_err(|err| err.to_string())
+    } else {
+        toml::from_str::<toml::Value>(&content)
+            .and_then(deserialize_theme_or_appearance)
+            .map_err(|err| err.to_string())
+    };
+
+    appearance.map_err(|reason| ConfigReadError::Theme {
+        path: path.to_path_buf(),
+        reason
     })
 }
 
+/// Log the [`ConfigWarning`]s of a configuration that is about to be applied.
+pub(crate) fn log_warnings(config: &Config) {
+    for warning in config.warnings() {
+        warn!("{warning}");
+    }
+}
+
 fn load_config_or_default(path: &Path) -> Config {
     info!("Decoding config file {path:?}");
 
     match read_config(path) {
-        Ok(config) => match config.validate() {
+        Ok(LoadedConfig {
+            config,
+            theme_error
+        }) => match config.validate() {
             Ok(()) => {
                 info!("Config file loaded successfully");
+                if let Some(err) = theme_error {
+                    warn!("{err}");
+                    warn!("Falling back to the default appearance");
+                }
+                log_warnings(&config);
                 config
             }
             Err(err) => {
@@ -237,6 +337,7 @@ fn load_config_or_default(path: &Path) -> Config {
 mod tests {
     use std::fs;
 
+    use hex_color::HexColor;
     use tempfile::TempDir;
 
     use super::*;
@@ -257,6 +358,71 @@ mod tests {
         assert_eq!(config.position, default.position);
     }
 
+    #[test]
+    fn theme_file_replaces_appearance() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let config_path = temp_dir.path().join("config.toml");
+        fs::write(&config_path, "theme_file = \"palette.toml\"\n").expect("write config");
+        fs::write(
+            temp_dir.path().join("palette.toml"),
+            "background_color = \"#101010\"\nprimary_color = \"#ff8800\"\n"
+        )
+        .expect("write theme");
+
+        let loaded = read_config(&config_path).expect("config with theme file");
+        assert!(loaded.theme_error.is_none());
+        let config = loaded.config;
+
+        assert_eq!(
+            config.appearance.background_color,
+            AppearanceColor::Simple(HexColor::rgb(16, 16, 16))
+        );
+        assert_eq!(
+            config.appearance.primary_color,
+            AppearanceColor::Simple(HexColor::rgb(255, 136, 0))
+        );
+        assert_eq!(
+            config.appearance.text_color,
+            Appearance::default().text_color
+        );
+    }
+
+    #[test]
+    fn json_theme_file_may_name_a_preset() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let theme_path = temp_dir.path().join("theme.json");
+        fs::write(&theme_path, "\"dracula\"").expect("write theme");
+
+        let appearance = read_theme_file(&theme_path).expect("preset theme");
+
+        assert_eq!(appearance, PresetTheme::Dracula.to_appearance());
+    }
+
+    #[test]
+    fn broken_theme_file_keeps_the_config_with_the_default_appearance() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let config_path = temp_dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "theme_file = \"palette.toml\"\nposition = \"Bottom\"\n"
+        )
+        .expect("write config");
+        fs::write(temp_dir.path().join("palette.toml"), "primary_color = 5\n")
+            .expect("write theme");
+
+        let loaded = read_config(&config_path).expect("config without its theme");
+
+        assert!(matches!(
+            loaded.theme_error,
+            Some(ConfigReadError::Theme { .. })
+        ));
+        assert_eq!(loaded.config.appearance, Appearance::default());
+
+        let (config, _) = get_config(Some(config_path)).expect("get_config should succeed");
+        assert_eq!(config.position, Position::Bottom);
+        assert_eq!(config.appearance, Appearance::default());
+    }
+
     #[test]
     fn get_config_errors_when_file_missing() {
         let temp_dir = TempDir::new().expect("failed to create temp dir");
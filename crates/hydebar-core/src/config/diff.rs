@@ -0,0 +1,371 @@
+//! Human-readable summaries of configuration changes.
+//!
+//! Like the impact computed by the [manager](super::manager), the diff
+//! compares the typed configuration field by field. Module sections are
+//! broken down into their own fields, anything else is reported as a whole.
+
+use std::fmt::Debug;
+
+use super::Config;
+
+/// Maximum number of characters rendered for a single value.
+const MAX_VALUE_LEN: usize = 60;
+
+/// Record every listed top-level field that differs.
+macro_rules! diff_fields {
+    ($changes:ident, $previous:expr, $next:expr, [$($field:ident),+ $(,)?]) => {
+        $(
+            record(
+                &mut $changes,
+                stringify!($field),
+                &$previous.$field,
+                &$next.$field
+            );
+        )+
+    };
+}
+
+/// Record every listed field of a section that differs as `section.field`.
+/// A change in a field missing from the list is reported for the whole
+/// section instead of being dropped.
+macro_rules! diff_section {
+    ($changes:ident, $previous:expr, $next:expr, $section:ident: [$($field:ident),+ $(,)?]) => {
+        if $previous.$section != $next.$section {
+            let recorded = $changes.len();
+            $(
+                record(
+                    &mut $changes,
+                    concat!(stringify!($section), ".", stringify!($field)),
+                    &$previous.$section.$field,
+                    &$next.$section.$field
+                );
+            )+
+            if $changes.len() == recorded {
+                record(
+                    &mut $changes,
+                    stringify!($section),
+                    &$previous.$section,
+                    &$next.$section
+                );
+            }
+        }
+    };
+}
+
+/// Describe every field that differs between two configurations.
+///
+/// Each entry has the form `path: old → new`, for example
+/// `appearance.opacity: 0.9 → 0.95`, in the order the fields are declared.
+pub fn describe_changes(previous: &Config, next: &Config) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    if previous == next {
+        return changes;
+    }
+
+    diff_fields!(changes, previous, next, [log_level, position, outputs]);
+    diff_section!(changes, previous, next, modules: [left, center, right]);
+    diff_fields!(
+        changes,
+        previous,
+        next,
+        [auxiliary_bars, reserved_height, app_launcher_cmd]
+    );
+    diff_section!(changes, previous, next, app_launcher: [icon, label]);
+    diff_fields!(
+        changes,
+        previous,
+        next,
+        [
+            custom_modules,
+            clipboard_cmd,
+            command_shell,
+            command_cwd,
+            command_debounce_ms,
+            locale,
+            updates,
+            command_output
+        ]
+    );
+    diff_section!(
+        changes,
+        previous,
+        next,
+        workspaces: [
+            visibility_mode,
+            enable_workspace_filling,
+            max_workspaces,
+            enable_scroll,
+            name_map
+        ]
+    );
+    diff_section!(
+        changes,
+        previous,
+        next,
+        window_title: [mode, truncate_title_after_length, show_state, font_name]
+    );
+    diff_section!(
+        changes,
+        previous,
+        next,
+        system: [
+            indicators,
+            cpu,
+            memory,
+            temperature,
+            disk,
+            network,
+            interval,
+            decimals,
+            smoothing
+        ]
+    );
+    diff_section!(
+        changes,
+        previous,
+        next,
+        battery: [
+            show_percentage,
+            show_power_profile,
+            open_settings_on_click,
+            hide_when_unavailable,
+            percent,
+            icon_position,
+            levels,
+            colors,
+            warn_thresholds
+        ]
+    );
+    diff_section!(
+        changes,
+        previous,
+        next,
+        clock: [
+            format,
+            show_weather,
+            font_name,
+            alarms,
+            copy_format,
+            extra_timezones,
+            timezone_format,
+            locale,
+            today,
+            agenda_cmd,
+            right_click_cmd
+        ]
+    );
+    diff_section!(
+        changes,
+        previous,
+        next,
+        settings: [
+            lock_cmd,
+            shutdown_cmd,
+            suspend_cmd,
+            reboot_cmd,
+            logout_cmd,
+            audio_sinks_more_cmd,
+            audio_sources_more_cmd,
+            wifi_more_cmd,
+            vpn_more_cmd,
+            bluetooth_more_cmd,
+            default_submenu,
+            remove_airplane_btn,
+            remove_idle_btn,
+            remove_retry_btn,
+            confirm_power_actions,
+            hide_when_unavailable,
+            volume_icons,
+            audio,
+            network,
+            volume,
+            brightness,
+            slider_step,
+            icon_position
+        ]
+    );
+    diff_section!(
+        changes,
+        previous,
+        next,
+        appearance: [
+            font_name,
+            scale_factor,
+            style,
+            opacity,
+            menu,
+            animations,
+            refresh,
+            section_separator,
+            empty_sections,
+            background_color,
+            primary_color,
+            secondary_color,
+            success_color,
+            danger_color,
+            text_color,
+            workspace_colors,
+            special_workspace_colors
+        ]
+    );
+    diff_fields!(changes, previous, next, [theme_file]);
+    diff_section!(
+        changes,
+        previous,
+        next,
+        media_player: [max_title_length, show_stop, show_shuffle, show_repeat]
+    );
+    diff_section!(changes, previous, next, keyboard_layout: [labels]);
+    diff_section!(changes, previous, next, tiling_layout: [gaps_in, gaps_out]);
+    diff_section!(changes, previous, next, lock_keys: [always_show, poll_interval_ms]);
+    diff_section!(changes, previous, next, mic: [hide_unmuted]);
+    diff_section!(
+        changes,
+        previous,
+        next,
+        tray: [max_visible, order, poll_interval, scroll]
+    );
+    diff_section!(
+        changes,
+        previous,
+        next,
+        privacy: [screenshare_cmd, location, display]
+    );
+    diff_fields!(changes, previous, next, [menu_keyboard_focus]);
+    diff_section!(
+        changes,
+        previous,
+        next,
+        menu_focus: [
+            updates,
+            settings,
+            tray,
+            media_player,
+            system_info,
+            notifications,
+            screenshot,
+            calendar
+        ]
+    );
+    diff_fields!(
+        changes,
+        previous,
+        next,
+        [
+            dim_inactive_monitors,
+            input_region,
+            layer,
+            hide_when_unavailable
+        ]
+    );
+    diff_section!(
+        changes,
+        previous,
+        next,
+        keybindings: [enabled, show_hints, global, menu]
+    );
+    diff_fields!(
+        changes,
+        previous,
+        next,
+        [debug_menu, freeze_readouts, invert_scroll, output_scale]
+    );
+    diff_section!(
+        changes,
+        previous,
+        next,
+        weather: [location, api_key, use_celsius, update_interval_minutes]
+    );
+
+    changes
+}
+
+/// Record `path: old → new` when the two values differ.
+fn record<T>(changes: &mut Vec<String>, path: &str, previous: &T, next: &T)
+where
+    T: PartialEq + Debug
+{
+    if previous != next {
+        changes.push(format!(
+            "{path}: {} → {}",
+            elide(&format!("{previous:?}")),
+            elide(&format!("{next:?}"))
+        ));
+    }
+}
+
+fn elide(value: &str) -> String {
+    if value.chars().count() <= MAX_VALUE_LEN {
+        return value.to_string();
+    }
+
+    let mut elided: String = value.chars().take(MAX_VALUE_LEN - 1).collect();
+    elided.push('…');
+    elided
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Position;
+
+    #[test]
+    fn identical_snapshots_have_no_changes() {
+        let config = Config::default();
+
+        assert!(describe_changes(&config, &config.clone()).is_empty());
+    }
+
+    #[test]
+    fn reports_nested_field_paths() {
+        let previous = Config::default();
+        let mut next = previous.clone();
+        next.appearance.opacity = 0.5;
+        next.position = Position::Bottom;
+
+        let changes = describe_changes(&previous, &next);
+
+        assert_eq!(
+            changes,
+            vec![
+                "position: Top → Bottom".to_string(),
+                format!(
+                    "appearance.opacity: {:?} → 0.5",
+                    previous.appearance.opacity
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_options_and_lists_as_values() {
+        let previous = Config::default();
+        let mut next = previous.clone();
+        next.reserved_height = Some(3);
+        next.clock.extra_timezones = vec!["Europe/Berlin".to_owned()];
+
+        let changes = describe_changes(&previous, &next);
+
+        assert_eq!(
+            changes,
+            vec![
+                "reserved_height: None → Some(3)".to_string(),
+                "clock.extra_timezones: [] → [\"Europe/Berlin\"]".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn elides_long_values() {
+        let previous = Config::default();
+        let mut next = previous.clone();
+        next.clipboard_cmd = Some("x".repeat(MAX_VALUE_LEN * 2));
+
+        let changes = describe_changes(&previous, &next);
+
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].starts_with("clipboard_cmd: None → "));
+        assert!(changes[0].contains('…'));
+    }
+}
@@ -0,0 +1,38 @@
+//! Curated example configurations printed by `hydebar --example <name>`.
+
+/// Names of the bundled examples, in the order they are listed to users.
+pub const EXAMPLE_NAMES: [&str; 3] = ["minimal", "full", "laptop"];
+
+/// TOML source of the bundled example called `name`.
+pub fn example(name: &str) -> Option<&'static str> {
+    match name {
+        "minimal" => Some(include_str!("examples/minimal.toml")),
+        "full" => Some(include_str!("examples/full.toml")),
+        "laptop" => Some(include_str!("examples/laptop.toml")),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn every_example_is_a_valid_config() {
+        for name in EXAMPLE_NAMES {
+            let source = example(name).expect("listed example exists");
+            let config: Config =
+                toml::from_str(source).unwrap_or_else(|err| panic!("{name}: {err}"));
+
+            config
+                .validate()
+                .unwrap_or_else(|err| panic!("{name}: {err}"));
+        }
+    }
+
+    #[test]
+    fn unknown_examples_are_absent() {
+        assert_eq!(example("kitchen-sink"), None);
+    }
+}
@@ -24,7 +24,9 @@ pub struct ConfigImpact {
     /// Whether menu keyboard focus changed.
     pub menu_focus_changed:     bool,
     /// Whether custom module definitions changed.
-    pub custom_modules_changed: bool
+    pub custom_modules_changed: bool,
+    /// Whether auxiliary bar placement or layout changed.
+    pub auxiliary_bars_changed: bool
 }
 
 impl ConfigImpact {
@@ -51,6 +53,8 @@ pub enum ConfigUpdateError {
     Read { path: PathBuf, context: String },
     /// Parsing TOML content failed.
     Parse { path: PathBuf, context: String },
+    /// Parsing the file named by `theme_file` failed.
+    Theme { path: PathBuf, context: String },
     /// Validation detected a logical inconsistency.
     Validation(ConfigValidationError),
     /// The configuration file was removed.
@@ -74,6 +78,12 @@ impl std::fmt::Display for ConfigUpdateError {
             } => {
                 write!(f, "failed to parse config at {:?}: {}", path, context)
             }
+            Self::Theme {
+                path,
+                context
+            } => {
+                write!(f, "failed to parse theme file at {:?}: {}", path, context)
+            }
             Self::Validation(err) => write!(f, "{}", err),
             Self::Removed => write!(f, "configuration file removed"),
             Self::State {
@@ -217,6 +227,11 @@ fn compute_impact(previous: &Config, next: &Config) -> ConfigImpact {
         impact.layout_changed = true;
     }
 
+    if previous.auxiliary_bars != next.auxiliary_bars {
+        impact.layout_changed = true;
+        impact.auxiliary_bars_changed = true;
+    }
+
     if previous.appearance != next.appearance {
         impact.appearance_changed = true;
     }
@@ -239,7 +254,9 @@ fn compute_impact(previous: &Config, next: &Config) -> ConfigImpact {
         impact.log_level_changed = true;
     }
 
-    if previous.menu_keyboard_focus != next.menu_keyboard_focus {
+    if previous.menu_keyboard_focus != next.menu_keyboard_focus
+        || previous.menu_focus != next.menu_focus
+    {
         impact.menu_focus_changed = true;
     }
 
@@ -249,6 +266,12 @@ fn compute_impact(previous: &Config, next: &Config) -> ConfigImpact {
         &previous.app_launcher_cmd,
         &next.app_launcher_cmd
     );
+    mark_if_changed(
+        &mut impact,
+        ModuleName::AppLauncher,
+        &previous.app_launcher,
+        &next.app_launcher
+    );
     mark_if_changed(
         &mut impact,
         ModuleName::Clipboard,
@@ -261,6 +284,12 @@ fn compute_impact(previous: &Config, next: &Config) -> ConfigImpact {
         &previous.updates,
         &next.updates
     );
+    mark_if_changed(
+        &mut impact,
+        ModuleName::CommandOutput,
+        &previous.command_output,
+        &next.command_output
+    );
     mark_if_changed(
         &mut impact,
         ModuleName::Workspaces,
@@ -286,6 +315,12 @@ fn compute_impact(previous: &Config, next: &Config) -> ConfigImpact {
         &next.battery
     );
     mark_if_changed(&mut impact, ModuleName::Clock, &previous.clock, &next.clock);
+    mark_if_changed(
+        &mut impact,
+        ModuleName::Clock,
+        &previous.locale,
+        &next.locale
+    );
     mark_if_changed(
         &mut impact,
         ModuleName::Settings,
@@ -304,6 +339,26 @@ fn compute_impact(previous: &Config, next: &Config) -> ConfigImpact {
         &previous.keyboard_layout,
         &next.keyboard_layout
     );
+    mark_if_changed(
+        &mut impact,
+        ModuleName::Privacy,
+        &previous.privacy,
+        &next.privacy
+    );
+    mark_if_changed(
+        &mut impact,
+        ModuleName::TilingLayout,
+        &previous.tiling_layout,
+        &next.tiling_layout
+    );
+    mark_if_changed(
+        &mut impact,
+        ModuleName::LockKeys,
+        &previous.lock_keys,
+        &next.lock_keys
+    );
+    mark_if_changed(&mut impact, ModuleName::Mic, &previous.mic, &next.mic);
+    mark_if_changed(&mut impact, ModuleName::Tray, &previous.tray, &next.tray);
 
     if previous.custom_modules != next.custom_modules {
         impact.custom_modules_changed = true;
@@ -0,0 +1,47 @@
+//! Shareable dump of the effective configuration for bug reports.
+
+use super::Config;
+
+/// Placeholder substituted for credential values.
+const REDACTED: &str = "<redacted>";
+
+/// Copy of `config` with every credential-bearing value replaced by a
+/// placeholder. Unset credentials stay unset so reports still show whether
+/// one was configured.
+pub fn redacted(config: &Config) -> Config {
+    let mut config = config.clone();
+
+    if config.weather.api_key.is_some() {
+        config.weather.api_key = Some(REDACTED.to_owned());
+    }
+
+    config
+}
+
+/// Pretty-printed, redacted configuration ready to paste into an issue.
+pub fn redacted_report(config: &Config) -> String {
+    format!("{:#?}", redacted(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_hides_api_key() {
+        let mut config = Config::default();
+        config.weather.api_key = Some("secret-token".to_owned());
+
+        let report = redacted_report(&config);
+
+        assert!(!report.contains("secret-token"));
+        assert!(report.contains(REDACTED));
+    }
+
+    #[test]
+    fn unset_credentials_stay_unset() {
+        let config = Config::default();
+
+        assert_eq!(redacted(&config), config);
+    }
+}
@@ -20,7 +20,7 @@ use iced::{
 use inotify::{EventMask, Inotify, WatchMask};
 use log::{debug, error, info, warn};
 
-use super::{ConfigReadError, read_config};
+use super::{ConfigReadError, LoadedConfig, log_warnings, read_config};
 use crate::config::manager::{ConfigApplied, ConfigDegradation, ConfigManager, ConfigUpdateError};
 
 /// Events produced by the configuration watcher subscription.
@@ -29,7 +29,7 @@ pub enum ConfigEvent {
     /// A new, validated configuration was applied.
     Applied(ConfigApplied),
     /// The configuration could not be refreshed and the previous state is
-    /// retained.
+    /// retained, or it was applied without its theme file.
     Degraded(ConfigDegradation)
 }
 
@@ -149,7 +149,12 @@ async fn handle_watch_event(
             info!("Reload config file");
 
             match load_candidate(path, &manager) {
-                Ok(applied) => output.send(ConfigEvent::Applied(applied)).await,
+                Ok((applied, None)) => output.send(ConfigEvent::Applied(applied)).await,
+                Ok((applied, Some(theme_error))) => {
+                    warn!("Theme file ignored: {theme_error}");
+                    output.send(ConfigEvent::Applied(applied)).await?;
+                    send_degradation(output, manager, theme_error).await
+                }
                 Err(reason) => {
                     warn!("Configuration update failed: {reason}");
                     send_degradation(output, manager, reason).await
@@ -164,17 +169,25 @@ async fn handle_watch_event(
     }
 }
 
+/// Applies the config at `path`, along with the theme file error that made
+/// it fall back to the default appearance.
 fn load_candidate(
     path: &Path,
     manager: &ConfigManager
-) -> Result<ConfigApplied, ConfigUpdateError> {
-    let config = read_config(path).map_err(convert_read_error)?;
+) -> Result<(ConfigApplied, Option<ConfigUpdateError>), ConfigUpdateError> {
+    let LoadedConfig {
+        config,
+        theme_error
+    } = read_config(path).map_err(convert_read_error)?;
 
     config.validate()?;
+    log_warnings(&config);
 
-    manager
+    let applied = manager
         .apply(config)
-        .map_err(|err| ConfigUpdateError::state(err.to_string()))
+        .map_err(|err| ConfigUpdateError::state(err.to_string()))?;
+
+    Ok((applied, theme_error.map(convert_read_error)))
 }
 
 fn convert_read_error(err: ConfigReadError) -> ConfigUpdateError {
@@ -186,7 +199,14 @@ fn convert_read_error(err: ConfigReadError) -> ConfigUpdateError {
         ConfigReadError::Parse {
             path,
             source
-        } => ConfigUpdateError::parse(path, &source)
+        } => ConfigUpdateError::parse(path, &source),
+        ConfigReadError::Theme {
+            path,
+            reason
+        } => ConfigUpdateError::Theme {
+            path,
+            context: reason
+        }
     }
 }
 
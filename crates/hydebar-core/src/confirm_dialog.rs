@@ -0,0 +1,106 @@
+use iced::{
+    Alignment, Element, Length,
+    alignment::Vertical,
+    widget::{button, column, horizontal_space, row, text}
+};
+
+use crate::{
+    components::icons::{Icons, icon},
+    style::{confirm_button_style, outline_button_style}
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+    Confirmed,
+    Cancelled
+}
+
+/// Yes/no prompt guarding an action of type `T` until the user confirms it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmDialog<T> {
+    icon:          Icons,
+    title:         String,
+    body:          String,
+    confirm_label: String,
+    action:        T
+}
+
+impl<T> ConfirmDialog<T> {
+    pub fn new(
+        icon: Icons,
+        title: impl Into<String>,
+        body: impl Into<String>,
+        confirm_label: impl Into<String>,
+        action: T
+    ) -> Self {
+        Self {
+            icon,
+            title: title.into(),
+            body: body.into(),
+            confirm_label: confirm_label.into(),
+            action
+        }
+    }
+
+    /// Close the dialog, handing back the guarded action only when the user
+    /// confirmed it.
+    pub fn resolve(self, message: Message) -> Option<T> {
+        match message {
+            Message::Confirmed => Some(self.action),
+            Message::Cancelled => None
+        }
+    }
+
+    pub fn view<'a>(&self, opacity: f32) -> Element<'a, Message> {
+        column!(
+            row!(icon(self.icon).size(32), text(self.title.clone()).size(22))
+                .spacing(16)
+                .align_y(Alignment::Center),
+            text(self.body.clone()),
+            row!(
+                horizontal_space(),
+                button(text("Cancel").align_y(Vertical::Center))
+                    .padding([4, 32])
+                    .style(outline_button_style(opacity))
+                    .height(Length::Fixed(50.))
+                    .on_press(Message::Cancelled),
+                button(text(self.confirm_label.clone()).align_y(Vertical::Center))
+                    .padding([4, 32])
+                    .height(Length::Fixed(50.))
+                    .style(confirm_button_style(opacity))
+                    .on_press(Message::Confirmed)
+            )
+            .spacing(8)
+            .width(Length::Fill)
+        )
+        .spacing(16)
+        .padding(16)
+        .max_width(350.)
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dialog() -> ConfirmDialog<&'static str> {
+        ConfirmDialog::new(
+            Icons::Power,
+            "Shutdown",
+            "Shut down now?",
+            "Shutdown",
+            "poweroff"
+        )
+    }
+
+    #[test]
+    fn confirming_returns_the_action() {
+        assert_eq!(dialog().resolve(Message::Confirmed), Some("poweroff"));
+    }
+
+    #[test]
+    fn cancelling_drops_the_action() {
+        assert_eq!(dialog().resolve(Message::Cancelled), None);
+    }
+}
@@ -1,7 +1,10 @@
 use std::{
     collections::VecDeque,
     num::NonZeroUsize,
-    sync::{Arc, Mutex}
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering}
+    }
 };
 
 use masterror::AppError;
@@ -29,11 +32,14 @@ impl BusEvent {
 #[non_exhaustive]
 pub enum ModuleEvent {
     Updates(modules::updates::Message),
+    CommandOutput(modules::command_output::Message),
     Workspaces(modules::workspaces::Message),
     WindowTitle(modules::window_title::Message),
     SystemInfo(modules::system_info::Message),
     KeyboardLayout(modules::keyboard_layout::Message),
     KeyboardSubmap(modules::keyboard_submap::Message),
+    LockKeys(modules::lock_keys::Message),
+    TilingLayout(modules::tiling_layout::Message),
     Tray(modules::tray::TrayMessage),
     Clock(modules::clock::Message),
     Battery(modules::battery::Message),
@@ -48,19 +54,130 @@ pub enum ModuleEvent {
     }
 }
 
+/// Totals of events that went through the bus since it was created.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EventBusMetrics {
+    /// Events accepted by the bus, including those coalesced with the
+    /// previous one.
+    pub sent:     u64,
+    /// Events handed to the receiver.
+    pub received: u64,
+    /// Events rejected because the queue was full.
+    pub dropped:  u64
+}
+
+impl std::fmt::Display for EventBusMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "sent {}, received {}, dropped {}",
+            self.sent, self.received, self.dropped
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    sent:     AtomicU64,
+    received: AtomicU64,
+    dropped:  AtomicU64
+}
+
+impl Counters {
+    fn snapshot(&self) -> EventBusMetrics {
+        EventBusMetrics {
+            sent:     self.sent.load(Ordering::Relaxed),
+            received: self.received.load(Ordering::Relaxed),
+            dropped:  self.dropped.load(Ordering::Relaxed)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lane {
+    Priority,
+    Normal
+}
+
+/// Pending events, split so that input-driven ones overtake module updates.
+/// Each lane keeps its own order and holds up to the bus capacity.
+#[derive(Debug)]
+struct Lanes {
+    priority: VecDeque<BusEvent>,
+    normal:   VecDeque<BusEvent>
+}
+
+impl Lanes {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            priority: VecDeque::new(),
+            normal:   VecDeque::with_capacity(capacity)
+        }
+    }
+
+    fn lane_mut(&mut self, lane: Lane) -> &mut VecDeque<BusEvent> {
+        match lane {
+            Lane::Priority => &mut self.priority,
+            Lane::Normal => &mut self.normal
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.priority.len() + self.normal.len()
+    }
+
+    fn pop_front(&mut self) -> Option<BusEvent> {
+        self.priority
+            .pop_front()
+            .or_else(|| self.normal.pop_front())
+    }
+
+    fn drain(&mut self) -> Vec<BusEvent> {
+        self.priority
+            .drain(..)
+            .chain(self.normal.drain(..))
+            .collect()
+    }
+}
+
 #[derive(Debug)]
 struct EventBusInner {
-    queue:    Mutex<VecDeque<BusEvent>>,
-    capacity: usize
+    queue:    Mutex<Lanes>,
+    capacity: usize,
+    counters: Counters
 }
 
 impl EventBusInner {
     fn new(capacity: NonZeroUsize) -> Self {
         Self {
-            queue:    Mutex::new(VecDeque::with_capacity(capacity.get())),
-            capacity: capacity.get()
+            queue:    Mutex::new(Lanes::with_capacity(capacity.get())),
+            capacity: capacity.get(),
+            counters: Counters::default()
         }
     }
+
+    fn push(&self, lane: Lane, event: BusEvent) -> Result<(), EventBusError> {
+        let mut lanes = self.queue.lock().map_err(|_| EventBusError::Poisoned)?;
+        let queue = lanes.lane_mut(lane);
+
+        if queue.len() >= self.capacity {
+            self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+            return Err(EventBusError::QueueFull {
+                capacity: self.capacity
+            });
+        }
+
+        self.counters.sent.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(last) = queue.back()
+            && event.is_coalescable_with(last)
+        {
+            return Ok(());
+        }
+
+        queue.push_back(event);
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -120,26 +237,7 @@ impl EventBus {
     }
 
     pub fn publish(&self, event: BusEvent) -> Result<(), EventBusError> {
-        let mut queue = self
-            .inner
-            .queue
-            .lock()
-            .map_err(|_| EventBusError::Poisoned)?;
-
-        if queue.len() >= self.inner.capacity {
-            return Err(EventBusError::QueueFull {
-                capacity: self.inner.capacity
-            });
-        }
-
-        if let Some(last) = queue.back()
-            && event.is_coalescable_with(last)
-        {
-            return Ok(());
-        }
-
-        queue.push_back(event);
-        Ok(())
+        self.inner.push(Lane::Normal, event)
     }
 
     pub fn drain(&self) -> Result<Vec<BusEvent>, EventBusError> {
@@ -149,7 +247,17 @@ impl EventBus {
             .lock()
             .map_err(|_| EventBusError::Poisoned)?;
 
-        Ok(queue.drain(..).collect())
+        let events = queue.drain();
+        self.inner
+            .counters
+            .received
+            .fetch_add(events.len() as u64, Ordering::Relaxed);
+        Ok(events)
+    }
+
+    /// Snapshot of the sent, received and dropped counters.
+    pub fn metrics(&self) -> EventBusMetrics {
+        self.inner.counters.snapshot()
     }
 }
 
@@ -160,26 +268,13 @@ pub struct EventSender {
 
 impl EventSender {
     pub fn try_send(&self, event: BusEvent) -> Result<(), EventBusError> {
-        let mut queue = self
-            .inner
-            .queue
-            .lock()
-            .map_err(|_| EventBusError::Poisoned)?;
-
-        if queue.len() >= self.inner.capacity {
-            return Err(EventBusError::QueueFull {
-                capacity: self.inner.capacity
-            });
-        }
-
-        if let Some(last) = queue.back()
-            && event.is_coalescable_with(last)
-        {
-            return Ok(());
-        }
+        self.inner.push(Lane::Normal, event)
+    }
 
-        queue.push_back(event);
-        Ok(())
+    /// Queue `event` ahead of everything sent through [`Self::try_send`].
+    /// Events sent this way keep their relative order.
+    pub fn send_priority(&self, event: BusEvent) -> Result<(), EventBusError> {
+        self.inner.push(Lane::Priority, event)
     }
 }
 
@@ -196,6 +291,128 @@ impl EventReceiver {
             .lock()
             .map_err(|_| EventBusError::Poisoned)?;
 
-        Ok(queue.pop_front())
+        let event = queue.pop_front();
+        if event.is_some() {
+            self.inner.counters.received.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(event)
+    }
+
+    /// Number of events waiting to be drained, across both lanes.
+    pub fn depth(&self) -> Result<usize, EventBusError> {
+        let queue = self
+            .inner
+            .queue
+            .lock()
+            .map_err(|_| EventBusError::Poisoned)?;
+
+        Ok(queue.len())
+    }
+
+    /// Maximum number of events the bus buffers before rejecting new ones.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+
+    /// Snapshot of the sent, received and dropped counters.
+    pub fn metrics(&self) -> EventBusMetrics {
+        self.inner.counters.snapshot()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receiver_reports_depth_and_capacity() {
+        let bus = EventBus::new(NonZeroUsize::new(4).expect("non-zero"));
+        let mut receiver = bus.receiver();
+
+        bus.publish(BusEvent::Redraw).expect("publish");
+        bus.publish(BusEvent::PopupToggle).expect("publish");
+
+        assert_eq!(receiver.depth(), Ok(2));
+        assert_eq!(receiver.capacity(), 4);
+
+        receiver.try_recv().expect("receive");
+        assert_eq!(receiver.depth(), Ok(1));
+    }
+
+    #[test]
+    fn overflowing_the_bus_counts_dropped_events() {
+        let bus = EventBus::new(NonZeroUsize::new(2).expect("non-zero"));
+        let sender = bus.sender();
+        let mut receiver = bus.receiver();
+
+        sender.try_send(BusEvent::Redraw).expect("send");
+        sender.try_send(BusEvent::PopupToggle).expect("send");
+        assert_eq!(
+            sender.try_send(BusEvent::Redraw),
+            Err(EventBusError::QueueFull {
+                capacity: 2
+            })
+        );
+        assert!(bus.publish(BusEvent::PopupToggle).is_err());
+
+        while receiver.try_recv().expect("receive").is_some() {}
+
+        assert_eq!(
+            receiver.metrics(),
+            EventBusMetrics {
+                sent:     2,
+                received: 2,
+                dropped:  2
+            }
+        );
+    }
+
+    #[test]
+    fn priority_events_are_received_first_in_order() {
+        use modules::updates::Message as Updates;
+
+        let bus = EventBus::new(NonZeroUsize::new(8).expect("non-zero"));
+        let sender = bus.sender();
+        let mut receiver = bus.receiver();
+
+        sender
+            .try_send(BusEvent::Module(ModuleEvent::Updates(Updates::CheckNow)))
+            .expect("send");
+        sender.send_priority(BusEvent::PopupToggle).expect("send");
+        sender
+            .try_send(BusEvent::Module(ModuleEvent::Updates(
+                Updates::UpdateFinished
+            )))
+            .expect("send");
+        sender.send_priority(BusEvent::Redraw).expect("send");
+
+        let mut received = Vec::new();
+        while let Some(event) = receiver.try_recv().expect("receive") {
+            received.push(match event {
+                BusEvent::Redraw => "redraw",
+                BusEvent::PopupToggle => "popup",
+                BusEvent::Module(ModuleEvent::Updates(Updates::CheckNow)) => "check",
+                BusEvent::Module(ModuleEvent::Updates(Updates::UpdateFinished)) => "finished",
+                BusEvent::Module(_) => unreachable!("no other module events were sent")
+            });
+        }
+
+        assert_eq!(received, ["popup", "redraw", "check", "finished"]);
+    }
+
+    #[test]
+    fn a_full_normal_lane_keeps_accepting_priority_events() {
+        let bus = EventBus::new(NonZeroUsize::new(1).expect("non-zero"));
+        let sender = bus.sender();
+
+        sender.try_send(BusEvent::Redraw).expect("send");
+        assert!(sender.try_send(BusEvent::PopupToggle).is_err());
+        sender.send_priority(BusEvent::PopupToggle).expect("send");
+
+        let drained = bus.drain().expect("drain");
+        assert!(matches!(
+            drained.as_slice(),
+            [BusEvent::PopupToggle, BusEvent::Redraw]
+        ));
     }
 }
@@ -4,6 +4,7 @@ pub const HEIGHT: f64 = 34.;
 pub mod adapters;
 pub mod components;
 pub mod config;
+pub mod confirm_dialog;
 /// Event bus primitives for communicating UI updates across the core.
 pub mod event_bus;
 pub mod menu;
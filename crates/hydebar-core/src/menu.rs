@@ -1,17 +1,17 @@
 use std::time::Instant;
 
 use iced::{
-    self, Element, Length, Padding, Task,
+    self, Alignment, Border, Element, Length, Padding, Task, Theme,
     alignment::{Horizontal, Vertical},
     platform_specific::shell::commands::layer_surface::{
         KeyboardInteractivity, Layer, set_keyboard_interactivity, set_layer
     },
-    widget::{container, mouse_area},
+    widget::{Column, Row, container, mouse_area, text},
     window::Id
 };
 
 use crate::{
-    config::{AnimationConfig, AppearanceStyle, Position},
+    config::{AnimationConfig, AppearanceStyle, MenuKeybindings, Position},
     position_button::ButtonUIRef,
     style::{menu_backdrop_style, menu_container_style}
 };
@@ -21,11 +21,35 @@ pub enum MenuType {
     Updates,
     Settings,
     Tray(String),
+    /// Popover holding the tray items beyond `tray.max_visible`.
+    TrayOverflow,
     MediaPlayer,
     SystemInfo,
     Notifications,
     Screenshot,
-    Calendar
+    Calendar,
+    Debug
+}
+
+impl MenuType {
+    /// Whether opening this menu grabs keyboard focus, honouring the
+    /// per-menu override before the global `menu_keyboard_focus` setting.
+    pub fn keyboard_focus(&self, config: &crate::config::Config) -> bool {
+        let overrides = &config.menu_focus;
+
+        match self {
+            MenuType::Updates => overrides.updates,
+            MenuType::Settings => overrides.settings,
+            MenuType::Tray(_) | MenuType::TrayOverflow => overrides.tray,
+            MenuType::MediaPlayer => overrides.media_player,
+            MenuType::SystemInfo => overrides.system_info,
+            MenuType::Notifications => overrides.notifications,
+            MenuType::Screenshot => overrides.screenshot,
+            MenuType::Calendar => overrides.calendar,
+            MenuType::Debug => None
+        }
+        .unwrap_or(config.menu_keyboard_focus)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -54,6 +78,7 @@ impl Menu {
         button_ui_ref: ButtonUIRef,
         config: &crate::config::Config
     ) -> Task<Message> {
+        let keyboard_focus = menu_type.keyboard_focus(config);
         self.menu_info.replace((menu_type, button_ui_ref));
 
         // Start fade-in animation
@@ -67,7 +92,7 @@ impl Menu {
 
         let mut tasks = vec![set_layer(self.id, Layer::Overlay)];
 
-        if config.menu_keyboard_focus {
+        if keyboard_focus {
             tasks.push(set_keyboard_interactivity(
                 self.id,
                 KeyboardInteractivity::OnDemand
@@ -78,9 +103,7 @@ impl Menu {
     }
 
     pub fn close<Message: 'static>(&mut self, config: &crate::config::Config) -> Task<Message> {
-        if self.menu_info.is_some() {
-            self.menu_info.take();
-
+        if let Some((menu_type, _)) = self.menu_info.take() {
             // Start fade-out animation
             if config.appearance.animations.enabled {
                 self.target_opacity = 0.0;
@@ -92,7 +115,9 @@ impl Menu {
 
             let mut tasks = vec![set_layer(self.id, Layer::Background)];
 
-            if config.menu_keyboard_focus {
+            // Dialogs inside a menu may request focus through the global
+            // setting even when the menu itself did not grab it.
+            if config.menu_keyboard_focus || menu_type.keyboard_focus(config) {
                 tasks.push(set_keyboard_interactivity(
                     self.id,
                     KeyboardInteractivity::None
@@ -115,9 +140,18 @@ impl Menu {
             None => self.open(menu_type, button_ui_ref, config),
             Some((current_type, _)) if *current_type == menu_type => self.close(config),
             Some((current_type, current_button_ui_ref)) => {
+                let keyboard_focus = menu_type.keyboard_focus(config);
+                let task = if current_type.keyboard_focus(config) == keyboard_focus {
+                    Task::none()
+                } else if keyboard_focus {
+                    set_keyboard_interactivity(self.id, KeyboardInteractivity::OnDemand)
+                } else {
+                    set_keyboard_interactivity(self.id, KeyboardInteractivity::None)
+                };
+
                 *current_type = menu_type;
                 *current_button_ui_ref = button_ui_ref;
-                Task::none()
+                task
             }
         }
     }
@@ -154,6 +188,12 @@ impl Menu {
         }
     }
 
+    /// Jump straight to the end of any running fade.
+    pub fn finish_animation(&mut self) {
+        self.current_opacity = self.target_opacity;
+        self.animation_start = None;
+    }
+
     /// Update menu animation state. Returns true if animation is in progress.
     pub fn tick_animation(&mut self, animation_config: &AnimationConfig) -> bool {
         if !animation_config.enabled {
@@ -203,19 +243,94 @@ impl MenuSize {
     }
 }
 
+/// Key hints rendered below a menu's content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyHints<'a> {
+    /// The menu has no keyboard focus or hints are disabled.
+    Hidden,
+    /// Only the keys handled while a menu is focused.
+    Menu,
+    /// Navigation mode is active, so the configured bar keys apply as well.
+    Navigation(&'a MenuKeybindings)
+}
+
+impl KeyHints<'_> {
+    /// Keys and what they do, matching the bar's keyboard listener.
+    fn entries(self) -> Vec<(String, &'static str)> {
+        let close = ("Esc".to_owned(), "close");
+        match self {
+            KeyHints::Hidden => Vec::new(),
+            KeyHints::Menu => vec![close],
+            KeyHints::Navigation(bindings) => vec![
+                (format!("{}/{}", bindings.left, bindings.right), "module"),
+                ("Enter".to_owned(), "open"),
+                close,
+            ]
+        }
+    }
+}
+
+fn key_hints_row<'a, Message: 'a>(hints: KeyHints) -> Option<Element<'a, Message>> {
+    let entries = hints.entries();
+
+    (!entries.is_empty()).then(|| {
+        Row::with_children(entries.into_iter().map(|(key, action)| {
+            Row::new()
+                .push(
+                    container(text(key).size(10))
+                        .padding([0, 4])
+                        .style(|theme: &Theme| container::Style {
+                            border: Border {
+                                color:  theme.extended_palette().background.strong.color,
+                                width:  1.,
+                                radius: 4.into()
+                            },
+                            ..Default::default()
+                        })
+                )
+                .push(text(action).size(10))
+                .align_y(Alignment::Center)
+                .spacing(4)
+                .into()
+        }))
+        .spacing(12)
+        .into()
+    })
+}
+
+/// Distance of a `menu_width` wide menu from the left edge of a
+/// `viewport_width` wide output. The menu is centered under `anchor_x` and
+/// shifted back inside when that would put it closer than `margin` to an
+/// edge; a menu too wide for both margins is centered on the output.
+pub fn menu_left_offset(anchor_x: f32, viewport_width: f32, menu_width: f32, margin: f32) -> f32 {
+    let max_left = viewport_width - menu_width - margin;
+    if max_left < margin {
+        return ((viewport_width - menu_width) / 2.).max(0.);
+    }
+
+    (anchor_x - menu_width / 2.).clamp(margin, max_left)
+}
+
 #[allow(clippy::too_many_arguments)]
-pub fn menu_wrapper<Message: Clone + 'static>(
+pub fn menu_wrapper<'a, Message: Clone + 'static>(
     _id: Id,
-    content: Element<'_, Message>,
+    content: Element<'a, Message>,
     menu_size: MenuSize,
     button_ui_ref: ButtonUIRef,
     bar_position: Position,
     style: AppearanceStyle,
+    margin: f32,
     opacity: f32,
     menu_backdrop: f32,
+    key_hints: KeyHints<'_>,
     none_message: Message,
     close_menu_message: Message
-) -> Element<'_, Message> {
+) -> Element<'a, Message> {
+    let content = match key_hints_row(key_hints) {
+        Some(hints) => Column::new().push(content).push(hints).spacing(12).into(),
+        None => content
+    };
+
     mouse_area(
         container(
             mouse_area(
@@ -229,8 +344,8 @@ pub fn menu_wrapper<Message: Clone + 'static>(
             .on_release(none_message)
         )
         .align_y(match bar_position {
-            Position::Top => Vertical::Top,
-            Position::Bottom => Vertical::Bottom
+            Position::Bottom => Vertical::Bottom,
+            Position::Top | Position::Left | Position::Right => Vertical::Top
         })
         .align_x(Horizontal::Left)
         .padding({
@@ -252,9 +367,11 @@ pub fn menu_wrapper<Message: Clone + 'static>(
                 } else {
                     0
                 })
-                .left(f32::min(
-                    f32::max(button_ui_ref.position.x - size / 2., 8.),
-                    button_ui_ref.viewport.0 - size - 8.
+                .left(menu_left_offset(
+                    button_ui_ref.position.x,
+                    button_ui_ref.viewport.0,
+                    size,
+                    margin
                 ))
         })
         .width(Length::Fill)
@@ -264,3 +381,68 @@ pub fn menu_wrapper<Message: Clone + 'static>(
     .on_release(close_menu_message)
     .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyHints, MenuType, menu_left_offset};
+    use crate::config::{Config, MenuKeybindings};
+
+    #[test]
+    fn menu_is_centered_under_its_button() {
+        assert_eq!(menu_left_offset(500., 1920., 400., 8.), 300.);
+    }
+
+    #[test]
+    fn menu_near_an_edge_is_shifted_inside_the_margin() {
+        assert_eq!(menu_left_offset(20., 1920., 400., 8.), 8.);
+        assert_eq!(menu_left_offset(1900., 1920., 400., 16.), 1504.);
+    }
+
+    #[test]
+    fn menu_wider_than_the_output_is_centered() {
+        assert_eq!(menu_left_offset(20., 400., 390., 8.), 5.);
+        assert_eq!(menu_left_offset(20., 300., 400., 8.), 0.);
+    }
+
+    #[test]
+    fn keyboard_focus_prefers_per_menu_override() {
+        let mut config = Config {
+            menu_keyboard_focus: true,
+            ..Config::default()
+        };
+        config.menu_focus.settings = Some(false);
+
+        assert!(!MenuType::Settings.keyboard_focus(&config));
+        assert!(MenuType::Tray("nm-applet".into()).keyboard_focus(&config));
+
+        config.menu_keyboard_focus = false;
+        config.menu_focus.calendar = Some(true);
+
+        assert!(MenuType::Calendar.keyboard_focus(&config));
+        assert!(!MenuType::Updates.keyboard_focus(&config));
+    }
+
+    #[test]
+    fn key_hints_always_offer_closing_unless_hidden() {
+        let close = ("Esc".to_owned(), "close");
+        let bindings = MenuKeybindings::default();
+
+        assert!(KeyHints::Hidden.entries().is_empty());
+        assert!(KeyHints::Menu.entries().contains(&close));
+        assert!(KeyHints::Navigation(&bindings).entries().contains(&close));
+    }
+
+    #[test]
+    fn navigation_hints_follow_the_configured_keys() {
+        let bindings = MenuKeybindings {
+            left: "a".to_owned(),
+            right: "d".to_owned(),
+            ..MenuKeybindings::default()
+        };
+
+        assert_eq!(
+            KeyHints::Navigation(&bindings).entries()[0],
+            ("a/d".to_owned(), "module")
+        );
+    }
+}
@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use tokio::runtime::Handle;
 
-use crate::event_bus::{BusEvent, EventBusError, EventSender, ModuleEvent};
+use crate::{
+    event_bus::{BusEvent, EventBusError, EventSender, ModuleEvent},
+    utils::launcher::{CommandContext, CommandSettings}
+};
 
 /// Shared utilities exposed to individual modules when they need to interact
 /// with the core event loop.
@@ -16,8 +19,9 @@ use crate::event_bus::{BusEvent, EventBusError, EventSender, ModuleEvent};
 /// publishes are left behind when a task is cancelled.
 #[derive(Debug, Clone)]
 pub struct ModuleContext {
-    event_sender:   EventSender,
-    runtime_handle: Handle
+    event_sender:     EventSender,
+    runtime_handle:   Handle,
+    command_settings: CommandSettings
 }
 
 impl ModuleContext {
@@ -37,10 +41,41 @@ impl ModuleContext {
     pub fn new(event_sender: EventSender, runtime_handle: Handle) -> Self {
         Self {
             event_sender,
-            runtime_handle
+            runtime_handle,
+            command_settings: CommandSettings::default()
         }
     }
 
+    /// Run commands launched by modules registered from now on with
+    /// `settings`.
+    pub fn set_command_settings(&mut self, settings: CommandSettings) {
+        self.command_settings = settings;
+    }
+
+    /// How launched commands are run, as configured when the modules were
+    /// last registered.
+    pub fn command_settings(&self) -> &CommandSettings {
+        &self.command_settings
+    }
+
+    /// Context of a command launched by the module named `module`, run with
+    /// the configured [`CommandSettings`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hydebar_core::{event_bus::EventBus, module_context::ModuleContext};
+    /// # use std::num::NonZeroUsize;
+    /// # let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    /// let bus = EventBus::new(NonZeroUsize::new(4).expect("capacity"));
+    /// let context = ModuleContext::new(bus.sender(), runtime.handle().clone());
+    /// let command = context.command_context("Clock");
+    /// assert_eq!(command.module.as_deref(), Some("Clock"));
+    /// ```
+    pub fn command_context(&self, module: &str) -> CommandContext {
+        CommandContext::module(module).with_settings(self.command_settings.clone())
+    }
+
     /// Access the runtime handle used for spawning background tasks.
     ///
     /// # Safety and cancellation
@@ -90,8 +125,9 @@ impl ModuleContext {
     ///
     /// # Postconditions
     ///
-    /// - Enqueues a [`BusEvent::PopupToggle`] if the bus has capacity,
-    ///   otherwise returns [`EventBusError::QueueFull`].
+    /// - Enqueues a [`BusEvent::PopupToggle`] on the priority lane, ahead of
+    ///   pending module updates, if it has capacity, otherwise returns
+    ///   [`EventBusError::QueueFull`].
     ///
     /// # Examples
     ///
@@ -104,7 +140,7 @@ impl ModuleContext {
     /// context.toggle_popup().expect("queued");
     /// ```
     pub fn toggle_popup(&self) -> Result<(), EventBusError> {
-        self.event_sender.try_send(BusEvent::PopupToggle)
+        self.event_sender.send_priority(BusEvent::PopupToggle)
     }
 
     fn publish_module_event(&self, event: ModuleEvent) -> Result<(), EventBusError> {
@@ -227,7 +263,8 @@ mod tests {
     use super::ModuleContext;
     use crate::{
         event_bus::{BusEvent, EventBus, ModuleEvent},
-        modules
+        modules,
+        utils::launcher::CommandSettings
     };
 
     #[test]
@@ -245,17 +282,35 @@ mod tests {
     }
 
     #[test]
-    fn toggle_popup_enqueues_event() {
+    fn command_context_uses_the_configured_settings() {
+        let runtime = Runtime::new().expect("runtime");
+        let bus = EventBus::new(NonZeroUsize::new(4).expect("capacity"));
+        let mut context = ModuleContext::new(bus.sender(), runtime.handle().clone());
+        let settings = CommandSettings::default().with_shell(Some("sh -c"));
+
+        context.set_command_settings(settings.clone());
+        let command = context.command_context("Clock");
+
+        assert_eq!(command.module.as_deref(), Some("Clock"));
+        assert_eq!(command.settings, settings);
+        assert_eq!(command.shell_command("true").as_std().get_program(), "sh");
+    }
+
+    #[test]
+    fn toggle_popup_enqueues_event_ahead_of_pending_ones() {
         let runtime = Runtime::new().expect("runtime");
         let bus = EventBus::new(NonZeroUsize::new(4).expect("capacity"));
         let sender = bus.sender();
         let mut receiver = bus.receiver();
         let context = ModuleContext::new(sender, runtime.handle().clone());
 
+        context.request_redraw().expect("redraw enqueued");
         context.toggle_popup().expect("popup enqueued");
 
         let event = receiver.try_recv().expect("receive");
         assert!(matches!(event, Some(BusEvent::PopupToggle)));
+        let event = receiver.try_recv().expect("receive");
+        assert!(matches!(event, Some(BusEvent::Redraw)));
     }
 
     #[test]
@@ -9,15 +9,20 @@ pub mod app_launcher;
 pub mod battery;
 pub mod clipboard;
 pub mod clock;
+pub mod command_output;
 pub mod custom_module;
+pub mod idle_inhibitor;
 pub mod keyboard_layout;
 pub mod keyboard_submap;
+pub mod lock_keys;
 pub mod media_player;
+pub mod mic;
 pub mod notifications;
 pub mod privacy;
 pub mod screenshot;
 pub mod settings;
 pub mod system_info;
+pub mod tiling_layout;
 pub mod tray;
 pub mod updates;
 pub mod weather;
@@ -28,7 +33,9 @@ pub mod workspaces;
 #[derive(Debug, Clone)]
 pub enum OnModulePress<M> {
     Action(Box<M>),
-    ToggleMenu(MenuType)
+    ToggleMenu(MenuType),
+    /// Toggle the menu on left click and send the message on right click.
+    ToggleMenuOrAction(MenuType, Box<M>)
 }
 
 /// Module registration and operation errors
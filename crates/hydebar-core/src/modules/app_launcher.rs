@@ -1,9 +1,13 @@
-use iced::Element;
+use iced::{
+    Alignment, Element,
+    widget::{Row, text}
+};
 
 use super::{Module, ModuleError, OnModulePress};
 use crate::{
     ModuleContext,
-    components::icons::{Icons, icon}
+    components::icons::{Icons, icon, icon_raw},
+    config::AppLauncherModuleConfig
 };
 
 #[derive(Default, Debug, Clone)]
@@ -13,7 +17,8 @@ impl<M> Module<M> for AppLauncher
 where
     M: 'static + Clone
 {
-    type ViewData<'a> = &'a Option<String>;
+    /// Launcher command, the button is hidden without one, and its look.
+    type ViewData<'a> = (&'a Option<String>, &'a AppLauncherModuleConfig);
     type RegistrationData<'a> = ();
 
     fn register(
@@ -26,16 +31,25 @@ where
 
     fn view(
         &self,
-        config: Self::ViewData<'_>
+        (command, config): Self::ViewData<'_>
     ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)> {
-        if config.is_some() {
-            Some((
-                icon(Icons::AppLauncher).into(),
-                None // Action handled in GUI layer
-            ))
-        } else {
-            None
-        }
+        command.as_ref()?;
+
+        let glyph = match config.icon.as_deref() {
+            None => Some(icon(Icons::AppLauncher)),
+            Some("") => None,
+            Some(glyph) => Some(icon_raw(glyph.to_owned()))
+        };
+
+        Some((
+            Row::new()
+                .push_maybe(glyph)
+                .push_maybe(config.label.clone().map(text))
+                .align_y(Alignment::Center)
+                .spacing(4)
+                .into(),
+            None // Action handled in GUI layer
+        ))
     }
 }
 
@@ -73,9 +87,10 @@ mod tests {
     #[test]
     fn view_returns_some_when_config_present() {
         let launcher = AppLauncher::default();
-        let config = Some("wofi".to_string());
+        let command = Some("wofi".to_string());
+        let config = AppLauncherModuleConfig::default();
 
-        let result = <AppLauncher as Module<()>>::view(&launcher, &config);
+        let result = <AppLauncher as Module<()>>::view(&launcher, (&command, &config));
         assert!(result.is_some());
 
         if let Some((_, action)) = result {
@@ -86,9 +101,39 @@ mod tests {
     #[test]
     fn view_returns_none_when_config_absent() {
         let launcher = AppLauncher::default();
-        let config = None;
+        let config = AppLauncherModuleConfig {
+            icon:  Some("".to_owned()),
+            label: Some("Apps".to_owned())
+        };
 
-        let result = <AppLauncher as Module<()>>::view(&launcher, &config);
+        let result = <AppLauncher as Module<()>>::view(&launcher, (&None, &config));
         assert!(result.is_none());
     }
+
+    #[test]
+    fn view_renders_only_the_configured_parts() {
+        let launcher = AppLauncher::default();
+        let command = Some("wofi".to_string());
+        let children = |config: &AppLauncherModuleConfig| {
+            let (element, _) = <AppLauncher as Module<()>>::view(&launcher, (&command, config))
+                .expect("launcher command is set");
+            element.as_widget().children().len()
+        };
+
+        assert_eq!(children(&AppLauncherModuleConfig::default()), 1);
+        assert_eq!(
+            children(&AppLauncherModuleConfig {
+                icon:  Some("".to_owned()),
+                label: Some("Apps".to_owned())
+            }),
+            1
+        );
+        assert_eq!(
+            children(&AppLauncherModuleConfig {
+                icon:  Some("\u{f303}".to_owned()),
+                label: Some("Apps".to_owned())
+            }),
+            2
+        );
+    }
 }
@@ -1,16 +1,146 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use iced::{Color, Theme};
 use log::warn;
 
 use crate::{
     ModuleContext,
     components::icons::Icons,
+    config::{BatteryColors, BatteryLevels, BatteryModuleConfig},
     services::{
         ServiceEvent,
-        upower::{BatteryData as UPowerBatteryData, UPowerEvent, UPowerService}
-    }
+        upower::{BatteryData as UPowerBatteryData, BatteryStatus, UPowerEvent, UPowerService}
+    },
+    utils::launcher::{self, CommandContext}
 };
 
+/// Time each glyph of the charging animation stays on screen.
+pub const CHARGING_FRAME_DURATION: Duration = Duration::from_millis(500);
+
+/// Weight of each new UPower reading in the smoothed time remaining.
+const ESTIMATE_WEIGHT: f64 = 0.2;
+
+/// Battery glyphs ordered from empty to full.
+const LEVEL_ICONS: [Icons; 5] = [
+    Icons::Battery0,
+    Icons::Battery1,
+    Icons::Battery2,
+    Icons::Battery3,
+    Icons::Battery4
+];
+
+fn level_index(capacity: u8) -> usize {
+    match capacity {
+        0..=20 => 0,
+        21..=40 => 1,
+        41..=60 => 2,
+        61..=80 => 3,
+        _ => 4
+    }
+}
+
+/// Glyph shown `elapsed` into the charging animation. The meter fills from
+/// the current level up to full and starts over, always spanning at least
+/// two frames so a nearly full battery still visibly pulses.
+pub fn charging_frame(capacity: u8, elapsed: Duration) -> Icons {
+    let start = level_index(capacity).min(LEVEL_ICONS.len() - 2);
+    let frames = LEVEL_ICONS.len() - start;
+    let step = (elapsed.as_millis() / CHARGING_FRAME_DURATION.as_millis()) as usize;
+
+    LEVEL_ICONS[start + step % frames]
+}
+
+/// Exponential moving average over UPower's time-to-empty and time-to-full,
+/// which swing between wildly different values right after the load changes.
+///
+/// Switching between charging and discharging starts the average over, since
+/// the previous direction says nothing about the new one.
+#[derive(Debug, Clone, Default)]
+pub struct RemainingEstimate {
+    charging: Option<bool>,
+    smoothed: Option<Duration>
+}
+
+impl RemainingEstimate {
+    /// Folds the reading carried by `status` into the average and returns
+    /// `status` with the smoothed duration in its place.
+    pub fn smooth(&mut self, status: BatteryStatus) -> BatteryStatus {
+        let (charging, reading) = match status {
+            BatteryStatus::Charging(reading) => (true, reading),
+            BatteryStatus::Discharging(reading) => (false, reading),
+            BatteryStatus::Full => {
+                *self = Self::default();
+                return status;
+            }
+        };
+
+        if self.charging != Some(charging) {
+            self.charging = Some(charging);
+            self.smoothed = None;
+        }
+
+        // UPower reports zero until it has gathered enough samples.
+        if !reading.is_zero() {
+            self.smoothed = Some(match self.smoothed {
+                Some(previous) => {
+                    previous.mul_f64(1.0 - ESTIMATE_WEIGHT) + reading.mul_f64(ESTIMATE_WEIGHT)
+                }
+                None => reading
+            });
+        }
+
+        let smoothed = self.smoothed.unwrap_or_default();
+        if charging {
+            BatteryStatus::Charging(smoothed)
+        } else {
+            BatteryStatus::Discharging(smoothed)
+        }
+    }
+}
+
+/// Tracks which low-battery thresholds were already announced.
+///
+/// A threshold fires once when a discharging battery drops from above it to
+/// it or below, and is re-armed only once the battery charges back above it,
+/// so a level hovering around a threshold is not announced again.
+#[derive(Debug, Clone, Default)]
+pub struct LowBatteryAlerts {
+    previous: Option<u8>,
+    fired:    Vec<u8>
+}
+
+impl LowBatteryAlerts {
+    /// Records `capacity` and returns the lowest threshold newly crossed on
+    /// the way down since the previous reading, if any.
+    pub fn check(&mut self, capacity: u8, charging: bool, thresholds: &[u8]) -> Option<u8> {
+        let previous = self.previous.replace(capacity);
+
+        if charging {
+            self.fired.retain(|threshold| capacity <= *threshold);
+            return None;
+        }
+
+        let previous = previous?;
+        let crossed: Vec<u8> = thresholds
+            .iter()
+            .copied()
+            .filter(|threshold| previous > *threshold && capacity <= *threshold)
+            .filter(|threshold| !self.fired.contains(threshold))
+            .collect();
+
+        self.fired.extend(&crossed);
+        crossed.into_iter().min()
+    }
+}
+
+/// Announce a low battery through the desktop notification daemon.
+fn notify_low_battery(capacity: u8, context: CommandContext) {
+    launcher::execute_command(
+        format!("notify-send -u critical -a hydebar 'Battery low' '{capacity}% remaining'"),
+        context
+    );
+}
+
 /// Battery icon type based on capacity and charging state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BatteryIcon {
@@ -24,13 +154,7 @@ impl From<BatteryIcon> for Icons {
     fn from(icon: BatteryIcon) -> Self {
         match icon {
             BatteryIcon::Charging(_) => Icons::BatteryCharging,
-            BatteryIcon::Discharging(capacity) => match capacity {
-                0..=20 => Icons::Battery0,
-                21..=40 => Icons::Battery1,
-                41..=60 => Icons::Battery2,
-                61..=80 => Icons::Battery3,
-                _ => Icons::Battery4
-            },
+            BatteryIcon::Discharging(capacity) => LEVEL_ICONS[level_index(capacity)],
             BatteryIcon::Full => Icons::Battery4,
             BatteryIcon::Unknown => Icons::Battery0
         }
@@ -78,6 +202,38 @@ pub enum IndicatorState {
     Success
 }
 
+impl IndicatorState {
+    /// State for `capacity` under the given breakpoints. Charging and a full
+    /// battery count as success whatever the level.
+    pub fn from_level(capacity: u8, charging: bool, levels: &BatteryLevels) -> Self {
+        if charging || capacity >= 100 {
+            IndicatorState::Success
+        } else if capacity <= levels.danger {
+            IndicatorState::Danger
+        } else if capacity <= levels.warning {
+            IndicatorState::Warning
+        } else {
+            IndicatorState::Normal
+        }
+    }
+
+    /// Configured color for the state, falling back to the theme palette.
+    pub fn color(self, colors: &BatteryColors, theme: &Theme) -> Color {
+        let (configured, fallback) = match self {
+            IndicatorState::Success => (colors.charging, theme.palette().success),
+            IndicatorState::Warning => {
+                (colors.warning, theme.extended_palette().danger.weak.color)
+            }
+            IndicatorState::Danger => (colors.danger, theme.palette().danger),
+            IndicatorState::Normal => (colors.normal, theme.palette().text)
+        };
+
+        configured.map_or(fallback, |color| {
+            Color::from_rgb8(color.r, color.g, color.b)
+        })
+    }
+}
+
 /// Complete battery state information for rendering
 #[derive(Debug, Clone)]
 pub struct BatteryData {
@@ -94,7 +250,8 @@ impl BatteryData {
         capacity: u8,
         charging: bool,
         time_remaining: Option<Duration>,
-        power_profile: PowerProfile
+        power_profile: PowerProfile,
+        levels: &BatteryLevels
     ) -> Self {
         let icon = if charging {
             if capacity >= 100 {
@@ -106,15 +263,7 @@ impl BatteryData {
             BatteryIcon::Discharging(capacity)
         };
 
-        let indicator_state = if charging || capacity >= 100 {
-            IndicatorState::Success
-        } else if capacity <= 10 {
-            IndicatorState::Danger
-        } else if capacity <= 20 {
-            IndicatorState::Warning
-        } else {
-            IndicatorState::Normal
-        };
+        let indicator_state = IndicatorState::from_level(capacity, charging, levels);
 
         Self {
             capacity,
@@ -145,8 +294,21 @@ pub enum Message {
 /// Battery monitoring module
 #[derive(Debug, Default)]
 pub struct Battery {
-    data: Option<BatteryData> /* sender: Option<ModuleEventSender<BatteryEvent>>, // Unused -
-                               * battery events not sent to UI */
+    data:           Option<BatteryData>,
+    // sender: Option<ModuleEventSender<BatteryEvent>> is unused, battery events are not sent
+    // to the UI.
+    /// Start of the current charging animation.
+    charging_since: Option<Instant>,
+    /// Set when UPower reported an error and has not been re-initialised.
+    unavailable:    bool,
+    estimate:       RemainingEstimate,
+    /// Levels from `warn_thresholds` that raise a notification.
+    thresholds:     Vec<u8>,
+    /// Breakpoints of the indicator state.
+    levels:         BatteryLevels,
+    alerts:         LowBatteryAlerts,
+    /// Context the low battery notification runs in.
+    commands:       CommandContext
 }
 
 impl Battery {
@@ -159,10 +321,31 @@ impl Battery {
         self.data.as_ref()
     }
 
+    /// Whether the UPower service is currently delivering updates.
+    pub fn is_available(&self) -> bool {
+        !self.unavailable
+    }
+
+    /// Current frame of the charging animation, or `None` when the battery
+    /// is not charging or already full.
+    pub fn charging_frame(&self, now: Instant) -> Option<Icons> {
+        match (self.data.as_ref()?.icon, self.charging_since) {
+            (BatteryIcon::Charging(capacity), Some(since)) => Some(charging_frame(
+                capacity,
+                now.saturating_duration_since(since)
+            )),
+            _ => None
+        }
+    }
+
     /// Registers module with event system
-    pub fn register(&mut self, _ctx: &ModuleContext) {
+    pub fn register(&mut self, ctx: &ModuleContext, config: &BatteryModuleConfig) {
         // BatteryEvent is not used for UI updates, Battery module only
         // subscribes to service events
+        self.thresholds = config.warn_thresholds.clone();
+        self.levels = config.levels;
+        self.commands = ctx.command_context("Battery");
+        self.alerts = LowBatteryAlerts::default();
     }
 
     /// Processes incoming messages from GUI layer
@@ -175,6 +358,8 @@ impl Battery {
     fn handle_service_event(&mut self, event: ServiceEvent<UPowerService>) {
         match event {
             ServiceEvent::Init(service) => {
+                self.unavailable = false;
+                self.estimate = RemainingEstimate::default();
                 if let Some(battery) = service.battery {
                     self.update_battery_data(battery, service.power_profile.into());
                 }
@@ -190,6 +375,7 @@ impl Battery {
                 }
                 UPowerEvent::NoBattery => {
                     self.data = None;
+                    self.charging_since = None;
                 }
                 UPowerEvent::UpdatePowerProfile(profile) => {
                     if let Some(data) = &mut self.data {
@@ -199,6 +385,7 @@ impl Battery {
             },
             ServiceEvent::Error(_) => {
                 warn!("Failed to receive battery updates from UPower");
+                self.unavailable = true;
             }
         }
     }
@@ -209,23 +396,33 @@ impl Battery {
         power_profile: PowerProfile
     ) {
         let capacity = upower_data.capacity.clamp(0, 100) as u8;
-        let charging = matches!(
-            upower_data.status,
-            crate::services::upower::BatteryStatus::Charging(_)
-        );
+        let status = self.estimate.smooth(upower_data.status);
+        let (charging, time_remaining) = match status {
+            BatteryStatus::Charging(remaining) => (true, Some(remaining)),
+            BatteryStatus::Discharging(remaining) => (false, Some(remaining)),
+            BatteryStatus::Full => (false, None)
+        };
 
-        let data = BatteryData::new(capacity, charging, None, power_profile);
+        let data = BatteryData::new(
+            capacity,
+            charging,
+            time_remaining.filter(|remaining| !remaining.is_zero()),
+            power_profile,
+            &self.levels
+        );
 
-        // Battery events are not currently sent to the UI
-        // Notification logic could be added here in the future
-        // if !charging {
-        //     if capacity <= 5 {
-        //         // Critical battery notification
-        //     } else if capacity <= 15 {
-        //         // Low battery notification
-        //     }
-        // }
+        if self
+            .alerts
+            .check(capacity, charging, &self.thresholds)
+            .is_some()
+        {
+            notify_low_battery(capacity, self.commands.clone());
+        }
 
+        self.charging_since = match data.icon {
+            BatteryIcon::Charging(_) => self.charging_since.or_else(|| Some(Instant::now())),
+            _ => None
+        };
         self.data = Some(data);
     }
 }
@@ -236,31 +433,215 @@ mod tests {
 
     #[test]
     fn battery_data_critical_state() {
-        let data = BatteryData::new(5, false, None, PowerProfile::default());
+        let data = BatteryData::new(
+            5,
+            false,
+            None,
+            PowerProfile::default(),
+            &BatteryLevels::default()
+        );
         assert_eq!(data.indicator_state, IndicatorState::Danger);
     }
 
     #[test]
     fn battery_data_warning_state() {
-        let data = BatteryData::new(15, false, None, PowerProfile::default());
+        let data = BatteryData::new(
+            15,
+            false,
+            None,
+            PowerProfile::default(),
+            &BatteryLevels::default()
+        );
         assert_eq!(data.indicator_state, IndicatorState::Warning);
     }
 
     #[test]
     fn battery_data_charging_success() {
-        let data = BatteryData::new(50, true, None, PowerProfile::default());
+        let data = BatteryData::new(
+            50,
+            true,
+            None,
+            PowerProfile::default(),
+            &BatteryLevels::default()
+        );
         assert_eq!(data.indicator_state, IndicatorState::Success);
     }
 
+    #[test]
+    fn indicator_state_follows_configured_levels() {
+        let levels = BatteryLevels {
+            warning: 40,
+            danger:  25
+        };
+
+        assert_eq!(
+            IndicatorState::from_level(30, false, &levels),
+            IndicatorState::Warning
+        );
+        assert_eq!(
+            IndicatorState::from_level(25, false, &levels),
+            IndicatorState::Danger
+        );
+        assert_eq!(
+            IndicatorState::from_level(5, true, &levels),
+            IndicatorState::Success
+        );
+    }
+
+    #[test]
+    fn battery_data_uses_the_given_levels() {
+        let levels = BatteryLevels {
+            warning: 40,
+            danger:  25
+        };
+
+        let data = BatteryData::new(30, false, None, PowerProfile::default(), &levels);
+        assert_eq!(data.indicator_state, IndicatorState::Warning);
+
+        let data = BatteryData::new(20, false, None, PowerProfile::default(), &levels);
+        assert_eq!(data.indicator_state, IndicatorState::Danger);
+    }
+
     #[test]
     fn battery_icon_charging() {
-        let data = BatteryData::new(50, true, None, PowerProfile::default());
+        let data = BatteryData::new(
+            50,
+            true,
+            None,
+            PowerProfile::default(),
+            &BatteryLevels::default()
+        );
         assert!(matches!(data.icon, BatteryIcon::Charging(50)));
     }
 
+    #[test]
+    fn service_error_marks_battery_unavailable() {
+        let mut battery = Battery::new();
+        assert!(battery.is_available());
+
+        battery.update(Message::Event(ServiceEvent::Error(
+            "UPower is not running".to_owned()
+        )));
+
+        assert!(!battery.is_available());
+    }
+
+    #[test]
+    fn low_battery_alert_fires_once_per_downward_crossing() {
+        let mut alerts = LowBatteryAlerts::default();
+        let thresholds = [20, 10];
+
+        assert_eq!(alerts.check(25, false, &thresholds), None);
+        assert_eq!(alerts.check(21, false, &thresholds), None);
+        assert_eq!(alerts.check(20, false, &thresholds), Some(20));
+
+        // Hovering around the threshold stays quiet.
+        assert_eq!(alerts.check(21, false, &thresholds), None);
+        assert_eq!(alerts.check(20, false, &thresholds), None);
+        assert_eq!(alerts.check(19, false, &thresholds), None);
+
+        assert_eq!(alerts.check(8, false, &thresholds), Some(10));
+        assert_eq!(alerts.check(5, false, &thresholds), None);
+    }
+
+    #[test]
+    fn low_battery_alert_reports_lowest_of_several_crossings() {
+        let mut alerts = LowBatteryAlerts::default();
+
+        assert_eq!(alerts.check(30, false, &[20, 10]), None);
+        assert_eq!(alerts.check(9, false, &[20, 10]), Some(10));
+        assert_eq!(alerts.check(5, false, &[20, 10]), None);
+    }
+
+    #[test]
+    fn charging_above_a_threshold_rearms_it() {
+        let mut alerts = LowBatteryAlerts::default();
+        let thresholds = [20];
+
+        alerts.check(21, false, &thresholds);
+        assert_eq!(alerts.check(20, false, &thresholds), Some(20));
+
+        // Charging up to the threshold does not re-arm it yet.
+        assert_eq!(alerts.check(20, true, &thresholds), None);
+        assert_eq!(alerts.check(21, false, &thresholds), None);
+        assert_eq!(alerts.check(20, false, &thresholds), None);
+
+        assert_eq!(alerts.check(22, true, &thresholds), None);
+        assert_eq!(alerts.check(21, false, &thresholds), None);
+        assert_eq!(alerts.check(20, false, &thresholds), Some(20));
+    }
+
+    #[test]
+    fn first_reading_never_alerts() {
+        let mut alerts = LowBatteryAlerts::default();
+
+        assert_eq!(alerts.check(5, false, &[20, 10]), None);
+    }
+
+    #[test]
+    fn charging_frames_fill_from_current_level() {
+        let frames: Vec<Icons> = (0..4)
+            .map(|step| charging_frame(50, CHARGING_FRAME_DURATION * step))
+            .collect();
+
+        assert_eq!(
+            frames,
+            vec![
+                Icons::Battery2,
+                Icons::Battery3,
+                Icons::Battery4,
+                Icons::Battery2
+            ]
+        );
+    }
+
+    #[test]
+    fn charging_frames_pulse_when_nearly_full() {
+        assert_eq!(charging_frame(95, Duration::ZERO), Icons::Battery3);
+        assert_eq!(charging_frame(95, CHARGING_FRAME_DURATION), Icons::Battery4);
+    }
+
+    #[test]
+    fn remaining_estimate_damps_swings() {
+        let mut estimate = RemainingEstimate::default();
+        let hours = |h: u64| Duration::from_secs(h * 3600);
+
+        estimate.smooth(BatteryStatus::Discharging(hours(4)));
+        let BatteryStatus::Discharging(smoothed) =
+            estimate.smooth(BatteryStatus::Discharging(hours(1)))
+        else {
+            panic!("direction should be kept");
+        };
+
+        assert!(smoothed > hours(3));
+    }
+
+    #[test]
+    fn remaining_estimate_restarts_on_direction_change() {
+        let mut estimate = RemainingEstimate::default();
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+
+        estimate.smooth(BatteryStatus::Discharging(minutes(240)));
+
+        assert!(matches!(
+            estimate.smooth(BatteryStatus::Charging(minutes(50))),
+            BatteryStatus::Charging(remaining) if remaining == minutes(50)
+        ));
+        assert!(matches!(
+            estimate.smooth(BatteryStatus::Charging(Duration::ZERO)),
+            BatteryStatus::Charging(remaining) if remaining == minutes(50)
+        ));
+    }
+
     #[test]
     fn battery_icon_discharging() {
-        let data = BatteryData::new(75, false, None, PowerProfile::default());
+        let data = BatteryData::new(
+            75,
+            false,
+            None,
+            PowerProfile::default(),
+            &BatteryLevels::default()
+        );
         assert!(matches!(data.icon, BatteryIcon::Discharging(75)));
     }
 }
@@ -0,0 +1,128 @@
+use std::process::Stdio;
+
+use chrono::{NaiveDate, NaiveTime};
+use log::warn;
+
+use crate::utils::launcher::CommandContext;
+
+/// One event listed under the calendar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgendaEvent {
+    /// Start time, `None` for all-day events.
+    pub time:  Option<NaiveTime>,
+    pub title: String
+}
+
+/// Events of the day picked in the calendar.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Agenda {
+    /// No day was picked yet.
+    #[default]
+    Empty,
+    /// The agenda command runs for this day.
+    Loading(NaiveDate),
+    Loaded(NaiveDate, Vec<AgendaEvent>),
+    /// The agenda command could not be run or failed for this day.
+    Failed(NaiveDate)
+}
+
+impl Agenda {
+    /// Day the agenda belongs to.
+    pub fn date(&self) -> Option<NaiveDate> {
+        match self {
+            Agenda::Empty => None,
+            Agenda::Loading(date) | Agenda::Loaded(date, _) | Agenda::Failed(date) => Some(*date)
+        }
+    }
+}
+
+/// Parse agenda command output, one event per line. Lines starting with an
+/// `HH:MM` time are timed events; any other line is an all-day title.
+pub fn parse_agenda(output: &str) -> Vec<AgendaEvent> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let timed = line
+                .split_once(char::is_whitespace)
+                .and_then(|(time, title)| {
+                    NaiveTime::parse_from_str(time, "%H:%M")
+                        .ok()
+                        .map(|time| (time, title.trim()))
+                });
+
+            match timed {
+                Some((time, title)) => AgendaEvent {
+                    time:  Some(time),
+                    title: title.to_owned()
+                },
+                None => AgendaEvent {
+                    time:  None,
+                    title: line.to_owned()
+                }
+            }
+        })
+        .collect()
+}
+
+/// Run the agenda `command` in `context`, whose date it receives as
+/// `HYDEBAR_DATE`. `None` when the command could not be run or exited with an
+/// error.
+pub(super) async fn fetch_agenda(
+    command: String,
+    context: CommandContext
+) -> Option<Vec<AgendaEvent>> {
+    match context
+        .shell_command(&command)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => {
+            Some(parse_agenda(&String::from_utf8_lossy(&output.stdout)))
+        }
+        Ok(output) => {
+            warn!("Agenda command exited with {}", output.status);
+            None
+        }
+        Err(err) => {
+            warn!("Failed to run agenda command: {err}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_timed_and_all_day_events() {
+        let events =
+            parse_agenda("09:30 Standup\n\n  Public holiday \n14:00\tDentist\n25:00 Bogus\n");
+
+        assert_eq!(
+            events,
+            [
+                AgendaEvent {
+                    time:  NaiveTime::from_hms_opt(9, 30, 0),
+                    title: "Standup".to_owned()
+                },
+                AgendaEvent {
+                    time:  None,
+                    title: "Public holiday".to_owned()
+                },
+                AgendaEvent {
+                    time:  NaiveTime::from_hms_opt(14, 0, 0),
+                    title: "Dentist".to_owned()
+                },
+                AgendaEvent {
+                    time:  None,
+                    title: "25:00 Bogus".to_owned()
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::{NaiveDateTime, Timelike};
+
+use crate::config::ClockAlarm;
+
+/// Tracks which configured alarms are due, firing each at most once per
+/// matching minute regardless of how often the clock ticks.
+#[derive(Debug, Clone, Default)]
+pub(super) struct AlarmSchedule {
+    alarms:      Vec<ClockAlarm>,
+    last_minute: Option<NaiveDateTime>
+}
+
+impl AlarmSchedule {
+    /// Creates a schedule that treats `now`'s minute as already handled, so
+    /// re-registering after a config reload does not repeat an alarm.
+    pub(super) fn new(alarms: Vec<ClockAlarm>, now: NaiveDateTime) -> Self {
+        Self {
+            alarms,
+            last_minute: truncate_to_minute(now)
+        }
+    }
+
+    /// Returns the commands whose time matches `now`, once per minute.
+    pub(super) fn due(&mut self, now: NaiveDateTime) -> Vec<&str> {
+        let minute = truncate_to_minute(now);
+        if minute.is_none() || minute == self.last_minute {
+            return Vec::new();
+        }
+        self.last_minute = minute;
+
+        self.alarms
+            .iter()
+            .filter(|alarm| {
+                u32::from(alarm.time.hour) == now.hour()
+                    && u32::from(alarm.time.minute) == now.minute()
+            })
+            .map(|alarm| alarm.command.as_str())
+            .collect()
+    }
+}
+
+fn truncate_to_minute(time: NaiveDateTime) -> Option<NaiveDateTime> {
+    time.with_second(0)?.with_nanosecond(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::config::AlarmTime;
+
+    fn at(hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 5, 1)
+            .and_then(|date| date.and_hms_opt(hour, minute, second))
+            .expect("valid timestamp")
+    }
+
+    fn alarm(hour: u8, minute: u8, command: &str) -> ClockAlarm {
+        ClockAlarm {
+            time:    AlarmTime {
+                hour,
+                minute
+            },
+            command: command.to_string()
+        }
+    }
+
+    #[test]
+    fn fires_once_per_matching_minute() {
+        let mut schedule = AlarmSchedule::new(vec![alarm(7, 30, "wake")], at(7, 29, 55));
+
+        assert_eq!(schedule.due(at(7, 30, 0)), ["wake"]);
+        assert!(schedule.due(at(7, 30, 5)).is_empty());
+        assert!(schedule.due(at(7, 31, 0)).is_empty());
+    }
+
+    #[test]
+    fn skips_the_minute_it_was_created_in() {
+        let mut schedule = AlarmSchedule::new(vec![alarm(7, 30, "wake")], at(7, 30, 10));
+
+        assert!(schedule.due(at(7, 30, 15)).is_empty());
+    }
+}
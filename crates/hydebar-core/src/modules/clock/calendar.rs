@@ -1,18 +1,20 @@
-use chrono::{Datelike, Local, Month, NaiveDate};
+use chrono::{Datelike, Local, Locale, Month, NaiveDate};
 
 /// Calendar state for navigation and current view.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CalendarState {
-    year:  i32,
-    month: u32,
+    year:     i32,
+    month:    u32,
+    selected: Option<NaiveDate>,
 }
 
 impl Default for CalendarState {
     fn default() -> Self {
         let now = Local::now();
         Self {
-            year:  now.year(),
-            month: now.month(),
+            year:     now.year(),
+            month:    now.month(),
+            selected: None,
         }
     }
 }
@@ -32,7 +34,11 @@ impl CalendarState {
         if !(1..=12).contains(&month) {
             return Err(CalendarError::InvalidMonth { month });
         }
-        Ok(Self { year, month })
+        Ok(Self {
+            year,
+            month,
+            selected: None,
+        })
     }
 
     /// Returns current year.
@@ -45,6 +51,16 @@ impl CalendarState {
         self.month
     }
 
+    /// Returns the day picked in the calendar, if any.
+    pub fn selected(&self) -> Option<NaiveDate> {
+        self.selected
+    }
+
+    /// Picks `date`, e.g. to show its agenda.
+    pub fn select(&mut self, date: NaiveDate) {
+        self.selected = Some(date);
+    }
+
     /// Navigates to previous month.
     pub fn previous_month(&mut self) {
         if self.month == 1 {
@@ -72,6 +88,13 @@ impl CalendarState {
             .unwrap_or("Unknown")
     }
 
+    /// Returns month name in the given locale.
+    pub fn localized_month_name(&self, locale: Locale) -> String {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1)
+            .map(|date| date.format_localized("%B", locale).to_string())
+            .unwrap_or_else(|| self.month_name().to_owned())
+    }
+
     /// Generates calendar data for current state.
     pub fn generate_calendar(&self) -> CalendarData {
         CalendarData::generate(self.year, self.month)
@@ -244,6 +267,12 @@ mod tests {
         assert_eq!(state.month_name(), "December");
     }
 
+    #[test]
+    fn calendar_state_localized_month_name() {
+        let state = CalendarState::new(2024, 1).expect("valid month");
+        assert_eq!(state.localized_month_name(Locale::de_DE), "Januar");
+    }
+
     #[test]
     fn calendar_data_generates_42_days() {
         let data = CalendarData::generate(2024, 10);
@@ -1,32 +1,48 @@
+mod agenda;
+mod alarm;
 mod calendar;
 mod view;
 
-use std::time::Duration;
+use std::{fmt::Display, time::Duration};
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, Locale, NaiveDate, TimeZone};
+use chrono_tz::Tz;
 use iced::Element;
-use log::error;
-use tokio::{task::JoinHandle, time::interval};
+use log::{error, warn};
+use tokio::{runtime::Handle, task::JoinHandle, time::interval};
 
+pub use agenda::{Agenda, AgendaEvent};
+use alarm::AlarmSchedule;
 pub use calendar::{CalendarData, CalendarError, CalendarState, DayInfo};
 
 use crate::{
-    ModuleContext, ModuleEventSender, event_bus::ModuleEvent, menu::MenuType,
-    modules::{Module, ModuleError, OnModulePress, weather::WeatherData}
+    ModuleContext, ModuleEventSender,
+    config::{CalendarTodayConfig, ClockAlarm, ClockModuleConfig},
+    event_bus::ModuleEvent,
+    menu::MenuType,
+    modules::{Module, ModuleError, OnModulePress, weather::WeatherData},
+    utils::{
+        font,
+        launcher::{self, CommandContext},
+        locale
+    }
 };
 
 /// Clock data for rendering
 #[derive(Debug, Clone)]
 pub struct ClockData {
     pub current_time: DateTime<Local>,
-    pub weather:      Option<WeatherData>
+    pub weather:      Option<WeatherData>,
+    /// Zones rendered after local time, in configuration order.
+    pub timezones:    Vec<Tz>
 }
 
 impl ClockData {
     pub fn new() -> Self {
         Self {
             current_time: Local::now(),
-            weather:      None
+            weather:      None,
+            timezones:    Vec::new()
         }
     }
 
@@ -38,12 +54,51 @@ impl ClockData {
         self.weather = Some(weather);
     }
 
-    /// Format the time according to chrono format string
-    pub fn format(&self, format: &str) -> String {
-        self.current_time.format(format).to_string()
+    /// Format local time with `locale`'s month and day names and AM/PM
+    /// marker, which plain `%p` always renders in English
+    pub fn format_localized(&self, format: &str, locale: Locale) -> String {
+        format_time(&self.current_time, format, Some(locale))
+    }
+
+    /// Format the time according to chrono format string, in `timezone`
+    /// instead of local time and using `locale` for month and day names when
+    /// given
+    pub fn format(&self, format: &str, locale: Option<Locale>, timezone: Option<Tz>) -> String {
+        match (timezone, locale) {
+            (Some(timezone), _) => {
+                format_time(&self.current_time.with_timezone(&timezone), format, locale)
+            }
+            (None, Some(locale)) => self.format_localized(format, locale),
+            (None, None) => format_time(&self.current_time, format, None),
+        }
+    }
+}
+
+fn format_time<Z>(time: &DateTime<Z>, format: &str, locale: Option<Locale>) -> String
+where
+    Z: TimeZone,
+    Z::Offset: Display,
+{
+    match locale {
+        Some(locale) => time.format_localized(format, locale).to_string(),
+        None => time.format(format).to_string(),
     }
 }
 
+/// Parses IANA zone names, warning about and skipping the unknown ones.
+fn parse_timezones(names: &[String]) -> Vec<Tz> {
+    names
+        .iter()
+        .filter_map(|name| match name.parse::<Tz>() {
+            Ok(timezone) => Some(timezone),
+            Err(err) => {
+                warn!("Ignoring unknown clock timezone {name:?}: {err}");
+                None
+            }
+        })
+        .collect()
+}
+
 impl Default for ClockData {
     fn default() -> Self {
         Self::new()
@@ -63,6 +118,14 @@ pub enum Message {
     UpdateWeather(WeatherData),
     PreviousMonth,
     NextMonth,
+    /// Copy the current time; the clipboard write happens in the GUI layer
+    CopyTimestamp,
+    /// Pick a day in the calendar and load its agenda
+    SelectDay(NaiveDate),
+    /// Result of the agenda command for a day, `None` when it failed
+    AgendaLoaded(NaiveDate, Option<Vec<AgendaEvent>>),
+    /// Run the configured right-click command
+    RunCommand(String),
 }
 
 /// Clock module - business logic only, no GUI!
@@ -73,6 +136,13 @@ pub struct Clock {
     sender:         Option<ModuleEventSender<ClockEvent>>,
     task:           Option<JoinHandle<()>>,
     calendar_state: CalendarState,
+    locale:         Option<Locale>,
+    alarms:         Vec<ClockAlarm>,
+    agenda:         Agenda,
+    agenda_cmd:     Option<String>,
+    agenda_sender:  Option<ModuleEventSender<Message>>,
+    runtime:        Option<Handle>,
+    commands:       CommandContext,
 }
 
 impl Default for Clock {
@@ -83,6 +153,13 @@ impl Default for Clock {
             sender:         None,
             task:           None,
             calendar_state: CalendarState::default(),
+            locale:         None,
+            alarms:         Vec::new(),
+            agenda:         Agenda::Empty,
+            agenda_cmd:     None,
+            agenda_sender:  None,
+            runtime:        None,
+            commands:       CommandContext::module("Clock"),
         }
     }
 }
@@ -102,12 +179,35 @@ impl Clock {
         &self.calendar_state
     }
 
-    /// Initialize with module context and time format
-    pub fn register(&mut self, ctx: &ModuleContext, format: &str) {
-        self.tick_interval = Self::determine_interval(format);
+    /// Set the locale used for month and day names, e.g. `de_DE`; a
+    /// `clock.locale` applied on registration takes precedence
+    pub fn set_locale(&mut self, name: Option<&str>) {
+        self.locale = name.and_then(locale::parse);
+    }
+
+    /// Set the alarms checked on every tick from the next registration on
+    pub fn set_alarms(&mut self, alarms: &[ClockAlarm]) {
+        self.alarms = alarms.to_vec();
+    }
+
+    /// Initialize with module context, time formats and extra timezones
+    pub fn register(&mut self, ctx: &ModuleContext, config: &ClockModuleConfig) {
+        let formats =
+            std::iter::once(config.format.as_str()).chain(config.timezone_format.as_deref());
+        self.tick_interval = Self::determine_interval(formats);
+        self.data.timezones = parse_timezones(&config.extra_timezones);
+        // An unknown name is reported by the parser and keeps the bar locale.
+        if let Some(locale) = config.locale.as_deref().and_then(locale::parse) {
+            self.locale = Some(locale);
+        }
         self.data.update();
         self.sender =
             Some(ctx.module_sender(|_event: ClockEvent| ModuleEvent::Clock(Message::Update)));
+        self.agenda_cmd = config.agenda_cmd.clone();
+        self.agenda_sender = Some(ctx.module_sender(ModuleEvent::Clock));
+        self.runtime = Some(ctx.runtime_handle().clone());
+        self.commands = ctx.command_context("Clock");
+        self.agenda = Agenda::Empty;
 
         if let Some(task) = self.task.take() {
             task.abort();
@@ -116,6 +216,8 @@ impl Clock {
         if let Some(sender) = self.sender.clone() {
             let interval_duration = self.tick_interval;
             let update_sender = sender.clone();
+            let mut alarms = AlarmSchedule::new(self.alarms.clone(), Local::now().naive_local());
+            let commands = self.commands.clone();
 
             self.task = Some(ctx.runtime_handle().spawn(async move {
                 let mut ticker = interval(interval_duration);
@@ -124,6 +226,13 @@ impl Clock {
                     ticker.tick().await;
                     let now = Local::now();
 
+                    for command in alarms.due(now.naive_local()) {
+                        launcher::execute_command(
+                            command.to_string(),
+                            commands.clone().with_date(now.date_naive())
+                        );
+                    }
+
                     if let Err(err) = update_sender.try_send(ClockEvent::Tick(now)) {
                         error!("Failed to publish clock tick: {err}");
                     }
@@ -153,22 +262,85 @@ impl Clock {
             Message::NextMonth => {
                 self.calendar_state.next_month();
             }
+            Message::CopyTimestamp => {}
+            Message::RunCommand(command) => {
+                launcher::execute_command(command, self.commands.clone());
+            }
+            Message::SelectDay(date) => {
+                self.calendar_state.select(date);
+                self.load_agenda(date);
+            }
+            Message::AgendaLoaded(date, events) => {
+                // Ignore results for a day that is no longer picked.
+                if self.agenda.date() == Some(date) {
+                    self.agenda = match events {
+                        Some(events) => Agenda::Loaded(date, events),
+                        None => Agenda::Failed(date),
+                    };
+                }
+            }
         }
     }
 
-    /// Renders the calendar menu view.
-    pub fn menu_view(&self) -> Element<'_, Message> {
-        view::build_calendar_menu_view(&self.calendar_state)
+    /// Pick today in the calendar, refreshing its agenda
+    pub fn select_today(&mut self) {
+        self.update(Message::SelectDay(Local::now().date_naive()));
     }
 
-    /// Determine tick interval based on format string
-    fn determine_interval(format: &str) -> Duration {
-        const SECOND_SPECIFIERS: [&str; 6] = ["%S", "%T", "%X", "%r", "%:z", "%s"];
+    /// Run the agenda command for `date` in the background; its result
+    /// arrives as [`Message::AgendaLoaded`]
+    fn load_agenda(&mut self, date: NaiveDate) {
+        let (Some(command), Some(runtime), Some(sender)) = (
+            self.agenda_cmd.clone(),
+            self.runtime.clone(),
+            self.agenda_sender.clone(),
+        ) else {
+            return;
+        };
+
+        self.agenda = Agenda::Loading(date);
+        let context = self.commands.clone().with_date(date);
+        runtime.spawn(async move {
+            let events = agenda::fetch_agenda(command, context).await;
+            if let Err(err) = sender.try_send(Message::AgendaLoaded(date, events)) {
+                error!("Failed to publish agenda: {err}");
+            }
+        });
+    }
 
-        if SECOND_SPECIFIERS
+    /// Current time in `format`, read fresh rather than from the last tick
+    pub fn timestamp(&self, format: &str) -> String {
+        Local::now().format(format).to_string()
+    }
+
+    /// Renders the calendar menu view, marking today as `today` describes.
+    pub fn menu_view(&self, today: CalendarTodayConfig) -> Element<'_, Message> {
+        let zone_times = self
+            .data
+            .timezones
             .iter()
-            .any(|specifier| format.contains(specifier))
-        {
+            .map(|timezone| {
+                (
+                    timezone.name().to_owned(),
+                    self.data.format("%a %H:%M", self.locale, Some(*timezone)),
+                )
+            })
+            .collect();
+
+        let agenda = self.agenda_cmd.is_some().then_some(&self.agenda);
+
+        view::build_calendar_menu_view(&self.calendar_state, self.locale, zone_times, today, agenda)
+    }
+
+    /// Determine tick interval based on the format strings in use
+    fn determine_interval<'a>(formats: impl IntoIterator<Item = &'a str>) -> Duration {
+        const SECOND_SPECIFIERS: [&str; 6] = ["%S", "%T", "%X", "%r", "%:z", "%s"];
+
+        if formats.into_iter().any(|format| {
+            SECOND_SPECIFIERS
+                .iter()
+                .any(|specifier| format.contains(specifier))
+        }) {
             Duration::from_secs(1)
         } else {
             Duration::from_secs(5)
@@ -180,26 +352,58 @@ impl<M> Module<M> for Clock
 where
     M: 'static + Clone + From<Message>,
 {
-    type ViewData<'a> = &'a str;
-    type RegistrationData<'a> = &'a str;
+    type ViewData<'a> = &'a ClockModuleConfig;
+    type RegistrationData<'a> = &'a ClockModuleConfig;
 
     fn register(
         &mut self,
         ctx: &ModuleContext,
-        format: Self::RegistrationData<'_>,
+        config: Self::RegistrationData<'_>,
     ) -> Result<(), ModuleError> {
-        self.register(ctx, format);
+        self.register(ctx, config);
         Ok(())
     }
 
     fn view(
         &self,
-        format: Self::ViewData<'_>,
+        config: Self::ViewData<'_>,
     ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)> {
-        use iced::widget::text;
-
-        let clock_text = text(self.data.format(format)).into();
-        let on_press = Some(OnModulePress::ToggleMenu(MenuType::Calendar));
+        use iced::{
+            Alignment,
+            widget::{Row, text},
+        };
+
+        let zone_format = config.timezone_format.as_deref().unwrap_or(&config.format);
+        let times = std::iter::once(self.data.format(&config.format, self.locale, None)).chain(
+            self.data
+                .timezones
+                .iter()
+                .map(|timezone| self.data.format(zone_format, self.locale, Some(*timezone))),
+        );
+
+        let clock_texts = times.map(|time| {
+            let mut clock_text = text(time);
+            if self.locale.is_some() {
+                // Localized names may be in scripts that need full shaping,
+                // including right-to-left ones.
+                clock_text = clock_text.shaping(text::Shaping::Advanced);
+            }
+            if let Some(font_name) = &config.font_name {
+                clock_text = clock_text.font(font::named(font_name));
+            }
+            clock_text.into()
+        });
+        let clock_text = Row::with_children(clock_texts)
+            .align_y(Alignment::Center)
+            .spacing(12)
+            .into();
+        let on_press = Some(match &config.right_click_cmd {
+            Some(command) => OnModulePress::ToggleMenuOrAction(
+                MenuType::Calendar,
+                Box::new(M::from(Message::RunCommand(command.clone()))),
+            ),
+            None => OnModulePress::ToggleMenu(MenuType::Calendar),
+        });
 
         Some((clock_text, on_press))
     }
@@ -207,25 +411,112 @@ where
 
 #[cfg(test)]
 mod tests {
+    use chrono::TimeZone;
+
     use super::*;
 
     #[test]
     fn clock_data_format() {
         let data = ClockData::new();
-        let formatted = data.format("%H:%M");
+        let formatted = data.format("%H:%M", None, None);
         assert!(formatted.contains(':'));
         assert_eq!(formatted.len(), 5);
     }
 
+    #[test]
+    fn clock_data_format_localized() {
+        let mut data = ClockData::new();
+        data.current_time = Local
+            .with_ymd_and_hms(2024, 3, 4, 12, 0, 0)
+            .single()
+            .expect("valid time");
+
+        assert_eq!(data.format("%A %B", Some(Locale::de_DE), None), "Montag März");
+        assert_eq!(data.format("%A %B", None, None), "Monday March");
+    }
+
+    #[test]
+    fn twelve_hour_format_is_localized() {
+        let mut data = ClockData::new();
+        data.current_time = Local
+            .with_ymd_and_hms(2024, 3, 4, 15, 5, 0)
+            .single()
+            .expect("valid time");
+
+        assert_eq!(data.format_localized("%I:%M %p", Locale::en_US), "03:05 PM");
+
+        for locale in [Locale::en_US, Locale::ja_JP, Locale::ko_KR] {
+            let formatted = data.format_localized("%I:%M %p", locale);
+            let (hour, rest) = formatted.split_once(':').expect("hour separator");
+            let (_, meridiem) = rest.split_once(' ').expect("meridiem separator");
+
+            assert_eq!(hour, "03", "{locale:?}");
+            assert!(!meridiem.is_empty(), "{locale:?}");
+        }
+    }
+
+    #[test]
+    fn clock_data_format_in_timezone() {
+        let mut data = ClockData::new();
+        data.current_time = chrono::Utc
+            .with_ymd_and_hms(2024, 3, 4, 23, 30, 0)
+            .single()
+            .expect("valid time")
+            .with_timezone(&Local);
+
+        assert_eq!(
+            data.format("%a %H:%M", None, Some(Tz::Asia__Tokyo)),
+            "Tue 08:30"
+        );
+        assert_eq!(
+            data.format("%a %H:%M", None, Some(Tz::America__New_York)),
+            "Mon 18:30"
+        );
+    }
+
+    #[test]
+    fn parse_timezones_skips_unknown_names() {
+        let names = ["Asia/Tokyo".to_owned(), "Mars/Olympus".to_owned()];
+
+        assert_eq!(parse_timezones(&names), vec![Tz::Asia__Tokyo]);
+    }
+
     #[test]
     fn determine_interval_with_seconds() {
-        let interval = Clock::determine_interval("%H:%M:%S");
+        let interval = Clock::determine_interval(["%H:%M:%S"]);
         assert_eq!(interval, Duration::from_secs(1));
     }
 
     #[test]
     fn determine_interval_without_seconds() {
-        let interval = Clock::determine_interval("%H:%M");
+        let interval = Clock::determine_interval(["%H:%M"]);
         assert_eq!(interval, Duration::from_secs(5));
     }
+
+    #[test]
+    fn determine_interval_checks_every_format() {
+        let interval = Clock::determine_interval(["%H:%M", "%H:%M:%S %Z"]);
+        assert_eq!(interval, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn right_click_runs_the_configured_command() {
+        let clock = Clock::new();
+        let mut config = ClockModuleConfig::default();
+
+        let (_, on_press) = Module::<Message>::view(&clock, &config).expect("clock view");
+        assert!(matches!(
+            on_press,
+            Some(OnModulePress::ToggleMenu(MenuType::Calendar))
+        ));
+
+        config.right_click_cmd = Some("gnome-calendar".to_owned());
+        let (_, on_press) = Module::<Message>::view(&clock, &config).expect("clock view");
+        match on_press {
+            Some(OnModulePress::ToggleMenuOrAction(MenuType::Calendar, message)) => {
+                assert!(matches!(*message, Message::RunCommand(ref cmd) if cmd == "gnome-calendar"));
+            }
+            other => panic!("unexpected press action: {other:?}"),
+        }
+    }
 }
@@ -1,24 +1,62 @@
+use chrono::{Days, Locale, NaiveDate};
 use iced::{
     Alignment, Border, Color, Element, Length, Theme,
     widget::{Column, Row, button, column, container, horizontal_rule, row, text},
 };
 
-use super::{CalendarState, Message};
-use crate::components::icons::{Icons, icon};
+use super::{Agenda, CalendarState, Message};
+use crate::{
+    components::icons::{Icons, icon},
+    config::{CalendarTodayConfig, TodayStyle},
+};
 
 const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
 
-/// Renders the calendar menu view with month navigation and day grid.
-pub fn build_calendar_menu_view(state: &CalendarState) -> Element<'_, Message> {
+/// Abbreviated weekday names starting with Monday, in `locale` when given.
+fn weekday_names(locale: Option<Locale>) -> Vec<String> {
+    let Some(locale) = locale else {
+        return WEEKDAYS.iter().map(|day| (*day).to_owned()).collect();
+    };
+
+    // 2024-01-01 was a Monday.
+    let monday = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap_or_default();
+    (0..7)
+        .map(|offset| {
+            (monday + Days::new(offset))
+                .format_localized("%a", locale)
+                .to_string()
+        })
+        .collect()
+}
+
+/// Renders the calendar menu view with month navigation and day grid,
+/// followed by the current time in each extra timezone as `(zone, time)` pairs.
+/// Today is marked as `today` describes. The events of the picked day are
+/// listed below the grid when an agenda source is configured.
+pub fn build_calendar_menu_view<'a>(
+    state: &'a CalendarState,
+    locale: Option<Locale>,
+    zone_times: Vec<(String, String)>,
+    today: CalendarTodayConfig,
+    agenda: Option<&'a Agenda>,
+) -> Element<'a, Message> {
     let calendar_data = state.generate_calendar();
+    let month_name = match locale {
+        Some(locale) => state.localized_month_name(locale),
+        None => state.month_name().to_owned(),
+    };
 
     let header = row![
         button(icon(Icons::LeftChevron))
             .on_press(Message::PreviousMonth)
             .style(nav_button_style),
-        container(text(format!("{} {}", state.month_name(), state.year())).size(18))
-            .width(Length::Fill)
-            .align_x(Alignment::Center),
+        container(
+            text(format!("{} {}", month_name, state.year()))
+                .size(18)
+                .shaping(text::Shaping::Advanced)
+        )
+        .width(Length::Fill)
+        .align_x(Alignment::Center),
         button(icon(Icons::RightChevron))
             .on_press(Message::NextMonth)
             .style(nav_button_style),
@@ -27,10 +65,10 @@ pub fn build_calendar_menu_view(state: &CalendarState) -> Element<'_, Message> {
     .spacing(8);
 
     let weekday_header = Row::with_children(
-        WEEKDAYS
-            .iter()
+        weekday_names(locale)
+            .into_iter()
             .map(|day| {
-                container(text(*day).size(12))
+                container(text(day).size(12).shaping(text::Shaping::Advanced))
                     .width(Length::Fixed(36.))
                     .height(Length::Shrink)
                     .align_x(Alignment::Center)
@@ -47,7 +85,13 @@ pub fn build_calendar_menu_view(state: &CalendarState) -> Element<'_, Message> {
                 .map(|day_info| {
                     let day_text = text(day_info.day.to_string()).size(14);
                     let in_month = day_info.in_month;
-                    let is_today = day_info.is_today;
+                    let today = day_info.is_today.then_some(today);
+                    let date = in_month
+                        .then(|| {
+                            NaiveDate::from_ymd_opt(state.year(), state.month(), day_info.day)
+                        })
+                        .flatten();
+                    let selected = date.is_some() && date == state.selected();
 
                     let day_button = button(
                         container(day_text)
@@ -58,8 +102,9 @@ pub fn build_calendar_menu_view(state: &CalendarState) -> Element<'_, Message> {
                     )
                     .width(Length::Fixed(36.))
                     .height(Length::Fixed(36.))
+                    .on_press_maybe(date.map(Message::SelectDay))
                     .style(move |theme: &Theme, status: button::Status| {
-                        day_button_style(theme, status, in_month, is_today)
+                        day_button_style(theme, status, in_month, selected, today)
                     });
 
                     day_button.into()
@@ -76,16 +121,85 @@ pub fn build_calendar_menu_view(state: &CalendarState) -> Element<'_, Message> {
 
     let calendar_width = 7. * 36. + 6. * 4.;
 
-    column![
-        header,
-        horizontal_rule(1),
-        weekday_header,
-        calendar_grid
-    ]
-    .spacing(8)
-    .padding(4)
-    .width(Length::Fixed(calendar_width))
-    .into()
+    let zones = (!zone_times.is_empty()).then(|| {
+        Column::with_children(
+            zone_times
+                .into_iter()
+                .map(|(zone, time)| {
+                    row![
+                        text(zone).size(12).width(Length::Fill),
+                        text(time).size(12).shaping(text::Shaping::Advanced)
+                    ]
+                    .spacing(8)
+                    .into()
+                })
+                .collect::<Vec<_>>(),
+        )
+        .spacing(4)
+    });
+
+    let agenda = agenda.map(|agenda| agenda_view(agenda, locale));
+
+    let copy_button = button(
+        row![icon(Icons::Copy), text("Copy timestamp").size(12)]
+            .spacing(8)
+            .align_y(Alignment::Center),
+    )
+    .on_press(Message::CopyTimestamp)
+    .style(nav_button_style)
+    .width(Length::Fill);
+
+    column![header, horizontal_rule(1), weekday_header, calendar_grid]
+        .push_maybe(agenda.map(|agenda| column![horizontal_rule(1), agenda].spacing(8)))
+        .push_maybe(zones.map(|zones| column![horizontal_rule(1), zones].spacing(8)))
+        .push(horizontal_rule(1))
+        .push(copy_button)
+        .spacing(8)
+        .padding(4)
+        .width(Length::Fixed(calendar_width))
+        .into()
+}
+
+/// Events of the picked day under a header with its date.
+fn agenda_view(agenda: &Agenda, locale: Option<Locale>) -> Column<'_, Message> {
+    let Some(date) = agenda.date() else {
+        return column![text("Pick a day to see its events").size(12)];
+    };
+
+    let header = match locale {
+        Some(locale) => date.format_localized("%A, %-d %B", locale).to_string(),
+        None => date.format("%A, %-d %B").to_string(),
+    };
+    let header = text(header).size(14).shaping(text::Shaping::Advanced);
+
+    let events = match agenda {
+        Agenda::Loaded(_, events) if !events.is_empty() => Column::with_children(
+            events
+                .iter()
+                .map(|event| {
+                    let time = event
+                        .time
+                        .map(|time| time.format("%H:%M").to_string())
+                        .unwrap_or_default();
+
+                    row![
+                        text(time).size(12).width(Length::Fixed(40.)),
+                        text(event.title.as_str())
+                            .size(12)
+                            .width(Length::Fill)
+                            .shaping(text::Shaping::Advanced)
+                    ]
+                    .spacing(8)
+                    .into()
+                })
+                .collect::<Vec<_>>(),
+        ),
+        Agenda::Loaded(..) => column![text("No events").size(12)],
+        Agenda::Loading(_) => column![text("Loading events…").size(12)],
+        Agenda::Failed(_) | Agenda::Empty => column![text("Could not load events").size(12)],
+    };
+
+    column![header, events.spacing(4)].spacing(8)
 }
 
 fn nav_button_style(theme: &Theme, status: button::Status) -> button::Style {
@@ -116,38 +230,53 @@ fn nav_button_style(theme: &Theme, status: button::Status) -> button::Style {
     }
 }
 
+/// Style of a day button; `today` carries the marker settings when the day
+/// is today.
 fn day_button_style(
     theme: &Theme,
     status: button::Status,
     in_month: bool,
-    is_today: bool,
+    selected: bool,
+    today: Option<CalendarTodayConfig>,
 ) -> button::Style {
-    let base_color = if in_month {
+    let mut base_color = if selected {
+        theme.extended_palette().background.strong.color
+    } else if in_month {
         theme.extended_palette().background.base.color
     } else {
         theme.extended_palette().background.weak.color
     };
 
-    let text_color = if in_month {
+    let mut text_color = if in_month {
         theme.palette().text
     } else {
         theme.extended_palette().background.weak.text
     };
 
-    let border = if is_today {
-        Border {
-            color:  theme.palette().primary,
-            width:  2.0,
-            radius: 4.0.into(),
-        }
-    } else {
-        Border {
-            width:  0.0,
-            radius: 4.0.into(),
-            color:  Color::TRANSPARENT,
-        }
+    let mut border = Border {
+        width:  0.0,
+        radius: 4.0.into(),
+        color:  Color::TRANSPARENT,
     };
 
+    if let Some(today) = today {
+        let marker = today.color.map_or(theme.palette().primary, |color| {
+            Color::from_rgb8(color.r, color.g, color.b)
+        });
+
+        match today.style {
+            TodayStyle::Outline => {
+                border.color = marker;
+                border.width = 2.0;
+            }
+            TodayStyle::Filled => {
+                base_color = marker;
+                text_color = theme.extended_palette().primary.base.text;
+            }
+            TodayStyle::Text => text_color = marker,
+        }
+    }
+
     let mut base = button::Style {
         background: Some(base_color.into()),
         border,
@@ -179,4 +308,43 @@ mod tests {
         assert_eq!(WEEKDAYS[0], "Mon");
         assert_eq!(WEEKDAYS[6], "Sun");
     }
+
+    #[test]
+    fn today_marker_follows_configured_style() {
+        let color = hex_color::HexColor::rgb(255, 0, 0);
+        let style = |style| {
+            day_button_style(
+                &Theme::Dark,
+                button::Status::Active,
+                true,
+                false,
+                Some(CalendarTodayConfig {
+                    style,
+                    color: Some(color),
+                }),
+            )
+        };
+        let red = Color::from_rgb8(255, 0, 0);
+
+        assert_eq!(style(TodayStyle::Outline).border.color, red);
+        assert_eq!(style(TodayStyle::Filled).background, Some(red.into()));
+        assert_eq!(style(TodayStyle::Text).text_color, red);
+        assert_eq!(style(TodayStyle::Text).border.width, 0.0);
+
+        let other_day = day_button_style(&Theme::Dark, button::Status::Active, true, false, None);
+        assert_eq!(other_day.border.width, 0.0);
+
+        let selected = day_button_style(&Theme::Dark, button::Status::Active, true, true, None);
+        assert_ne!(selected.background, other_day.background);
+    }
+
+    #[test]
+    fn weekday_names_follow_locale() {
+        assert_eq!(weekday_names(None)[0], "Mon");
+
+        let names = weekday_names(Some(Locale::de_DE));
+        assert_eq!(names.len(), 7);
+        assert_eq!(names[0], "Mo");
+        assert_eq!(names[6], "So");
+    }
 }
@@ -0,0 +1,128 @@
+use std::{process::Stdio, time::Duration};
+
+use iced::{Element, widget::text};
+use log::warn;
+use tokio::{task::JoinHandle, time::sleep};
+
+use super::{Module, ModuleError, OnModulePress};
+use crate::{
+    ModuleContext, config::CommandOutputModuleConfig, event_bus::ModuleEvent,
+    utils::launcher::CommandContext
+};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Output(Option<String>)
+}
+
+/// Shows the trimmed output of a command run on registration and then on a
+/// fixed interval.
+///
+/// Unlike custom modules the command is not kept running and its output is
+/// shown as is, without any JSON protocol.
+#[derive(Debug, Default)]
+pub struct CommandOutput {
+    output: Option<String>,
+    task:   Option<JoinHandle<()>>
+}
+
+impl CommandOutput {
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Output(output) => {
+                self.output = output;
+            }
+        }
+    }
+}
+
+/// Trimmed `stdout`, or `None` when nothing but whitespace was printed.
+fn trimmed(stdout: &[u8]) -> Option<String> {
+    let output = String::from_utf8_lossy(stdout);
+    let output = output.trim();
+
+    (!output.is_empty()).then(|| output.to_owned())
+}
+
+async fn run(command: &str, context: &CommandContext) -> Option<String> {
+    match context
+        .shell_command(command)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => trimmed(&output.stdout),
+        Ok(output) => {
+            warn!("command output `{command}` exited with {}", output.status);
+            None
+        }
+        Err(err) => {
+            warn!("failed to run command output `{command}`: {err}");
+            None
+        }
+    }
+}
+
+impl<M> Module<M> for CommandOutput
+where
+    M: 'static + Clone
+{
+    type ViewData<'a> = ();
+    type RegistrationData<'a> = Option<&'a CommandOutputModuleConfig>;
+
+    fn register(
+        &mut self,
+        ctx: &ModuleContext,
+        config: Self::RegistrationData<'_>
+    ) -> Result<(), ModuleError> {
+        if let Some(handle) = self.task.take() {
+            handle.abort();
+        }
+
+        self.output = None;
+
+        let Some(config) = config else {
+            return Ok(());
+        };
+
+        let sender = ctx.module_sender(ModuleEvent::CommandOutput);
+        let command = config.command.clone();
+        let interval = Duration::from_secs(config.interval.max(1));
+        let context = ctx.command_context("CommandOutput");
+
+        self.task = Some(ctx.runtime_handle().spawn(async move {
+            loop {
+                let output = run(&command, &context).await;
+                if let Err(err) = sender.try_send(Message::Output(output)) {
+                    warn!("failed to publish command output: {err}");
+                }
+
+                sleep(interval).await;
+            }
+        }));
+
+        Ok(())
+    }
+
+    fn view(
+        &self,
+        _: Self::ViewData<'_>
+    ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)> {
+        self.output
+            .as_ref()
+            .map(|output| (text(output.clone()).size(12).into(), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_is_trimmed_and_blank_output_hidden() {
+        assert_eq!(trimmed(b"  42%\n"), Some("42%".to_owned()));
+        assert_eq!(trimmed(b"\n \t\n"), None);
+        assert_eq!(trimmed(b""), None);
+    }
+}
@@ -13,7 +13,6 @@ use log::{error, info};
 use serde::Deserialize;
 use tokio::{
     io::{AsyncBufRead, AsyncBufReadExt, BufReader, Lines},
-    process::Command,
     task::JoinHandle
 };
 
@@ -23,7 +22,8 @@ use crate::{
     components::icons::{Icons, icon, icon_raw},
     config::CustomModuleDef,
     event_bus::ModuleEvent,
-    services::ServiceEvent
+    services::ServiceEvent,
+    utils::launcher::CommandContext
 };
 
 #[derive(Default, Debug)]
@@ -38,7 +38,8 @@ pub struct Custom {
 #[derive(Debug, Clone)]
 struct CustomRegistration {
     name:           Arc<str>,
-    listen_command: Arc<str>
+    listen_command: Arc<str>,
+    context:        CommandContext
 }
 
 impl Custom {
@@ -299,7 +300,10 @@ where
                 .as_ref()
                 .map(|command| CustomRegistration {
                     name:           Arc::from(definition.name.as_str()),
-                    listen_command: Arc::from(command.as_str())
+                    listen_command: Arc::from(command.as_str()),
+                    context:        ctx
+                        .command_context(definition.name.as_str())
+                        .with_cwd(definition.cwd.as_deref())
                 })
         });
 
@@ -316,11 +320,19 @@ where
         self.sender = Some(sender.clone());
         let module_name_for_task = Arc::clone(&registration.name);
         let listen_command = Arc::clone(&registration.listen_command);
+        let context = registration.context;
         let error_sender = sender.clone();
         let runtime_handle = ctx.runtime_handle().clone();
 
         self.listener_task = Some(runtime_handle.spawn(async move {
-            match run_custom_listener(module_name_for_task.clone(), listen_command, sender).await {
+            match run_custom_listener(
+                module_name_for_task.clone(),
+                listen_command,
+                &context,
+                sender
+            )
+            .await
+            {
                 Ok(()) => {}
                 Err(CustomListenerError::Command(error)) => {
                     error!(
@@ -432,11 +444,11 @@ where
 async fn run_custom_listener(
     module_name: Arc<str>,
     command: Arc<str>,
+    context: &CommandContext,
     sender: ModuleEventSender<Message>
 ) -> Result<(), CustomListenerError> {
-    let mut child = Command::new("bash")
-        .arg("-c")
-        .arg(command.as_ref())
+    let mut child = context
+        .shell_command(&command)
         .stdout(Stdio::piped())
         .spawn()
         .map_err(|err| CustomListenerError::Command(CustomCommandError::Spawn(Arc::new(err))))?;
@@ -122,6 +122,7 @@ async fn re_register_aborts_previous_listener() {
         name:       String::from("first"),
         command:    String::from("true"),
         icon:       None,
+        cwd:        None,
         listen_cmd: Some(String::from(
             r#"while true; do printf '{"alt":"first","text":"one"}
 '; sleep 0.1; done"#
@@ -160,6 +161,7 @@ async fn re_register_aborts_previous_listener() {
         name:       String::from("second"),
         command:    String::from("true"),
         icon:       None,
+        cwd:        None,
         listen_cmd: Some(String::from(
             r#"while true; do printf '{"alt":"second","text":"two"}
 '; sleep 0.1; done"#
@@ -0,0 +1,67 @@
+//! Bar indicator for the idle inhibitor owned by the settings module.
+//!
+//! The inhibitor itself lives in [`Settings`](super::settings::Settings) so
+//! the quick-settings button and the bar indicator toggle the same lock.
+
+use crate::components::icons::Icons;
+
+/// Glyph showing whether idle is inhibited: a full cup while the screen is
+/// kept awake, an empty one otherwise.
+pub fn indicator_icon(inhibited: bool) -> Icons {
+    if inhibited {
+        Icons::CoffeeFilled
+    } else {
+        Icons::CoffeeOutline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{Config, SettingsModuleConfig},
+        modules::settings::{Message, Settings},
+        outputs::Outputs,
+        services::idle_inhibitor::IdleInhibit
+    };
+
+    #[derive(Default)]
+    struct FakeInhibitor {
+        inhibited: bool
+    }
+
+    impl IdleInhibit for FakeInhibitor {
+        fn is_inhibited(&self) -> bool {
+            self.inhibited
+        }
+
+        fn toggle(&mut self) {
+            self.inhibited = !self.inhibited;
+        }
+    }
+
+    #[test]
+    fn toggling_swaps_the_glyph() {
+        let config = Config::default();
+        let mut outputs = Outputs::new::<()>(config.appearance.style, config.position, &config).0;
+        let mut settings = Settings::with_idle_inhibitor(FakeInhibitor::default());
+        let glyph = |settings: &Settings| settings.idle_inhibited().map(indicator_icon);
+
+        assert_eq!(glyph(&settings), Some(Icons::CoffeeOutline));
+
+        let toggle = |settings: &mut Settings, outputs: &mut Outputs| {
+            settings.update(
+                Message::ToggleInhibitIdle,
+                &SettingsModuleConfig::default(),
+                outputs,
+                &config
+            );
+        };
+
+        toggle(&mut settings, &mut outputs);
+        assert_eq!(glyph(&settings), Some(Icons::CoffeeFilled));
+
+        toggle(&mut settings, &mut outputs);
+        assert_eq!(glyph(&settings), Some(Icons::CoffeeOutline));
+    }
+}
@@ -77,6 +77,10 @@ where
             handle.abort();
         }
 
+        if !self.hyprland.is_available() {
+            return Ok(());
+        }
+
         if let Some(sender) = self.sender.clone() {
             let hyprland = Arc::clone(&self.hyprland);
             self.task = Some(ctx.runtime_handle().spawn(async move {
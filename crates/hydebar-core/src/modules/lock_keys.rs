@@ -0,0 +1,244 @@
+use std::{sync::Arc, time::Duration};
+
+use hydebar_proto::ports::hyprland::{HyprlandLockState, HyprlandPort};
+use iced::{
+    Element, Theme,
+    alignment::Vertical,
+    widget::{Row, text}
+};
+use log::error;
+use tokio::{
+    task::{self, JoinHandle},
+    time::{MissedTickBehavior, interval}
+};
+
+use super::{Module, ModuleError, OnModulePress};
+use crate::{
+    ModuleContext, ModuleEventSender,
+    components::icons::{Icons, icon},
+    config::LockKeysModuleConfig,
+    event_bus::ModuleEvent
+};
+
+/// Shortest accepted `poll_interval_ms`; Hyprland does not broadcast
+/// modifier lock changes, so every poll is a device list query.
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Shows a glyph for each engaged lock key.
+pub struct LockKeys {
+    hyprland: Arc<dyn HyprlandPort>,
+    state:    HyprlandLockState,
+    sender:   Option<ModuleEventSender<Message>>,
+    task:     Option<JoinHandle<()>>
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    StateChanged(HyprlandLockState)
+}
+
+impl LockKeys {
+    pub fn new(hyprland: Arc<dyn HyprlandPort>) -> Self {
+        let state = query_state(hyprland.as_ref()).unwrap_or_default();
+
+        Self {
+            hyprland,
+            state,
+            sender: None,
+            task: None
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::StateChanged(state) => {
+                self.state = state;
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn state(&self) -> HyprlandLockState {
+        self.state
+    }
+
+    /// Glyph of every lock key to draw and whether it is engaged. Released
+    /// keys are only listed with `always_show`.
+    fn glyphs(&self, always_show: bool) -> Vec<(Icons, bool)> {
+        [
+            (Icons::CapsLock, self.state.caps_lock),
+            (Icons::NumLock, self.state.num_lock),
+            (Icons::ScrollLock, self.state.scroll_lock)
+        ]
+        .into_iter()
+        .filter(|(_, active)| *active || always_show)
+        .collect()
+    }
+}
+
+fn query_state(hyprland: &dyn HyprlandPort) -> Option<HyprlandLockState> {
+    if !hyprland.is_available() {
+        return None;
+    }
+
+    hyprland
+        .lock_state()
+        .map_err(|err| error!("failed to query lock keys: {err}"))
+        .ok()
+}
+
+impl<M> Module<M> for LockKeys
+where
+    M: 'static + Clone
+{
+    type ViewData<'a> = &'a LockKeysModuleConfig;
+    type RegistrationData<'a> = &'a LockKeysModuleConfig;
+
+    fn register(
+        &mut self,
+        ctx: &ModuleContext,
+        config: Self::RegistrationData<'_>
+    ) -> Result<(), ModuleError> {
+        self.sender = Some(ctx.module_sender(ModuleEvent::LockKeys));
+
+        if let Some(handle) = self.task.take() {
+            handle.abort();
+        }
+
+        if !self.hyprland.is_available() {
+            return Ok(());
+        }
+
+        if let Some(sender) = self.sender.clone() {
+            let hyprland = Arc::clone(&self.hyprland);
+            let mut last = self.state;
+            let poll_interval =
+                Duration::from_millis(config.poll_interval_ms).max(MIN_POLL_INTERVAL);
+
+            self.task = Some(ctx.runtime_handle().spawn(async move {
+                let mut ticker = interval(poll_interval);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+                loop {
+                    ticker.tick().await;
+
+                    // The query talks to the Hyprland socket synchronously.
+                    let hyprland = Arc::clone(&hyprland);
+                    let queried =
+                        task::spawn_blocking(move || query_state(hyprland.as_ref())).await;
+                    let Ok(Some(state)) = queried else {
+                        continue;
+                    };
+
+                    if state != last {
+                        last = state;
+
+                        if let Err(err) = sender.try_send(Message::StateChanged(state)) {
+                            error!("failed to publish lock key update: {err}");
+                        }
+                    }
+                }
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn view(
+        &self,
+        config: Self::ViewData<'_>
+    ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)> {
+        if !self.hyprland.is_available() {
+            return None;
+        }
+
+        let glyphs: Vec<Element<'static, M>> = self
+            .glyphs(config.always_show)
+            .into_iter()
+            .map(|(glyph, active)| {
+                if active {
+                    icon(glyph).into()
+                } else {
+                    icon(glyph)
+                        .style(|theme: &Theme| text::Style {
+                            color: Some(theme.extended_palette().background.strong.color)
+                        })
+                        .into()
+                }
+            })
+            .collect();
+
+        if glyphs.is_empty() {
+            return None;
+        }
+
+        Some((
+            Row::with_children(glyphs)
+                .align_y(Vertical::Center)
+                .spacing(4)
+                .into(),
+            None
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockHyprlandPort;
+
+    #[test]
+    fn initializes_from_keyboard_state() {
+        let port: Arc<dyn HyprlandPort> = Arc::new(MockHyprlandPort::default());
+
+        let module = LockKeys::new(port);
+
+        assert_eq!(
+            module.state(),
+            HyprlandLockState {
+                caps_lock:   false,
+                num_lock:    true,
+                scroll_lock: false
+            }
+        );
+    }
+
+    #[test]
+    fn shows_caps_lock_glyph_when_engaged() {
+        let port = MockHyprlandPort::default();
+        *port.lock_state.lock().expect("lock state") = HyprlandLockState {
+            caps_lock: true,
+            ..HyprlandLockState::default()
+        };
+        let module = LockKeys::new(Arc::new(port));
+
+        assert_eq!(module.glyphs(false), [(Icons::CapsLock, true)]);
+        assert_eq!(
+            module.glyphs(true),
+            [
+                (Icons::CapsLock, true),
+                (Icons::NumLock, false),
+                (Icons::ScrollLock, false)
+            ]
+        );
+        assert!(
+            <LockKeys as Module<()>>::view(&module, &LockKeysModuleConfig::default()).is_some()
+        );
+    }
+
+    #[test]
+    fn hides_when_no_lock_is_active_unless_always_shown() {
+        let port: Arc<dyn HyprlandPort> = Arc::new(MockHyprlandPort::default());
+        let mut module = LockKeys::new(port);
+        module.update(Message::StateChanged(HyprlandLockState::default()));
+
+        let hidden = LockKeysModuleConfig::default();
+        assert!(<LockKeys as Module<()>>::view(&module, &hidden).is_none());
+
+        let always = LockKeysModuleConfig {
+            always_show: true,
+            ..LockKeysModuleConfig::default()
+        };
+        assert!(<LockKeys as Module<()>>::view(&module, &always).is_some());
+    }
+}
@@ -1,12 +1,16 @@
+mod selection;
+
 use std::{
     future::{Future, ready},
-    pin::Pin
+    pin::Pin,
+    time::Duration
 };
 
 use iced::{
-    Background, Border, Element, Length, Theme,
+    Background, Border, Element, Length, Subscription, Theme,
     alignment::Vertical,
-    widget::{Column, button, column, container, horizontal_rule, row, slider, text}
+    time,
+    widget::{Column, Row, button, column, container, horizontal_rule, row, slider, text}
 };
 use log::{error, warn};
 use tokio::{
@@ -14,6 +18,8 @@ use tokio::{
     task::{JoinHandle, yield_now}
 };
 
+pub use self::selection::PlayerSelection;
+use self::selection::player_name;
 use super::{Module, ModuleError, OnModulePress};
 use crate::{
     ModuleContext, ModuleEventSender,
@@ -24,20 +30,24 @@ use crate::{
     services::{
         ReadOnlyService, ServiceEvent,
         mpris::{
-            ListenerState, MprisEventPublisher, MprisPlayerCommand, MprisPlayerData,
+            ListenerState, LoopStatus, MprisEventPublisher, MprisPlayerCommand, MprisPlayerData,
             MprisPlayerEvent, MprisPlayerService, PlaybackStatus, PlayerCommand
         }
     },
-    style::settings_button_style,
-    utils::truncate_text
+    style::{quick_settings_button_style, settings_button_style},
+    utils::{format_timestamp, truncate_text}
 };
 
+/// How often the playback position is polled while the menu is open.
+const POSITION_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Default)]
 pub struct MediaPlayer {
-    service: Option<MprisPlayerService>,
-    sender:  Option<ModuleEventSender<Message>>,
-    runtime: Option<Handle>,
-    tasks:   Vec<JoinHandle<()>>
+    service:   Option<MprisPlayerService>,
+    selection: PlayerSelection,
+    sender:    Option<ModuleEventSender<Message>>,
+    runtime:   Option<Handle>,
+    tasks:     Vec<JoinHandle<()>>
 }
 
 struct MediaPlayerPublisher {
@@ -263,6 +273,12 @@ pub enum Message {
     PlayPause(String),
     Next(String),
     SetVolume(String, f64),
+    Stop(String),
+    SetShuffle(String, bool),
+    SetLoop(String, LoopStatus),
+    RefreshPositions,
+    /// Make the player with this bus name the one the menu controls.
+    SelectPlayer(String),
     Event(ServiceEvent<MprisPlayerService>)
 }
 
@@ -273,14 +289,23 @@ impl MediaPlayer {
             Message::PlayPause(s) => self.handle_command(s, PlayerCommand::PlayPause),
             Message::Next(s) => self.handle_command(s, PlayerCommand::Next),
             Message::SetVolume(s, v) => self.handle_command(s, PlayerCommand::Volume(v)),
+            Message::Stop(s) => self.handle_command(s, PlayerCommand::Stop),
+            Message::SetShuffle(s, v) => self.handle_command(s, PlayerCommand::Shuffle(v)),
+            Message::SetLoop(s, v) => self.handle_command(s, PlayerCommand::Loop(v)),
+            Message::RefreshPositions => self.refresh_positions(),
+            Message::SelectPlayer(s) => self.selection.select(s),
             Message::Event(event) => match event {
                 ServiceEvent::Init(s) => {
+                    let previous = self.player_names();
                     self.service = Some(s);
+                    self.sync_selection(previous);
                 }
                 ServiceEvent::Update(d) => {
+                    let previous = self.player_names();
                     if let Some(service) = self.service.as_mut() {
                         service.update(d);
                     }
+                    self.sync_selection(previous);
                 }
                 ServiceEvent::Error(error) => {
                     error!("media player service error: {error}");
@@ -289,6 +314,36 @@ impl MediaPlayer {
         }
     }
 
+    fn player_names(&self) -> Vec<String> {
+        self.service
+            .iter()
+            .flat_map(|s| s.iter().map(|d| d.service.clone()))
+            .collect()
+    }
+
+    fn sync_selection(&mut self, previous: Vec<String>) {
+        let previous: Vec<&str> = previous.iter().map(String::as_str).collect();
+        let players = self.player_names();
+        let players: Vec<&str> = players.iter().map(String::as_str).collect();
+
+        self.selection.sync(&previous, &players);
+    }
+
+    /// Player the menu controls and the bar shows.
+    fn selected(&self) -> Option<&MprisPlayerData> {
+        let service = self.service.as_ref()?;
+        let players: Vec<&str> = service.iter().map(|d| d.service.as_str()).collect();
+        let selected = self.selection.current(&players)?;
+
+        service.iter().find(|d| d.service == selected)
+    }
+
+    /// Polls playback positions; MPRIS never signals `Position` changes, so
+    /// this is only subscribed while the menu is open.
+    pub fn menu_subscription(&self) -> Subscription<Message> {
+        time::every(POSITION_REFRESH_INTERVAL).map(|_| Message::RefreshPositions)
+    }
+
     pub fn menu_view(
         &self,
         config: &MediaPlayerModuleConfig,
@@ -296,68 +351,133 @@ impl MediaPlayer {
     ) -> Element<'_, Message> {
         match &self.service {
             None => text("Not connected to MPRIS service").into(),
-            Some(s) => column!(
-                text("Players").size(20),
-                horizontal_rule(1),
-                column(s.iter().map(|d| {
-                    let title = text(Self::get_title(d, config))
-                        .wrapping(text::Wrapping::WordOrGlyph)
-                        .width(Length::Fill);
-
-                    let play_pause_icon = match d.state {
-                        PlaybackStatus::Playing => Icons::Pause,
-                        PlaybackStatus::Paused | PlaybackStatus::Stopped => Icons::Play
+            Some(s) => column!(text("Players").size(20), horizontal_rule(1))
+                .push_maybe((s.len() > 1).then(|| self.player_switcher(opacity)))
+                .push_maybe(
+                    self.selected()
+                        .map(|d| Self::player_view(d, config, opacity))
+                )
+                .spacing(8)
+                .into()
+        }
+    }
+
+    /// Row of buttons choosing which player the menu controls.
+    fn player_switcher(&self, opacity: f32) -> Element<'_, Message> {
+        let selected = self.selected().map(|d| d.service.as_str());
+
+        Row::with_children(self.service.iter().flat_map(|s| s.iter()).map(|d| {
+            button(text(player_name(&d.service)).size(12))
+                .padding([4, 12])
+                .style(quick_settings_button_style(
+                    Some(d.service.as_str()) == selected,
+                    opacity
+                ))
+                .on_press(Message::SelectPlayer(d.service.clone()))
+                .into()
+        }))
+        .spacing(4)
+        .into()
+    }
+
+    fn player_view<'a>(
+        d: &'a MprisPlayerData,
+        config: &MediaPlayerModuleConfig,
+        opacity: f32
+    ) -> Element<'a, Message> {
+        let title = text(Self::get_title(d, config))
+            .wrapping(text::Wrapping::WordOrGlyph)
+            .width(Length::Fill);
+
+        let play_pause_icon = match d.state {
+            PlaybackStatus::Playing => Icons::Pause,
+            PlaybackStatus::Paused | PlaybackStatus::Stopped => Icons::Play
+        };
+
+        let buttons = row![
+            button(icon(Icons::SkipPrevious))
+                .on_press(Message::Prev(d.service.clone()))
+                .padding([5, 12])
+                .style(settings_button_style(opacity)),
+            button(icon(play_pause_icon))
+                .on_press(Message::PlayPause(d.service.clone()))
+                .style(settings_button_style(opacity)),
+            button(icon(Icons::SkipNext))
+                .on_press(Message::Next(d.service.clone()))
+                .padding([5, 12])
+                .style(settings_button_style(opacity)),
+        ]
+        .push_maybe((config.show_stop && d.can_control).then(|| {
+            button(icon(Icons::Stop))
+                .on_press(Message::Stop(d.service.clone()))
+                .padding([5, 12])
+                .style(settings_button_style(opacity))
+        }))
+        .push_maybe(
+            d.shuffle
+                .filter(|_| config.show_shuffle && d.can_control)
+                .map(|shuffle| {
+                    let shuffle_icon = if shuffle {
+                        Icons::Shuffle
+                    } else {
+                        Icons::ShuffleDisabled
+                    };
+
+                    button(icon(shuffle_icon))
+                        .on_press(Message::SetShuffle(d.service.clone(), !shuffle))
+                        .padding([5, 12])
+                        .style(settings_button_style(opacity))
+                })
+        )
+        .push_maybe(
+            d.loop_status
+                .filter(|_| config.show_repeat && d.can_control)
+                .map(|status| {
+                    let repeat_icon = match status {
+                        LoopStatus::None => Icons::RepeatOff,
+                        LoopStatus::Track => Icons::RepeatOnce,
+                        LoopStatus::Playlist => Icons::Repeat
                     };
 
-                    let buttons = row![
-                        button(icon(Icons::SkipPrevious))
-                            .on_press(Message::Prev(d.service.clone()))
-                            .padding([5, 12])
-                            .style(settings_button_style(opacity)),
-                        button(icon(play_pause_icon))
-                            .on_press(Message::PlayPause(d.service.clone()))
-                            .style(settings_button_style(opacity)),
-                        button(icon(Icons::SkipNext))
-                            .on_press(Message::Next(d.service.clone()))
-                            .padding([5, 12])
-                            .style(settings_button_style(opacity)),
-                    ]
-                    .spacing(8);
-
-                    let volume_slider = d.volume.map(|v| {
-                        slider(0.0..=100.0, v, move |v| {
-                            Message::SetVolume(d.service.clone(), v)
-                        })
-                    });
-
-                    container(
-                        Column::new()
-                            .push(row!(title, buttons).spacing(8).align_y(Vertical::Center))
-                            .push_maybe(volume_slider)
-                            .spacing(8)
-                    )
-                    .style(move |theme: &Theme| container::Style {
-                        background: Background::Color(
-                            theme
-                                .extended_palette()
-                                .secondary
-                                .strong
-                                .color
-                                .scale_alpha(opacity)
-                        )
-                        .into(),
-                        border: Border::default().rounded(16),
-                        ..container::Style::default()
-                    })
-                    .padding(16)
-                    .width(Length::Fill)
-                    .into()
-                }))
-                .spacing(16)
+                    button(icon(repeat_icon))
+                        .on_press(Message::SetLoop(d.service.clone(), status.next()))
+                        .padding([5, 12])
+                        .style(settings_button_style(opacity))
+                })
+        )
+        .spacing(8);
+
+        let volume_slider = d.volume.map(|v| {
+            slider(0.0..=100.0, v, move |v| {
+                Message::SetVolume(d.service.clone(), v)
+            })
+        });
+
+        let progress = text(Self::get_progress(d)).size(12);
+
+        container(
+            Column::new()
+                .push(row!(title, buttons).spacing(8).align_y(Vertical::Center))
+                .push(progress)
+                .push_maybe(volume_slider)
+                .spacing(8)
+        )
+        .style(move |theme: &Theme| container::Style {
+            background: Background::Color(
+                theme
+                    .extended_palette()
+                    .secondary
+                    .strong
+                    .color
+                    .scale_alpha(opacity)
             )
-            .spacing(8)
-            .into()
-        }
+            .into(),
+            border: Border::default().rounded(16),
+            ..container::Style::default()
+        })
+        .padding(16)
+        .width(Length::Fill)
+        .into()
     }
 
     fn handle_command(&mut self, service_name: String, command: PlayerCommand) {
@@ -388,6 +508,40 @@ impl MediaPlayer {
         }
     }
 
+    fn refresh_positions(&mut self) {
+        let (Some(runtime), Some(sender)) = (self.runtime.clone(), self.sender.clone()) else {
+            return;
+        };
+        let service = self.service.clone();
+
+        runtime.spawn(async move {
+            match MprisPlayerService::fetch_positions(service).await {
+                Ok(positions) => {
+                    for (service, position) in positions {
+                        let event =
+                            ServiceEvent::Update(MprisPlayerEvent::Position(service, position));
+
+                        if let Err(err) = sender.try_send(Message::Event(event)) {
+                            warn!("failed to publish media player position: {err}");
+                        }
+                    }
+                }
+                Err(error) => warn!("failed to poll media player positions: {error}")
+            }
+        });
+    }
+
+    fn get_progress(d: &MprisPlayerData) -> String {
+        let format = |value: Option<Duration>| {
+            value
+                .as_ref()
+                .map_or_else(|| "--:--".to_string(), format_timestamp)
+        };
+        let length = d.metadata.as_ref().and_then(|m| m.length);
+
+        format!("{} / {}", format(d.position), format(length))
+    }
+
     fn get_title(d: &MprisPlayerData, config: &MediaPlayerModuleConfig) -> String {
         match &d.metadata {
             Some(m) => truncate_text(&m.to_string(), config.max_title_length),
@@ -453,20 +607,24 @@ where
         &self,
         config: Self::ViewData<'_>
     ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)> {
-        self.service.as_ref().and_then(|s| match s.len() {
-            0 => None,
-            _ => Some((
-                row![
-                    icon(Icons::MusicNote),
-                    text(Self::get_title(&s[0], config))
-                        .wrapping(text::Wrapping::WordOrGlyph)
-                        .size(12)
-                ]
-                .align_y(Vertical::Center)
-                .spacing(8)
-                .into(),
-                Some(OnModulePress::ToggleMenu(MenuType::MediaPlayer))
-            ))
-        })
+        let player = self.selected()?;
+        let glyph = match player.state {
+            PlaybackStatus::Playing => Icons::MusicNote,
+            PlaybackStatus::Paused => Icons::Pause,
+            PlaybackStatus::Stopped => Icons::Stop
+        };
+
+        Some((
+            row![
+                icon(glyph),
+                text(Self::get_title(player, config))
+                    .wrapping(text::Wrapping::WordOrGlyph)
+                    .size(12)
+            ]
+            .align_y(Vertical::Center)
+            .spacing(8)
+            .into(),
+            Some(OnModulePress::ToggleMenu(MenuType::MediaPlayer))
+        ))
     }
 }
@@ -0,0 +1,102 @@
+/// Bus name prefix shared by every MPRIS player.
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+
+/// Player the menu controls and the bar follows, identified by its bus name.
+///
+/// Without an explicit choice the first listed player is used.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerSelection {
+    selected: Option<String>
+}
+
+impl PlayerSelection {
+    /// Make `service` the selected player.
+    pub fn select(&mut self, service: String) {
+        self.selected = Some(service);
+    }
+
+    /// Bus name of the selected player among `players`.
+    pub fn current<'a>(&self, players: &[&'a str]) -> Option<&'a str> {
+        self.selected
+            .as_deref()
+            .and_then(|selected| players.iter().copied().find(|player| *player == selected))
+            .or_else(|| players.first().copied())
+    }
+
+    /// Keep the selection valid after the player list changed from
+    /// `previous` to `players`.
+    ///
+    /// A selected player that went away hands over to the one listed after
+    /// it, or to the last remaining player when it was the last one.
+    pub fn sync(&mut self, previous: &[&str], players: &[&str]) {
+        let Some(selected) = self.selected.as_deref() else {
+            return;
+        };
+
+        if players.contains(&selected) {
+            return;
+        }
+
+        self.selected = previous
+            .iter()
+            .position(|player| *player == selected)
+            .and_then(|index| {
+                previous[index + 1..]
+                    .iter()
+                    .find(|player| players.contains(player))
+                    .or_else(|| players.last())
+            })
+            .map(|player| (*player).to_owned());
+    }
+}
+
+/// Short name of a player for the switcher, e.g. `firefox` for
+/// `org.mpris.MediaPlayer2.firefox.instance_1_42`.
+pub fn player_name(service: &str) -> &str {
+    let name = service.strip_prefix(MPRIS_PREFIX).unwrap_or(service);
+    name.split('.').next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BROWSER: &str = "org.mpris.MediaPlayer2.firefox.instance_1_42";
+    const MUSIC: &str = "org.mpris.MediaPlayer2.spotify";
+
+    #[test]
+    fn switches_between_two_players() {
+        let players = [BROWSER, MUSIC];
+        let mut selection = PlayerSelection::default();
+        assert_eq!(selection.current(&players), Some(BROWSER));
+
+        selection.select(MUSIC.to_owned());
+        assert_eq!(selection.current(&players), Some(MUSIC));
+
+        selection.select(BROWSER.to_owned());
+        assert_eq!(selection.current(&players), Some(BROWSER));
+    }
+
+    #[test]
+    fn falls_back_when_the_selected_player_disappears() {
+        let mut selection = PlayerSelection::default();
+
+        selection.select(BROWSER.to_owned());
+        selection.sync(&[BROWSER, MUSIC], &[MUSIC]);
+        assert_eq!(selection.current(&[MUSIC]), Some(MUSIC));
+
+        selection.sync(&[MUSIC], &[]);
+        assert_eq!(selection.current(&[]), None);
+
+        selection.select(MUSIC.to_owned());
+        selection.sync(&[BROWSER, MUSIC], &[BROWSER]);
+        assert_eq!(selection.current(&[BROWSER]), Some(BROWSER));
+    }
+
+    #[test]
+    fn short_names_drop_bus_prefix_and_instance() {
+        assert_eq!(player_name(BROWSER), "firefox");
+        assert_eq!(player_name(MUSIC), "spotify");
+        assert_eq!(player_name("custom"), "custom");
+    }
+}
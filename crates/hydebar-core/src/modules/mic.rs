@@ -0,0 +1,120 @@
+use iced::{Element, Theme, widget::text};
+
+use super::{Module, OnModulePress};
+use crate::{
+    components::icons::{Icons, icon},
+    config::MicModuleConfig,
+    services::audio::AudioData
+};
+
+/// Shows whether the default audio source is muted. The audio state comes
+/// from the settings module, which owns the audio service, so the indicator
+/// does not hold a second connection to the sound server.
+#[derive(Debug, Default)]
+pub struct Mic;
+
+/// Glyph for the default source, or `None` while it is not known yet.
+fn mic_icon(audio: &AudioData) -> Option<Icons> {
+    audio
+        .sources
+        .iter()
+        .find(|source| source.name == audio.server_info.default_source)
+        .map(|source| {
+            if source.is_mute {
+                Icons::Mic0
+            } else {
+                Icons::Mic1
+            }
+        })
+}
+
+impl<M> Module<M> for Mic
+where
+    M: 'static + Clone
+{
+    /// Message emitted on click, the module options and the audio state of
+    /// the settings module.
+    type ViewData<'a> = (M, &'a MicModuleConfig, Option<&'a AudioData>);
+    type RegistrationData<'a> = ();
+
+    fn view(
+        &self,
+        (on_press, config, audio): Self::ViewData<'_>
+    ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)> {
+        let glyph = mic_icon(audio?)?;
+        if glyph == Icons::Mic1 && config.hide_unmuted {
+            return None;
+        }
+
+        let content = if glyph == Icons::Mic0 {
+            icon(glyph)
+                .style(|theme: &Theme| text::Style {
+                    color: Some(theme.palette().danger)
+                })
+                .into()
+        } else {
+            icon(glyph).into()
+        };
+
+        Some((content, Some(OnModulePress::Action(Box::new(on_press)))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libpulse_binding::volume::ChannelVolumes;
+
+    use super::*;
+    use crate::services::audio::{Device, ServerInfo};
+
+    fn source(name: &str, is_mute: bool) -> Device {
+        Device {
+            name: name.to_owned(),
+            description: String::new(),
+            volume: ChannelVolumes::default(),
+            is_mute,
+            in_use: true,
+            ports: Vec::new()
+        }
+    }
+
+    fn audio(default_source: &str) -> AudioData {
+        AudioData {
+            server_info: ServerInfo {
+                default_sink:   String::new(),
+                default_source: default_source.to_owned()
+            },
+            sources: vec![source("muted", true), source("live", false)],
+            ..AudioData::default()
+        }
+    }
+
+    #[test]
+    fn icon_follows_mute_state_of_default_source() {
+        let muted = mic_icon(&audio("muted"));
+        let live = mic_icon(&audio("live"));
+
+        assert_eq!(muted, Some(Icons::Mic0));
+        assert_eq!(live, Some(Icons::Mic1));
+        assert_ne!(muted, live);
+        assert_eq!(mic_icon(&audio("missing")), None);
+    }
+
+    #[test]
+    fn view_follows_the_source_mute_state() {
+        let shown = MicModuleConfig::default();
+        let hide_unmuted = MicModuleConfig {
+            hide_unmuted: true
+        };
+        let muted = audio("muted");
+        let live = audio("live");
+
+        let view = |config, audio| <Mic as Module<()>>::view(&Mic, ((), config, audio));
+
+        assert!(view(&shown, None).is_none());
+        assert!(view(&shown, Some(&muted)).is_some());
+        assert!(view(&shown, Some(&live)).is_some());
+        assert!(view(&hide_unmuted, Some(&muted)).is_some());
+        assert!(view(&hide_unmuted, Some(&live)).is_none());
+    }
+}
@@ -6,8 +6,8 @@ use std::sync::{
 };
 
 use iced::{
-    Alignment, Element,
-    widget::{Row, container}
+    Alignment, Color, Element, Theme,
+    widget::{Row, container, mouse_area, text}
 };
 use log::{error, warn};
 use tokio::task::JoinHandle;
@@ -18,6 +18,7 @@ use crate::event_bus::BusEvent;
 use crate::{
     ModuleContext, ModuleEventSender,
     components::icons::{Icons, icon},
+    config::{PrivacyDisplay, PrivacyModuleConfig},
     event_bus::ModuleEvent,
     services::{
         ReadOnlyService, ServiceEvent,
@@ -66,13 +67,15 @@ impl<M> Module<M> for Privacy
 where
     M: 'static + Clone
 {
-    type ViewData<'a> = ();
-    type RegistrationData<'a> = ();
+    /// Message emitted when the screenshare indicator is clicked, and how
+    /// the active resources are shown.
+    type ViewData<'a> = (Option<M>, PrivacyDisplay);
+    type RegistrationData<'a> = &'a PrivacyModuleConfig;
 
     fn register(
         &mut self,
         ctx: &ModuleContext,
-        _: Self::RegistrationData<'_>
+        config: Self::RegistrationData<'_>
     ) -> Result<(), ModuleError> {
         for task in self.tasks.drain(..) {
             task.abort();
@@ -81,12 +84,13 @@ where
         let sender = ctx.module_sender(ModuleEvent::Privacy);
         let mut publisher = ModulePublisher::new(sender.clone());
         let error_sender = sender.clone();
+        let location = config.location;
 
         let task = ctx.runtime_handle().spawn(async move {
             let mut state = State::Init;
 
             loop {
-                match run_start_listening(state, &mut publisher).await {
+                match run_start_listening(state, &mut publisher, location).await {
                     Ok(next_state) => {
                         state = next_state;
                     }
@@ -113,20 +117,29 @@ where
     /// Render the privacy indicator when data is available.
     fn view(
         &self,
-        _: Self::ViewData<'_>
+        (on_screenshare_press, display): Self::ViewData<'_>
     ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)> {
         if let Some(service) = self.service.as_ref() {
-            if !service.no_access() {
+            if service.no_access() {
+                None
+            } else if display == PrivacyDisplay::Combined {
+                Some((combined_indicator(service, on_screenshare_press), None))
+            } else {
+                let screenshare = service.screenshare_access().then(|| {
+                    let indicator = mouse_area(icon(Icons::ScreenShare));
+                    match on_screenshare_press {
+                        Some(message) => indicator.on_press(message),
+                        None => indicator
+                    }
+                });
+
                 Some((
                     container(
                         Row::new()
-                            .push_maybe(
-                                service
-                                    .screenshare_access()
-                                    .then(|| icon(Icons::ScreenShare))
-                            )
+                            .push_maybe(screenshare)
                             .push_maybe(service.webcam_access().then(|| icon(Icons::Webcam)))
                             .push_maybe(service.microphone_access().then(|| icon(Icons::Mic1)))
+                            .push_maybe(service.location_access().then(|| icon(Icons::Location)))
                             .align_y(Alignment::Center)
                             .spacing(8)
                     )
@@ -137,8 +150,6 @@ where
                     .into(),
                     None
                 ))
-            } else {
-                None
             }
         } else {
             None
@@ -146,6 +157,82 @@ where
     }
 }
 
+/// Resources in use, as summarised by the combined indicator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Access {
+    screenshare: bool,
+    webcam:      bool,
+    microphone:  bool,
+    location:    bool
+}
+
+impl Access {
+    fn of(service: &PrivacyService) -> Self {
+        Self {
+            screenshare: service.screenshare_access(),
+            webcam:      service.webcam_access(),
+            microphone:  service.microphone_access(),
+            location:    service.location_access()
+        }
+    }
+
+    /// Number of resources in use, when there is more than one to count.
+    fn badge(self) -> Option<String> {
+        let active = [
+            self.screenshare,
+            self.webcam,
+            self.microphone,
+            self.location
+        ]
+        .into_iter()
+        .filter(|active| *active)
+        .count();
+
+        (active > 1).then(|| active.to_string())
+    }
+
+    /// Screen and camera capture take the strongest colour, then the
+    /// microphone; location alone falls back to the primary colour.
+    fn color(self, theme: &Theme) -> Color {
+        if self.screenshare || self.webcam {
+            theme.palette().danger
+        } else if self.microphone {
+            theme.extended_palette().danger.weak.color
+        } else {
+            theme.palette().primary
+        }
+    }
+}
+
+/// Single shield glyph standing in for every active resource, coloured by
+/// the most sensitive one and badged with their count.
+fn combined_indicator<M>(
+    service: &PrivacyService,
+    on_screenshare_press: Option<M>
+) -> Element<'static, M>
+where
+    M: 'static + Clone
+{
+    let access = Access::of(service);
+
+    let shield = container(
+        Row::new()
+            .push(icon(Icons::Shield))
+            .push_maybe(access.badge().map(|badge| text(badge).size(10)))
+            .align_y(Alignment::Center)
+            .spacing(2)
+    )
+    .style(move |theme: &Theme| container::Style {
+        text_color: Some(access.color(theme)),
+        ..Default::default()
+    });
+
+    match on_screenshare_press.filter(|_| access.screenshare) {
+        Some(message) => mouse_area(shield).on_press(message).into(),
+        None => shield.into()
+    }
+}
+
 struct ModulePublisher {
     sender: ModuleEventSender<PrivacyMessage>
 }
@@ -175,13 +262,17 @@ impl PrivacyEventPublisher for ModulePublisher {
     }
 }
 
-async fn run_start_listening<P>(state: State, publisher: &mut P) -> Result<State, PrivacyError>
+async fn run_start_listening<P>(
+    state: State,
+    publisher: &mut P,
+    location: bool
+) -> Result<State, PrivacyError>
 where
     P: PrivacyEventPublisher + Send
 {
     // Note: Test override mechanism removed due to GAT incompatibility with dyn
     // trait objects Tests will now use the real implementation
-    PrivacyService::start_listening(state, publisher).await
+    PrivacyService::start_listening(state, publisher, location).await
 }
 
 // Test override infrastructure removed due to GAT incompatibility with dyn
@@ -216,6 +307,46 @@ impl Drop for CancellationProbe {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combined_access_is_badged_and_coloured_by_capture() {
+        let theme = Theme::Dark;
+        let access = Access {
+            screenshare: true,
+            microphone: true,
+            location: true,
+            ..Access::default()
+        };
+
+        assert_eq!(access.badge().as_deref(), Some("3"));
+        assert_eq!(access.color(&theme), theme.palette().danger);
+    }
+
+    #[test]
+    fn single_access_falls_back_to_weaker_colours() {
+        let theme = Theme::Dark;
+        let microphone = Access {
+            microphone: true,
+            ..Access::default()
+        };
+        let location = Access {
+            location: true,
+            ..Access::default()
+        };
+
+        assert_eq!(microphone.badge(), None);
+        assert_eq!(
+            microphone.color(&theme),
+            theme.extended_palette().danger.weak.color
+        );
+        assert_eq!(location.badge(), None);
+        assert_eq!(location.color(&theme), theme.palette().primary);
+    }
+}
+
 /* TESTS DISABLED - need to be rewritten without dyn trait object support
 #[cfg(test)]
 mod tests {
@@ -10,7 +10,8 @@ use super::{Module, ModuleError, OnModulePress};
 use crate::{
     ModuleContext,
     components::icons::{Icons, icon},
-    menu::MenuType
+    menu::MenuType,
+    utils::launcher::CommandSettings
 };
 
 /// Screenshot action types
@@ -32,12 +33,22 @@ pub enum ScreenshotMessage {
 /// Screenshot and recording module.
 #[derive(Debug, Default)]
 pub struct Screenshot {
-    pub is_recording: bool
+    pub is_recording: bool,
+    /// Debounces repeated screenshots.
+    commands:         CommandSettings
 }
 
 impl Screenshot {
     /// Take a screenshot with the specified action.
     pub fn take_screenshot(&self, action: ScreenshotAction) {
+        if !self
+            .commands
+            .should_launch(&format!("screenshot {action:?}"))
+        {
+            debug!("Ignoring repeated {action:?} screenshot");
+            return;
+        }
+
         let screenshot_dir = dirs::picture_dir()
             .unwrap_or_else(|| std::path::PathBuf::from("/tmp"))
             .join("Screenshots");
@@ -249,9 +260,10 @@ where
 
     fn register(
         &mut self,
-        _: &ModuleContext,
+        ctx: &ModuleContext,
         _: Self::RegistrationData<'_>
     ) -> Result<(), ModuleError> {
+        self.commands = ctx.command_settings().clone();
         Ok(())
     }
 
@@ -8,6 +8,7 @@ pub mod bluetooth;
 pub mod brightness;
 pub mod network;
 mod power;
+mod talkers;
 mod upower;
 
 pub use audio::AudioMessage;
@@ -15,6 +16,7 @@ pub use bluetooth::BluetoothMessage;
 pub use brightness::BrightnessMessage;
 pub use network::NetworkMessage;
 pub use power::PowerMessage;
-pub use state::{Message, Settings, SubMenu};
+pub use state::{Message, ServiceStatus, Settings, SettingsService, SettingsTab, SubMenu};
+pub use talkers::{ProcessTraffic, TALKERS_REFRESH_INTERVAL, Talker, TopTalkers};
 pub use upower::UPowerMessage;
-pub use view::{SettingsViewExt, quick_setting_button};
+pub use view::{IndicatorOptions, SettingsViewExt, quick_setting_button};
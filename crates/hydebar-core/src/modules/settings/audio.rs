@@ -6,12 +6,14 @@ use iced::{
 
 use super::{Message, SubMenu};
 use crate::{
-    components::icons::{Icons, icon},
+    components::icons::{Icons, icon, icon_raw, icon_with_label},
+    config::{IconPosition, PercentLabel, VolumeIconsConfig},
     services::{
         ServiceEvent,
-        audio::{AudioData, AudioService, DeviceType, Sinks}
+        audio::{AudioData, AudioService, DeviceType, Sinks, Volume}
     },
-    style::{ghost_button_style, settings_button_style}
+    style::{ghost_button_style, settings_button_style},
+    utils
 };
 
 #[derive(Debug, Clone)]
@@ -23,24 +25,62 @@ pub enum AudioMessage {
     SinkVolumeChanged(i32),
     ToggleSourceMute,
     SourceVolumeChanged(i32),
+    ToggleSinkInputMute(u32),
+    SinkInputVolumeChanged(u32, i32),
     SinksMore(Id),
     SourcesMore(Id)
 }
 
 impl AudioData {
-    pub fn sink_indicator<Message: 'static>(&self) -> Option<Element<'static, Message>> {
+    pub fn sink_indicator<Message: 'static>(
+        &self,
+        config: &VolumeIconsConfig,
+        readout: Option<&PercentLabel>,
+        position: IconPosition
+    ) -> Option<Element<'static, Message>> {
         if !self.sinks.is_empty() {
-            let icon_type = self.sinks.get_icon(&self.server_info.default_sink);
+            let level = self
+                .sinks
+                .volume_level(&self.server_info.default_sink, config);
+            let glyph = match level.glyph(config) {
+                Some(glyph) => icon_raw(glyph.to_owned()),
+                None => icon(level.icon())
+            };
 
-            Some(icon(icon_type).into())
+            Some(
+                icon_with_label(
+                    glyph,
+                    readout.map(|label| text(label.format(self.cur_sink_volume))),
+                    position
+                )
+                .align_y(Alignment::Center)
+                .spacing(4)
+                .into()
+            )
         } else {
             None
         }
     }
 
+    /// Whether the output ports span more than one entry, so the sinks list
+    /// has something to choose from.
+    pub fn sinks_expandable(&self) -> bool {
+        self.sinks.iter().map(|s| s.ports.len()).sum::<usize>() > 1
+    }
+
+    /// Input counterpart of [`Self::sinks_expandable`].
+    pub fn sources_expandable(&self) -> bool {
+        self.sources.iter().map(|s| s.ports.len()).sum::<usize>() > 1
+    }
+
+    /// Volume sliders for the default sink and, while an input is in use,
+    /// the default source. The toggle for a device list is left out when
+    /// `auto_expand` keeps the list open.
     pub fn audio_sliders(
         &self,
         sub_menu: Option<SubMenu>,
+        auto_expand: bool,
+        step: u32,
         opacity: f32
     ) -> (Option<Element<'_, Message>>, Option<Element<'_, Message>>) {
         let active_sink = self
@@ -55,11 +95,9 @@ impl AudioData {
                 Message::Audio(AudioMessage::ToggleSinkMute),
                 self.cur_sink_volume,
                 |v| Message::Audio(AudioMessage::SinkVolumeChanged(v)),
-                if self.sinks.iter().map(|s| s.ports.len()).sum::<usize>() > 1 {
-                    Some((sub_menu, Message::ToggleSubMenu(SubMenu::Sinks)))
-                } else {
-                    None
-                },
+                (self.sinks_expandable() && !auto_expand)
+                    .then_some((sub_menu, Message::ToggleSubMenu(SubMenu::Sinks))),
+                step,
                 opacity
             )
         });
@@ -77,11 +115,9 @@ impl AudioData {
                     Message::Audio(AudioMessage::ToggleSourceMute),
                     self.cur_source_volume,
                     |v| Message::Audio(AudioMessage::SourceVolumeChanged(v)),
-                    if self.sources.iter().map(|s| s.ports.len()).sum::<usize>() > 1 {
-                        Some((sub_menu, Message::ToggleSubMenu(SubMenu::Sources)))
-                    } else {
-                        None
-                    },
+                    (self.sources_expandable() && !auto_expand)
+                        .then_some((sub_menu, Message::ToggleSubMenu(SubMenu::Sources))),
+                    step,
                     opacity
                 )
             });
@@ -92,6 +128,72 @@ impl AudioData {
         }
     }
 
+    /// Row opening the per-application volume list, shown while at least
+    /// one application is playing.
+    pub fn sink_inputs_button(
+        &self,
+        sub_menu: Option<SubMenu>,
+        opacity: f32
+    ) -> Option<Element<'_, Message>> {
+        (!self.sink_inputs.is_empty()).then(|| {
+            button(
+                row!(
+                    text(format!("Applications ({})", self.sink_inputs.len())).width(Length::Fill),
+                    icon(if sub_menu == Some(SubMenu::SinkInputs) {
+                        Icons::Close
+                    } else {
+                        Icons::RightArrow
+                    })
+                )
+                .align_y(Alignment::Center)
+                .spacing(8)
+            )
+            .on_press(Message::ToggleSubMenu(SubMenu::SinkInputs))
+            .padding([4, 12])
+            .width(Length::Fill)
+            .style(ghost_button_style(opacity))
+            .into()
+        })
+    }
+
+    /// One volume slider per application stream, labelled with the
+    /// application name.
+    pub fn sink_inputs_submenu(&self, step: u32, opacity: f32) -> Element<'_, Message> {
+        Column::with_children(
+            self.sink_inputs
+                .iter()
+                .map(|input| {
+                    let index = input.index;
+                    let volume = if input.muted {
+                        0
+                    } else {
+                        (input.volume.get_volume() * 100.0) as i32
+                    };
+
+                    column!(
+                        text(input.name.as_str()).size(12),
+                        audio_slider(
+                            SliderType::Sink,
+                            input.muted,
+                            Message::Audio(AudioMessage::ToggleSinkInputMute(index)),
+                            volume,
+                            move |v| {
+                                Message::Audio(AudioMessage::SinkInputVolumeChanged(index, v))
+                            },
+                            None,
+                            step,
+                            opacity
+                        )
+                    )
+                    .spacing(4)
+                    .into()
+                })
+                .collect::<Vec<_>>()
+        )
+        .spacing(12)
+        .into()
+    }
+
     pub fn sinks_submenu(&self, id: Id, show_more: bool, opacity: f32) -> Element<'_, Message> {
         audio_submenu(
             self.sinks
@@ -155,13 +257,14 @@ pub fn audio_slider<'a, Message: 'a + Clone>(
     volume: i32,
     volume_changed: impl Fn(i32) -> Message + 'a,
     with_submenu: Option<(Option<SubMenu>, Message)>,
+    step: u32,
     opacity: f32
 ) -> Element<'a, Message> {
     Row::new()
         .push(
             button(icon(if is_mute {
                 match slider_type {
-                    SliderType::Sink => Icons::Speaker0,
+                    SliderType::Sink => Icons::SpeakerMuted,
                     SliderType::Source => Icons::Mic0
                 }
             } else {
@@ -181,9 +284,11 @@ pub fn audio_slider<'a, Message: 'a + Clone>(
             .style(settings_button_style(opacity))
         )
         .push(
-            slider(0..=100, volume, volume_changed)
-                .step(1)
-                .width(Length::Fill)
+            slider(0..=100, volume, move |v| {
+                volume_changed(utils::snap_to_step(v as u32, step, 100) as i32)
+            })
+            .step(step.max(1) as i32)
+            .width(Length::Fill)
         )
         .push_maybe(with_submenu.map(|(submenu, msg)| {
             button(icon(match (slider_type, submenu) {
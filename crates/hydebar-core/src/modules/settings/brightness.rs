@@ -1,35 +1,68 @@
 use iced::{
     Alignment, Element, Length,
-    widget::{container, row, slider}
+    widget::{container, row, slider, text}
 };
 
 use super::Message;
 use crate::{
     components::icons::{Icons, icon},
+    config::PercentLabel,
     services::{
         ServiceEvent,
         brightness::{BrightnessData, BrightnessService}
-    }
+    },
+    utils
 };
 
 #[derive(Debug, Clone)]
 pub enum BrightnessMessage {
     Event(ServiceEvent<BrightnessService>),
-    Change(u32)
+    Change(u32),
+    ChangeKeyboard(u32)
 }
 
 impl BrightnessData {
-    pub fn brightness_slider(&self) -> Element<'_, Message> {
+    /// Slider row snapping to `step` percent, followed by the percentage when
+    /// `readout` is set.
+    pub fn brightness_slider(
+        &self,
+        readout: Option<&PercentLabel>,
+        step: u32
+    ) -> Element<'_, Message> {
+        let percent = self.current * 100 / self.max;
+
         row!(
             container(icon(Icons::Brightness)).padding([8, 11]),
-            slider(0..=100, self.current * 100 / self.max, |v| {
+            slider(0..=100, percent, move |v| {
+                let v = utils::snap_to_step(v, step, 100);
                 Message::Brightness(BrightnessMessage::Change(v * self.max / 100))
             })
-            .step(1_u32)
+            .step(step.max(1))
             .width(Length::Fill),
         )
+        .push_maybe(readout.map(|label| text(label.format(percent))))
         .align_y(Alignment::Center)
         .spacing(8)
         .into()
     }
+
+    /// Slider over the raw levels of the keyboard backlight, which usually
+    /// has only a few, or `None` without one.
+    pub fn keyboard_brightness_slider(&self) -> Option<Element<'_, Message>> {
+        let keyboard = self.keyboard_brightness?;
+
+        Some(
+            row!(
+                container(icon(Icons::KeyboardBacklight)).padding([8, 11]),
+                slider(0..=keyboard.max, keyboard.current, |v| {
+                    Message::Brightness(BrightnessMessage::ChangeKeyboard(v))
+                })
+                .step(1_u32)
+                .width(Length::Fill),
+            )
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .into()
+        )
+    }
 }
@@ -192,13 +192,15 @@ mod tests {
         let (runtime, mut receiver, sender) = setup_forwarder();
         let mut forwarder = AudioEventForwarder::new(sender);
 
-        let _ = forwarder.send(ServiceEvent::Error(()));
+        let _ = forwarder.send(ServiceEvent::Error("no PulseAudio server".to_owned()));
 
         let event = receiver.try_recv().expect("event queued");
         match event {
             Some(BusEvent::Module(ModuleEvent::Settings(Message::Audio(
-                AudioMessage::Event(ServiceEvent::Error(()))
-            )))) => {}
+                AudioMessage::Event(ServiceEvent::Error(reason))
+            )))) => {
+                assert_eq!(reason, "no PulseAudio server");
+            }
             other => panic!("unexpected event: {other:?}")
         }
 
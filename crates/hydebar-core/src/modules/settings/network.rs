@@ -1,34 +1,92 @@
 use iced::{
     Alignment, Element, Length, Theme,
-    widget::{Column, button, column, container, horizontal_rule, row, scrollable, text, toggler},
+    widget::{
+        Column, Row, button, column, container, horizontal_rule, row, scrollable, text, toggler
+    },
     window::Id
 };
 
-use super::{Message, SubMenu, quick_setting_button};
+use super::{
+    Message, SubMenu, quick_setting_button,
+    talkers::{ProcessTraffic, TopTalkers}
+};
 use crate::{
     components::icons::{Icons, icon},
+    config::{SettingsNetworkConfig, ThroughputFormat},
     services::{
         ServiceEvent,
         network::{
             AccessPoint, ActiveConnectionInfo, ConnectivityState, KnownConnection, NetworkData,
-            NetworkService, Vpn
+            NetworkService, Vpn, VpnKind
         }
     },
     style::{ghost_button_style, settings_button_style},
-    utils::IndicatorState
+    utils::{IndicatorState, throughput::format_throughput}
 };
 
 #[derive(Debug, Clone)]
 pub enum NetworkMessage {
     Event(ServiceEvent<NetworkService>),
     ToggleWiFi,
-    ScanNearByWiFi,
+    Refresh,
     WiFiMore(Id),
     VpnMore(Id),
     SelectAccessPoint(AccessPoint),
     RequestWiFiPassword(Id, String),
     ToggleVpn(Vpn),
-    ToggleAirplaneMode
+    ToggleAirplaneMode,
+    /// Re-read the traffic counters of the active interface.
+    SampleThroughput,
+    /// Run the configured command opening the captive portal.
+    OpenPortal(Id),
+    /// Traffic per process read from `ss`, or `None` when it could not run.
+    Talkers(Option<ProcessTraffic>)
+}
+
+/// Processes moving the most traffic, or why none are listed; `None` while
+/// `top_talkers` is off.
+pub(super) fn top_talkers_section<'a>(
+    talkers: &TopTalkers,
+    config: &SettingsNetworkConfig
+) -> Option<Element<'a, NetworkMessage>> {
+    if config.top_talkers == 0 {
+        return None;
+    }
+
+    let status = |message: &'a str| -> Vec<Element<'a, NetworkMessage>> {
+        vec![text(message).size(12).into()]
+    };
+    let rows = if talkers.is_unavailable() {
+        status("Could not run ss")
+    } else {
+        match talkers.ranked() {
+            None => status("Measuring…"),
+            Some([]) => status("No traffic"),
+            Some(ranked) => ranked
+                .iter()
+                .take(config.top_talkers)
+                .map(|talker| {
+                    let (value, unit) = format_throughput(talker.bytes_per_second, &config.format);
+
+                    row!(
+                        text(talker.process.clone()).width(Length::Fill),
+                        text(format!("{value} {unit}")).size(12)
+                    )
+                    .spacing(8)
+                    .into()
+                })
+                .collect()
+        }
+    };
+
+    Some(
+        column!(
+            text("Top Talkers"),
+            Column::with_children(rows).spacing(4).padding([0, 8])
+        )
+        .spacing(4)
+        .into()
+    )
 }
 
 static WIFI_SIGNAL_ICONS: [Icons; 6] = [
@@ -71,6 +129,15 @@ impl ActiveConnectionInfo {
         }
     }
 
+    /// Warning glyph shown while the connection is held by a captive portal.
+    pub fn get_portal_icon(&self) -> Icons {
+        if matches!(self, Self::WiFi { .. }) {
+            Icons::WifiAlert
+        } else {
+            Icons::Alert
+        }
+    }
+
     pub fn get_indicator_state(&self) -> IndicatorState {
         match self {
             Self::WiFi {
@@ -81,6 +148,15 @@ impl ActiveConnectionInfo {
     }
 }
 
+impl Vpn {
+    pub fn get_icon(&self) -> Icons {
+        match self.kind {
+            VpnKind::Plugin => Icons::Vpn,
+            VpnKind::WireGuard => Icons::WireGuard
+        }
+    }
+}
+
 impl NetworkData {
     pub fn get_connection_indicator<Message: 'static>(&self) -> Option<Element<'static, Message>> {
         if self.airplane_mode || !self.wifi_present {
@@ -96,13 +172,18 @@ impl NetworkData {
                     .map_or_else(
                         || icon(Icons::Wifi0).into(),
                         |a| {
-                            let icon_type = a.get_icon();
+                            let icon_type = if self.connectivity == ConnectivityState::Portal {
+                                a.get_portal_icon()
+                            } else {
+                                a.get_icon()
+                            };
                             let state = (self.connectivity, a.get_indicator_state());
 
                             container(icon(icon_type))
                                 .style(move |theme: &Theme| container::Style {
                                     text_color: match state {
-                                        (ConnectivityState::Full, IndicatorState::Warning) => {
+                                        (ConnectivityState::Full, IndicatorState::Warning)
+                                        | (ConnectivityState::Portal, _) => {
                                             Some(theme.extended_palette().danger.weak.color)
                                         }
                                         (ConnectivityState::Full, _) => None,
@@ -133,13 +214,55 @@ impl NetworkData {
             })
     }
 
-    pub fn get_wifi_quick_setting_button(
+    /// Traffic rates of the active interface, once they have been sampled.
+    pub fn get_throughput_indicator<Message: 'static>(
+        &self,
+        format: &ThroughputFormat
+    ) -> Option<Element<'static, Message>> {
+        self.throughput.map(|throughput| {
+            let (rx, rx_unit) = format_throughput(throughput.rx, format);
+            let (tx, tx_unit) = format_throughput(throughput.tx, format);
+
+            text(format!("↓ {rx} {rx_unit} ↑ {tx} {tx_unit}"))
+                .size(12)
+                .into()
+        })
+    }
+
+    /// "Sign in required" notice shown while the network sits behind a
+    /// captive portal. The notice opens the portal when `can_open` is set.
+    pub fn portal_notice(
         &self,
         id: Id,
+        can_open: bool,
+        opacity: f32
+    ) -> Option<Element<'_, NetworkMessage>> {
+        (self.connectivity == ConnectivityState::Portal).then(|| {
+            Row::new()
+                .push(icon(Icons::Alert).size(12))
+                .push(text("Sign in required").size(12).width(Length::Fill))
+                .push_maybe(can_open.then(|| {
+                    button(text("Open").size(12))
+                        .padding([2, 8])
+                        .on_press(NetworkMessage::OpenPortal(id))
+                        .style(settings_button_style(opacity))
+                }))
+                .spacing(8)
+                .align_y(Alignment::Center)
+                .into()
+        })
+    }
+
+    /// Wi-Fi toggle, with the list of nearby networks and `talkers` below it
+    /// while its submenu is open.
+    pub fn get_wifi_quick_setting_button<'a>(
+        &'a self,
+        id: Id,
         sub_menu: Option<SubMenu>,
         show_more_button: bool,
+        talkers: Option<Element<'a, NetworkMessage>>,
         opacity: f32
-    ) -> Option<(Element<'_, Message>, Option<Element<'_, Message>>)> {
+    ) -> Option<(Element<'a, Message>, Option<Element<'a, Message>>)> {
         if self.wifi_present {
             let active_connection = self.active_connections.iter().find_map(|c| match c {
                 ActiveConnectionInfo::WiFi {
@@ -173,6 +296,7 @@ impl NetworkData {
                             id,
                             active_connection.map(|(name, strengh, _)| (name.as_str(), *strengh)),
                             show_more_button,
+                            talkers,
                             opacity
                         )
                         .map(Message::Network)
@@ -216,26 +340,33 @@ impl NetworkData {
             })
     }
 
-    pub fn wifi_menu(
-        &self,
+    /// Progress of the Wi-Fi refresh, shown next to the refresh button.
+    fn refresh_status(&self) -> &'static str {
+        if self.scanning_nearby_wifi {
+            "Scanning..."
+        } else if self.refresh_failed {
+            "Refresh failed"
+        } else {
+            ""
+        }
+    }
+
+    pub fn wifi_menu<'a>(
+        &'a self,
         id: Id,
         active_connection: Option<(&str, u8)>,
         show_more_button: bool,
+        talkers: Option<Element<'a, NetworkMessage>>,
         opacity: f32
-    ) -> Element<'_, NetworkMessage> {
+    ) -> Element<'a, NetworkMessage> {
         let main = column!(
             row!(
                 text("Nearby Wifi").width(Length::Fill),
-                text(if self.scanning_nearby_wifi {
-                    "Scanning..."
-                } else {
-                    ""
-                })
-                .size(12),
+                text(self.refresh_status()).size(12),
                 button(icon(Icons::Refresh))
                     .padding([4, 10])
                     .style(settings_button_style(opacity))
-                    .on_press(NetworkMessage::ScanNearByWiFi),
+                    .on_press(NetworkMessage::Refresh),
             )
             .spacing(8)
             .width(Length::Fill)
@@ -304,6 +435,7 @@ impl NetworkData {
             ))
             .max_height(200),
         )
+        .push_maybe(talkers.map(|talkers| column!(horizontal_rule(1), talkers).spacing(8)))
         .spacing(8);
 
         if show_more_button {
@@ -342,11 +474,14 @@ impl NetworkData {
                     );
 
                     row!(
+                        icon(vpn.get_icon()),
                         text(vpn.name.to_string()).width(Length::Fill),
                         toggler(is_active)
                             .on_toggle(|_| { NetworkMessage::ToggleVpn(vpn.clone()) })
                             .width(Length::Shrink),
                     )
+                    .spacing(8)
+                    .align_y(Alignment::Center)
                     .into()
                 })
                 .collect::<Vec<Element<NetworkMessage>>>(),
@@ -388,3 +523,46 @@ impl NetworkData {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::network::NetworkServiceError;
+
+    #[test]
+    fn portal_notice_follows_connectivity() {
+        let mut data = NetworkData {
+            connectivity: ConnectivityState::Portal,
+            ..NetworkData::default()
+        };
+        assert!(data.portal_notice(Id::unique(), true, 1.0).is_some());
+
+        data.connectivity = ConnectivityState::Full;
+        assert!(data.portal_notice(Id::unique(), true, 1.0).is_none());
+    }
+
+    #[test]
+    fn refresh_status_reports_only_refresh_failures() {
+        let mut data = NetworkData {
+            last_error: Some(NetworkServiceError::new("command failed")),
+            ..NetworkData::default()
+        };
+        assert_eq!(data.refresh_status(), "");
+
+        data.refresh_failed = true;
+        assert_eq!(data.refresh_status(), "Refresh failed");
+
+        data.scanning_nearby_wifi = true;
+        assert_eq!(data.refresh_status(), "Scanning...");
+    }
+
+    #[test]
+    fn top_talkers_are_listed_only_when_enabled() {
+        let talkers = TopTalkers::default();
+        let mut config = SettingsNetworkConfig::default();
+        assert!(top_talkers_section(&talkers, &config).is_none());
+
+        config.top_talkers = 5;
+        assert!(top_talkers_section(&talkers, &config).is_some());
+    }
+}
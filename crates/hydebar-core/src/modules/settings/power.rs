@@ -6,8 +6,9 @@ use iced::{
 use crate::{
     components::icons::{Icons, icon},
     config::SettingsModuleConfig,
+    confirm_dialog::ConfirmDialog,
     style::ghost_button_style,
-    utils
+    utils::{self, launcher::CommandContext}
 };
 
 #[derive(Debug, Clone)]
@@ -19,22 +20,35 @@ pub enum PowerMessage {
 }
 
 impl PowerMessage {
-    pub fn update(self) {
+    /// Run the action's command in `context`.
+    pub fn update(self, context: CommandContext) {
         match self {
             PowerMessage::Suspend(cmd) => {
-                utils::launcher::suspend(cmd);
+                utils::launcher::suspend(cmd, context);
             }
             PowerMessage::Reboot(cmd) => {
-                utils::launcher::reboot(cmd);
+                utils::launcher::reboot(cmd, context);
             }
             PowerMessage::Shutdown(cmd) => {
-                utils::launcher::shutdown(cmd);
+                utils::launcher::shutdown(cmd, context);
             }
             PowerMessage::Logout(cmd) => {
-                utils::launcher::logout(cmd);
+                utils::launcher::logout(cmd, context);
             }
         }
     }
+
+    /// Prompt asking the user to confirm this action before it runs.
+    pub fn confirm_dialog(self) -> ConfirmDialog<PowerMessage> {
+        let (icon, label, body) = match &self {
+            PowerMessage::Suspend(_) => (Icons::Suspend, "Suspend", "Suspend the system now?"),
+            PowerMessage::Reboot(_) => (Icons::Reboot, "Reboot", "Reboot the system now?"),
+            PowerMessage::Shutdown(_) => (Icons::Power, "Shutdown", "Shut down the system now?"),
+            PowerMessage::Logout(_) => (Icons::Logout, "Logout", "End the current session now?")
+        };
+
+        ConfirmDialog::new(icon, label, body, label, self)
+    }
 }
 
 pub fn power_menu<'a>(opacity: f32, config: &SettingsModuleConfig) -> Element<'a, PowerMessage> {
@@ -1,5 +1,15 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant}
+};
+
+use iced::Subscription;
 use log::info;
-use tokio::{runtime::Handle, task::JoinHandle};
+use tokio::{
+    runtime::Handle,
+    task::JoinHandle,
+    time::{MissedTickBehavior, interval}
+};
 
 use super::{
     audio::AudioMessage,
@@ -12,46 +22,145 @@ use super::{
     },
     network::NetworkMessage,
     power::PowerMessage,
+    talkers::{self, TopTalkers},
     upower::UPowerMessage,
     view::SettingsViewExt
 };
 use crate::{
     ModuleContext, ModuleEventSender,
-    config::SettingsModuleConfig,
+    config::{SettingsModuleConfig, SettingsSubmenu},
+    confirm_dialog::{self, ConfirmDialog},
     event_bus::ModuleEvent,
     menu::MenuType,
-    modules::{Module, ModuleError, OnModulePress},
+    modules::{Module, ModuleError, OnModulePress, battery::RemainingEstimate},
     outputs::Outputs,
     password_dialog,
     services::{
         ReadOnlyService, ServiceEvent,
-        audio::{AudioCommand, AudioService},
+        audio::{AudioCommand, AudioData, AudioService},
         bluetooth::{BluetoothCommand, BluetoothService},
         brightness::{BrightnessCommand, BrightnessService},
-        idle_inhibitor::IdleInhibitorManager,
-        network::{NetworkCommand, NetworkEvent, NetworkService},
-        upower::{PowerProfileCommand, UPowerService}
-    }
+        idle_inhibitor::{IdleInhibit, IdleInhibitorManager},
+        network::{NetworkCommand, NetworkEvent, NetworkService, ScanCache},
+        upower::{PowerProfileCommand, UPowerEvent, UPowerService}
+    },
+    utils::launcher::{self, CommandContext}
 };
 
+/// How often the traffic counters of the active interface are read.
+const THROUGHPUT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Connection state of a settings backing service, derived from the
+/// `Init` and `Error` events its forwarder delivers.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum ServiceStatus {
+    /// No event has arrived yet; the service is still connecting.
+    #[default]
+    Initializing,
+    Ready,
+    /// The latest event was an error. The last known service state, if any,
+    /// is kept around while it reconnects.
+    Unavailable(String)
+}
+
+impl ServiceStatus {
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self, ServiceStatus::Unavailable(_))
+    }
+}
+
+/// Backing service the settings module runs a listener task for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsService {
+    Audio,
+    Network,
+    Bluetooth,
+    Brightness,
+    UPower
+}
+
+impl SettingsService {
+    pub const ALL: [SettingsService; 5] = [
+        SettingsService::Audio,
+        SettingsService::Network,
+        SettingsService::Bluetooth,
+        SettingsService::Brightness,
+        SettingsService::UPower
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingsService::Audio => "Audio",
+            SettingsService::Network => "Network",
+            SettingsService::Bluetooth => "Bluetooth",
+            SettingsService::Brightness => "Brightness",
+            SettingsService::UPower => "Battery"
+        }
+    }
+}
+
+/// Status of every service the settings module listens to.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(super) struct ServiceStatuses {
+    pub audio:      ServiceStatus,
+    pub network:    ServiceStatus,
+    pub bluetooth:  ServiceStatus,
+    pub brightness: ServiceStatus,
+    pub upower:     ServiceStatus
+}
+
+impl ServiceStatuses {
+    pub fn get(&self, service: SettingsService) -> &ServiceStatus {
+        match service {
+            SettingsService::Audio => &self.audio,
+            SettingsService::Network => &self.network,
+            SettingsService::Bluetooth => &self.bluetooth,
+            SettingsService::Brightness => &self.brightness,
+            SettingsService::UPower => &self.upower
+        }
+    }
+
+    fn get_mut(&mut self, service: SettingsService) -> &mut ServiceStatus {
+        match service {
+            SettingsService::Audio => &mut self.audio,
+            SettingsService::Network => &mut self.network,
+            SettingsService::Bluetooth => &mut self.bluetooth,
+            SettingsService::Brightness => &mut self.brightness,
+            SettingsService::UPower => &mut self.upower
+        }
+    }
+}
+
 pub struct Settings {
     pub(super) audio:           Option<AudioService>,
     pub brightness:             Option<BrightnessService>,
     pub(super) network:         Option<NetworkService>,
     pub(super) bluetooth:       Option<BluetoothService>,
-    pub(super) idle_inhibitor:  Option<IdleInhibitorManager>,
+    pub(super) idle_inhibitor:  Option<Box<dyn IdleInhibit>>,
     pub sub_menu:               Option<SubMenu>,
+    pub(super) tab:             SettingsTab,
     pub(super) upower:          Option<UPowerService>,
+    pub(super) remaining:       RemainingEstimate,
     pub(super) password_dialog: Option<(String, String)>,
+    pub(super) confirm_dialog:  Option<ConfirmDialog<PowerMessage>>,
+    pub(super) status:          ServiceStatuses,
     pub(super) sender:          Option<ModuleEventSender<Message>>,
     pub(super) runtime:         Option<Handle>,
-    pub(super) tasks:           Vec<JoinHandle<()>>
+    pub(super) tasks:           Vec<(SettingsService, JoinHandle<()>)>,
+    /// Ticker sampling the network throughput, when it is shown.
+    pub(super) throughput_task: Option<JoinHandle<()>>,
+    /// Processes ranked by traffic, listed in the Wi-Fi menu.
+    pub(super) talkers:         TopTalkers,
+    /// Wi-Fi scan results kept for `network.scan_cache_ttl_secs`.
+    pub(super) scan_cache:      Arc<ScanCache>,
+    /// Context the configured commands run in.
+    pub(super) commands:        CommandContext
 }
 
 impl Default for Settings {
     fn default() -> Self {
         let idle_inhibitor = match IdleInhibitorManager::new() {
-            Ok(manager) => Some(manager),
+            Ok(manager) => Some(Box::new(manager) as Box<dyn IdleInhibit>),
             Err(err) => {
                 log::warn!("Failed to initialize idle inhibitor: {err}");
                 None
@@ -65,16 +174,130 @@ impl Default for Settings {
             bluetooth: None,
             idle_inhibitor,
             sub_menu: None,
+            tab: SettingsTab::default(),
             upower: None,
+            remaining: RemainingEstimate::default(),
             password_dialog: None,
+            confirm_dialog: None,
+            status: ServiceStatuses::default(),
             sender: None,
             runtime: None,
-            tasks: Vec::new()
+            tasks: Vec::new(),
+            throughput_task: None,
+            talkers: TopTalkers::default(),
+            scan_cache: Arc::default(),
+            commands: CommandContext::module("Settings")
         }
     }
 }
 
 impl Settings {
+    /// Status of every backing service, labelled for display.
+    pub fn service_statuses(&self) -> [(&'static str, ServiceStatus); 6] {
+        let idle_inhibitor = if self.idle_inhibitor.is_some() {
+            ServiceStatus::Ready
+        } else {
+            ServiceStatus::Unavailable("not supported by the compositor".to_owned())
+        };
+
+        [
+            ("Audio", self.status.audio.clone()),
+            ("Network", self.status.network.clone()),
+            ("Bluetooth", self.status.bluetooth.clone()),
+            ("Brightness", self.status.brightness.clone()),
+            ("UPower", self.status.upower.clone()),
+            ("Idle inhibitor", idle_inhibitor)
+        ]
+    }
+
+    /// Audio state of the default devices, `None` until the audio service
+    /// has started.
+    pub fn audio(&self) -> Option<&AudioData> {
+        self.audio.as_deref()
+    }
+
+    /// Whether idle is currently inhibited, `None` when the compositor does
+    /// not support the idle inhibit protocol.
+    pub fn idle_inhibited(&self) -> Option<bool> {
+        self.idle_inhibitor
+            .as_ref()
+            .map(|inhibitor| inhibitor.is_inhibited())
+    }
+
+    /// Settings toggling `inhibitor` instead of the compositor's.
+    #[cfg(test)]
+    pub(crate) fn with_idle_inhibitor(inhibitor: impl IdleInhibit + 'static) -> Self {
+        Self {
+            idle_inhibitor: Some(Box::new(inhibitor)),
+            ..Self::default()
+        }
+    }
+
+    /// Spawns the listener task that feeds `service` events to `sender`.
+    fn spawn_listener(
+        runtime: &Handle,
+        sender: ModuleEventSender<Message>,
+        service: SettingsService,
+        scan_cache: &Arc<ScanCache>
+    ) -> JoinHandle<()> {
+        match service {
+            SettingsService::Audio => {
+                let mut publisher = AudioEventForwarder::new(sender);
+                runtime.spawn(async move {
+                    AudioService::listen(&mut publisher).await;
+                })
+            }
+            SettingsService::Network => {
+                let mut publisher = NetworkEventForwarder::new(sender);
+                let scan_cache = Arc::clone(scan_cache);
+                runtime.spawn(async move {
+                    NetworkService::listen(&mut publisher, scan_cache).await;
+                })
+            }
+            SettingsService::Bluetooth => {
+                let mut publisher = BluetoothEventForwarder::new(sender);
+                runtime.spawn(async move {
+                    BluetoothService::listen(&mut publisher).await;
+                })
+            }
+            SettingsService::Brightness => {
+                let mut publisher = BrightnessEventForwarder::new(sender);
+                runtime.spawn(async move {
+                    BrightnessService::listen(&mut publisher).await;
+                })
+            }
+            SettingsService::UPower => {
+                let mut publisher = UPowerEventForwarder::new(sender);
+                runtime.spawn(async move {
+                    UPowerService::listen(&mut publisher).await;
+                })
+            }
+        }
+    }
+
+    /// Restarts the listener of a single service, leaving the others running.
+    fn retry(&mut self, service: SettingsService) {
+        let (Some(runtime), Some(sender)) = (self.runtime(), self.sender()) else {
+            log::warn!(
+                "{} retry ignored because the module is not registered",
+                service.label()
+            );
+            return;
+        };
+
+        self.tasks.retain(|(owner, task)| {
+            if *owner == service {
+                task.abort();
+            }
+            *owner != service
+        });
+        *self.status.get_mut(service) = ServiceStatus::Initializing;
+        self.tasks.push((
+            service,
+            Self::spawn_listener(&runtime, sender, service, &self.scan_cache)
+        ));
+    }
+
     pub(super) fn runtime(&self) -> Option<Handle> {
         self.runtime.as_ref().cloned()
     }
@@ -83,6 +306,27 @@ impl Settings {
         self.sender.as_ref().cloned()
     }
 
+    /// Puts the submenu back to how the menu opens: collapsed, or expanded
+    /// to `default_submenu`. Expanding Wi-Fi starts a scan like the toggle
+    /// does, so this is only called when the menu opens.
+    pub fn reset_sub_menu(&mut self, config: &SettingsModuleConfig) {
+        self.sub_menu = config.default_submenu.map(SubMenu::from);
+
+        if self.sub_menu == Some(SubMenu::Wifi) {
+            let _spawned = self.spawn_network_command(NetworkCommand::ScanNearByWiFi);
+        }
+    }
+
+    /// Sample the top talkers when enabled; subscribed only while the menu is
+    /// open, and only runs while the Wi-Fi submenu lists them.
+    pub fn menu_subscription(&self, config: &SettingsModuleConfig) -> Subscription<Message> {
+        if self.sub_menu == Some(SubMenu::Wifi) && config.network.top_talkers > 0 {
+            talkers::subscription()
+        } else {
+            Subscription::none()
+        }
+    }
+
     pub fn update(
         &mut self,
         message: Message,
@@ -92,35 +336,55 @@ impl Settings {
     ) {
         match message {
             Message::ToggleMenu(id, button_ui_ref) => {
-                self.sub_menu = None;
                 self.password_dialog = None;
+                self.confirm_dialog = None;
                 let _ = outputs.toggle_menu::<Message>(
                     id,
                     MenuType::Settings,
                     button_ui_ref,
                     main_config
                 );
+
+                if outputs.menu_type_is_open(&MenuType::Settings) {
+                    self.reset_sub_menu(config);
+                }
             }
             Message::Audio(msg) => match msg {
                 AudioMessage::Event(event) => match event {
                     ServiceEvent::Init(service) => {
                         self.audio = Some(service);
+                        self.status.audio = ServiceStatus::Ready;
                     }
                     ServiceEvent::Update(data) => {
                         if let Some(audio) = self.audio.as_mut() {
                             audio.update(data);
 
-                            if self.sub_menu == Some(SubMenu::Sinks) && audio.sinks.len() < 2 {
+                            // Auto expanded lists do not need the toggle, and a
+                            // leftover one would outlive the expansion.
+                            let expanded = config.audio.auto_expand_devices;
+
+                            if self.sub_menu == Some(SubMenu::Sinks)
+                                && (audio.sinks.len() < 2 || expanded)
+                            {
+                                self.sub_menu = None;
+                            }
+
+                            if self.sub_menu == Some(SubMenu::Sources)
+                                && (audio.sources.len() < 2 || expanded)
+                            {
                                 self.sub_menu = None;
                             }
 
-                            if self.sub_menu == Some(SubMenu::Sources) && audio.sources.len() < 2 {
+                            if self.sub_menu == Some(SubMenu::SinkInputs)
+                                && audio.sink_inputs.is_empty()
+                            {
                                 self.sub_menu = None;
                             }
                         }
                     }
                     ServiceEvent::Error(err) => {
-                        log::error!("Audio service error: {err:?}");
+                        log::error!("Audio service error: {err}");
+                        self.status.audio = ServiceStatus::Unavailable(err);
                     }
                 },
                 AudioMessage::ToggleSinkMute => {
@@ -138,35 +402,62 @@ impl Settings {
                 AudioMessage::SourceVolumeChanged(value) => {
                     let _spawned = self.spawn_audio_command(AudioCommand::SourceVolume(value));
                 }
+                AudioMessage::ToggleSinkInputMute(index) => {
+                    let _spawned =
+                        self.spawn_audio_command(AudioCommand::ToggleSinkInputMute(index));
+                }
+                AudioMessage::SinkInputVolumeChanged(index, value) => {
+                    let _spawned =
+                        self.spawn_audio_command(AudioCommand::SinkInputVolume(index, value));
+                }
                 AudioMessage::DefaultSourceChanged(name, port) => {
                     let _spawned =
                         self.spawn_audio_command(AudioCommand::DefaultSource(name, port));
                 }
                 AudioMessage::SinksMore(id) => {
                     if let Some(cmd) = &config.audio_sinks_more_cmd {
-                        crate::utils::launcher::execute_command(cmd.to_string());
+                        launcher::execute_command(
+                            cmd.to_string(),
+                            self.commands
+                                .clone()
+                                .with_output(outputs.get_monitor_name(id))
+                        );
                         let _ = outputs.close_menu::<Message>(id, main_config);
                     }
                 }
                 AudioMessage::SourcesMore(id) => {
                     if let Some(cmd) = &config.audio_sources_more_cmd {
-                        crate::utils::launcher::execute_command(cmd.to_string());
+                        launcher::execute_command(
+                            cmd.to_string(),
+                            self.commands
+                                .clone()
+                                .with_output(outputs.get_monitor_name(id))
+                        );
                         let _ = outputs.close_menu::<Message>(id, main_config);
                     }
                 }
             },
             Message::UPower(msg) => match msg {
                 UPowerMessage::Event(event) => match event {
-                    ServiceEvent::Init(service) => {
+                    ServiceEvent::Init(mut service) => {
+                        self.remaining = RemainingEstimate::default();
+                        if let Some(battery) = service.battery.as_mut() {
+                            battery.status = self.remaining.smooth(battery.status);
+                        }
                         self.upower = Some(service);
+                        self.status.upower = ServiceStatus::Ready;
                     }
-                    ServiceEvent::Update(data) => {
+                    ServiceEvent::Update(mut data) => {
+                        if let UPowerEvent::UpdateBattery(battery) = &mut data {
+                            battery.status = self.remaining.smooth(battery.status);
+                        }
                         if let Some(upower) = self.upower.as_mut() {
                             upower.update(data);
                         }
                     }
                     ServiceEvent::Error(err) => {
-                        log::error!("UPower service error: {err:?}");
+                        log::error!("UPower service error: {err}");
+                        self.status.upower = ServiceStatus::Unavailable(err);
                     }
                 },
                 UPowerMessage::TogglePowerProfile => {
@@ -177,6 +468,7 @@ impl Settings {
                 NetworkMessage::Event(event) => match event {
                     ServiceEvent::Init(service) => {
                         self.network = Some(service);
+                        self.status.network = ServiceStatus::Ready;
                     }
                     ServiceEvent::Update(NetworkEvent::RequestPasswordForSSID(ssid)) => {
                         self.password_dialog = Some((ssid, String::new()));
@@ -188,6 +480,7 @@ impl Settings {
                     }
                     ServiceEvent::Error(err) => {
                         log::error!("Network service error: {err:?}");
+                        self.status.network = ServiceStatus::Unavailable(err.message().to_owned());
                     }
                 },
                 NetworkMessage::ToggleAirplaneMode => {
@@ -214,29 +507,62 @@ impl Settings {
                     let _ =
                         outputs.request_keyboard::<Message>(id, main_config.menu_keyboard_focus);
                 }
-                NetworkMessage::ScanNearByWiFi => {
+                NetworkMessage::Refresh => {
+                    // Re-reading the state catches a roam whose signals have not
+                    // arrived yet, the scan updates the list of nearby networks.
+                    let _spawned = self.spawn_network_command(NetworkCommand::RefreshState);
                     let _spawned = self.spawn_network_command(NetworkCommand::ScanNearByWiFi);
                 }
                 NetworkMessage::WiFiMore(id) => {
                     if let Some(cmd) = &config.wifi_more_cmd {
-                        crate::utils::launcher::execute_command(cmd.to_string());
+                        launcher::execute_command(
+                            cmd.to_string(),
+                            self.commands
+                                .clone()
+                                .with_output(outputs.get_monitor_name(id))
+                        );
                         let _ = outputs.close_menu::<Message>(id, main_config);
                     }
                 }
                 NetworkMessage::VpnMore(id) => {
                     if let Some(cmd) = &config.vpn_more_cmd {
-                        crate::utils::launcher::execute_command(cmd.to_string());
+                        launcher::execute_command(
+                            cmd.to_string(),
+                            self.commands
+                                .clone()
+                                .with_output(outputs.get_monitor_name(id))
+                        );
                         let _ = outputs.close_menu::<Message>(id, main_config);
                     }
                 }
                 NetworkMessage::ToggleVpn(vpn) => {
                     let _spawned = self.spawn_network_command(NetworkCommand::ToggleVpn(vpn));
                 }
+                NetworkMessage::SampleThroughput => {
+                    if let Some(network) = self.network.as_mut() {
+                        network.sample_throughput();
+                    }
+                }
+                NetworkMessage::OpenPortal(id) => {
+                    if let Some(cmd) = &config.network.portal_cmd {
+                        launcher::execute_command(
+                            cmd.to_string(),
+                            self.commands
+                                .clone()
+                                .with_output(outputs.get_monitor_name(id))
+                        );
+                        let _ = outputs.close_menu::<Message>(id, main_config);
+                    }
+                }
+                NetworkMessage::Talkers(traffic) => {
+                    self.talkers.record(traffic, Instant::now());
+                }
             },
             Message::Bluetooth(msg) => match msg {
                 BluetoothMessage::Event(event) => match event {
                     ServiceEvent::Init(service) => {
                         self.bluetooth = Some(service);
+                        self.status.bluetooth = ServiceStatus::Ready;
                     }
                     ServiceEvent::Update(data) => {
                         if let Some(bluetooth) = self.bluetooth.as_mut() {
@@ -244,7 +570,8 @@ impl Settings {
                         }
                     }
                     ServiceEvent::Error(err) => {
-                        log::error!("Bluetooth service error: {err:?}");
+                        log::error!("Bluetooth service error: {err}");
+                        self.status.bluetooth = ServiceStatus::Unavailable(err);
                     }
                 },
                 BluetoothMessage::Toggle => match self.bluetooth.as_mut() {
@@ -269,7 +596,12 @@ impl Settings {
                 }
                 BluetoothMessage::More(id) => {
                     if let Some(cmd) = &config.bluetooth_more_cmd {
-                        crate::utils::launcher::execute_command(cmd.to_string());
+                        launcher::execute_command(
+                            cmd.to_string(),
+                            self.commands
+                                .clone()
+                                .with_output(outputs.get_monitor_name(id))
+                        );
                         let _ = outputs.close_menu::<Message>(id, main_config);
                     }
                 }
@@ -278,6 +610,7 @@ impl Settings {
                 BrightnessMessage::Event(event) => match event {
                     ServiceEvent::Init(service) => {
                         self.brightness = Some(service);
+                        self.status.brightness = ServiceStatus::Ready;
                     }
                     ServiceEvent::Update(data) => {
                         if let Some(brightness) = self.brightness.as_mut() {
@@ -286,12 +619,20 @@ impl Settings {
                     }
                     ServiceEvent::Error(err) => {
                         log::error!("Brightness service error: {err:?}");
+                        self.status.brightness = ServiceStatus::Unavailable(err.to_string());
                     }
                 },
                 BrightnessMessage::Change(value) => {
                     let _spawned = self.spawn_brightness_command(BrightnessCommand::Set(value));
                 }
+                BrightnessMessage::ChangeKeyboard(value) => {
+                    let _spawned =
+                        self.spawn_brightness_command(BrightnessCommand::SetKeyboard(value));
+                }
             },
+            Message::Retry(service) => {
+                self.retry(service);
+            }
             Message::ToggleSubMenu(menu_type) => {
                 if self.sub_menu == Some(menu_type) {
                     self.sub_menu.take();
@@ -303,6 +644,10 @@ impl Settings {
                     }
                 }
             }
+            Message::SelectTab(tab) => {
+                self.tab = tab;
+                self.sub_menu = None;
+            }
             Message::ToggleInhibitIdle => {
                 if let Some(idle_inhibitor) = &mut self.idle_inhibitor {
                     idle_inhibitor.toggle();
@@ -310,11 +655,24 @@ impl Settings {
             }
             Message::Lock => {
                 if let Some(lock_cmd) = &config.lock_cmd {
-                    crate::utils::launcher::execute_command(lock_cmd.to_string());
+                    launcher::execute_command(lock_cmd.to_string(), self.commands.clone());
                 }
             }
             Message::Power(msg) => {
-                msg.update();
+                if config.confirm_power_actions {
+                    self.confirm_dialog = Some(msg.confirm_dialog());
+                } else {
+                    msg.update(self.commands.clone());
+                }
+            }
+            Message::ConfirmDialog(msg) => {
+                if let Some(action) = self
+                    .confirm_dialog
+                    .take()
+                    .and_then(|dialog| dialog.resolve(msg))
+                {
+                    action.update(self.commands.clone());
+                }
             }
             Message::PasswordDialog(msg) => match msg {
                 password_dialog::Message::PasswordChanged(password) => {
@@ -362,45 +720,56 @@ where
     M: 'static + Clone + From<Message>
 {
     type ViewData<'a> = <Self as SettingsViewExt>::ViewData<'a>;
-    type RegistrationData<'a> = ();
+    type RegistrationData<'a> = &'a SettingsModuleConfig;
 
     fn register(
         &mut self,
         ctx: &ModuleContext,
-        _: Self::RegistrationData<'_>
+        config: Self::RegistrationData<'_>
     ) -> Result<(), ModuleError> {
-        for task in self.tasks.drain(..) {
+        for (_, task) in self.tasks.drain(..) {
+            task.abort();
+        }
+        if let Some(task) = self.throughput_task.take() {
             task.abort();
         }
 
+        self.commands = ctx.command_context("Settings");
+        self.scan_cache = Arc::new(ScanCache::new(Duration::from_secs(
+            config.network.scan_cache_ttl_secs
+        )));
         let sender = ctx.module_sender(ModuleEvent::Settings);
 
-        let mut tasks = Vec::new();
-
-        let mut audio_publisher = AudioEventForwarder::new(sender.clone());
-        tasks.push(ctx.runtime_handle().spawn(async move {
-            AudioService::listen(&mut audio_publisher).await;
-        }));
-
-        let mut brightness_publisher = BrightnessEventForwarder::new(sender.clone());
-        tasks.push(ctx.runtime_handle().spawn(async move {
-            BrightnessService::listen(&mut brightness_publisher).await;
-        }));
+        let tasks = SettingsService::ALL
+            .into_iter()
+            .map(|service| {
+                let task = Self::spawn_listener(
+                    ctx.runtime_handle(),
+                    sender.clone(),
+                    service,
+                    &self.scan_cache
+                );
+                (service, task)
+            })
+            .collect();
 
-        let mut network_publisher = NetworkEventForwarder::new(sender.clone());
-        tasks.push(ctx.runtime_handle().spawn(async move {
-            NetworkService::listen(&mut network_publisher).await;
-        }));
+        if config.network.show_throughput {
+            let sender = sender.clone();
+            self.throughput_task = Some(ctx.runtime_handle().spawn(async move {
+                let mut ticker = interval(THROUGHPUT_INTERVAL);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
-        let mut bluetooth_publisher = BluetoothEventForwarder::new(sender.clone());
-        tasks.push(ctx.runtime_handle().spawn(async move {
-            BluetoothService::listen(&mut bluetooth_publisher).await;
-        }));
+                loop {
+                    ticker.tick().await;
 
-        let mut upower_publisher = UPowerEventForwarder::new(sender.clone());
-        tasks.push(ctx.runtime_handle().spawn(async move {
-            UPowerService::listen(&mut upower_publisher).await;
-        }));
+                    if let Err(err) =
+                        sender.try_send(Message::Network(NetworkMessage::SampleThroughput))
+                    {
+                        log::warn!("failed to request a throughput sample: {err}");
+                    }
+                }
+            }));
+        }
 
         self.sender = Some(sender);
         self.runtime = Some(ctx.runtime_handle().clone());
@@ -429,7 +798,50 @@ pub enum Message {
     Lock,
     Power(PowerMessage),
     ToggleSubMenu(SubMenu),
-    PasswordDialog(password_dialog::Message)
+    SelectTab(SettingsTab),
+    PasswordDialog(password_dialog::Message),
+    ConfirmDialog(confirm_dialog::Message),
+    /// Restarts the listener of a service that failed.
+    Retry(SettingsService)
+}
+
+/// Category page of the settings menu. The selected tab survives closing the
+/// menu for the rest of the session.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum SettingsTab {
+    /// Every section on a single page.
+    #[default]
+    All,
+    Network,
+    Audio,
+    Display,
+    Power
+}
+
+impl SettingsTab {
+    pub const ALL: [SettingsTab; 5] = [
+        SettingsTab::All,
+        SettingsTab::Network,
+        SettingsTab::Audio,
+        SettingsTab::Display,
+        SettingsTab::Power
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingsTab::All => "All",
+            SettingsTab::Network => "Network",
+            SettingsTab::Audio => "Audio",
+            SettingsTab::Display => "Display",
+            SettingsTab::Power => "Power"
+        }
+    }
+
+    /// Whether the section belonging to `section` is visible while this tab
+    /// is selected.
+    pub fn shows(self, section: SettingsTab) -> bool {
+        self == SettingsTab::All || self == section
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -437,11 +849,26 @@ pub enum SubMenu {
     Power,
     Sinks,
     Sources,
+    /// Per-application volume sliders.
+    SinkInputs,
     Wifi,
     Vpn,
     Bluetooth
 }
 
+impl From<SettingsSubmenu> for SubMenu {
+    fn from(submenu: SettingsSubmenu) -> Self {
+        match submenu {
+            SettingsSubmenu::Power => Self::Power,
+            SettingsSubmenu::Sinks => Self::Sinks,
+            SettingsSubmenu::Sources => Self::Sources,
+            SettingsSubmenu::Wifi => Self::Wifi,
+            SettingsSubmenu::Vpn => Self::Vpn,
+            SettingsSubmenu::Bluetooth => Self::Bluetooth
+        }
+    }
+}
+
 // TODO: Fix broken tests
 #[cfg(all(test, feature = "enable-broken-tests"))]
 mod tests {
@@ -466,14 +893,18 @@ mod tests {
         let ctx = ModuleContext::new(bus.sender(), runtime.handle().clone());
         let mut settings = Settings::default();
 
-        <Settings as Module<Message>>::register(&mut settings, &ctx, ())
-            .expect("register should succeed");
+        <Settings as Module<Message>>::register(
+            &mut settings,
+            &ctx,
+            &SettingsModuleConfig::default()
+        )
+        .expect("register should succeed");
 
         assert!(settings.sender.is_some());
         assert!(settings.runtime.is_some());
         assert_eq!(settings.tasks.len(), 5);
 
-        for task in settings.tasks.drain(..) {
+        for (_, task) in settings.tasks.drain(..) {
             task.abort();
         }
     }
@@ -489,27 +920,283 @@ mod tests {
         let cancelled = Arc::new(AtomicBool::new(false));
         let guard_flag = Arc::clone(&cancelled);
 
-        settings.tasks.push(runtime.spawn(async move {
-            struct CancelGuard(Arc<AtomicBool>);
+        settings.tasks.push((
+            SettingsService::Audio,
+            runtime.spawn(async move {
+                struct CancelGuard(Arc<AtomicBool>);
 
-            impl Drop for CancelGuard {
-                fn drop(&mut self) {
-                    self.0.store(true, Ordering::SeqCst);
+                impl Drop for CancelGuard {
+                    fn drop(&mut self) {
+                        self.0.store(true, Ordering::SeqCst);
+                    }
                 }
-            }
 
-            let _guard = CancelGuard(guard_flag);
+                let _guard = CancelGuard(guard_flag);
 
-            future::pending::<()>().await;
-        }));
+                future::pending::<()>().await;
+            })
+        ));
 
-        <Settings as Module<Message>>::register(&mut settings, &ctx, ())
-            .expect("register should succeed");
+        <Settings as Module<Message>>::register(
+            &mut settings,
+            &ctx,
+            &SettingsModuleConfig::default()
+        )
+        .expect("register should succeed");
 
         assert!(cancelled.load(Ordering::SeqCst));
 
-        for task in settings.tasks.drain(..) {
+        for (_, task) in settings.tasks.drain(..) {
+            task.abort();
+        }
+    }
+    #[test]
+    fn bluetooth_error_is_retried_with_a_new_listener() {
+        let runtime = Runtime::new().expect("runtime");
+        let bus = EventBus::new(NonZeroUsize::new(4).expect("capacity"));
+        let ctx = ModuleContext::new(bus.sender(), runtime.handle().clone());
+        let config = SettingsModuleConfig::default();
+        let main_config = crate::config::Config::default();
+        let mut outputs = dummy_outputs();
+        let mut settings = Settings::default();
+
+        <Settings as Module<Message>>::register(&mut settings, &ctx, &config)
+            .expect("register should succeed");
+
+        let reason = "org.bluez was not provided by any .service files".to_owned();
+        settings.update(
+            Message::Bluetooth(BluetoothMessage::Event(ServiceEvent::Error(reason.clone()))),
+            &config,
+            &mut outputs,
+            &main_config
+        );
+        assert_eq!(
+            settings.status.bluetooth,
+            ServiceStatus::Unavailable(reason)
+        );
+
+        settings.update(
+            Message::Retry(SettingsService::Bluetooth),
+            &config,
+            &mut outputs,
+            &main_config
+        );
+        assert_eq!(settings.status.bluetooth, ServiceStatus::Initializing);
+
+        let owners: Vec<SettingsService> =
+            settings.tasks.iter().map(|(owner, _)| *owner).collect();
+        assert_eq!(owners.len(), 5);
+        assert_eq!(owners.last(), Some(&SettingsService::Bluetooth));
+        assert_eq!(
+            owners
+                .iter()
+                .filter(|owner| **owner == SettingsService::Bluetooth)
+                .count(),
+            1
+        );
+
+        for (_, task) in settings.tasks.drain(..) {
+            task.abort();
+        }
+    }
+
+    #[test]
+    fn audio_and_upower_errors_keep_their_reason() {
+        let config = SettingsModuleConfig::default();
+        let main_config = crate::config::Config::default();
+        let mut outputs = dummy_outputs();
+        let mut settings = Settings::default();
+
+        settings.update(
+            Message::Audio(AudioMessage::Event(ServiceEvent::Error(
+                "Connection refused".to_owned()
+            ))),
+            &config,
+            &mut outputs,
+            &main_config
+        );
+        settings.update(
+            Message::UPower(UPowerMessage::Event(ServiceEvent::Error(
+                "org.freedesktop.UPower was not provided".to_owned()
+            ))),
+            &config,
+            &mut outputs,
+            &main_config
+        );
+
+        assert_eq!(
+            settings.status.audio,
+            ServiceStatus::Unavailable("Connection refused".to_owned())
+        );
+        assert_eq!(
+            settings.status.upower,
+            ServiceStatus::Unavailable("org.freedesktop.UPower was not provided".to_owned())
+        );
+    }
+
+    #[test]
+    fn retry_restarts_only_the_requested_listener() {
+        let runtime = Runtime::new().expect("runtime");
+        let bus = EventBus::new(NonZeroUsize::new(4).expect("capacity"));
+        let ctx = ModuleContext::new(bus.sender(), runtime.handle().clone());
+        let mut settings = Settings::default();
+
+        <Settings as Module<Message>>::register(
+            &mut settings,
+            &ctx,
+            &SettingsModuleConfig::default()
+        )
+        .expect("register should succeed");
+        settings.status.audio = ServiceStatus::Unavailable("Connection refused".to_owned());
+        settings.status.network = ServiceStatus::Unavailable("NetworkManager is down".to_owned());
+
+        settings.retry(SettingsService::Audio);
+
+        assert_eq!(settings.status.audio, ServiceStatus::Initializing);
+        assert!(settings.status.network.is_unavailable());
+
+        let owners: Vec<SettingsService> =
+            settings.tasks.iter().map(|(owner, _)| *owner).collect();
+        assert_eq!(
+            owners,
+            [
+                SettingsService::Network,
+                SettingsService::Bluetooth,
+                SettingsService::Brightness,
+                SettingsService::UPower,
+                SettingsService::Audio
+            ]
+        );
+
+        for (_, task) in settings.tasks.drain(..) {
             task.abort();
         }
     }
+
+    #[test]
+    fn retry_is_ignored_before_registration() {
+        let mut settings = Settings::default();
+        settings.status.audio = ServiceStatus::Unavailable("Connection refused".to_owned());
+
+        settings.retry(SettingsService::Audio);
+
+        assert!(settings.status.audio.is_unavailable());
+        assert!(settings.tasks.is_empty());
+    }
+
+    #[test]
+    fn power_actions_run_only_once_confirmed() {
+        let runtime = Runtime::new().expect("runtime");
+        let _guard = runtime.enter();
+        let config = SettingsModuleConfig {
+            confirm_power_actions: true,
+            ..SettingsModuleConfig::default()
+        };
+        let main_config = crate::config::Config::default();
+        let mut outputs = dummy_outputs();
+        let mut settings = Settings::default();
+
+        let marker = |name: &str| {
+            let path =
+                std::env::temp_dir().join(format!("hydebar-power-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_file(&path);
+            path
+        };
+        let cancelled = marker("cancelled");
+        let confirmed = marker("confirmed");
+        let suspend = |path: &std::path::Path| {
+            Message::Power(PowerMessage::Suspend(format!("touch {}", path.display())))
+        };
+
+        settings.update(suspend(&cancelled), &config, &mut outputs, &main_config);
+        assert!(settings.confirm_dialog.is_some());
+
+        settings.update(
+            Message::ConfirmDialog(confirm_dialog::Message::Cancelled),
+            &config,
+            &mut outputs,
+            &main_config
+        );
+        assert!(settings.confirm_dialog.is_none());
+
+        settings.update(suspend(&confirmed), &config, &mut outputs, &main_config);
+        settings.update(
+            Message::ConfirmDialog(confirm_dialog::Message::Confirmed),
+            &config,
+            &mut outputs,
+            &main_config
+        );
+        assert!(settings.confirm_dialog.is_none());
+
+        runtime
+            .block_on(tokio::time::timeout(
+                std::time::Duration::from_secs(5),
+                async {
+                    while !confirmed.exists() {
+                        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                    }
+                }
+            ))
+            .expect("the confirmed action runs");
+        assert!(!cancelled.exists());
+
+        let _ = std::fs::remove_file(&confirmed);
+    }
+
+    #[test]
+    fn power_actions_run_without_a_prompt_by_default() {
+        let runtime = Runtime::new().expect("runtime");
+        let _guard = runtime.enter();
+        let config = SettingsModuleConfig::default();
+        let main_config = crate::config::Config::default();
+        let mut outputs = dummy_outputs();
+        let mut settings = Settings::default();
+
+        settings.update(
+            Message::Power(PowerMessage::Logout("true".to_owned())),
+            &config,
+            &mut outputs,
+            &main_config
+        );
+
+        assert!(settings.confirm_dialog.is_none());
+    }
+
+    #[test]
+    fn submenu_is_reset_only_when_the_menu_opens() {
+        let config = SettingsModuleConfig {
+            default_submenu: Some(SettingsSubmenu::Power),
+            ..SettingsModuleConfig::default()
+        };
+        let main_config = crate::config::Config::default();
+        let mut outputs = dummy_outputs();
+        let id = outputs.first_main_window_id().expect("main window");
+        let toggle = || {
+            Message::ToggleMenu(
+                id,
+                crate::position_button::ButtonUIRef {
+                    position: iced::Point::new(0., 0.),
+                    viewport: (0., 0.)
+                }
+            )
+        };
+        let mut settings = Settings::default();
+
+        settings.update(toggle(), &config, &mut outputs, &main_config);
+        assert!(outputs.menu_type_is_open(&MenuType::Settings));
+        assert_eq!(settings.sub_menu, Some(SubMenu::Power));
+
+        settings.sub_menu = Some(SubMenu::Wifi);
+        settings.update(toggle(), &config, &mut outputs, &main_config);
+        assert!(!outputs.menu_type_is_open(&MenuType::Settings));
+        assert_eq!(settings.sub_menu, Some(SubMenu::Wifi));
+
+        settings.update(toggle(), &config, &mut outputs, &main_config);
+        assert_eq!(settings.sub_menu, Some(SubMenu::Power));
+    }
+
+    fn dummy_outputs() -> Outputs {
+        let config = crate::config::Config::default();
+        Outputs::new::<()>(config.appearance.style, config.position, &config).0
+    }
 }
@@ -0,0 +1,250 @@
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    io::ErrorKind,
+    time::{Duration, Instant}
+};
+
+use iced::{
+    Subscription,
+    futures::{SinkExt, future::pending},
+    stream::channel
+};
+use log::{error, warn};
+use tokio::{process::Command, time::sleep};
+
+use super::{Message, NetworkMessage};
+
+/// Interval between `ss` runs while the Wi-Fi menu lists top talkers.
+pub const TALKERS_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Label for sockets whose owner `ss` cannot see, typically because they
+/// belong to another user.
+const UNKNOWN_PROCESS: &str = "other";
+
+/// Bytes sent and received so far over the open TCP connections of each
+/// process.
+pub type ProcessTraffic = HashMap<String, u64>;
+
+/// A process and the traffic it moved per second since the previous sample.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Talker {
+    pub process:          String,
+    pub bytes_per_second: u64
+}
+
+/// Processes ranked by their current traffic, built from successive `ss`
+/// samples.
+#[derive(Debug, Clone, Default)]
+pub struct TopTalkers {
+    previous:    Option<(Instant, ProcessTraffic)>,
+    ranked:      Option<Vec<Talker>>,
+    unavailable: bool
+}
+
+impl TopTalkers {
+    /// Rank processes by the traffic between `sample` and the previous one;
+    /// `None` records that `ss` could not be run.
+    ///
+    /// A previous sample older than a few refreshes is left over from the
+    /// last time the Wi-Fi menu was open and only serves as a new baseline.
+    pub fn record(&mut self, sample: Option<ProcessTraffic>, now: Instant) {
+        let Some(sample) = sample else {
+            self.unavailable = true;
+            return;
+        };
+
+        self.ranked = self.previous.as_ref().and_then(|(then, previous)| {
+            let elapsed = now.saturating_duration_since(*then);
+            (elapsed <= TALKERS_REFRESH_INTERVAL * 3).then(|| rank(previous, &sample, elapsed))
+        });
+        self.unavailable = false;
+        self.previous = Some((now, sample));
+    }
+
+    /// Busiest processes first, or `None` while waiting for a second sample
+    /// to compare against.
+    pub fn ranked(&self) -> Option<&[Talker]> {
+        self.ranked.as_deref()
+    }
+
+    /// Whether the last attempt to run `ss` failed.
+    pub fn is_unavailable(&self) -> bool {
+        self.unavailable
+    }
+}
+
+fn rank(previous: &ProcessTraffic, current: &ProcessTraffic, elapsed: Duration) -> Vec<Talker> {
+    let seconds = elapsed.as_secs_f64();
+    if seconds <= 0. {
+        return Vec::new();
+    }
+
+    let mut talkers: Vec<Talker> = current
+        .iter()
+        .filter_map(|(process, total)| {
+            // Closed connections take their bytes with them, so a total may
+            // shrink between samples.
+            let moved = total.saturating_sub(previous.get(process).copied().unwrap_or_default());
+            (moved > 0).then(|| Talker {
+                process:          process.clone(),
+                bytes_per_second: (moved as f64 / seconds) as u64
+            })
+        })
+        .collect();
+
+    talkers.sort_by(|a, b| {
+        b.bytes_per_second
+            .cmp(&a.bytes_per_second)
+            .then_with(|| a.process.cmp(&b.process))
+    });
+    talkers
+}
+
+/// Sum the `bytes_sent` and `bytes_received` counters of `ss -tinpH` output
+/// per owning process. Each connection line is followed by an indented line
+/// holding its TCP info.
+fn parse_ss(output: &str) -> ProcessTraffic {
+    let mut traffic = ProcessTraffic::new();
+    let mut process = None;
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            process = Some(
+                line.split_once("users:((\"")
+                    .and_then(|(_, users)| users.split_once('"'))
+                    .map_or(UNKNOWN_PROCESS, |(name, _)| name)
+                    .to_owned()
+            );
+            continue;
+        }
+
+        let Some(process) = process.take() else {
+            continue;
+        };
+
+        let bytes: u64 = line
+            .split_whitespace()
+            .filter_map(|field| {
+                field
+                    .strip_prefix("bytes_sent:")
+                    .or_else(|| field.strip_prefix("bytes_received:"))
+            })
+            .filter_map(|value| value.parse::<u64>().ok())
+            .sum();
+
+        *traffic.entry(process).or_default() += bytes;
+    }
+
+    traffic
+}
+
+async fn sample() -> Option<ProcessTraffic> {
+    match Command::new("ss").arg("-tinpH").output().await {
+        Ok(output) if output.status.success() => {
+            Some(parse_ss(&String::from_utf8_lossy(&output.stdout)))
+        }
+        Ok(output) => {
+            error!("ss exited with {}", output.status);
+            None
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            warn!("ss is not installed, top talkers are unavailable");
+            None
+        }
+        Err(err) => {
+            error!("Failed to run ss: {err}");
+            None
+        }
+    }
+}
+
+/// Samples `ss` every [`TALKERS_REFRESH_INTERVAL`]. Only subscribed while
+/// the Wi-Fi menu is open, so the tool never runs otherwise; a failed run is
+/// reported once and not retried until the menu opens again.
+pub fn subscription() -> Subscription<Message> {
+    Subscription::run_with_id(
+        TypeId::of::<TopTalkers>(),
+        channel(1, async |mut output| {
+            loop {
+                let traffic = sample().await;
+                let failed = traffic.is_none();
+
+                if output
+                    .send(Message::Network(NetworkMessage::Talkers(traffic)))
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+
+                if failed {
+                    pending::<()>().await;
+                }
+
+                sleep(TALKERS_REFRESH_INTERVAL).await;
+            }
+        })
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SS_OUTPUT: &str = "\
+ESTAB 0 0 10.0.0.2:50000 1.2.3.4:443 users:((\"firefox\",pid=1234,fd=88))
+\t cubic wscale:7,7 rto:204 bytes_sent:1000 bytes_acked:1001 bytes_received:4000 segs_out:10
+ESTAB 0 0 10.0.0.2:50002 1.2.3.5:443 users:((\"firefox\",pid=1234,fd=90))
+\t cubic bytes_sent:500 bytes_received:500
+ESTAB 0 0 10.0.0.2:22 10.0.0.9:40000
+\t cubic bytes_sent:300 bytes_received:200
+";
+
+    #[test]
+    fn parse_ss_sums_connections_per_process() {
+        let traffic = parse_ss(SS_OUTPUT);
+
+        assert_eq!(traffic.get("firefox"), Some(&6000));
+        assert_eq!(traffic.get(UNKNOWN_PROCESS), Some(&500));
+    }
+
+    #[test]
+    fn ranks_by_traffic_since_previous_sample() {
+        let mut talkers = TopTalkers::default();
+        let start = Instant::now();
+
+        talkers.record(Some(parse_ss(SS_OUTPUT)), start);
+        assert!(talkers.ranked().is_none());
+
+        let mut next = parse_ss(SS_OUTPUT);
+        next.insert("firefox".to_owned(), 10_000);
+        next.insert("curl".to_owned(), 20_000);
+        talkers.record(Some(next), start + Duration::from_secs(2));
+
+        assert_eq!(
+            talkers.ranked(),
+            Some(
+                &[
+                    Talker {
+                        process:          "curl".to_owned(),
+                        bytes_per_second: 10_000
+                    },
+                    Talker {
+                        process:          "firefox".to_owned(),
+                        bytes_per_second: 2_000
+                    }
+                ][..]
+            )
+        );
+    }
+
+    #[test]
+    fn missing_tool_marks_unavailable() {
+        let mut talkers = TopTalkers::default();
+
+        talkers.record(None, Instant::now());
+
+        assert!(talkers.is_unavailable());
+    }
+}
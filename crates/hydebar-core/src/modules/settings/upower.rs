@@ -1,11 +1,12 @@
 use iced::{
-    Alignment, Element, Theme,
+    Alignment, Color, Element, Theme,
     widget::{Container, container, row, text}
 };
 
 use super::{Message, quick_setting_button};
 use crate::{
-    components::icons::{Icons, icon},
+    components::icons::{Icons, icon, icon_with_label},
+    config::{BatteryModuleConfig, IconPosition},
     services::{
         ServiceEvent,
         upower::{BatteryData, BatteryStatus, PowerProfile, UPowerService}
@@ -20,53 +21,69 @@ pub enum UPowerMessage {
 }
 
 impl BatteryData {
-    pub fn indicator<Message: 'static>(&self) -> Element<'static, Message> {
+    pub fn indicator<Message: 'static>(
+        &self,
+        position: IconPosition,
+        config: &BatteryModuleConfig
+    ) -> Element<'static, Message> {
         let icon_type = self.get_icon();
-        let state = self.get_indicator_state();
+        let state = self.get_indicator_state(&config.levels);
 
         container(
-            row!(icon(icon_type), text(format!("{}%", self.capacity)))
-                .spacing(4)
-                .align_y(Alignment::Center)
+            icon_with_label(
+                icon(icon_type),
+                Some(text(config.percent.format(self.capacity))),
+                position
+            )
+            .spacing(4)
+            .align_y(Alignment::Center)
         )
         .style(move |theme: &Theme| container::Style {
-            text_color: Some(match state {
-                IndicatorState::Success => theme.palette().success,
-                IndicatorState::Danger => theme.palette().danger,
-                _ => theme.palette().text
-            }),
+            text_color: Some(state_color(state, theme)),
             ..Default::default()
         })
         .into()
     }
 
-    pub fn settings_indicator<'a, Message: 'static>(&self) -> Container<'a, Message> {
-        let state = self.get_indicator_state();
+    pub fn settings_indicator<'a, Message: 'static>(
+        &self,
+        config: &BatteryModuleConfig
+    ) -> Container<'a, Message> {
+        let state = self.get_indicator_state(&config.levels);
 
         container({
             let battery_info = container(
-                row!(icon(self.get_icon()), text(format!("{}%", self.capacity))).spacing(4)
+                row!(
+                    icon(self.get_icon()),
+                    text(config.percent.format(self.capacity))
+                )
+                .spacing(4)
             )
             .style(move |theme: &Theme| container::Style {
-                text_color: Some(match state {
-                    IndicatorState::Success => theme.palette().success,
-                    IndicatorState::Danger => theme.palette().danger,
-                    _ => theme.palette().text
-                }),
+                text_color: Some(state_color(state, theme)),
                 ..Default::default()
             });
 
             match self.status {
-                BatteryStatus::Charging(remaining) if self.capacity < 95 => row!(
-                    battery_info,
-                    text(format!("Full in {}", format_duration(&remaining)))
-                )
-                .spacing(16),
-                BatteryStatus::Discharging(remaining) if self.capacity < 95 => row!(
-                    battery_info,
-                    text(format!("Empty in {}", format_duration(&remaining)))
-                )
-                .spacing(16),
+                // UPower reports zero while the estimate is still unknown.
+                BatteryStatus::Charging(remaining)
+                    if self.capacity < 95 && !remaining.is_zero() =>
+                {
+                    row!(
+                        battery_info,
+                        text(format!("Full in {}", format_duration(&remaining)))
+                    )
+                    .spacing(16)
+                }
+                BatteryStatus::Discharging(remaining)
+                    if self.capacity < 95 && !remaining.is_zero() =>
+                {
+                    row!(
+                        battery_info,
+                        text(format!("{} remaining", format_duration(&remaining)))
+                    )
+                    .spacing(16)
+                }
                 _ => row!(battery_info)
             }
         })
@@ -74,6 +91,16 @@ impl BatteryData {
     }
 }
 
+/// Text color of a battery readout in `state`.
+fn state_color(state: IndicatorState, theme: &Theme) -> Color {
+    match state {
+        IndicatorState::Success => theme.palette().success,
+        IndicatorState::Warning => theme.extended_palette().danger.weak.color,
+        IndicatorState::Danger => theme.palette().danger,
+        IndicatorState::Normal => theme.palette().text
+    }
+}
+
 impl PowerProfile {
     pub fn indicator<Message: 'static>(&self) -> Option<Element<'static, Message>> {
         match self {
@@ -6,21 +6,42 @@ use iced::{
 };
 
 use super::{
+    network::top_talkers_section,
     power::power_menu,
-    state::{Message, Settings, SubMenu}
+    state::{Message, ServiceStatus, Settings, SettingsService, SettingsTab, SubMenu}
 };
 use crate::{
     components::icons::{Icons, icon},
-    config::{Position, SettingsModuleConfig},
+    config::{
+        BatteryModuleConfig, IconPosition, PercentLabel, Position, SettingsModuleConfig,
+        ThroughputFormat, VolumeIconsConfig
+    },
     menu::MenuType,
     modules::OnModulePress,
     password_dialog,
-    services::bluetooth::BluetoothState,
+    services::{bluetooth::BluetoothState, idle_inhibitor::IdleInhibit},
     style::{
         quick_settings_button_style, quick_settings_submenu_button_style, settings_button_style
     }
 };
 
+/// Options for rendering the bar indicators.
+#[derive(Debug, Clone, Copy)]
+pub struct IndicatorOptions<'a> {
+    /// Whether indicators of unavailable services are hidden.
+    pub hide_unavailable: bool,
+    pub volume_icons:     &'a VolumeIconsConfig,
+    /// Text around the sink volume; no readout when unset.
+    pub volume_label:     Option<&'a PercentLabel>,
+    /// Side of the text the volume and battery glyphs are drawn on.
+    pub icon_position:    IconPosition,
+    /// Battery module settings the battery readout follows, its label and
+    /// levels included.
+    pub battery:          &'a BatteryModuleConfig,
+    /// How the network throughput readout is formatted.
+    pub throughput:       &'a ThroughputFormat
+}
+
 pub trait SettingsViewExt {
     type ViewData<'a>;
 
@@ -35,17 +56,19 @@ pub trait SettingsViewExt {
         &self,
         id: Id,
         config: &SettingsModuleConfig,
+        battery: &BatteryModuleConfig,
+        hide_unavailable: bool,
         opacity: f32,
         position: Position
     ) -> Element<'_, Message>;
 }
 
 impl SettingsViewExt for Settings {
-    type ViewData<'a> = ();
+    type ViewData<'a> = IndicatorOptions<'a>;
 
     fn settings_view<M>(
         &self,
-        _: Self::ViewData<'_>
+        options: Self::ViewData<'_>
     ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)>
     where
         M: 'static + From<Message>
@@ -55,21 +78,33 @@ impl SettingsViewExt for Settings {
             .as_ref()
             .map(|i| i.is_inhibited())
             .unwrap_or(false);
-        let power_profile_indicator = self
+        let visible =
+            |status: &ServiceStatus| !(options.hide_unavailable && status.is_unavailable());
+        let upower = self
             .upower
             .as_ref()
-            .and_then(|p| p.power_profile.indicator());
-        let sink_indicator = self.audio.as_ref().and_then(|a| a.sink_indicator());
-        let connection_indicator = self
+            .filter(|_| visible(&self.status.upower));
+        let audio = self.audio.as_ref().filter(|_| visible(&self.status.audio));
+        let network = self
             .network
             .as_ref()
-            .and_then(|n| n.get_connection_indicator());
-        let vpn_indicator = self.network.as_ref().and_then(|n| n.get_vpn_indicator());
-        let battery_indicator = self
-            .upower
-            .as_ref()
+            .filter(|_| visible(&self.status.network));
+
+        let power_profile_indicator = upower.and_then(|p| p.power_profile.indicator());
+        let sink_indicator = audio.and_then(|a| {
+            a.sink_indicator(
+                options.volume_icons,
+                options.volume_label,
+                options.icon_position
+            )
+        });
+        let connection_indicator = network.and_then(|n| n.get_connection_indicator());
+        let vpn_indicator = network.and_then(|n| n.get_vpn_indicator());
+        let throughput_indicator =
+            network.and_then(|n| n.get_throughput_indicator(options.throughput));
+        let battery_indicator = upower
             .and_then(|upower| upower.battery)
-            .map(|battery| battery.indicator());
+            .map(|battery| battery.indicator(options.icon_position, options.battery));
 
         Some((
             Row::new()
@@ -89,6 +124,8 @@ impl SettingsViewExt for Settings {
                     Row::new()
                         .push_maybe(connection_indicator)
                         .push_maybe(vpn_indicator)
+                        .push_maybe(throughput_indicator)
+                        .align_y(Alignment::Center)
                         .spacing(4)
                 )
                 .push_maybe(battery_indicator)
@@ -102,17 +139,21 @@ impl SettingsViewExt for Settings {
         &self,
         id: Id,
         config: &SettingsModuleConfig,
+        battery: &BatteryModuleConfig,
+        hide_unavailable: bool,
         opacity: f32,
         position: Position
     ) -> Element<'_, Message> {
         if let Some((ssid, current_password)) = &self.password_dialog {
             password_dialog::view(id, ssid, current_password, opacity).map(Message::PasswordDialog)
+        } else if let Some(dialog) = &self.confirm_dialog {
+            dialog.view(opacity).map(Message::ConfirmDialog)
         } else {
             let battery_data = self
                 .upower
                 .as_ref()
                 .and_then(|upower| upower.battery)
-                .map(|battery| battery.settings_indicator());
+                .map(|data| data.settings_indicator(battery));
             let right_buttons = Row::new()
                 .push_maybe(config.lock_cmd.as_ref().map(|_| {
                     button(icon(Icons::Lock))
@@ -139,17 +180,30 @@ impl SettingsViewExt for Settings {
                 .spacing(8)
                 .width(Length::Fill);
 
-            let (sink_slider, source_slider) = self
-                .audio
-                .as_ref()
-                .map(|a| a.audio_sliders(self.sub_menu, opacity))
-                .unwrap_or((None, None));
+            let network_tab = self.tab.shows(SettingsTab::Network);
+            let audio_tab = self.tab.shows(SettingsTab::Audio);
+            let display_tab = self.tab.shows(SettingsTab::Display);
+            let power_tab = self.tab.shows(SettingsTab::Power);
+            let network = self.network.as_ref().filter(|_| network_tab);
+            let audio = self.audio.as_ref().filter(|_| audio_tab);
 
-            let wifi_setting_button = self.network.as_ref().and_then(|n| {
+            let auto_expand = config.audio.auto_expand_devices;
+            let (sink_slider, source_slider) = audio
+                .map(|a| a.audio_sliders(self.sub_menu, auto_expand, config.slider_step, opacity))
+                .unwrap_or((None, None));
+            let show_sinks = self.sub_menu == Some(SubMenu::Sinks)
+                || (auto_expand && audio.is_some_and(|a| a.sinks_expandable()));
+            let show_sources = self.sub_menu == Some(SubMenu::Sources)
+                || (auto_expand
+                    && source_slider.is_some()
+                    && audio.is_some_and(|a| a.sources_expandable()));
+
+            let wifi_setting_button = network.and_then(|n| {
                 n.get_wifi_quick_setting_button(
                     id,
                     self.sub_menu,
                     config.wifi_more_cmd.is_some(),
+                    top_talkers_section(&self.talkers, &config.network),
                     opacity
                 )
             });
@@ -158,7 +212,14 @@ impl SettingsViewExt for Settings {
                     wifi_setting_button,
                     self.bluetooth
                         .as_ref()
-                        .filter(|b| b.state != BluetoothState::Unavailable)
+                        .filter(|b| {
+                            network_tab
+                                && bluetooth_toggle_shown(
+                                    &b.state,
+                                    &self.status.bluetooth,
+                                    hide_unavailable
+                                )
+                        })
                         .and_then(|b| {
                             b.get_quick_setting_button(
                                 id,
@@ -167,7 +228,7 @@ impl SettingsViewExt for Settings {
                                 opacity
                             )
                         }),
-                    self.network.as_ref().and_then(|n| {
+                    network.and_then(|n| {
                         n.get_vpn_quick_setting_button(
                             id,
                             self.sub_menu,
@@ -175,7 +236,7 @@ impl SettingsViewExt for Settings {
                             opacity
                         )
                     }),
-                    self.network.as_ref().and_then(|n| {
+                    network.and_then(|n| {
                         if config.remove_airplane_btn {
                             None
                         } else {
@@ -183,7 +244,7 @@ impl SettingsViewExt for Settings {
                         }
                     }),
                     self.idle_inhibitor.as_ref().and_then(|i| {
-                        if config.remove_idle_btn {
+                        if config.remove_idle_btn || !display_tab {
                             None
                         } else {
                             Some((
@@ -206,6 +267,7 @@ impl SettingsViewExt for Settings {
                     }),
                     self.upower
                         .as_ref()
+                        .filter(|_| power_tab)
                         .and_then(|u| u.power_profile.get_quick_setting_button(opacity)),
                 ]
                 .into_iter()
@@ -215,63 +277,90 @@ impl SettingsViewExt for Settings {
             );
 
             let (top_sink_slider, bottom_sink_slider) = match position {
-                Position::Top => (sink_slider, None),
-                Position::Bottom => (None, sink_slider)
+                Position::Bottom => (None, sink_slider),
+                Position::Top | Position::Left | Position::Right => (sink_slider, None)
             };
             let (top_source_slider, bottom_source_slider) = match position {
-                Position::Top => (source_slider, None),
-                Position::Bottom => (None, source_slider)
+                Position::Bottom => (None, source_slider),
+                Position::Top | Position::Left | Position::Right => (source_slider, None)
             };
 
+            let notices: Vec<Element<'_, Message>> = SettingsService::ALL
+                .into_iter()
+                .filter(|service| {
+                    self.tab.shows(match service {
+                        SettingsService::Network | SettingsService::Bluetooth => {
+                            SettingsTab::Network
+                        }
+                        SettingsService::Audio => SettingsTab::Audio,
+                        SettingsService::Brightness => SettingsTab::Display,
+                        SettingsService::UPower => SettingsTab::Power
+                    })
+                })
+                .filter_map(|service| {
+                    status_notice(
+                        service,
+                        self.status.get(service),
+                        !config.remove_retry_btn,
+                        opacity
+                    )
+                })
+                .collect();
+
+            let show_power_menu =
+                self.tab == SettingsTab::Power || self.sub_menu == Some(SubMenu::Power);
+
             Column::new()
                 .push(header)
+                .push(tab_bar(self.tab, opacity))
+                .push_maybe(
+                    (!notices.is_empty()).then(|| Column::with_children(notices).spacing(4))
+                )
+                .push_maybe(network.and_then(|n| {
+                    n.portal_notice(id, config.network.portal_cmd.is_some(), opacity)
+                        .map(|notice| notice.map(Message::Network))
+                }))
+                .push_maybe(show_power_menu.then(|| {
+                    sub_menu_wrapper(power_menu(opacity, config).map(Message::Power), opacity)
+                }))
+                .push_maybe(top_sink_slider)
+                .push_maybe(audio.filter(|_| show_sinks).map(|a| {
+                    sub_menu_wrapper(
+                        a.sinks_submenu(id, config.audio_sinks_more_cmd.is_some(), opacity),
+                        opacity
+                    )
+                }))
+                .push_maybe(bottom_sink_slider)
+                .push_maybe(audio.and_then(|a| a.sink_inputs_button(self.sub_menu, opacity)))
                 .push_maybe(
-                    self.sub_menu
-                        .filter(|menu_type| *menu_type == SubMenu::Power)
-                        .map(|_| {
+                    audio
+                        .filter(|_| self.sub_menu == Some(SubMenu::SinkInputs))
+                        .map(|a| {
                             sub_menu_wrapper(
-                                power_menu(opacity, config).map(Message::Power),
+                                a.sink_inputs_submenu(config.slider_step, opacity),
                                 opacity
                             )
                         })
                 )
-                .push_maybe(top_sink_slider)
+                .push_maybe(top_source_slider)
+                .push_maybe(audio.filter(|_| show_sources).map(|a| {
+                    sub_menu_wrapper(
+                        a.sources_submenu(id, config.audio_sources_more_cmd.is_some(), opacity),
+                        opacity
+                    )
+                }))
+                .push_maybe(bottom_source_slider)
                 .push_maybe(
-                    self.sub_menu
-                        .filter(|menu_type| *menu_type == SubMenu::Sinks)
-                        .and_then(|_| {
-                            self.audio.as_ref().map(|a| {
-                                sub_menu_wrapper(
-                                    a.sinks_submenu(
-                                        id,
-                                        config.audio_sinks_more_cmd.is_some(),
-                                        opacity
-                                    ),
-                                    opacity
-                                )
-                            })
-                        })
+                    self.brightness.as_ref().filter(|_| display_tab).map(|b| {
+                        b.brightness_slider(config.brightness.as_ref(), config.slider_step)
+                    })
                 )
-                .push_maybe(bottom_sink_slider)
-                .push_maybe(top_source_slider)
                 .push_maybe(
-                    self.sub_menu
-                        .filter(|menu_type| *menu_type == SubMenu::Sources)
-                        .and_then(|_| {
-                            self.audio.as_ref().map(|a| {
-                                sub_menu_wrapper(
-                                    a.sources_submenu(
-                                        id,
-                                        config.audio_sources_more_cmd.is_some(),
-                                        opacity
-                                    ),
-                                    opacity
-                                )
-                            })
-                        })
+                    self.brightness
+                        .as_ref()
+                        .filter(|_| display_tab)
+                        .and_then(|b| b.keyboard_brightness_slider())
                 )
-                .push_maybe(bottom_source_slider)
-                .push_maybe(self.brightness.as_ref().map(|b| b.brightness_slider()))
                 .push(quick_settings)
                 .spacing(16)
                 .into()
@@ -279,6 +368,80 @@ impl SettingsViewExt for Settings {
     }
 }
 
+/// Row of category tabs; the selected one is highlighted.
+fn tab_bar<'a>(selected: SettingsTab, opacity: f32) -> Element<'a, Message> {
+    Row::with_children(SettingsTab::ALL.into_iter().map(|tab| {
+        button(
+            text(tab.label())
+                .size(12)
+                .align_x(Horizontal::Center)
+                .width(Length::Fill)
+        )
+        .padding([4, 8])
+        .width(Length::Fill)
+        .style(quick_settings_button_style(tab == selected, opacity))
+        .on_press(Message::SelectTab(tab))
+        .into()
+    }))
+    .spacing(4)
+    .width(Length::Fill)
+    .into()
+}
+
+/// Whether the Bluetooth quick setting is offered: never without an adapter,
+/// and not while the service is unavailable when `hide_unavailable` is set.
+fn bluetooth_toggle_shown(
+    state: &BluetoothState,
+    status: &ServiceStatus,
+    hide_unavailable: bool
+) -> bool {
+    *state != BluetoothState::Unavailable && !(hide_unavailable && status.is_unavailable())
+}
+
+/// One-line notice for a section whose service is not ready yet, or `None`
+/// once it is. Unavailable services get a retry button when `retry` is set.
+fn status_notice<'a>(
+    service: SettingsService,
+    status: &ServiceStatus,
+    retry: bool,
+    opacity: f32
+) -> Option<Element<'a, Message>> {
+    let name = service.label();
+
+    match status {
+        ServiceStatus::Ready => None,
+        ServiceStatus::Initializing => Some(
+            row![
+                icon(Icons::Refresh).size(12),
+                text(format!("Connecting to {name}…")).size(12)
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center)
+            .into()
+        ),
+        ServiceStatus::Unavailable(reason) => Some(
+            Row::new()
+                .push(
+                    text(format!("{name} unavailable: {reason}"))
+                        .size(12)
+                        .width(Length::Fill)
+                        .style(|theme: &Theme| text::Style {
+                            color: Some(theme.palette().danger)
+                        })
+                )
+                .push_maybe(retry.then(|| {
+                    button(text("Retry").size(12))
+                        .padding([2, 8])
+                        .on_press(Message::Retry(service))
+                        .style(settings_button_style(opacity))
+                }))
+                .spacing(8)
+                .align_y(Alignment::Center)
+                .into()
+        )
+    }
+}
+
 pub(crate) fn quick_settings_section<'a>(
     buttons: Vec<(Element<'a, Message>, Option<Element<'a, Message>>)>,
     opacity: f32
@@ -427,6 +590,53 @@ mod tests {
         assert_eq!(children.len(), 2);
     }
 
+    #[test]
+    fn status_notice_is_hidden_once_ready() {
+        let audio = SettingsService::Audio;
+
+        assert!(status_notice(audio, &ServiceStatus::Ready, true, 1.0).is_none());
+        assert!(status_notice(audio, &ServiceStatus::Initializing, true, 1.0).is_some());
+    }
+
+    #[test]
+    fn unavailable_notice_offers_retry_when_enabled() {
+        let status = ServiceStatus::Unavailable("audio backend is not reachable".to_owned());
+
+        let with_retry = status_notice(SettingsService::Audio, &status, true, 1.0)
+            .expect("unavailable services get a notice");
+        assert_eq!(with_retry.as_widget().children().len(), 2);
+
+        let without_retry = status_notice(SettingsService::Audio, &status, false, 1.0)
+            .expect("unavailable services get a notice");
+        assert_eq!(without_retry.as_widget().children().len(), 1);
+    }
+
+    #[test]
+    fn bluetooth_toggle_follows_hide_when_unavailable() {
+        let unavailable = ServiceStatus::Unavailable("org.bluez is not running".to_owned());
+
+        assert!(bluetooth_toggle_shown(
+            &BluetoothState::Active,
+            &unavailable,
+            false
+        ));
+        assert!(!bluetooth_toggle_shown(
+            &BluetoothState::Active,
+            &unavailable,
+            true
+        ));
+        assert!(bluetooth_toggle_shown(
+            &BluetoothState::Active,
+            &ServiceStatus::Ready,
+            true
+        ));
+        assert!(!bluetooth_toggle_shown(
+            &BluetoothState::Unavailable,
+            &ServiceStatus::Ready,
+            false
+        ));
+    }
+
     #[test]
     fn quick_setting_button_can_render_submenu_toggle() {
         let element: Element<'_, Message> = quick_setting_button(
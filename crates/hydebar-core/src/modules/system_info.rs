@@ -1,11 +1,17 @@
+mod cores;
 mod data;
+mod disks;
 mod runtime;
+mod sensors;
 mod view;
 
-pub use data::{NetworkData, SystemInfoData, SystemInfoSampler};
+use std::sync::{Arc, Mutex};
+
+pub use data::{Metrics, NetworkData, Smoothing, SystemInfoData, SystemInfoSampler};
 use hydebar_proto::config::SystemModuleConfig;
 use iced::Element;
-pub use runtime::REFRESH_INTERVAL;
+pub use runtime::{MENU_REFRESH_INTERVAL, PollingIntervals, REFRESH_INTERVAL};
+pub use sensors::LoadAverage;
 pub use view::{build_indicator_view, build_menu_view, indicator_elements};
 
 use super::{Module, ModuleError, OnModulePress};
@@ -14,14 +20,16 @@ use crate::{ModuleContext, event_bus::ModuleEvent};
 /// Messages published by the system information module.
 #[derive(Debug, Clone)]
 pub enum Message {
-    Update
+    /// Fresh values of the listed metric groups, sampled by the polling task.
+    Update(Metrics, Box<SystemInfoData>)
 }
 
 /// Module responsible for sampling and presenting local system metrics.
 pub struct SystemInfo {
-    sampler: SystemInfoSampler,
-    data:    SystemInfoData,
-    polling: runtime::PollingTask
+    sampler:   Arc<Mutex<SystemInfoSampler>>,
+    data:      SystemInfoData,
+    smoothing: Option<Smoothing>,
+    polling:   runtime::PollingTask
 }
 
 impl Default for SystemInfo {
@@ -30,8 +38,9 @@ impl Default for SystemInfo {
         let data = sampler.sample_with_extras();
 
         Self {
-            sampler,
+            sampler: Arc::new(Mutex::new(sampler)),
             data,
+            smoothing: None,
             polling: runtime::PollingTask::new()
         }
     }
@@ -41,15 +50,27 @@ impl SystemInfo {
     /// React to module messages by updating cached metrics when necessary.
     pub fn update(&mut self, message: Message) {
         match message {
-            Message::Update => {
-                self.data = self.sampler.sample_with_extras();
+            Message::Update(metrics, sample) => {
+                let mut next = self.data.clone();
+                next.merge(*sample, metrics);
+                self.data = match self.smoothing {
+                    Some(smoothing) if metrics.usage => next.smoothed(&self.data, smoothing),
+                    _ => next
+                };
             }
         }
     }
 
+    /// Poll every metric at [`MENU_REFRESH_INTERVAL`] while the menu is open;
+    /// otherwise only the metrics shown on the bar are polled, at their
+    /// configured intervals.
+    pub fn set_menu_open(&mut self, open: bool) {
+        self.polling.set_menu_open(open);
+    }
+
     /// Render the menu entry exposing detailed system information.
-    pub fn menu_view(&self) -> Element<'_, Message> {
-        view::build_menu_view(&self.data)
+    pub fn menu_view(&self, config: &SystemModuleConfig) -> Element<'_, Message> {
+        view::build_menu_view(&self.data, config)
     }
 }
 
@@ -58,15 +79,31 @@ where
     M: 'static + Clone + From<Message>
 {
     type ViewData<'a> = &'a SystemModuleConfig;
-    type RegistrationData<'a> = ();
+    type RegistrationData<'a> = &'a SystemModuleConfig;
 
     fn register(
         &mut self,
         ctx: &ModuleContext,
-        _: Self::RegistrationData<'_>
+        config: Self::RegistrationData<'_>
     ) -> Result<(), ModuleError> {
+        self.smoothing = config.smoothing.and_then(Smoothing::over);
+        {
+            let mut sampler = self
+                .sampler
+                .lock()
+                .expect("system info sampler lock poisoned");
+            sampler.set_temperature_sensor(&config.temperature.sensor);
+            sampler.set_disk_mounts(&config.disk.mounts);
+        }
+
         let sender = ctx.module_sender(ModuleEvent::SystemInfo);
-        self.polling.spawn(ctx, sender);
+        self.polling.spawn(
+            ctx,
+            sender,
+            Arc::clone(&self.sampler),
+            PollingIntervals::from_config(config),
+            Metrics::shown_by(&config.indicators)
+        );
 
         Ok(())
     }
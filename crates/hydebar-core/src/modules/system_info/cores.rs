@@ -0,0 +1,126 @@
+use std::fs;
+
+/// Where the kernel publishes cumulative CPU time counters.
+const PROC_STAT_PATH: &str = "/proc/stat";
+
+/// Cumulative jiffies one core spent busy and in total.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CpuTimes {
+    busy:  u64,
+    total: u64
+}
+
+/// Per-core counters of `/proc/stat` keyed by CPU number. Offline cores are
+/// missing from the file, so the numbers need not be contiguous.
+fn parse_proc_stat(contents: &str) -> Vec<(usize, CpuTimes)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let cpu = fields.next()?.strip_prefix("cpu")?.parse().ok()?;
+            // Guest time is already part of user and nice time.
+            let times: Vec<u64> = fields
+                .take(8)
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .ok()?;
+            let total: u64 = times.iter().sum();
+            let idle = times.get(3).copied().unwrap_or_default()
+                + times.get(4).copied().unwrap_or_default();
+
+            Some((
+                cpu,
+                CpuTimes {
+                    busy: total.saturating_sub(idle),
+                    total
+                }
+            ))
+        })
+        .collect()
+}
+
+/// Turns successive `/proc/stat` snapshots into per-core usage.
+#[derive(Debug, Clone, Default)]
+pub struct CoreUsage {
+    previous: Vec<(usize, CpuTimes)>
+}
+
+impl CoreUsage {
+    /// Usage in percent of every online core since the previous reading, in
+    /// CPU order; empty when `/proc/stat` cannot be read.
+    pub fn sample(&mut self) -> Vec<f32> {
+        self.record(&fs::read_to_string(PROC_STAT_PATH).unwrap_or_default())
+    }
+
+    fn record(&mut self, contents: &str) -> Vec<f32> {
+        let current = parse_proc_stat(contents);
+
+        // Cores that just came online have no previous reading and start
+        // at zero.
+        let usage = current
+            .iter()
+            .map(|(cpu, times)| {
+                self.previous
+                    .iter()
+                    .find(|(previous_cpu, _)| previous_cpu == cpu)
+                    .map_or(0., |(_, previous)| usage_between(previous, times))
+            })
+            .collect();
+
+        self.previous = current;
+        usage
+    }
+}
+
+fn usage_between(previous: &CpuTimes, current: &CpuTimes) -> f32 {
+    let total = current.total.saturating_sub(previous.total);
+    if total == 0 {
+        return 0.;
+    }
+
+    current.busy.saturating_sub(previous.busy) as f32 * 100. / total as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST: &str = "\
+cpu  300 0 100 1600 0 0 0 0 0 0
+cpu0 100 0 50 800 0 0 0 0 0 0
+cpu1 200 0 50 800 0 0 0 0 0 0
+intr 12345
+";
+
+    const SECOND: &str = "\
+cpu  500 0 150 1900 0 0 0 0 0 0
+cpu0 175 0 75 900 0 0 0 0 0 0
+cpu1 210 0 55 835 0 0 0 0 0 0
+";
+
+    #[test]
+    fn usage_comes_from_the_delta_between_samples() {
+        let mut cores = CoreUsage::default();
+
+        assert_eq!(cores.record(FIRST), [0., 0.]);
+        assert_eq!(cores.record(SECOND), [50., 30.]);
+    }
+
+    #[test]
+    fn cores_going_offline_and_online_resize_the_readings() {
+        let mut cores = CoreUsage::default();
+        cores.record(FIRST);
+
+        let offline = "cpu0 175 0 75 900 0 0 0 0 0 0\n";
+        assert_eq!(cores.record(offline), [50.]);
+
+        assert_eq!(cores.record(SECOND), [0., 0.]);
+    }
+
+    #[test]
+    fn aggregate_and_other_lines_are_skipped() {
+        let cpus: Vec<usize> = parse_proc_stat(FIRST).iter().map(|(cpu, _)| *cpu).collect();
+
+        assert_eq!(cpus, [0, 1]);
+    }
+}
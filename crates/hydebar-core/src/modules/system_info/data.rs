@@ -1,20 +1,30 @@
-use std::time::Instant;
+use std::{path::Path, time::Instant};
 
+use hydebar_proto::config::SystemIndicator;
 use itertools::Itertools;
-use sysinfo::{Components, Disks, Networks, System};
+use log::warn;
+use sysinfo::{Disks, Networks, System};
+
+use super::{
+    cores::CoreUsage,
+    disks,
+    sensors::{self, LoadAverage}
+};
 
 /// Snapshot of network utilisation metrics captured during sampling.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NetworkData {
     pub ip:             String,
-    pub download_speed: u32,
-    pub upload_speed:   u32,
+    /// Download throughput in bytes per second.
+    pub download_speed: u64,
+    /// Upload throughput in bytes per second.
+    pub upload_speed:   u64,
     last_check:         Instant
 }
 
 impl NetworkData {
     /// Create a new network metric snapshot with the provided parameters.
-    pub fn new(ip: String, download_speed: u32, upload_speed: u32, last_check: Instant) -> Self {
+    pub fn new(ip: String, download_speed: u64, upload_speed: u64, last_check: Instant) -> Self {
         Self {
             ip,
             download_speed,
@@ -30,16 +40,154 @@ impl NetworkData {
 }
 
 /// Aggregated system information consumed by the UI layer.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct SystemInfoData {
-    pub cpu_usage:         u32,
-    pub memory_usage:      u32,
-    pub memory_swap_usage: u32,
+    pub cpu_usage:         f32,
+    /// Usage of each online core in percent, in CPU order.
+    pub core_usage:        Vec<f32>,
+    pub memory_usage:      f32,
+    pub memory_swap_usage: f32,
     pub temperature:       Option<i32>,
+    /// Load averages, refreshed along with CPU and memory usage.
+    pub load_average:      Option<LoadAverage>,
     pub disks:             Vec<(String, u32)>,
     pub network:           Option<NetworkData>
 }
 
+impl SystemInfoData {
+    /// Take the `metrics` groups from a fresh `sample`, keeping the previous
+    /// values of everything else.
+    pub fn merge(&mut self, sample: SystemInfoData, metrics: Metrics) {
+        if metrics.usage {
+            self.cpu_usage = sample.cpu_usage;
+            self.core_usage = sample.core_usage;
+            self.memory_usage = sample.memory_usage;
+            self.memory_swap_usage = sample.memory_swap_usage;
+            self.load_average = sample.load_average;
+        }
+        if metrics.temperature {
+            self.temperature = sample.temperature;
+        }
+        if metrics.disks {
+            self.disks = sample.disks;
+        }
+        if metrics.network {
+            self.network = sample.network;
+        }
+    }
+
+    /// Blend the percentage readings with the previous snapshot so short
+    /// spikes do not make the indicator jitter.
+    pub fn smoothed(mut self, previous: &SystemInfoData, smoothing: Smoothing) -> Self {
+        self.cpu_usage = smoothing.apply(previous.cpu_usage, self.cpu_usage);
+        self.memory_usage = smoothing.apply(previous.memory_usage, self.memory_usage);
+        self.memory_swap_usage =
+            smoothing.apply(previous.memory_swap_usage, self.memory_swap_usage);
+        self
+    }
+}
+
+/// Groups of metrics refreshed together by a single polling tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    pub usage:       bool,
+    pub disks:       bool,
+    pub temperature: bool,
+    pub network:     bool
+}
+
+impl Metrics {
+    /// CPU, memory and swap usage.
+    pub const USAGE: Self = Self {
+        usage:       true,
+        disks:       false,
+        temperature: false,
+        network:     false
+    };
+
+    /// Every metric the sampler knows about.
+    pub const ALL: Self = Self {
+        usage:       true,
+        disks:       true,
+        temperature: true,
+        network:     true
+    };
+
+    /// Metric groups read by the given bar indicators.
+    pub fn shown_by(indicators: &[SystemIndicator]) -> Self {
+        indicators
+            .iter()
+            .map(|indicator| match indicator {
+                SystemIndicator::Cpu | SystemIndicator::Memory | SystemIndicator::MemorySwap => {
+                    Self::USAGE
+                }
+                SystemIndicator::Temperature => Self {
+                    temperature: true,
+                    ..Self::default()
+                },
+                SystemIndicator::Disk(_) => Self {
+                    disks: true,
+                    ..Self::default()
+                },
+                SystemIndicator::IpAddress
+                | SystemIndicator::DownloadSpeed
+                | SystemIndicator::UploadSpeed => Self {
+                    network: true,
+                    ..Self::default()
+                }
+            })
+            .fold(Self::default(), Self::union)
+    }
+
+    /// Combine two metric groups into one.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            usage:       self.usage || other.usage,
+            disks:       self.disks || other.disks,
+            temperature: self.temperature || other.temperature,
+            network:     self.network || other.network
+        }
+    }
+
+    /// Keep only the groups present in both.
+    pub fn intersection(self, other: Self) -> Self {
+        Self {
+            usage:       self.usage && other.usage,
+            disks:       self.disks && other.disks,
+            temperature: self.temperature && other.temperature,
+            network:     self.network && other.network
+        }
+    }
+
+    /// Whether no group is selected.
+    pub fn is_empty(self) -> bool {
+        self == Self::default()
+    }
+}
+
+/// Exponential moving average applied to successive percentage readings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Smoothing {
+    alpha: f32
+}
+
+impl Smoothing {
+    /// Build a filter spanning roughly `samples` readings, or `None` when the
+    /// window is too short to have any effect.
+    pub fn over(samples: u32) -> Option<Self> {
+        (samples > 1).then(|| Self {
+            alpha: 2. / (samples as f32 + 1.)
+        })
+    }
+
+    fn apply(self, previous: f32, current: f32) -> f32 {
+        previous + self.alpha * (current - previous)
+    }
+}
+
+/// Sensor read until the configuration picks another one.
+const DEFAULT_TEMPERATURE_SENSOR: &str = "acpitz temp1";
+
 #[derive(Debug, Clone)]
 struct NetworkSnapshot {
     ip:                Option<String>,
@@ -83,15 +231,15 @@ impl NetworkSnapshot {
         let elapsed = previous
             .map(|snapshot| self.timestamp.saturating_duration_since(snapshot.timestamp))
             .unwrap_or_default();
-        let seconds = elapsed.as_secs();
+        let seconds = elapsed.as_secs_f64();
 
-        let compute_speed = |current: u64, previous_total: u64| -> u32 {
-            if seconds == 0 {
+        let compute_speed = |current: u64, previous_total: u64| -> u64 {
+            if seconds <= 0. {
                 return 0;
             }
 
             let delta = current.saturating_sub(previous_total);
-            ((delta / 1000) as u32) / (seconds as u32)
+            (delta as f64 / seconds) as u64
         };
 
         NetworkData {
@@ -109,14 +257,21 @@ impl NetworkSnapshot {
     }
 }
 
-/// Samples system metrics using the [`sysinfo`] crate.
+/// Samples system metrics using the [`sysinfo`] crate, with temperature and
+/// load averages read from sysfs and procfs.
 #[derive(Debug)]
 pub struct SystemInfoSampler {
-    system:       System,
-    components:   Option<Components>,
-    disks:        Option<Disks>,
-    networks:     Option<Networks>,
-    last_network: Option<NetworkSnapshot>
+    system:             System,
+    /// Label of the hwmon sensor reported as the temperature.
+    temperature_sensor: String,
+    cores:              CoreUsage,
+    /// Mount points reported as disks; every local disk when empty.
+    disk_mounts:        Vec<String>,
+    /// Configured mount points that could not be read, logged once each.
+    missing_mounts:     Vec<String>,
+    disks:              Option<Disks>,
+    networks:           Option<Networks>,
+    last_network:       Option<NetworkSnapshot>
 }
 
 impl Default for SystemInfoSampler {
@@ -129,22 +284,31 @@ impl SystemInfoSampler {
     /// Instantiate a sampler with refreshed sysinfo collections.
     pub fn new() -> Self {
         Self {
-            system:       System::new_with_specifics(
+            system:             System::new_with_specifics(
                 sysinfo::RefreshKind::nothing()
                     .with_cpu(sysinfo::CpuRefreshKind::nothing().with_cpu_usage())
                     .with_memory(sysinfo::MemoryRefreshKind::nothing().with_ram())
             ),
-            components:   None,
-            disks:        None,
-            networks:     None,
-            last_network: None
+            temperature_sensor: DEFAULT_TEMPERATURE_SENSOR.to_owned(),
+            cores:              CoreUsage::default(),
+            disk_mounts:        Vec::new(),
+            missing_mounts:     Vec::new(),
+            disks:              None,
+            networks:           None,
+            last_network:       None
         }
     }
 
-    fn ensure_components(&mut self) {
-        if self.components.is_none() {
-            self.components = Some(Components::new_with_refreshed_list());
-        }
+    /// Report only the disks mounted at `mounts`, or every local disk when
+    /// empty.
+    pub fn set_disk_mounts(&mut self, mounts: &[String]) {
+        mounts.clone_into(&mut self.disk_mounts);
+        self.missing_mounts.clear();
+    }
+
+    /// Report the hwmon sensor labelled `label` as the temperature.
+    pub fn set_temperature_sensor(&mut self, label: &str) {
+        label.clone_into(&mut self.temperature_sensor);
     }
 
     fn ensure_disks(&mut self) {
@@ -159,94 +323,77 @@ impl SystemInfoSampler {
         }
     }
 
-    /// Capture the latest system metrics, updating internal state for
-    /// subsequent samples.
+    /// Capture the latest CPU and memory usage, leaving the slower metrics
+    /// empty.
     pub fn sample(&mut self) -> SystemInfoData {
+        let mut data = SystemInfoData::default();
+        self.refresh(&mut data, Metrics::USAGE);
+        data
+    }
+
+    /// Capture every metric, updating internal state for subsequent samples.
+    pub fn sample_with_extras(&mut self) -> SystemInfoData {
+        let mut data = SystemInfoData::default();
+        self.refresh(&mut data, Metrics::ALL);
+        data
+    }
+
+    /// Refresh the selected metric groups in place, keeping the previous
+    /// values of everything else.
+    pub fn refresh(&mut self, data: &mut SystemInfoData, metrics: Metrics) {
+        if metrics.usage {
+            self.refresh_usage(data);
+        }
+        if metrics.temperature {
+            self.refresh_temperature(data);
+        }
+        if metrics.disks {
+            self.refresh_disks(data);
+        }
+        if metrics.network {
+            self.refresh_network(data);
+        }
+    }
+
+    fn refresh_usage(&mut self, data: &mut SystemInfoData) {
         self.system
             .refresh_cpu_specifics(sysinfo::CpuRefreshKind::nothing().with_cpu_usage());
         self.system.refresh_memory();
 
-        let cpu_usage = self.system.global_cpu_usage().floor() as u32;
-        let memory_usage = percentage(
+        data.cpu_usage = self.system.global_cpu_usage();
+        data.core_usage = self.cores.sample();
+        data.memory_usage = percentage(
             self.system
                 .total_memory()
                 .saturating_sub(self.system.available_memory()),
             self.system.total_memory()
         );
-        let memory_swap_usage = percentage(
+        data.memory_swap_usage = percentage(
             self.system
                 .total_swap()
                 .saturating_sub(self.system.free_swap()),
             self.system.total_swap()
         );
+        data.load_average = sensors::read_load_average();
+    }
 
-        let temperature = None;
-
-        let disks = Vec::new();
-
-        let network = None;
+    fn refresh_temperature(&mut self, data: &mut SystemInfoData) {
+        data.temperature = sensors::hwmon_temperature(&self.temperature_sensor);
+    }
 
-        SystemInfoData {
-            cpu_usage,
-            memory_usage,
-            memory_swap_usage,
-            temperature,
-            disks,
-            network
+    fn refresh_disks(&mut self, data: &mut SystemInfoData) {
+        if !self.disk_mounts.is_empty() {
+            self.refresh_mounts(data);
+            return;
         }
-    }
 
-    pub fn sample_with_extras(&mut self) -> SystemInfoData {
-        self.ensure_components();
         self.ensure_disks();
-        self.ensure_networks();
-
-        self.system
-            .refresh_cpu_specifics(sysinfo::CpuRefreshKind::nothing().with_cpu_usage());
-        self.system.refresh_memory();
 
-        if let Some(ref mut components) = self.components {
-            components.refresh(true);
-        }
         if let Some(ref mut disks) = self.disks {
             disks.refresh(true);
         }
-        if let Some(ref mut networks) = self.networks {
-            networks.refresh(true);
-        }
-
-        let now = Instant::now();
-        let observation = self
-            .networks
-            .as_ref()
-            .and_then(|networks| NetworkSnapshot::capture(networks, now));
-        let network = observation
-            .as_ref()
-            .map(|snapshot| snapshot.to_data(self.last_network.as_ref()));
-        self.last_network = observation;
-
-        let cpu_usage = self.system.global_cpu_usage().floor() as u32;
-        let memory_usage = percentage(
-            self.system
-                .total_memory()
-                .saturating_sub(self.system.available_memory()),
-            self.system.total_memory()
-        );
-        let memory_swap_usage = percentage(
-            self.system
-                .total_swap()
-                .saturating_sub(self.system.free_swap()),
-            self.system.total_swap()
-        );
-
-        let temperature = self.components.as_ref().and_then(|components| {
-            components
-                .iter()
-                .find(|component| component.label() == "acpitz temp1")
-                .and_then(|component| component.temperature().map(|value| value as i32))
-        });
 
-        let disks = self
+        data.disks = self
             .disks
             .as_ref()
             .map(|disks| {
@@ -258,7 +405,7 @@ impl SystemInfoSampler {
                         let usage = percentage(
                             disk.total_space().saturating_sub(disk.available_space()),
                             disk.total_space()
-                        );
+                        ) as u32;
 
                         (mount_point, usage)
                     })
@@ -266,24 +413,52 @@ impl SystemInfoSampler {
                     .collect()
             })
             .unwrap_or_default();
+    }
+
+    fn refresh_mounts(&mut self, data: &mut SystemInfoData) {
+        data.disks = self
+            .disk_mounts
+            .iter()
+            .filter_map(|mount| match disks::statvfs(Path::new(mount)) {
+                Ok(counts) => {
+                    self.missing_mounts.retain(|missing| missing != mount);
+                    Some((mount.clone(), counts.usage()))
+                }
+                Err(err) => {
+                    if !self.missing_mounts.contains(mount) {
+                        warn!("Skipping disk {mount}: {err}");
+                        self.missing_mounts.push(mount.clone());
+                    }
+                    None
+                }
+            })
+            .collect();
+    }
 
-        SystemInfoData {
-            cpu_usage,
-            memory_usage,
-            memory_swap_usage,
-            temperature,
-            disks,
-            network
+    fn refresh_network(&mut self, data: &mut SystemInfoData) {
+        self.ensure_networks();
+
+        if let Some(ref mut networks) = self.networks {
+            networks.refresh(true);
         }
+
+        let observation = self
+            .networks
+            .as_ref()
+            .and_then(|networks| NetworkSnapshot::capture(networks, Instant::now()));
+        data.network = observation
+            .as_ref()
+            .map(|snapshot| snapshot.to_data(self.last_network.as_ref()));
+        self.last_network = observation;
     }
 }
 
-fn percentage(used: u64, total: u64) -> u32 {
+fn percentage(used: u64, total: u64) -> f32 {
     if total == 0 {
-        return 0;
+        return 0.;
     }
 
-    ((used as f32 / total as f32) * 100.) as u32
+    (used as f32 / total as f32) * 100.
 }
 
 #[cfg(test)]
@@ -311,9 +486,80 @@ mod tests {
         assert_eq!(data.upload_speed, 0);
     }
 
+    #[test]
+    fn indicators_select_the_metrics_they_read() {
+        let metrics = Metrics::shown_by(&[
+            SystemIndicator::Memory,
+            SystemIndicator::Disk("/".to_owned())
+        ]);
+
+        assert_eq!(
+            metrics,
+            Metrics {
+                usage: true,
+                disks: true,
+                ..Metrics::default()
+            }
+        );
+        assert!(Metrics::shown_by(&[]).is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_groups_that_were_not_sampled() {
+        let mut data = SystemInfoData {
+            cpu_usage: 10.,
+            temperature: Some(40),
+            ..SystemInfoData::default()
+        };
+        let sample = SystemInfoData {
+            cpu_usage: 20.,
+            temperature: None,
+            ..SystemInfoData::default()
+        };
+
+        data.merge(sample, Metrics::USAGE);
+
+        assert_eq!(data.cpu_usage, 20.);
+        assert_eq!(data.temperature, Some(40));
+    }
+
     #[test]
     fn percentage_handles_zero_total() {
-        assert_eq!(percentage(5, 0), 0);
+        assert_eq!(percentage(5, 0), 0.);
+    }
+
+    #[test]
+    fn smoothing_requires_more_than_one_sample() {
+        assert_eq!(Smoothing::over(0), None);
+        assert_eq!(Smoothing::over(1), None);
+        assert!(Smoothing::over(3).is_some());
+    }
+
+    #[test]
+    fn smoothed_moves_towards_latest_sample() {
+        let smoothing = Smoothing::over(3).expect("smoothing window");
+        let previous = SystemInfoData {
+            cpu_usage:         10.,
+            core_usage:        Vec::new(),
+            memory_usage:      40.,
+            memory_swap_usage: 0.,
+            temperature:       None,
+            load_average:      None,
+            disks:             Vec::new(),
+            network:           None
+        };
+        let current = SystemInfoData {
+            cpu_usage: 30.,
+            memory_usage: 40.,
+            memory_swap_usage: 8.,
+            ..previous.clone()
+        };
+
+        let data = current.smoothed(&previous, smoothing);
+
+        assert_eq!(data.cpu_usage, 20.);
+        assert_eq!(data.memory_usage, 40.);
+        assert_eq!(data.memory_swap_usage, 4.);
     }
 
     #[test]
@@ -321,8 +567,8 @@ mod tests {
         let mut sampler = SystemInfoSampler::new();
         let data = sampler.sample();
 
-        assert!(data.cpu_usage <= 100);
-        assert!(data.memory_usage <= 100);
-        assert!(data.memory_swap_usage <= 100);
+        assert!(data.cpu_usage <= 100.);
+        assert!(data.memory_usage <= 100.);
+        assert!(data.memory_swap_usage <= 100.);
     }
 }
@@ -0,0 +1,82 @@
+use std::{ffi::CString, io, mem::MaybeUninit, os::unix::ffi::OsStrExt, path::Path};
+
+/// Block counts of a filesystem as reported by `statvfs`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockCounts {
+    pub blocks:    u64,
+    /// Free blocks, including those reserved for root.
+    pub free:      u64,
+    /// Free blocks available to unprivileged users.
+    pub available: u64
+}
+
+impl BlockCounts {
+    /// Used space in percent, rounded up and measured against the space
+    /// unprivileged users can fill, as `df` does.
+    pub fn usage(self) -> u32 {
+        let used = self.blocks.saturating_sub(self.free);
+        let usable = used + self.available;
+        if usable == 0 {
+            return 0;
+        }
+
+        (used * 100).div_ceil(usable) as u32
+    }
+}
+
+/// Block counts of the filesystem mounted at `path`.
+pub fn statvfs(path: &Path) -> io::Result<BlockCounts> {
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+
+    // SAFETY: `path` is NUL-terminated and `stat` is only read after the
+    // call reported success, which means it was filled in.
+    let stat = unsafe {
+        if libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        stat.assume_init()
+    };
+
+    Ok(BlockCounts {
+        blocks:    stat.f_blocks,
+        free:      stat.f_bfree,
+        available: stat.f_bavail
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usage_counts_reserved_blocks_as_unavailable() {
+        let counts = BlockCounts {
+            blocks:    1000,
+            free:      450,
+            available: 400
+        };
+
+        // 550 used out of 950 usable blocks.
+        assert_eq!(counts.usage(), 58);
+    }
+
+    #[test]
+    fn usage_of_full_and_empty_filesystems() {
+        let full = BlockCounts {
+            blocks:    100,
+            free:      0,
+            available: 0
+        };
+        let empty = BlockCounts {
+            blocks:    100,
+            free:      100,
+            available: 100
+        };
+
+        assert_eq!(full.usage(), 100);
+        assert_eq!(empty.usage(), 0);
+        assert_eq!(BlockCounts::default().usage(), 0);
+    }
+}
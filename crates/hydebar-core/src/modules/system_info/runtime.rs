@@ -1,58 +1,236 @@
-use std::time::Duration;
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration
+};
 
+use hydebar_proto::config::SystemModuleConfig;
 use log::error;
 use tokio::{
-    task::JoinHandle,
+    runtime::Handle,
+    task::{self, JoinHandle},
     time::{MissedTickBehavior, interval}
 };
 
-use super::Message;
+use super::{
+    Message,
+    data::{Metrics, SystemInfoData, SystemInfoSampler}
+};
 use crate::{ModuleContext, ModuleEventSender};
 
 /// Interval between system information refresh ticks.
 pub const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
 
-/// Manages the background polling task responsible for refreshing system
+/// Interval between refreshes of every metric while the detailed menu is
+/// open.
+pub const MENU_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Refresh cadence for each metric group sampled by the module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollingIntervals {
+    pub usage:       Duration,
+    pub disks:       Duration,
+    pub temperature: Duration,
+    pub network:     Duration
+}
+
+impl Default for PollingIntervals {
+    fn default() -> Self {
+        Self {
+            usage:       REFRESH_INTERVAL,
+            disks:       REFRESH_INTERVAL,
+            temperature: REFRESH_INTERVAL,
+            network:     REFRESH_INTERVAL
+        }
+    }
+}
+
+impl PollingIntervals {
+    /// Refresh every metric at the same `interval`.
+    pub fn uniform(interval: Duration) -> Self {
+        Self {
+            usage:       interval,
+            disks:       interval,
+            temperature: interval,
+            network:     interval
+        }
+    }
+
+    /// Resolve per-metric intervals, falling back to the module interval when
+    /// a metric does not override it.
+    pub fn from_config(config: &SystemModuleConfig) -> Self {
+        let seconds = |value: u64| Duration::from_secs(value.max(1));
+        let usage = seconds(config.interval);
+
+        Self {
+            usage,
+            disks: config.disk.interval.map_or(usage, seconds),
+            temperature: config.temperature.interval.map_or(usage, seconds),
+            network: config.network.interval.map_or(usage, seconds)
+        }
+    }
+
+    /// Group the `shown` metrics sharing an interval so each distinct cadence
+    /// needs only one timer.
+    fn groups(&self, shown: Metrics) -> Vec<(Duration, Metrics)> {
+        let entries = [
+            (self.usage, Metrics::USAGE),
+            (
+                self.disks,
+                Metrics {
+                    disks: true,
+                    ..Metrics::default()
+                }
+            ),
+            (
+                self.temperature,
+                Metrics {
+                    temperature: true,
+                    ..Metrics::default()
+                }
+            ),
+            (
+                self.network,
+                Metrics {
+                    network: true,
+                    ..Metrics::default()
+                }
+            )
+        ];
+
+        let mut groups: Vec<(Duration, Metrics)> = Vec::new();
+        for (interval, metrics) in entries {
+            let metrics = metrics.intersection(shown);
+            if metrics.is_empty() {
+                continue;
+            }
+
+            match groups
+                .iter_mut()
+                .find(|(existing, _)| *existing == interval)
+            {
+                Some((_, group)) => *group = group.union(metrics),
+                None => groups.push((interval, metrics))
+            }
+        }
+
+        groups
+    }
+}
+
+/// Where and what a [`PollingTask`] polls while the menu is closed.
+struct PollingTarget {
+    runtime:   Handle,
+    sender:    ModuleEventSender<Message>,
+    sampler:   Arc<Mutex<SystemInfoSampler>>,
+    intervals: PollingIntervals,
+    shown:     Metrics
+}
+
+/// Manages the background polling tasks responsible for refreshing system
 /// metrics.
 #[derive(Default)]
 pub struct PollingTask {
-    handle: Option<JoinHandle<()>>
+    handles:   Vec<JoinHandle<()>>,
+    target:    Option<PollingTarget>,
+    menu_open: bool
 }
 
 impl PollingTask {
     /// Create a new polling task manager with no active background work.
     pub fn new() -> Self {
-        Self {
-            handle: None
-        }
+        Self::default()
     }
 
-    /// Abort any in-flight polling task.
+    /// Abort any in-flight polling tasks.
     pub fn abort(&mut self) {
-        if let Some(handle) = self.handle.take() {
+        for handle in self.handles.drain(..) {
             handle.abort();
         }
     }
 
-    /// Spawn a periodic refresh loop bound to the provided runtime context.
-    pub fn spawn(&mut self, ctx: &ModuleContext, sender: ModuleEventSender<Message>) {
+    /// Sample the `shown` metrics at their `intervals` on the provided
+    /// runtime context, replacing any previous loops.
+    pub fn spawn(
+        &mut self,
+        ctx: &ModuleContext,
+        sender: ModuleEventSender<Message>,
+        sampler: Arc<Mutex<SystemInfoSampler>>,
+        intervals: PollingIntervals,
+        shown: Metrics
+    ) {
+        self.target = Some(PollingTarget {
+            runtime: ctx.runtime_handle().clone(),
+            sender,
+            sampler,
+            intervals,
+            shown
+        });
+        self.restart();
+    }
+
+    /// Refresh every metric at [`MENU_REFRESH_INTERVAL`] while the detailed
+    /// menu is open and return to the configured polling once it closes.
+    pub fn set_menu_open(&mut self, open: bool) {
+        if self.menu_open != open {
+            self.menu_open = open;
+            self.restart();
+        }
+    }
+
+    /// Spawn one periodic refresh loop per distinct interval.
+    fn restart(&mut self) {
         self.abort();
 
-        let handle = ctx.runtime_handle().spawn(async move {
-            let mut ticker = interval(REFRESH_INTERVAL);
-            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
-            let _ = ticker.tick().await;
+        let Some(target) = self.target.as_ref() else {
+            return;
+        };
+        let (intervals, shown) = if self.menu_open {
+            (
+                PollingIntervals::uniform(MENU_REFRESH_INTERVAL),
+                Metrics::ALL
+            )
+        } else {
+            (target.intervals, target.shown)
+        };
+
+        for (period, metrics) in intervals.groups(shown) {
+            let sender = target.sender.clone();
+            let sampler = Arc::clone(&target.sampler);
+            let handle = target.runtime.spawn(async move {
+                let mut ticker = interval(period);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                let _ = ticker.tick().await;
 
-            loop {
-                ticker.tick().await;
+                loop {
+                    ticker.tick().await;
 
-                if let Err(err) = sender.try_send(Message::Update) {
-                    error!("failed to publish system info refresh: {err}");
+                    // Sampling reads procfs and sysfs synchronously.
+                    let sampler = Arc::clone(&sampler);
+                    let refreshed = task::spawn_blocking(move || {
+                        let mut sample = SystemInfoData::default();
+                        sampler
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .refresh(&mut sample, metrics);
+                        sample
+                    })
+                    .await;
+                    let sample = match refreshed {
+                        Ok(sample) => sample,
+                        Err(err) => {
+                            error!("system info refresh failed: {err}");
+                            continue;
+                        }
+                    };
+
+                    if let Err(err) = sender.try_send(Message::Update(metrics, Box::new(sample))) {
+                        error!("failed to publish system info refresh: {err}");
+                    }
                 }
-            }
-        });
+            });
 
-        self.handle = Some(handle);
+            self.handles.push(handle);
+        }
     }
 }
 
@@ -64,15 +242,15 @@ impl Drop for PollingTask {
 
 #[cfg(test)]
 mod tests {
-    use std::num::NonZeroUsize;
+    use std::{num::NonZeroUsize, time::Instant};
 
     use tokio::{task::yield_now, time::advance};
 
     use super::*;
     use crate::{
         ModuleContext,
-        event_bus::{BusEvent, EventBus, ModuleEvent},
-        modules::system_info::Message
+        event_bus::{BusEvent, EventBus, EventReceiver, ModuleEvent},
+        modules::system_info::{Message, Metrics}
     };
 
     fn module_context() -> (ModuleContext, EventBus) {
@@ -83,9 +261,34 @@ mod tests {
         (ctx, bus)
     }
 
-    fn expect_system_info_update(event: Option<BusEvent>) {
+    fn sampler() -> Arc<Mutex<SystemInfoSampler>> {
+        Arc::new(Mutex::new(SystemInfoSampler::new()))
+    }
+
+    /// Real time allowed for a refresh on the blocking pool to publish.
+    const REFRESH_WAIT: Duration = Duration::from_secs(5);
+
+    /// Wait up to `wait` of real time for the next event; refreshes run on
+    /// the blocking pool, outside the paused clock.
+    async fn next_event(receiver: &mut EventReceiver, wait: Duration) -> Option<BusEvent> {
+        let deadline = Instant::now() + wait;
+
+        loop {
+            if let Some(event) = receiver.try_recv().expect("bus read") {
+                return Some(event);
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            yield_now().await;
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn expect_system_info_update(event: Option<BusEvent>, expected: Metrics) {
         match event {
-            Some(BusEvent::Module(ModuleEvent::SystemInfo(Message::Update))) => {}
+            Some(BusEvent::Module(ModuleEvent::SystemInfo(Message::Update(metrics, _))))
+                if metrics == expected => {}
             other => panic!("unexpected event: {other:?}")
         }
     }
@@ -97,16 +300,21 @@ mod tests {
         let mut receiver = bus.receiver();
 
         let sender = ctx.module_sender(ModuleEvent::SystemInfo);
-        polling.spawn(&ctx, sender);
+        polling.spawn(
+            &ctx,
+            sender,
+            sampler(),
+            PollingIntervals::default(),
+            Metrics::ALL
+        );
         yield_now().await;
 
         assert!(receiver.try_recv().expect("initial queue state").is_none());
 
         advance(REFRESH_INTERVAL).await;
-        yield_now().await;
 
-        let event = receiver.try_recv().expect("queued refresh after interval");
-        expect_system_info_update(event);
+        let event = next_event(&mut receiver, REFRESH_WAIT).await;
+        expect_system_info_update(event, Metrics::ALL);
     }
 
     #[tokio::test(start_paused = true)]
@@ -116,24 +324,135 @@ mod tests {
         let mut receiver = bus.receiver();
 
         let sender = ctx.module_sender(ModuleEvent::SystemInfo);
-        polling.spawn(&ctx, sender.clone());
+        polling.spawn(
+            &ctx,
+            sender.clone(),
+            sampler(),
+            PollingIntervals::default(),
+            Metrics::ALL
+        );
         yield_now().await;
 
         advance(REFRESH_INTERVAL).await;
-        yield_now().await;
 
-        let first = receiver.try_recv().expect("first refresh after interval");
-        expect_system_info_update(first);
+        let first = next_event(&mut receiver, REFRESH_WAIT).await;
+        expect_system_info_update(first, Metrics::ALL);
         assert!(receiver.try_recv().expect("drain first interval").is_none());
 
-        polling.spawn(&ctx, sender);
+        polling.spawn(
+            &ctx,
+            sender,
+            sampler(),
+            PollingIntervals::default(),
+            Metrics::ALL
+        );
         yield_now().await;
 
         advance(REFRESH_INTERVAL).await;
-        yield_now().await;
 
-        let second = receiver.try_recv().expect("refresh after respawn");
-        expect_system_info_update(second);
+        let second = next_event(&mut receiver, REFRESH_WAIT).await;
+        expect_system_info_update(second, Metrics::ALL);
         assert!(receiver.try_recv().expect("no duplicate refresh").is_none());
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn metrics_with_own_interval_refresh_independently() {
+        let (ctx, bus) = module_context();
+        let mut polling = PollingTask::default();
+        let mut receiver = bus.receiver();
+        let intervals = PollingIntervals {
+            usage:       Duration::from_secs(1),
+            disks:       Duration::from_secs(30),
+            temperature: Duration::from_secs(1),
+            network:     Duration::from_secs(1)
+        };
+
+        let sender = ctx.module_sender(ModuleEvent::SystemInfo);
+        polling.spawn(&ctx, sender, sampler(), intervals, Metrics::ALL);
+        yield_now().await;
+
+        advance(Duration::from_secs(1)).await;
+
+        let event = next_event(&mut receiver, REFRESH_WAIT).await;
+        expect_system_info_update(
+            event,
+            Metrics {
+                disks: false,
+                ..Metrics::ALL
+            }
+        );
+        assert!(receiver.try_recv().expect("disk poll not due").is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn only_shown_metrics_are_polled() {
+        let (ctx, bus) = module_context();
+        let mut polling = PollingTask::default();
+        let mut receiver = bus.receiver();
+        let intervals = PollingIntervals {
+            disks: Duration::from_secs(30),
+            ..PollingIntervals::default()
+        };
+
+        let sender = ctx.module_sender(ModuleEvent::SystemInfo);
+        polling.spawn(&ctx, sender, sampler(), intervals, Metrics::USAGE);
+        yield_now().await;
+
+        advance(Duration::from_secs(30)).await;
+
+        let event = next_event(&mut receiver, REFRESH_WAIT).await;
+        expect_system_info_update(event, Metrics::USAGE);
+        while let Some(event) = receiver.try_recv().expect("queued refreshes") {
+            expect_system_info_update(Some(event), Metrics::USAGE);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn open_menu_polls_every_metric_at_the_menu_interval() {
+        let (ctx, bus) = module_context();
+        let mut polling = PollingTask::default();
+        let mut receiver = bus.receiver();
+
+        let sender = ctx.module_sender(ModuleEvent::SystemInfo);
+        polling.spawn(
+            &ctx,
+            sender,
+            sampler(),
+            PollingIntervals::default(),
+            Metrics::USAGE
+        );
+        polling.set_menu_open(true);
+        yield_now().await;
+
+        advance(MENU_REFRESH_INTERVAL).await;
+
+        let event = next_event(&mut receiver, REFRESH_WAIT).await;
+        expect_system_info_update(event, Metrics::ALL);
+
+        polling.set_menu_open(false);
+        yield_now().await;
+
+        advance(MENU_REFRESH_INTERVAL).await;
+
+        // A refresh at the menu cadence would publish well within this wait.
+        let stray = next_event(&mut receiver, Duration::from_millis(200)).await;
+        assert!(stray.is_none(), "bar cadence restored");
+    }
+
+    #[test]
+    fn intervals_fall_back_to_module_interval() {
+        let mut config = SystemModuleConfig {
+            interval: 2,
+            ..SystemModuleConfig::default()
+        };
+        config.disk.interval = Some(30);
+
+        let intervals = PollingIntervals::from_config(&config);
+
+        assert_eq!(intervals.usage, Duration::from_secs(2));
+        assert_eq!(intervals.disks, Duration::from_secs(30));
+        assert_eq!(intervals.temperature, Duration::from_secs(2));
+        assert_eq!(intervals.network, Duration::from_secs(2));
+        assert_eq!(intervals.groups(Metrics::ALL).len(), 2);
+    }
 }
@@ -0,0 +1,129 @@
+use std::{fs, path::Path};
+
+/// Where the kernel publishes hardware monitoring chips.
+const HWMON_DIR: &str = "/sys/class/hwmon";
+
+/// Where the kernel publishes the run queue averages.
+const LOADAVG_PATH: &str = "/proc/loadavg";
+
+/// Run queue length averaged over the last 1, 5 and 15 minutes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LoadAverage {
+    pub one:     f32,
+    pub five:    f32,
+    pub fifteen: f32
+}
+
+/// Parse the first three fields of `/proc/loadavg`.
+pub fn parse_loadavg(contents: &str) -> Option<LoadAverage> {
+    let mut fields = contents.split_whitespace().map(str::parse::<f32>);
+
+    Some(LoadAverage {
+        one:     fields.next()?.ok()?,
+        five:    fields.next()?.ok()?,
+        fifteen: fields.next()?.ok()?
+    })
+}
+
+/// Current load averages, `None` when `/proc/loadavg` cannot be read.
+pub fn read_load_average() -> Option<LoadAverage> {
+    parse_loadavg(&fs::read_to_string(LOADAVG_PATH).ok()?)
+}
+
+/// Temperature in degrees Celsius of the hwmon sensor labelled `label`.
+///
+/// A sensor matches either its `tempN_label` or `<chip> <label>`, where the
+/// label falls back to `tempN` when the chip provides none, so both `Tctl`
+/// and `acpitz temp1` can be used.
+pub fn hwmon_temperature(label: &str) -> Option<i32> {
+    find_temperature(Path::new(HWMON_DIR), label)
+}
+
+fn find_temperature(root: &Path, label: &str) -> Option<i32> {
+    let mut chips: Vec<_> = fs::read_dir(root)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect();
+    chips.sort();
+
+    chips.iter().find_map(|chip| chip_temperature(chip, label))
+}
+
+fn chip_temperature(chip: &Path, label: &str) -> Option<i32> {
+    let name = read_trimmed(&chip.join("name")).unwrap_or_default();
+    let mut inputs: Vec<_> = fs::read_dir(chip)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_str()?.to_owned();
+            let sensor = file_name.strip_suffix("_input")?;
+            sensor.starts_with("temp").then(|| sensor.to_owned())
+        })
+        .collect();
+    inputs.sort();
+
+    inputs.iter().find_map(|sensor| {
+        let sensor_label =
+            read_trimmed(&chip.join(format!("{sensor}_label"))).unwrap_or_else(|| sensor.clone());
+
+        if sensor_label != label && format!("{name} {sensor_label}") != label {
+            return None;
+        }
+
+        let millidegrees: i32 = read_trimmed(&chip.join(format!("{sensor}_input")))?
+            .parse()
+            .ok()?;
+        Some(millidegrees / 1000)
+    })
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn write(root: &Path, file: &str, contents: &str) {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().expect("parent dir")).expect("create dir");
+        fs::write(path, contents).expect("write fixture");
+    }
+
+    #[test]
+    fn parses_proc_loadavg() {
+        assert_eq!(
+            parse_loadavg("0.52 1.07 2.50 2/1312 48213\n"),
+            Some(LoadAverage {
+                one:     0.52,
+                five:    1.07,
+                fifteen: 2.5
+            })
+        );
+        assert_eq!(parse_loadavg("0.52 oops 2.50"), None);
+        assert_eq!(parse_loadavg(""), None);
+    }
+
+    #[test]
+    fn finds_hwmon_sensor_by_label() {
+        let root = TempDir::new().expect("temp dir");
+        write(root.path(), "hwmon0/name", "acpitz\n");
+        write(root.path(), "hwmon0/temp1_input", "41000\n");
+        write(root.path(), "hwmon1/name", "k10temp\n");
+        write(root.path(), "hwmon1/temp1_label", "Tctl\n");
+        write(root.path(), "hwmon1/temp1_input", "56875\n");
+        write(root.path(), "hwmon1/temp3_label", "Tccd1\n");
+        write(root.path(), "hwmon1/temp3_input", "49250\n");
+
+        assert_eq!(find_temperature(root.path(), "Tctl"), Some(56));
+        assert_eq!(find_temperature(root.path(), "k10temp Tccd1"), Some(49));
+        assert_eq!(find_temperature(root.path(), "acpitz temp1"), Some(41));
+        assert_eq!(find_temperature(root.path(), "Package id 0"), None);
+    }
+}
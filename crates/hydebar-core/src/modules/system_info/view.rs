@@ -1,6 +1,6 @@
 use iced::{
     Alignment, Element, Length, Theme,
-    widget::{Column, Row, column, container, horizontal_rule, row, text}
+    widget::{Column, Row, Space, column, container, horizontal_rule, row, text}
 };
 
 use super::{Message, data::SystemInfoData};
@@ -8,7 +8,8 @@ use crate::{
     components::icons::{Icons, icon},
     config::{SystemIndicator, SystemModuleConfig},
     menu::MenuType,
-    modules::OnModulePress
+    modules::OnModulePress,
+    utils::throughput::format_throughput
 };
 
 fn info_element<'a>(info_icon: Icons, label: &'a str, value: String) -> Element<'a, Message> {
@@ -25,6 +26,7 @@ fn info_element<'a>(info_icon: Icons, label: &'a str, value: String) -> Element<
 fn indicator_info_element<V>(
     info_icon: Icons,
     value: V,
+    decimals: usize,
     unit: &str,
     threshold: Option<(V, V)>,
     prefix: Option<String>
@@ -36,9 +38,9 @@ where
         row!(
             icon(info_icon),
             if let Some(prefix) = prefix {
-                text(format!("{prefix} {value}{unit}"))
+                text(format!("{prefix} {value:.decimals$}{unit}"))
             } else {
-                text(format!("{value}{unit}"))
+                text(format!("{value:.decimals$}{unit}"))
             }
         )
         .spacing(4)
@@ -62,16 +64,43 @@ where
     }
 }
 
-fn format_speed(speed: u32) -> (u32, &'static str) {
-    if speed > 1000 {
-        (speed / 1000, "MB/s")
-    } else {
-        (speed, "KB/s")
-    }
+/// Height of a fully busy core in the per-core usage row.
+const CORE_BAR_HEIGHT: f32 = 16.;
+
+/// Row of small vertical bars, one per core, filled up to its usage.
+fn core_bars<'a>(core_usage: &[f32]) -> Element<'a, Message> {
+    Row::with_children(core_usage.iter().map(|usage| {
+        let filled = CORE_BAR_HEIGHT * usage.clamp(0., 100.) / 100.;
+
+        container(
+            container(Space::new(Length::Fill, Length::Fixed(filled))).style(|theme: &Theme| {
+                container::Style {
+                    background: Some(theme.palette().primary.into()),
+                    ..Default::default()
+                }
+            })
+        )
+        .width(Length::Fill)
+        .height(Length::Fixed(CORE_BAR_HEIGHT))
+        .align_y(Alignment::End)
+        .style(|theme: &Theme| container::Style {
+            background: Some(theme.extended_palette().background.weak.color.into()),
+            ..Default::default()
+        })
+        .into()
+    }))
+    .spacing(2)
+    .padding([0, 8])
+    .into()
 }
 
 /// Render the module menu displaying detailed system metrics.
-pub fn build_menu_view(data: &SystemInfoData) -> Element<'_, Message> {
+pub fn build_menu_view<'a>(
+    data: &'a SystemInfoData,
+    config: &SystemModuleConfig
+) -> Element<'a, Message> {
+    let decimals = usize::from(config.decimals);
+
     column![
         text("System Info").size(20),
         horizontal_rule(1),
@@ -79,23 +108,31 @@ pub fn build_menu_view(data: &SystemInfoData) -> Element<'_, Message> {
             .push(info_element(
                 Icons::Cpu,
                 "CPU Usage",
-                format!("{}%", data.cpu_usage)
+                format!("{:.decimals$}%", data.cpu_usage)
             ))
+            .push_maybe((!data.core_usage.is_empty()).then(|| core_bars(&data.core_usage)))
             .push(info_element(
                 Icons::Mem,
                 "Memory Usage",
-                format!("{}%", data.memory_usage)
+                format!("{:.decimals$}%", data.memory_usage)
             ))
             .push(info_element(
                 Icons::Mem,
                 "Swap memory Usage",
-                format!("{}%", data.memory_swap_usage),
+                format!("{:.decimals$}%", data.memory_swap_usage),
             ))
             .push_maybe(
                 data.temperature.map(|temp| {
                     info_element(Icons::Temp, "Temperature", format!("{temp}°C"))
                 })
             )
+            .push_maybe(data.load_average.map(|load| {
+                info_element(
+                    Icons::Cpu,
+                    "Load Average",
+                    format!("{:.2} {:.2} {:.2}", load.one, load.five, load.fifteen)
+                )
+            }))
             .push(
                 Column::with_children(
                     data.disks
@@ -116,8 +153,10 @@ pub fn build_menu_view(data: &SystemInfoData) -> Element<'_, Message> {
                 .spacing(4),
             )
             .push_maybe(data.network.as_ref().map(|network| {
-                let (download_value, download_unit) = format_speed(network.download_speed);
-                let (upload_value, upload_unit) = format_speed(network.upload_speed);
+                let (download_value, download_unit) =
+                    format_throughput(network.download_speed, &config.network.format);
+                let (upload_value, upload_unit) =
+                    format_throughput(network.upload_speed, &config.network.format);
 
                 Column::with_children(vec![
                     info_element(Icons::IpAddress, "IP Address", network.ip.clone()),
@@ -148,6 +187,8 @@ pub fn indicator_elements<M>(
 where
     M: 'static + From<Message>
 {
+    let decimals = usize::from(config.decimals);
+
     config
         .indicators
         .iter()
@@ -156,28 +197,41 @@ where
                 SystemIndicator::Cpu => Some(indicator_info_element(
                     Icons::Cpu,
                     data.cpu_usage,
+                    decimals,
                     "%",
-                    Some((config.cpu.warn_threshold, config.cpu.alert_threshold)),
+                    Some((
+                        config.cpu.warn_threshold as f32,
+                        config.cpu.alert_threshold as f32
+                    )),
                     None
                 )),
                 SystemIndicator::Memory => Some(indicator_info_element(
                     Icons::Mem,
                     data.memory_usage,
+                    decimals,
                     "%",
-                    Some((config.memory.warn_threshold, config.memory.alert_threshold)),
+                    Some((
+                        config.memory.warn_threshold as f32,
+                        config.memory.alert_threshold as f32
+                    )),
                     None
                 )),
                 SystemIndicator::MemorySwap => Some(indicator_info_element(
                     Icons::Mem,
                     data.memory_swap_usage,
+                    decimals,
                     "%",
-                    Some((config.memory.warn_threshold, config.memory.alert_threshold)),
+                    Some((
+                        config.memory.warn_threshold as f32,
+                        config.memory.alert_threshold as f32
+                    )),
                     Some("swap".to_string())
                 )),
                 SystemIndicator::Temperature => data.temperature.map(|temperature| {
                     indicator_info_element(
                         Icons::Temp,
                         temperature,
+                        0,
                         "°C",
                         Some((
                             config.temperature.warn_threshold,
@@ -192,6 +246,7 @@ where
                             Some(indicator_info_element(
                                 Icons::Drive,
                                 *disk,
+                                0,
                                 "%",
                                 Some((config.disk.warn_threshold, config.disk.alert_threshold)),
                                 Some(disk_mount.clone())
@@ -206,12 +261,21 @@ where
                     container(row!(icon(Icons::IpAddress), text(ip)).spacing(4)).into()
                 }),
                 SystemIndicator::DownloadSpeed => data.network.as_ref().map(|network| {
-                    let (value, unit) = format_speed(network.download_speed);
-                    indicator_info_element(Icons::DownloadSpeed, value, unit, None, None)
+                    let (value, unit) =
+                        format_throughput(network.download_speed, &config.network.format);
+                    container(
+                        row!(icon(Icons::DownloadSpeed), text(format!("{value}{unit}")))
+                            .spacing(4)
+                    )
+                    .into()
                 }),
                 SystemIndicator::UploadSpeed => data.network.as_ref().map(|network| {
-                    let (value, unit) = format_speed(network.upload_speed);
-                    indicator_info_element(Icons::UploadSpeed, value, unit, None, None)
+                    let (value, unit) =
+                        format_throughput(network.upload_speed, &config.network.format);
+                    container(
+                        row!(icon(Icons::UploadSpeed), text(format!("{value}{unit}"))).spacing(4)
+                    )
+                    .into()
                 })
             }
         })
@@ -246,10 +310,12 @@ mod tests {
 
     fn data_fixture() -> SystemInfoData {
         SystemInfoData {
-            cpu_usage:         25,
-            memory_usage:      50,
-            memory_swap_usage: 10,
+            cpu_usage:         25.,
+            core_usage:        vec![10., 90.],
+            memory_usage:      50.,
+            memory_swap_usage: 10.,
             temperature:       Some(42),
+            load_average:      None,
             disks:             vec![("/".to_string(), 60)],
             network:           None
         }
@@ -259,17 +325,19 @@ mod tests {
     fn indicator_row_contains_configured_entries() {
         let data = data_fixture();
         let config = SystemModuleConfig {
-            indicators:  vec![SystemIndicator::Cpu, SystemIndicator::Memory],
-            cpu:         Default::default(),
-            memory:      SystemInfoMemory {
+            indicators: vec![SystemIndicator::Cpu, SystemIndicator::Memory],
+            cpu: Default::default(),
+            memory: SystemInfoMemory {
                 warn_threshold:  70,
                 alert_threshold: 90
             },
             temperature: SystemInfoTemperature {
                 warn_threshold:  70,
-                alert_threshold: 90
+                alert_threshold: 90,
+                interval:        None,
+                sensor:          "acpitz temp1".to_owned()
             },
-            disk:        Default::default()
+            ..SystemModuleConfig::default()
         };
 
         let indicators: Vec<Element<'_, Message>> = indicator_elements(data, &config);
@@ -294,10 +362,4 @@ mod tests {
         let indicators: Vec<Element<'_, Message>> = indicator_elements(data, &config);
         assert_eq!(indicators.len(), 2);
     }
-
-    #[test]
-    fn format_speed_converts_large_values_to_megabytes() {
-        let (value, unit) = format_speed(2048);
-        assert_eq!((value, unit), (2, "MB/s"));
-    }
 }
@@ -0,0 +1,208 @@
+use std::{sync::Arc, time::Duration};
+
+use hydebar_proto::ports::hyprland::{HyprlandKeyboardEvent, HyprlandLayoutState, HyprlandPort};
+use iced::{Element, widget::text};
+use log::error;
+use tokio::{task::JoinHandle, time::sleep};
+use tokio_stream::StreamExt;
+
+use super::{Module, ModuleError, OnModulePress};
+use crate::{
+    ModuleContext, ModuleEventSender, config::TilingLayoutModuleConfig, event_bus::ModuleEvent
+};
+
+const LAYOUT_EVENT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Shows the active Hyprland tiling layout and whether gaps are disabled.
+pub struct TilingLayout {
+    hyprland: Arc<dyn HyprlandPort>,
+    state:    Option<HyprlandLayoutState>,
+    sender:   Option<ModuleEventSender<Message>>,
+    task:     Option<JoinHandle<()>>
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    StateChanged(HyprlandLayoutState),
+    ToggleGaps
+}
+
+impl TilingLayout {
+    pub fn new(hyprland: Arc<dyn HyprlandPort>) -> Self {
+        let state = if hyprland.is_available() {
+            hyprland
+                .layout_state()
+                .map_err(|err| error!("failed to query tiling layout: {err}"))
+                .ok()
+        } else {
+            None
+        };
+
+        Self {
+            hyprland,
+            state,
+            sender: None,
+            task: None
+        }
+    }
+
+    /// Apply `message`, using `config` as the preset restored when gaps are
+    /// toggled back on.
+    pub fn update(&mut self, message: Message, config: &TilingLayoutModuleConfig) {
+        match message {
+            Message::StateChanged(state) => {
+                self.state = Some(state);
+            }
+            Message::ToggleGaps => {
+                let Some(state) = &self.state else {
+                    return;
+                };
+
+                let result = if state.gaps_zero() {
+                    self.hyprland.set_gaps(&config.gaps_in, &config.gaps_out)
+                } else {
+                    self.hyprland.set_gaps("0", "0")
+                };
+
+                if let Err(err) = result {
+                    error!("failed to toggle gaps: {err}");
+                    return;
+                }
+
+                // Keyword changes do not emit a config reload, so re-query
+                // right away instead of waiting for the next event.
+                match self.hyprland.layout_state() {
+                    Ok(state) => self.state = Some(state),
+                    Err(err) => error!("failed to query tiling layout: {err}")
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn state(&self) -> Option<&HyprlandLayoutState> {
+        self.state.as_ref()
+    }
+}
+
+impl<M> Module<M> for TilingLayout
+where
+    M: 'static + Clone
+{
+    type ViewData<'a> = ();
+    type RegistrationData<'a> = ();
+
+    fn register(
+        &mut self,
+        ctx: &ModuleContext,
+        _: Self::RegistrationData<'_>
+    ) -> Result<(), ModuleError> {
+        self.sender = Some(ctx.module_sender(ModuleEvent::TilingLayout));
+
+        if let Some(handle) = self.task.take() {
+            handle.abort();
+        }
+
+        if !self.hyprland.is_available() {
+            return Ok(());
+        }
+
+        if let Some(sender) = self.sender.clone() {
+            let hyprland = Arc::clone(&self.hyprland);
+            self.task = Some(ctx.runtime_handle().spawn(async move {
+                loop {
+                    match hyprland.keyboard_events() {
+                        Ok(mut stream) => {
+                            while let Some(event) = stream.next().await {
+                                match event {
+                                    // Hyprland reports config reloads through the keyboard
+                                    // listener; layout and gaps may have changed with it.
+                                    Ok(HyprlandKeyboardEvent::LayoutConfigurationChanged(_)) => {
+                                        match hyprland.layout_state() {
+                                            Ok(state) => {
+                                                if let Err(err) =
+                                                    sender.try_send(Message::StateChanged(state))
+                                                {
+                                                    error!(
+                                                        "failed to publish tiling layout update: {err}"
+                                                    );
+                                                }
+                                            }
+                                            Err(err) => {
+                                                error!("failed to query tiling layout: {err}");
+                                            }
+                                        }
+                                    }
+                                    Ok(_) => {}
+                                    Err(err) => {
+                                        error!("tiling layout stream error: {err}");
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+                        Err(err) => {
+                            error!("failed to start tiling layout stream: {err}");
+                        }
+                    }
+
+                    sleep(LAYOUT_EVENT_RETRY_DELAY).await;
+                }
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn view(
+        &self,
+        _: Self::ViewData<'_>
+    ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)> {
+        let state = self.state.as_ref()?;
+        let label = if state.gaps_zero() {
+            format!("{} · no gaps", state.layout)
+        } else {
+            state.layout.clone()
+        };
+
+        Some((
+            text(label).into(),
+            None // Action handled in GUI layer
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockHyprlandPort;
+
+    #[test]
+    fn initializes_from_layout_state() {
+        let port: Arc<dyn HyprlandPort> = Arc::new(MockHyprlandPort::default());
+
+        let module = TilingLayout::new(port);
+
+        let state = module.state().expect("layout state");
+        assert_eq!(state.layout, "dwindle");
+        assert!(!state.gaps_zero());
+    }
+
+    #[test]
+    fn toggle_gaps_switches_between_zero_and_preset() {
+        let port: Arc<dyn HyprlandPort> = Arc::new(MockHyprlandPort::default());
+        let mut module = TilingLayout::new(port);
+        let config = TilingLayoutModuleConfig {
+            gaps_in:  "3".into(),
+            gaps_out: "8".into()
+        };
+
+        module.update(Message::ToggleGaps, &config);
+        assert!(module.state().expect("layout state").gaps_zero());
+
+        module.update(Message::ToggleGaps, &config);
+        let state = module.state().expect("layout state");
+        assert_eq!(state.gaps_in, "3");
+        assert_eq!(state.gaps_out, "8");
+    }
+}
@@ -1,7 +1,8 @@
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc, time::Duration};
 
 use iced::{
     Element, Length,
+    mouse::ScrollDelta,
     widget::{Column, Row, button, horizontal_rule, row, text, toggler},
     window::Id
 };
@@ -12,11 +13,12 @@ use super::{Module, ModuleError, OnModulePress};
 use crate::{
     ModuleContext, ModuleEventSender,
     components::icons::{Icons, icon},
+    config::{TrayModuleConfig, TrayOrder},
     event_bus::ModuleEvent,
     services::{
         ReadOnlyService, ServiceEvent,
         tray::{
-            TrayCommand, TrayService,
+            ItemActivation, ScrollAxis, StatusNotifierItem, TrayCommand, TrayService,
             dbus::{Layout, LayoutProps}
         }
     },
@@ -27,11 +29,24 @@ use crate::{
 pub enum TrayMessage {
     Event(Box<ServiceEvent<TrayService>>),
     ToggleSubmenu(i32),
-    MenuSelected(String, i32)
+    MenuSelected(String, i32),
+    /// Pointer scroll over the named item, before it is split into steps.
+    Scrolled(String, ScrollDelta),
+    Scroll(String, i32, ScrollAxis),
+    /// Right or middle click on the named item.
+    Activate(String, ItemActivation)
 }
 
-type ListenerSpawner =
-    Arc<dyn Fn(ModuleEventSender<TrayMessage>, Handle) -> JoinHandle<()> + Send + Sync>;
+/// Pixel distance treated as one scroll step for touchpad gestures.
+const PIXELS_PER_SCROLL_STEP: f32 = 20.;
+
+/// Starts the tray listener; the duration is the optional interval at which
+/// registered items are polled.
+type ListenerSpawner = Arc<
+    dyn Fn(ModuleEventSender<TrayMessage>, Handle, Option<Duration>) -> JoinHandle<()>
+        + Send
+        + Sync
+>;
 type CommandFactory =
     Arc<dyn Fn(Option<&TrayService>, TrayCommand) -> Option<TrayCommandFuture> + Send + Sync>;
 type TrayCommandFuture = Pin<Box<dyn Future<Output = ServiceEvent<TrayService>> + Send + 'static>>;
@@ -39,8 +54,11 @@ type TrayCommandFuture = Pin<Box<dyn Future<Output = ServiceEvent<TrayService>>
 pub struct TrayModule {
     pub service:      Option<TrayService>,
     pub submenus:     Vec<i32>,
+    /// Scroll left over below a whole step, per item and per axis.
+    scroll_offsets:   HashMap<String, (f32, f32)>,
     sender:           Option<ModuleEventSender<TrayMessage>>,
     runtime:          Option<Handle>,
+    poll_interval:    Option<Duration>,
     listener_handles: Vec<JoinHandle<()>>,
     listener_spawner: ListenerSpawner,
     command_factory:  CommandFactory
@@ -51,8 +69,10 @@ impl std::fmt::Debug for TrayModule {
         f.debug_struct("TrayModule")
             .field("service", &self.service)
             .field("submenus", &self.submenus)
+            .field("scroll_offsets", &self.scroll_offsets)
             .field("sender", &self.sender)
             .field("runtime", &self.runtime)
+            .field("poll_interval", &self.poll_interval)
             .field(
                 "listener_handles",
                 &format!("<{} handles>", self.listener_handles.len())
@@ -81,7 +101,8 @@ impl TrayModule {
         };
 
         let spawner = Arc::clone(&self.listener_spawner);
-        self.listener_handles.push(spawner(sender, runtime));
+        self.listener_handles
+            .push(spawner(sender, runtime, self.poll_interval));
     }
 
     fn dispatch_command(&self, command_future: TrayCommandFuture) {
@@ -102,6 +123,39 @@ impl TrayModule {
         });
     }
 
+    /// Add a pointer scroll over the named item to its remainder and take out
+    /// the whole steps.
+    ///
+    /// The dominant axis wins and the delta keeps the sign of the wheel, as
+    /// KDE's StatusNotifierItem host does: scrolling up or left is positive.
+    /// Touchpad gestures build up across events until they amount to a step.
+    fn scroll_steps(&mut self, name: &str, delta: ScrollDelta) -> Option<TrayMessage> {
+        let (x, y) = match delta {
+            ScrollDelta::Lines {
+                x,
+                y
+            } => (x, y),
+            ScrollDelta::Pixels {
+                x,
+                y
+            } => (x / PIXELS_PER_SCROLL_STEP, y / PIXELS_PER_SCROLL_STEP)
+        };
+
+        let (offset_x, offset_y) = self.scroll_offsets.entry(name.to_owned()).or_default();
+        let (offset, axis) = if y.abs() >= x.abs() {
+            *offset_y += y;
+            (offset_y, ScrollAxis::Vertical)
+        } else {
+            *offset_x += x;
+            (offset_x, ScrollAxis::Horizontal)
+        };
+
+        let steps = offset.trunc();
+        *offset -= steps;
+
+        (steps != 0.).then(|| TrayMessage::Scroll(name.to_owned(), steps as i32, axis))
+    }
+
     pub fn update(&mut self, message: TrayMessage) {
         match message {
             TrayMessage::Event(event) => match *event {
@@ -111,6 +165,8 @@ impl TrayModule {
                 ServiceEvent::Update(data) => {
                     if let Some(service) = self.service.as_mut() {
                         service.update(data);
+                        self.scroll_offsets
+                            .retain(|name, _| service.data.iter().any(|item| &item.name == name));
                     }
                 }
                 ServiceEvent::Error(_) => {
@@ -134,6 +190,43 @@ impl TrayModule {
                     self.dispatch_command(command);
                 }
             }
+            TrayMessage::Scrolled(name, delta) => {
+                if let Some(message) = self.scroll_steps(&name, delta) {
+                    self.update(message);
+                }
+            }
+            TrayMessage::Scroll(name, delta, axis) => {
+                debug!("Tray scroll: {name} {delta} {}", axis.as_str());
+
+                if let Some(command) = (self.command_factory)(
+                    self.service.as_ref(),
+                    TrayCommand::Scroll(name, delta, axis)
+                ) {
+                    self.dispatch_command(command);
+                }
+            }
+            TrayMessage::Activate(name, activation) => {
+                debug!("Tray {}: {name}", activation.method_name());
+
+                if let Some(command) = (self.command_factory)(
+                    self.service.as_ref(),
+                    TrayCommand::Activate(name, activation)
+                ) {
+                    self.dispatch_command(command);
+                }
+            }
+        }
+    }
+
+    /// Registered items split into those shown on the bar and those moved
+    /// into the overflow popover.
+    pub fn arranged_items(
+        &self,
+        config: &TrayModuleConfig
+    ) -> (Vec<&StatusNotifierItem>, Vec<&StatusNotifierItem>) {
+        match self.service.as_ref() {
+            Some(service) => split_items(&service.data, config),
+            None => (Vec::new(), Vec::new())
         }
     }
 
@@ -231,14 +324,17 @@ where
     M: 'static + Clone
 {
     type ViewData<'a> = (Id, f32);
-    type RegistrationData<'a> = ();
+    type RegistrationData<'a> = &'a TrayModuleConfig;
 
     fn register(
         &mut self,
         ctx: &ModuleContext,
-        _: Self::RegistrationData<'_>
+        config: Self::RegistrationData<'_>
     ) -> Result<(), ModuleError> {
         self.abort_listener_handles();
+        self.poll_interval = config
+            .poll_interval
+            .map(|seconds| Duration::from_secs(seconds.max(1)));
         self.sender = Some(ctx.module_sender(ModuleEvent::Tray));
         self.runtime = Some(ctx.runtime_handle().clone());
         self.spawn_listener();
@@ -250,10 +346,9 @@ where
         &self,
         (_id, _opacity): Self::ViewData<'_>
     ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)> {
-        // TODO: Tray view needs special handling for position_button messages
-        // This requires GUI layer integration as buttons need to construct messages
-        // with ButtonUIRef which can't be done generically in core.
-        // For now, disabled to allow compilation.
+        // Tray buttons construct GUI messages carrying a ButtonUIRef, which
+        // can't be done generically in core; the bar view is rendered by the
+        // GUI layer instead.
         None
     }
 
@@ -267,8 +362,10 @@ impl Default for TrayModule {
         Self {
             service:          None,
             submenus:         Vec::new(),
+            scroll_offsets:   HashMap::new(),
             sender:           None,
             runtime:          None,
+            poll_interval:    None,
             listener_handles: Vec::new(),
             listener_spawner: default_listener_spawner(),
             command_factory:  default_command_factory()
@@ -282,10 +379,26 @@ impl Drop for TrayModule {
     }
 }
 
+/// Orders `items` as configured and splits them after `max_visible`.
+fn split_items<'a, T>(items: &'a [T], config: &TrayModuleConfig) -> (Vec<&'a T>, Vec<&'a T>) {
+    let mut ordered: Vec<&T> = items.iter().collect();
+
+    if config.order == TrayOrder::Newest {
+        ordered.reverse();
+    }
+
+    let overflow = match config.max_visible {
+        Some(max) if max < ordered.len() => ordered.split_off(max),
+        _ => Vec::new()
+    };
+
+    (ordered, overflow)
+}
+
 fn default_listener_spawner() -> ListenerSpawner {
-    Arc::new(|sender, runtime| {
+    Arc::new(|sender, runtime, poll_interval| {
         runtime.spawn(async move {
-            TrayService::start_listening(|event| {
+            TrayService::start_listening(poll_interval, |event| {
                 let sender = sender.clone();
                 async move {
                     if let Err(err) = sender.try_send(TrayMessage::Event(Box::new(event))) {
@@ -308,8 +421,10 @@ impl TrayModule {
         Self {
             service: None,
             submenus: Vec::new(),
+            scroll_offsets: HashMap::new(),
             sender: None,
             runtime: None,
+            poll_interval: None,
             listener_handles: Vec::new(),
             listener_spawner,
             command_factory
@@ -326,19 +441,21 @@ mod tests {
         time::Duration
     };
 
+    use iced::mouse::ScrollDelta;
     use tokio::{runtime::Handle, task::yield_now, time::timeout};
 
     use super::{
-        CommandFactory, ListenerSpawner, TrayMessage, TrayModule, default_command_factory,
-        default_listener_spawner
+        CommandFactory, ListenerSpawner, ScrollAxis, TrayMessage, TrayModule,
+        default_command_factory, default_listener_spawner, split_items
     };
     use crate::{
         ModuleContext,
+        config::{TrayModuleConfig, TrayOrder},
         event_bus::{BusEvent, EventBus, ModuleEvent},
         modules::Module,
         services::{
             ServiceEvent,
-            tray::{TrayCommand, TrayEvent}
+            tray::{ItemActivation, TrayCommand, TrayEvent}
         }
     };
 
@@ -357,7 +474,7 @@ mod tests {
         let cancellation = Arc::new(Mutex::new(Some(tx)));
         let cancellation_spawner = Arc::clone(&cancellation);
 
-        let listener_spawner: ListenerSpawner = Arc::new(move |_, handle: Handle| {
+        let listener_spawner: ListenerSpawner = Arc::new(move |_, handle: Handle, _| {
             let cancellation = Arc::clone(&cancellation_spawner);
 
             handle.spawn(async move {
@@ -383,9 +500,9 @@ mod tests {
 
         let mut module = TrayModule::with_factories(listener_spawner, default_command_factory());
 
-        <TrayModule as Module<()>>::register(&mut module, &context, ())
+        <TrayModule as Module<()>>::register(&mut module, &context, &TrayModuleConfig::default())
             .expect("first registration");
-        <TrayModule as Module<()>>::register(&mut module, &context, ())
+        <TrayModule as Module<()>>::register(&mut module, &context, &TrayModuleConfig::default())
             .expect("second registration");
 
         runtime
@@ -416,7 +533,7 @@ mod tests {
         let context = ModuleContext::new(sender, runtime.handle().clone());
 
         let listener_spawner: ListenerSpawner =
-            Arc::new(|_, handle: Handle| handle.spawn(async {}));
+            Arc::new(|_, handle: Handle, _| handle.spawn(async {}));
         let command_factory: CommandFactory = Arc::new(|_, command| match command {
             TrayCommand::MenuSelected(name, _) => {
                 let layout = super::Layout(
@@ -435,10 +552,12 @@ mod tests {
                     ServiceEvent::Update(TrayEvent::MenuLayoutChanged(name, layout))
                 }))
             }
+            TrayCommand::Scroll(..) | TrayCommand::Activate(..) => None
         });
 
         let mut module = TrayModule::with_factories(listener_spawner, command_factory);
-        <TrayModule as Module<()>>::register(&mut module, &context, ()).expect("registration");
+        <TrayModule as Module<()>>::register(&mut module, &context, &TrayModuleConfig::default())
+            .expect("registration");
 
         // update() returns (), just verify it doesn't panic
         module.update(TrayMessage::MenuSelected("tray".into(), 42));
@@ -468,6 +587,127 @@ mod tests {
         }
     }
 
+    #[test]
+    fn clicks_call_the_matching_item_method() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let bus = EventBus::new(NonZeroUsize::new(4).expect("capacity"));
+        let context = ModuleContext::new(bus.sender(), runtime.handle().clone());
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&calls);
+        let listener_spawner: ListenerSpawner =
+            Arc::new(|_, handle: Handle, _| handle.spawn(async {}));
+        let command_factory: CommandFactory = Arc::new(move |_, command| {
+            if let TrayCommand::Activate(name, activation) = command {
+                recorded
+                    .lock()
+                    .expect("calls lock")
+                    .push((name, activation.method_name()));
+            }
+            None
+        });
+
+        let mut module = TrayModule::with_factories(listener_spawner, command_factory);
+        <TrayModule as Module<()>>::register(&mut module, &context, &TrayModuleConfig::default())
+            .expect("registration");
+
+        module.update(TrayMessage::Activate(
+            "nm-applet".into(),
+            ItemActivation::Secondary
+        ));
+        module.update(TrayMessage::Activate(
+            "nm-applet".into(),
+            ItemActivation::ContextMenu
+        ));
+
+        assert_eq!(
+            *calls.lock().expect("calls lock"),
+            [
+                ("nm-applet".to_owned(), "SecondaryActivate"),
+                ("nm-applet".to_owned(), "ContextMenu")
+            ]
+        );
+    }
+
+    #[test]
+    fn scroll_delta_maps_to_dominant_axis() {
+        let mut module = TrayModule::default();
+
+        let up = module.scroll_steps(
+            "tray",
+            ScrollDelta::Lines {
+                x: 0., y: 1.
+            }
+        );
+        assert!(matches!(
+            up,
+            Some(TrayMessage::Scroll(ref name, 1, ScrollAxis::Vertical)) if name == "tray"
+        ));
+        assert_eq!(ScrollAxis::Vertical.as_str(), "vertical");
+
+        let right = module.scroll_steps(
+            "tray",
+            ScrollDelta::Pixels {
+                x: -40., y: 5.
+            }
+        );
+        assert!(matches!(
+            right,
+            Some(TrayMessage::Scroll(_, -2, ScrollAxis::Horizontal))
+        ));
+
+        let tiny = module.scroll_steps(
+            "tray",
+            ScrollDelta::Pixels {
+                x: 0., y: 4.
+            }
+        );
+        assert!(tiny.is_none());
+    }
+
+    #[test]
+    fn small_scrolls_add_up_to_whole_steps_per_item() {
+        let mut module = TrayModule::default();
+        let touchpad = ScrollDelta::Pixels {
+            x: 0., y: 4.
+        };
+
+        let messages: Vec<_> = (0..6)
+            .filter_map(|_| module.scroll_steps("tray", touchpad))
+            .collect();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(
+            messages[0],
+            TrayMessage::Scroll(ref name, 1, ScrollAxis::Vertical) if name == "tray"
+        ));
+
+        // Another item keeps its own remainder.
+        assert!(module.scroll_steps("other", touchpad).is_none());
+        assert!(module.scroll_steps("tray", touchpad).is_none());
+    }
+
+    #[test]
+    fn split_items_moves_extra_items_into_overflow() {
+        let items = [1, 2, 3, 4];
+
+        let (visible, overflow) = split_items(&items, &TrayModuleConfig::default());
+        assert_eq!(visible, [&1, &2, &3, &4]);
+        assert!(overflow.is_empty());
+
+        let compact = TrayModuleConfig {
+            max_visible:   Some(2),
+            order:         TrayOrder::Newest,
+            poll_interval: None,
+            scroll:        true
+        };
+        let (visible, overflow) = split_items(&items, &compact);
+        assert_eq!(visible, [&4, &3]);
+        assert_eq!(overflow, [&2, &1]);
+    }
+
     #[test]
     fn retains_default_listener_spawner() {
         let _module =
@@ -1,8 +1,7 @@
 use std::process::{ExitStatus, Stdio};
 
-use tokio::process;
-
 use super::state::Update;
+use crate::utils::launcher::CommandContext;
 
 /// Errors that can occur while executing an update-related shell command.
 #[derive(Debug)]
@@ -53,10 +52,12 @@ impl CommandError {
     }
 }
 
-pub(super) async fn check_for_updates(command: &str) -> Result<Vec<Update>, CommandError> {
-    let output = process::Command::new("bash")
-        .arg("-c")
-        .arg(command)
+pub(super) async fn check_for_updates(
+    command: &str,
+    context: &CommandContext
+) -> Result<Vec<Update>, CommandError> {
+    let output = context
+        .shell_command(command)
         .stdout(Stdio::piped())
         .output()
         .await?;
@@ -69,10 +70,12 @@ pub(super) async fn check_for_updates(command: &str) -> Result<Vec<Update>, Comm
     Ok(parse_updates(stdout.trim_end_matches('\n')))
 }
 
-pub(super) async fn apply_updates(command: &str) -> Result<(), CommandError> {
-    let output = process::Command::new("bash")
-        .arg("-c")
-        .arg(command)
+pub(super) async fn apply_updates(
+    command: &str,
+    context: &CommandContext
+) -> Result<(), CommandError> {
+    let output = context
+        .shell_command(command)
         .stdout(Stdio::null())
         .stderr(Stdio::null())
         .status()
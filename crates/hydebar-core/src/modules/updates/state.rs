@@ -11,7 +11,8 @@ use crate::{
     event_bus::ModuleEvent,
     menu::MenuType,
     modules::{Module, ModuleError, OnModulePress},
-    outputs::Outputs
+    outputs::Outputs,
+    utils::launcher::CommandContext
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -79,7 +80,8 @@ impl Clone for Updates {
 #[derive(Debug, Clone)]
 struct UpdatesRegistration {
     check_command:  Arc<str>,
-    update_command: Arc<str>
+    update_command: Arc<str>,
+    context:        CommandContext
 }
 
 impl Updates {
@@ -108,13 +110,16 @@ impl Updates {
                 match (
                     self.runtime.clone(),
                     self.sender.clone(),
-                    self.registration
-                        .as_ref()
-                        .map(|registration| Arc::clone(&registration.check_command))
+                    self.registration.clone()
                 ) {
-                    (Some(runtime), Some(sender), Some(check_command)) => {
+                    (Some(runtime), Some(sender), Some(registration)) => {
                         runtime.spawn(async move {
-                            match commands::check_for_updates(check_command.as_ref()).await {
+                            match commands::check_for_updates(
+                                registration.check_command.as_ref(),
+                                &registration.context
+                            )
+                            .await
+                            {
                                 Ok(updates) => {
                                     if let Err(err) =
                                         sender.try_send(Message::UpdatesCheckCompleted(updates))
@@ -148,9 +153,12 @@ impl Updates {
                     self.registration.as_ref()
                 ) {
                     let update_command = Arc::clone(&registration.update_command);
+                    let context = registration.context.clone();
 
                     runtime.spawn(async move {
-                        if let Err(err) = commands::apply_updates(update_command.as_ref()).await {
+                        if let Err(err) =
+                            commands::apply_updates(update_command.as_ref(), &context).await
+                        {
                             err.or_log("failed to execute update command");
                         }
 
@@ -205,17 +213,19 @@ where
 
         self.registration = config.map(|definition| UpdatesRegistration {
             check_command:  Arc::from(definition.check_cmd.as_str()),
-            update_command: Arc::from(definition.update_cmd.as_str())
+            update_command: Arc::from(definition.update_cmd.as_str()),
+            context:        ctx.command_context("Updates")
         });
 
         if let (Some(registration), Some(sender)) =
             (self.registration.as_ref(), self.sender.clone())
         {
             let check_command = Arc::clone(&registration.check_command);
+            let context = registration.context.clone();
 
             let task = ctx.runtime_handle().spawn(async move {
                 loop {
-                    match commands::check_for_updates(check_command.as_ref()).await {
+                    match commands::check_for_updates(check_command.as_ref(), &context).await {
                         Ok(updates) => {
                             if let Err(err) =
                                 sender.try_send(Message::UpdatesCheckCompleted(updates))
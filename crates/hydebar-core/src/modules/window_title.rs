@@ -1,16 +1,20 @@
 use std::{sync::Arc, time::Duration};
 
-use hydebar_proto::ports::hyprland::{HyprlandPort, HyprlandWindowEvent};
-use iced::{Element, widget::text};
+use hydebar_proto::ports::hyprland::{HyprlandPort, HyprlandWindowEvent, HyprlandWindowState};
+use iced::{
+    Alignment, Element,
+    widget::{row, text}
+};
 use log::error;
 use tokio::{task::JoinHandle, time::sleep};
 use tokio_stream::StreamExt;
 
 use crate::{
     ModuleContext, ModuleEventSender,
+    components::icons::{Icons, icon},
     config::{WindowTitleConfig, WindowTitleMode},
     event_bus::ModuleEvent,
-    utils::truncate_text
+    utils::{font, truncate_text}
 };
 
 const WINDOW_EVENT_RETRY_DELAY: Duration = Duration::from_millis(500);
@@ -18,6 +22,10 @@ const WINDOW_EVENT_RETRY_DELAY: Duration = Duration::from_millis(500);
 use super::{Module, ModuleError, OnModulePress};
 
 fn get_window(port: &dyn HyprlandPort, config: &WindowTitleConfig) -> Option<String> {
+    if !port.is_available() {
+        return None;
+    }
+
     match port.active_window() {
         Ok(Some(window)) => Some(match config.mode {
             WindowTitleMode::Title => window.title,
@@ -34,22 +42,30 @@ fn get_window(port: &dyn HyprlandPort, config: &WindowTitleConfig) -> Option<Str
 pub struct WindowTitle {
     hyprland: Arc<dyn HyprlandPort>,
     value:    Option<String>,
+    state:    Option<HyprlandWindowState>,
     sender:   Option<ModuleEventSender<Message>>,
     task:     Option<JoinHandle<()>>
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
-    TitleChanged
+    TitleChanged,
+    StateChanged(Option<HyprlandWindowState>)
 }
 
 impl WindowTitle {
     pub fn new(hyprland: Arc<dyn HyprlandPort>, config: &WindowTitleConfig) -> Self {
         let init = get_window(hyprland.as_ref(), config);
+        let state = hyprland
+            .active_window()
+            .ok()
+            .flatten()
+            .map(|window| window.state);
 
         Self {
             hyprland,
             value: init,
+            state,
             sender: None,
             task: None
         }
@@ -92,6 +108,22 @@ mod tests {
 
         assert_eq!(module.current_value(), None);
     }
+
+    #[test]
+    fn state_changes_follow_focused_window() {
+        let port: Arc<dyn HyprlandPort> = Arc::new(MockHyprlandPort::default());
+        let config = WindowTitleConfig::default();
+
+        let mut module = WindowTitle::new(port, &config);
+        assert_eq!(module.current_state(), Some(HyprlandWindowState::Tiled));
+
+        module.update(
+            Message::StateChanged(Some(HyprlandWindowState::Floating)),
+            &config
+        );
+
+        assert_eq!(module.current_state(), Some(HyprlandWindowState::Floating));
+    }
 }
 
 impl WindowTitle {
@@ -102,8 +134,12 @@ impl WindowTitle {
                     self.value = Some(truncate_text(&value, config.truncate_title_after_length));
                 } else {
                     self.value = None;
+                    self.state = None;
                 }
             }
+            Message::StateChanged(state) => {
+                self.state = state;
+            }
         }
     }
 
@@ -111,13 +147,26 @@ impl WindowTitle {
     pub(crate) fn current_value(&self) -> Option<&str> {
         self.value.as_deref()
     }
+
+    #[cfg(test)]
+    pub(crate) fn current_state(&self) -> Option<HyprlandWindowState> {
+        self.state
+    }
+}
+
+fn state_icon(state: HyprlandWindowState) -> Icons {
+    match state {
+        HyprlandWindowState::Tiled => Icons::WindowTiled,
+        HyprlandWindowState::Floating => Icons::WindowFloating,
+        HyprlandWindowState::Fullscreen => Icons::WindowFullscreen
+    }
 }
 
 impl<M> Module<M> for WindowTitle
 where
     M: 'static + Clone
 {
-    type ViewData<'a> = ();
+    type ViewData<'a> = &'a WindowTitleConfig;
     type RegistrationData<'a> = ();
 
     fn register(
@@ -131,6 +180,10 @@ where
             handle.abort();
         }
 
+        if !self.hyprland.is_available() {
+            return Ok(());
+        }
+
         if let Some(sender) = self.sender.clone() {
             let hyprland = Arc::clone(&self.hyprland);
             self.task = Some(ctx.runtime_handle().spawn(async move {
@@ -139,9 +192,18 @@ where
                         Ok(mut stream) => {
                             while let Some(event) = stream.next().await {
                                 match event {
+                                    Ok(HyprlandWindowEvent::ActiveWindowChanged(state)) => {
+                                        if let Err(err) =
+                                            sender.try_send(Message::StateChanged(state))
+                                        {
+                                            error!("failed to publish window state update: {err}");
+                                        }
+                                        if let Err(err) = sender.try_send(Message::TitleChanged) {
+                                            error!("failed to publish window title update: {err}");
+                                        }
+                                    }
                                     Ok(
-                                        HyprlandWindowEvent::ActiveWindowChanged
-                                        | HyprlandWindowEvent::WindowClosed
+                                        HyprlandWindowEvent::WindowClosed
                                         | HyprlandWindowEvent::WorkspaceFocusChanged
                                     ) => {
                                         if let Err(err) = sender.try_send(Message::TitleChanged) {
@@ -170,16 +232,25 @@ where
 
     fn view(
         &self,
-        _: Self::ViewData<'_>
+        config: Self::ViewData<'_>
     ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)> {
         self.value.as_ref().map(|value| {
-            (
-                text(value.clone())
-                    .size(12)
-                    .wrapping(text::Wrapping::WordOrGlyph)
+            let mut title = text(value.clone())
+                .size(12)
+                .wrapping(text::Wrapping::WordOrGlyph);
+            if let Some(font_name) = &config.font_name {
+                title = title.font(font::named(font_name));
+            }
+
+            let content = match self.state.filter(|_| config.show_state) {
+                Some(state) => row!(icon(state_icon(state)).size(12), title)
+                    .spacing(4)
+                    .align_y(Alignment::Center)
                     .into(),
-                None
-            )
+                None => title.into()
+            };
+
+            (content, None)
         })
     }
 
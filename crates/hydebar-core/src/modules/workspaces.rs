@@ -6,7 +6,8 @@ use hydebar_proto::ports::hyprland::{
 };
 use iced::{
     Element, Length, alignment,
-    widget::{Row, button, container, text},
+    mouse::ScrollDelta,
+    widget::{Row, button, container, mouse_area, text},
     window::Id
 };
 use itertools::Itertools;
@@ -25,26 +26,58 @@ use crate::{
 
 const WORKSPACE_EVENT_RETRY_DELAY: Duration = Duration::from_millis(500);
 
+/// Touchpad scroll distance in pixels that counts as one wheel notch.
+const PIXELS_PER_SCROLL_STEP: f32 = 20.;
+
 #[derive(Debug, Clone)]
 pub struct Workspace {
     pub id:         i32,
     pub name:       String,
+    /// Text shown on the button: the `name_map` entry, else the name.
+    pub label:      String,
     pub monitor_id: Option<usize>, // index for color lookup; may be None
     pub monitor:    String,        // monitor name for fallback
     pub active:     bool,
     pub windows:    u16
 }
 
-fn get_workspaces(port: &dyn HyprlandPort, config: &WorkspacesModuleConfig) -> Vec<Workspace> {
+/// Fetch the workspace list together with the name of the focused monitor.
+fn get_workspaces(
+    port: &dyn HyprlandPort,
+    config: &WorkspacesModuleConfig
+) -> (Vec<Workspace>, Option<String>) {
+    if !port.is_available() {
+        return (Vec::new(), None);
+    }
+
     let snapshot = match port.workspace_snapshot() {
         Ok(snapshot) => snapshot,
         Err(err) => {
             error!("failed to retrieve workspace snapshot: {err}");
-            return Vec::new();
+            return (Vec::new(), None);
         }
     };
 
-    map_snapshot_to_workspaces(&snapshot, config)
+    let active_monitor = snapshot
+        .monitors
+        .iter()
+        .find(|monitor| monitor.focused)
+        .map(|monitor| monitor.name.clone());
+
+    (
+        map_snapshot_to_workspaces(&snapshot, config),
+        active_monitor
+    )
+}
+
+/// Label of the normal workspace `id`, preferring the configured `name_map`
+/// entry over its Hyprland `name` and falling back to the id.
+fn workspace_label(id: i32, name: &str, config: &WorkspacesModuleConfig) -> String {
+    match config.name_map.get(&id.to_string()) {
+        Some(label) => label.clone(),
+        None if name.is_empty() => id.to_string(),
+        None => name.to_owned()
+    }
 }
 
 fn map_snapshot_to_workspaces(
@@ -64,21 +97,24 @@ fn map_snapshot_to_workspaces(
 
     // Map special workspaces.
     for w in special.iter() {
+        let name = w
+            .name
+            .as_str()
+            .split(':')
+            .next_back()
+            .map_or_else(String::new, ToOwned::to_owned);
+
         result.push(Workspace {
-            id:         w.id,
-            name:       w
-                .name
-                .as_str()
-                .split(':')
-                .next_back()
-                .map_or_else(String::new, ToOwned::to_owned),
+            id: w.id,
+            label: name.clone(),
+            name,
             // Option<i128> -> Option<usize> with bounds check.
             monitor_id: w.monitor_id,
-            monitor:    w.monitor_name.clone(),
-            active:     monitors
+            monitor: w.monitor_name.clone(),
+            active: monitors
                 .iter()
                 .any(|m| m.special_workspace_id == Some(w.id)),
-            windows:    w.window_count
+            windows: w.window_count
         });
     }
 
@@ -87,6 +123,7 @@ fn map_snapshot_to_workspaces(
         result.push(Workspace {
             id:         w.id,
             name:       w.name.clone(),
+            label:      workspace_label(w.id, &w.name, config),
             monitor_id: w.monitor_id,
             monitor:    w.monitor_name.clone(),
             active:     Some(w.id) == active,
@@ -118,6 +155,7 @@ fn map_snapshot_to_workspaces(
         result.push(Workspace {
             id,
             name: id.to_string(),
+            label: workspace_label(id, "", config),
             monitor_id: None,
             monitor: String::new(),
             active: false,
@@ -130,42 +168,94 @@ fn map_snapshot_to_workspaces(
 }
 
 pub struct Workspaces {
-    hyprland:   Arc<dyn HyprlandPort>,
-    workspaces: Vec<Workspace>,
-    sender:     Option<ModuleEventSender<Message>>,
-    task:       Option<JoinHandle<()>>
+    hyprland:       Arc<dyn HyprlandPort>,
+    workspaces:     Vec<Workspace>,
+    active_monitor: Option<String>,
+    /// Scroll distance in notches not yet turned into a workspace switch.
+    scroll_offset:  f32,
+    sender:         Option<ModuleEventSender<Message>>,
+    task:           Option<JoinHandle<()>>
 }
 
 impl Workspaces {
     pub fn new(hyprland: Arc<dyn HyprlandPort>, config: &WorkspacesModuleConfig) -> Self {
-        let workspaces = get_workspaces(hyprland.as_ref(), config);
+        let (workspaces, active_monitor) = get_workspaces(hyprland.as_ref(), config);
 
         Self {
             hyprland,
             workspaces,
+            active_monitor,
+            scroll_offset: 0.,
             sender: None,
             task: None
         }
     }
 
+    /// Name of the monitor Hyprland currently reports as focused.
+    ///
+    /// Refreshed whenever [`HyprlandWorkspaceEvent::ActiveMonitorChanged`] or
+    /// any other workspace event triggers a snapshot reload.
+    pub fn active_monitor(&self) -> Option<&str> {
+        self.active_monitor.as_deref()
+    }
+
     #[cfg(test)]
     pub(crate) fn items(&self) -> &[Workspace] {
         &self.workspaces
     }
+
+    /// Switch to the normal workspace `step` places after the active one,
+    /// wrapping around at either end of the list.
+    fn focus_relative(&self, step: isize) {
+        let ids: Vec<i32> = self
+            .workspaces
+            .iter()
+            .filter(|w| w.id > 0)
+            .map(|w| w.id)
+            .collect();
+        let Some(index) = self
+            .workspaces
+            .iter()
+            .filter(|w| w.id > 0)
+            .position(|w| w.active)
+        else {
+            return;
+        };
+
+        let target = ids[(index as isize + step).rem_euclid(ids.len() as isize) as usize];
+        if target == ids[index] {
+            return;
+        }
+
+        debug!("changing workspace to: {target}");
+        if let Err(e) = self
+            .hyprland
+            .change_workspace(HyprlandWorkspaceSelector::Id(target))
+        {
+            error!("failed to dispatch workspace change: {e}");
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     WorkspacesChanged,
     ChangeWorkspace(i32),
-    ToggleSpecialWorkspace(i32)
+    ToggleSpecialWorkspace(i32),
+    /// Switch to the next workspace on the bar.
+    FocusNext,
+    /// Switch to the previous workspace on the bar.
+    FocusPrevious,
+    /// Scrolled over the module; a notch up moves to the previous workspace.
+    Scrolled(ScrollDelta)
 }
 
 impl Workspaces {
     pub fn update(&mut self, message: Message, config: &WorkspacesModuleConfig) {
         match message {
             Message::WorkspacesChanged => {
-                self.workspaces = get_workspaces(self.hyprland.as_ref(), config);
+                (self.workspaces, self.active_monitor) =
+                    get_workspaces(self.hyprland.as_ref(), config);
             }
             Message::ChangeWorkspace(id) => {
                 if id > 0 {
@@ -201,6 +291,28 @@ impl Workspaces {
                     }
                 }
             }
+            Message::FocusNext => self.focus_relative(1),
+            Message::FocusPrevious => self.focus_relative(-1),
+            Message::Scrolled(delta) => {
+                self.scroll_offset += match delta {
+                    ScrollDelta::Lines {
+                        y, ..
+                    } => y,
+                    ScrollDelta::Pixels {
+                        y, ..
+                    } => y / PIXELS_PER_SCROLL_STEP
+                };
+
+                // At most one switch per event, so a fast touchpad swipe does
+                // not race across every workspace.
+                if self.scroll_offset >= 1. {
+                    self.scroll_offset = 0.;
+                    self.update(Message::FocusPrevious, config);
+                } else if self.scroll_offset <= -1. {
+                    self.scroll_offset = 0.;
+                    self.update(Message::FocusNext, config);
+                }
+            }
         }
     }
 }
@@ -223,7 +335,7 @@ where
         ctx: &ModuleContext,
         config: Self::RegistrationData<'_>
     ) -> Result<(), ModuleError> {
-        self.workspaces = get_workspaces(self.hyprland.as_ref(), config);
+        (self.workspaces, self.active_monitor) = get_workspaces(self.hyprland.as_ref(), config);
 
         self.sender = Some(ctx.module_sender(ModuleEvent::Workspaces));
 
@@ -231,6 +343,10 @@ where
             handle.abort();
         }
 
+        if !self.hyprland.is_available() {
+            return Ok(());
+        }
+
         if let Some(sender) = self.sender.clone() {
             let hyprland = Arc::clone(&self.hyprland);
             self.task = Some(ctx.runtime_handle().spawn(async move {
@@ -287,77 +403,89 @@ where
         &self,
         (outputs, id, config, workspace_colors, special_workspace_colors): Self::ViewData<'_>
     ) -> Option<(Element<'static, M>, Option<OnModulePress<M>>)> {
+        if !self.hyprland.is_available() {
+            return None;
+        }
+
         let monitor_name = outputs.get_monitor_name(id).map(|s| s.to_string());
 
-        Some((
-            Row::with_children(
-                self.workspaces
-                    .iter()
-                    .filter_map(|w| {
-                        if config.visibility_mode == WorkspaceVisibilityMode::All
-                            || w.monitor == monitor_name.as_deref().unwrap_or(&w.monitor)
-                            || !outputs.has_name(&w.monitor)
-                        {
-                            let empty = w.windows == 0;
-                            let monitor = w.monitor_id;
-
-                            // Safe color lookup by monitor index; None means "no color".
-                            let color = monitor.map(|m| {
-                                if w.id > 0 {
-                                    workspace_colors.get(m).copied()
-                                } else {
-                                    special_workspace_colors
-                                        .unwrap_or(workspace_colors)
-                                        .get(m)
-                                        .copied()
-                                }
-                            });
+        let workspaces = Row::with_children(
+            self.workspaces
+                .iter()
+                .filter_map(|w| {
+                    if config.visibility_mode == WorkspaceVisibilityMode::All
+                        || w.monitor == monitor_name.as_deref().unwrap_or(&w.monitor)
+                        || !outputs.has_name(&w.monitor)
+                    {
+                        let empty = w.windows == 0;
+                        let monitor = w.monitor_id;
+
+                        // Safe color lookup by monitor index; None means "no color".
+                        let color = monitor.map(|m| {
+                            if w.id > 0 {
+                                workspace_colors.get(m).copied()
+                            } else {
+                                special_workspace_colors
+                                    .unwrap_or(workspace_colors)
+                                    .get(m)
+                                    .copied()
+                            }
+                        });
 
-                            let w_id = w.id;
-                            let w_name = w.name.clone();
-                            let w_active = w.active;
+                        let w_id = w.id;
+                        let w_active = w.active;
+                        // Ids and glyphs fit the fixed-width pill; special
+                        // workspaces and longer names grow with their text.
+                        let wide = w_id < 0 || w.label.chars().count() > 2;
 
-                            Some(
-                                button(
-                                    container(
-                                        if w_id < 0 { text(w_name) } else { text(w_id) }.size(10)
-                                    )
+                        Some(
+                            button(
+                                container(text(w.label.clone()).size(10))
                                     .align_x(alignment::Horizontal::Center)
                                     .align_y(alignment::Vertical::Center)
-                                )
-                                .style(workspace_button_style(empty, color))
-                                .padding(if w_id < 0 {
-                                    if w_active { [0, 16] } else { [0, 8] }
-                                } else {
-                                    [0, 0]
-                                })
-                                .on_press(if w_id > 0 {
-                                    Message::ChangeWorkspace(w_id)
-                                } else {
-                                    Message::ToggleSpecialWorkspace(w_id)
-                                })
-                                .width(if w_id < 0 {
-                                    Length::Shrink
-                                } else if w_active {
-                                    Length::Fixed(32.)
-                                } else {
-                                    Length::Fixed(16.)
-                                })
-                                .height(16)
-                                .into()
                             )
-                        } else {
-                            None
-                        }
-                    })
-                    .map(|elem: Element<'_, Message>| elem.map(M::from))
-                    .collect::<Vec<Element<'_, M, _, _>>>()
-            )
-            .padding([2, 0])
-            .spacing(4)
-            .into(),
-            None
-        ))
+                            .style(workspace_button_style(empty, color))
+                            .padding(if wide {
+                                if w_active { [0, 16] } else { [0, 8] }
+                            } else {
+                                [0, 0]
+                            })
+                            .on_press(if w_id > 0 {
+                                Message::ChangeWorkspace(w_id)
+                            } else {
+                                Message::ToggleSpecialWorkspace(w_id)
+                            })
+                            .width(if wide {
+                                Length::Shrink
+                            } else if w_active {
+                                Length::Fixed(32.)
+                            } else {
+                                Length::Fixed(16.)
+                            })
+                            .height(16)
+                            .into()
+                        )
+                    } else {
+                        None
+                    }
+                })
+                .map(|elem: Element<'_, Message>| elem.map(M::from))
+                .collect::<Vec<Element<'_, M, _, _>>>()
+        )
+        .padding([2, 0])
+        .spacing(4);
+
+        // The mouse area captures the scroll, so it never reaches the bar or
+        // neighbouring modules.
+        let content = if config.enable_scroll {
+            mouse_area(workspaces)
+                .on_scroll(|delta| M::from(Message::Scrolled(delta)))
+                .into()
+        } else {
+            workspaces.into()
+        };
+
+        Some((content, None))
     }
 
     // Background updates are delivered via the shared module event sender.
@@ -381,6 +509,81 @@ mod tests {
         assert!(!module.items().is_empty());
     }
 
+    #[test]
+    fn tracks_focused_monitor_from_snapshot() {
+        let port = Arc::new(MockHyprlandPort::default());
+        let port_trait: Arc<dyn HyprlandPort> = port.clone();
+        let config = WorkspacesModuleConfig::default();
+
+        let mut module = Workspaces::new(port_trait, &config);
+        assert_eq!(module.active_monitor(), Some("MockMonitor"));
+
+        port.workspace_snapshot
+            .lock()
+            .expect("poisoned workspace snapshot lock")
+            .monitors[0]
+            .focused = false;
+        module.update(Message::WorkspacesChanged, &config);
+
+        assert_eq!(module.active_monitor(), None);
+    }
+
+    #[test]
+    fn named_workspace_shows_its_name_unless_mapped() {
+        let port = Arc::new(MockHyprlandPort::default());
+        port.workspace_snapshot
+            .lock()
+            .expect("poisoned workspace snapshot lock")
+            .workspaces[0]
+            .name = "web".into();
+        let port_trait: Arc<dyn HyprlandPort> = port.clone();
+        let mut config = WorkspacesModuleConfig {
+            enable_workspace_filling: true,
+            max_workspaces: Some(2),
+            ..WorkspacesModuleConfig::default()
+        };
+
+        let mut module = Workspaces::new(port_trait, &config);
+        let labels: Vec<_> = module.items().iter().map(|w| w.label.as_str()).collect();
+        assert_eq!(labels, ["web", "2"]);
+
+        config.name_map.insert("1".into(), "\u{f0239}".into());
+        module.update(Message::WorkspacesChanged, &config);
+        assert_eq!(module.items()[0].label, "\u{f0239}");
+        assert_eq!(module.items()[0].name, "web");
+    }
+
+    #[test]
+    fn scrolling_up_switches_to_previous_workspace_once() {
+        let port = Arc::new(MockHyprlandPort::default());
+        {
+            let mut snapshot = port
+                .workspace_snapshot
+                .lock()
+                .expect("poisoned workspace snapshot lock");
+            let mut second = snapshot.workspaces[0].clone();
+            second.id = 2;
+            second.name = "2".into();
+            snapshot.workspaces.push(second);
+            snapshot.active_workspace_id = Some(2);
+        }
+        let port_trait: Arc<dyn HyprlandPort> = port.clone();
+        let config = WorkspacesModuleConfig {
+            enable_scroll: true,
+            ..WorkspacesModuleConfig::default()
+        };
+
+        let mut module = Workspaces::new(port_trait, &config);
+        module.update(
+            Message::Scrolled(ScrollDelta::Lines {
+                x: 0., y: 1.
+            }),
+            &config
+        );
+
+        assert_eq!(port.workspace_calls(), 1);
+    }
+
     #[test]
     fn change_workspace_dispatches_via_port() {
         let port = Arc::new(MockHyprlandPort::default());
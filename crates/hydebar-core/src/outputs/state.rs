@@ -1,7 +1,7 @@
 use iced::{
-    Task,
+    Rectangle, Task,
     platform_specific::shell::commands::layer_surface::{
-        Anchor, set_anchor, set_exclusive_zone, set_size
+        destroy_layer_surface, set_anchor, set_exclusive_zone, set_layer, set_size
     },
     window::Id
 };
@@ -10,29 +10,44 @@ use wayland_client::protocol::wl_output::WlOutput;
 
 use super::{
     config::is_output_requested,
-    wayland::{LayerSurfaceCreation, create_layer_surfaces, destroy_layer_surfaces, layer_height}
+    wayland::{
+        BackdropChange, LayerSurfaceCreation, backdrop_change, bar_anchor, bar_positions,
+        bar_size, create_backdrop_surface, create_layer_surfaces, destroy_layer_surfaces,
+        exclusive_zone, layer_height, set_input_region, shell_layer
+    }
 };
 use crate::{
-    config::{self, AppearanceStyle, Position},
+    config::{self, AppearanceStyle, BackdropScope, BarLayer, Position},
     menu::{Menu, MenuType},
     position_button::ButtonUIRef
 };
 
 #[derive(Debug, Clone)]
 struct ShellInfo {
-    id:           Id,
-    position:     Position,
-    style:        AppearanceStyle,
-    menu:         Menu,
-    scale_factor: f64
+    id:              Id,
+    /// Index of the bar instance, `0` being the primary bar.
+    bar:             usize,
+    position:        Position,
+    style:           AppearanceStyle,
+    menu:            Menu,
+    scale_factor:    f64,
+    reserved_height: Option<u32>,
+    layer:           BarLayer,
+    /// Screen-dimming surface shown behind the open menu.
+    backdrop:        Option<Id>
 }
 
+type OutputEntry = (Option<String>, Option<ShellInfo>, Option<WlOutput>);
+
 /// Collection of Wayland outputs currently tracked by the bar.
 ///
 /// Instances manage Wayland layer-surfaces for both the main bar surface and
-/// the associated menu surface per monitor. All operations return [`Task`]
-/// objects that must be executed by the caller to coordinate with the
-/// compositor.
+/// the associated menu surface per monitor. Every configured bar instance
+/// (the primary bar plus [`Config::auxiliary_bars`]) gets its own entry, so a
+/// monitor is tracked once per bar. All operations return [`Task`] objects
+/// that must be executed by the caller to coordinate with the compositor.
+///
+/// [`Config::auxiliary_bars`]: crate::config::Config::auxiliary_bars
 ///
 /// # Examples
 ///
@@ -44,7 +59,7 @@ struct ShellInfo {
 /// assert!(!outputs.menu_is_open());
 /// ```
 #[derive(Debug, Clone)]
-pub struct Outputs(Vec<(Option<String>, Option<ShellInfo>, Option<WlOutput>)>);
+pub struct Outputs(Vec<OutputEntry>);
 
 /// Result of looking up a Wayland surface identifier.
 ///
@@ -52,11 +67,15 @@ pub struct Outputs(Vec<(Option<String>, Option<ShellInfo>, Option<WlOutput>)>);
 /// so that event handlers can update the appropriate component.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HasOutput<'a> {
-    /// The identifier refers to the main bar surface.
-    Main,
+    /// The identifier refers to a bar surface, carrying the index of the bar
+    /// instance (`0` for the primary bar).
+    Main(usize),
     /// The identifier refers to the menu surface along with its optional
     /// metadata about the menu currently shown.
-    Menu(Option<&'a (MenuType, ButtonUIRef)>)
+    Menu(Option<&'a (MenuType, ButtonUIRef)>),
+    /// The identifier refers to the surface dimming the screen behind an
+    /// open menu.
+    Backdrop
 }
 
 impl Outputs {
@@ -82,30 +101,15 @@ impl Outputs {
         position: Position,
         config: &crate::config::Config
     ) -> (Self, Task<Message>) {
-        let LayerSurfaceCreation {
-            main_id,
-            menu_id,
-            task
-        } = create_layer_surfaces(
-            style,
-            None,
-            position,
-            config.menu_keyboard_focus,
-            config.appearance.scale_factor
-        );
+        let (shells, task) = create_shells(style, None, None, position, config);
 
         (
-            Self(vec![(
-                None,
-                Some(ShellInfo {
-                    id: main_id,
-                    menu: Menu::new(menu_id),
-                    position,
-                    style,
-                    scale_factor: config.appearance.scale_factor
-                }),
-                None
-            )]),
+            Self(
+                shells
+                    .into_iter()
+                    .map(|shell_info| (None, Some(shell_info), None))
+                    .collect()
+            ),
             task
         )
     }
@@ -129,9 +133,11 @@ impl Outputs {
         self.0.iter().find_map(|(_, info, _)| {
             if let Some(info) = info {
                 if info.id == id {
-                    Some(HasOutput::Main)
+                    Some(HasOutput::Main(info.bar))
                 } else if info.menu.id == id {
                     Some(HasOutput::Menu(info.menu.menu_info.as_ref()))
+                } else if info.backdrop == Some(id) {
+                    Some(HasOutput::Backdrop)
                 } else {
                     None
                 }
@@ -141,7 +147,8 @@ impl Outputs {
         })
     }
 
-    /// Retrieve the monitor name associated with a given surface identifier.
+    /// Retrieve the monitor name associated with a given bar or menu surface
+    /// identifier.
     ///
     /// Returns [`None`] when the identifier does not belong to a tracked output
     /// or the output has no reported name yet.
@@ -159,7 +166,11 @@ impl Outputs {
     pub fn get_monitor_name(&self, id: Id) -> Option<&str> {
         self.0.iter().find_map(|(name, info, _)| {
             if let Some(info) = info {
-                if info.id == id { name.as_deref() } else { None }
+                if info.id == id || info.menu.id == id {
+                    name.as_deref()
+                } else {
+                    None
+                }
             } else {
                 None
             }
@@ -212,61 +223,22 @@ impl Outputs {
         if target {
             debug!("Found target output, creating a new layer surface");
 
-            let LayerSurfaceCreation {
-                main_id,
-                menu_id,
-                task
-            } = create_layer_surfaces(
-                style,
-                Some(wl_output.clone()),
-                position,
-                config.menu_keyboard_focus,
-                config.appearance.scale_factor
-            );
+            let (shells, task) =
+                create_shells(style, Some(name), Some(wl_output.clone()), position, config);
 
-            let destroy_task = match self
-                .0
-                .iter()
-                .position(|(key, _, _)| key.as_deref() == Some(name))
-            {
-                Some(index) => {
-                    let old_output = self.0.swap_remove(index);
+            let destroy_task =
+                destroy_entries(&self.take_entries(|(key, _, _)| key.as_deref() == Some(name)));
 
-                    match old_output.1 {
-                        Some(shell_info) => {
-                            destroy_layer_surfaces(shell_info.id, shell_info.menu.id)
-                        }
-                        _ => Task::none()
-                    }
-                }
-                _ => Task::none()
-            };
-
-            self.0.push((
-                Some(name.to_owned()),
-                Some(ShellInfo {
-                    id: main_id,
-                    menu: Menu::new(menu_id),
-                    position,
-                    style,
-                    scale_factor: config.appearance.scale_factor
-                }),
-                Some(wl_output)
-            ));
-
-            let destroy_fallback_task = match self.0.iter().position(|(key, _, _)| key.is_none()) {
-                Some(index) => {
-                    let old_output = self.0.swap_remove(index);
-
-                    match old_output.1 {
-                        Some(shell_info) => {
-                            destroy_layer_surfaces(shell_info.id, shell_info.menu.id)
-                        }
-                        _ => Task::none()
-                    }
-                }
-                _ => Task::none()
-            };
+            self.0.extend(shells.into_iter().map(|shell_info| {
+                (
+                    Some(name.to_owned()),
+                    Some(shell_info),
+                    Some(wl_output.clone())
+                )
+            }));
+
+            let destroy_fallback_task =
+                destroy_entries(&self.take_entries(|(key, _, _)| key.is_none()));
 
             Task::batch(vec![destroy_task, destroy_fallback_task, task])
         } else {
@@ -294,58 +266,37 @@ impl Outputs {
         wl_output: WlOutput,
         config: &crate::config::Config
     ) -> Task<Message> {
-        match self.0.iter().position(|(_, _, assigned_wl_output)| {
+        let removed = self.take_entries(|(_, _, assigned_wl_output)| {
             assigned_wl_output
                 .as_ref()
                 .map(|assigned_wl_output| *assigned_wl_output == wl_output)
                 .unwrap_or_default()
-        }) {
-            Some(index_to_remove) => {
-                debug!("Removing layer surface for output");
+        });
 
-                let (name, shell_info, wl_output) = self.0.swap_remove(index_to_remove);
+        let Some((name, _, _)) = removed.first() else {
+            return Task::none();
+        };
 
-                let destroy_task = if let Some(shell_info) = shell_info {
-                    destroy_layer_surfaces(shell_info.id, shell_info.menu.id)
-                } else {
-                    Task::none()
-                };
+        debug!("Removing layer surfaces for output");
 
-                self.0.push((name.to_owned(), None, wl_output));
+        let destroy_task = destroy_entries(&removed);
 
-                if !self.0.iter().any(|(_, shell_info, _)| shell_info.is_some()) {
-                    debug!("No outputs left, creating a fallback layer surface");
+        self.0.push((name.clone(), None, Some(wl_output)));
 
-                    let LayerSurfaceCreation {
-                        main_id,
-                        menu_id,
-                        task
-                    } = create_layer_surfaces(
-                        style,
-                        None,
-                        position,
-                        config.menu_keyboard_focus,
-                        config.appearance.scale_factor
-                    );
+        if !self.0.iter().any(|(_, shell_info, _)| shell_info.is_some()) {
+            debug!("No outputs left, creating a fallback layer surface");
 
-                    self.0.push((
-                        None,
-                        Some(ShellInfo {
-                            id: main_id,
-                            menu: Menu::new(menu_id),
-                            position,
-                            style,
-                            scale_factor: config.appearance.scale_factor
-                        }),
-                        None
-                    ));
+            let (shells, task) = create_shells(style, None, None, position, config);
 
-                    Task::batch(vec![destroy_task, task])
-                } else {
-                    Task::batch(vec![destroy_task])
-                }
-            }
-            _ => Task::none()
+            self.0.extend(
+                shells
+                    .into_iter()
+                    .map(|shell_info| (None, Some(shell_info), None))
+            );
+
+            Task::batch(vec![destroy_task, task])
+        } else {
+            Task::batch(vec![destroy_task])
         }
     }
 
@@ -391,7 +342,12 @@ impl Outputs {
                 }
             })
             .flatten()
-            .collect::<Vec<_>>();
+            .fold(Vec::new(), |mut acc, wl_output| {
+                if !acc.contains(&wl_output) {
+                    acc.push(wl_output);
+                }
+                acc
+            });
         debug!("Removing outputs: {to_remove:?}");
 
         let to_add = self
@@ -428,56 +384,159 @@ impl Outputs {
             tasks.push(self.remove(style, position, wl_output, config));
         }
 
+        let bar_count = bar_positions(position, &config.auxiliary_bars).len();
+        let mut outdated: Vec<(Option<String>, Option<WlOutput>)> = Vec::new();
+        for (name, _, wl_output) in self
+            .0
+            .iter()
+            .filter(|(_, shell_info, _)| shell_info.is_some())
+        {
+            let shells = self
+                .0
+                .iter()
+                .filter(|(other, shell_info, _)| other == name && shell_info.is_some())
+                .count();
+            if shells != bar_count && !outdated.iter().any(|(other, _)| other == name) {
+                outdated.push((name.clone(), wl_output.clone()));
+            }
+        }
+        debug!("Recreating outputs with a changed bar count: {outdated:?}");
+
+        for (name, wl_output) in outdated {
+            match (name, wl_output) {
+                (Some(name), Some(wl_output)) => {
+                    tasks.push(self.add(
+                        style,
+                        request_outputs,
+                        position,
+                        name.as_str(),
+                        wl_output,
+                        config
+                    ));
+                }
+                (None, _) => {
+                    let destroy_task =
+                        destroy_entries(&self.take_entries(|(key, _, _)| key.is_none()));
+                    let (shells, task) = create_shells(style, None, None, position, config);
+                    self.0.extend(
+                        shells
+                            .into_iter()
+                            .map(|shell_info| (None, Some(shell_info), None))
+                    );
+                    tasks.push(Task::batch(vec![destroy_task, task]));
+                }
+                _ => {}
+            }
+        }
+
         for shell_info in self.0.iter_mut().filter_map(|(_, shell_info, _)| {
             if let Some(shell_info) = shell_info
-                && shell_info.position != position
+                && shell_info.position != bar_position(position, config, shell_info.bar)
             {
                 Some(shell_info)
             } else {
                 None
             }
         }) {
+            let position = bar_position(position, config, shell_info.bar);
             debug!(
                 "Repositioning output: {:?}, new position {:?}",
                 shell_info.id, position
             );
+            // Switching between a horizontal and a vertical bar swaps which
+            // dimension is fixed.
+            let (width, height) = bar_size(
+                position,
+                layer_height(shell_info.style, shell_info.scale_factor)
+            );
             shell_info.position = position;
-            tasks.push(set_anchor(
-                shell_info.id,
-                match position {
-                    Position::Top => Anchor::TOP,
-                    Position::Bottom => Anchor::BOTTOM
-                } | Anchor::LEFT
-                    | Anchor::RIGHT
-            ));
+            tasks.push(Task::batch(vec![
+                set_anchor(shell_info.id, bar_anchor(position)),
+                set_size(shell_info.id, width, height),
+            ]));
         }
 
-        for shell_info in self.0.iter_mut().filter_map(|(_, shell_info, _)| {
+        for (shell_info, scale_factor) in self.0.iter_mut().filter_map(|(name, shell_info, _)| {
+            let scale_factor = config.scale_factor_for(name.as_deref());
+
             if let Some(shell_info) = shell_info
                 && (shell_info.style != style
-                    || shell_info.scale_factor != config.appearance.scale_factor)
+                    || shell_info.scale_factor != scale_factor
+                    || shell_info.reserved_height != config.reserved_height)
             {
-                Some(shell_info)
+                Some((shell_info, scale_factor))
             } else {
                 None
             }
         }) {
             debug!(
-                "Change style or scale_factor for output: {:?}, new style {:?}, new scale_factor {:?}",
-                shell_info.id, style, config.appearance.scale_factor
+                "Change style, scale_factor or reserved_height for output: {:?}, new style {:?}, new scale_factor {:?}, new reserved_height {:?}",
+                shell_info.id, style, scale_factor, config.reserved_height
             );
             shell_info.style = style;
-            shell_info.scale_factor = config.appearance.scale_factor;
-            let height = layer_height(style, config.appearance.scale_factor);
+            shell_info.scale_factor = scale_factor;
+            shell_info.reserved_height = config.reserved_height;
+            let height = layer_height(style, scale_factor);
+            let (width, size_height) = bar_size(shell_info.position, height);
             tasks.push(Task::batch(vec![
-                set_size(shell_info.id, None, Some(height as u32)),
-                set_exclusive_zone(shell_info.id, height as i32),
+                set_size(shell_info.id, width, size_height),
+                set_exclusive_zone(
+                    shell_info.id,
+                    exclusive_zone(height, config.reserved_height)
+                ),
             ]));
         }
 
+        for shell_info in self.0.iter_mut().filter_map(|(_, shell_info, _)| {
+            shell_info
+                .as_mut()
+                .filter(|shell_info| shell_info.layer != config.layer)
+        }) {
+            debug!(
+                "Moving output {:?} to layer {:?}",
+                shell_info.id, config.layer
+            );
+            shell_info.layer = config.layer;
+            tasks.push(set_layer(shell_info.id, shell_layer(config.layer)));
+        }
+
+        tasks.push(self.sync_backdrops(config));
+
         Task::batch(tasks)
     }
 
+    /// Resolve the placement of the bar owning the provided bar or menu
+    /// surface.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use hydebar_core::outputs::Outputs;
+    /// # use hydebar_core::config::Config;
+    /// # use iced::window::Id;
+    /// let config = Config::default();
+    /// let (outputs, _task) = Outputs::new::<()>(config.appearance.style, config.position, &config);
+    /// assert!(outputs.position(Id::unique()).is_none());
+    /// ```
+    pub fn position(&self, id: Id) -> Option<Position> {
+        self.0.iter().find_map(|(_, shell_info, _)| {
+            shell_info
+                .as_ref()
+                .filter(|shell_info| shell_info.id == id || shell_info.menu.id == id)
+                .map(|shell_info| shell_info.position)
+        })
+    }
+
+    /// Scale factor of the bar or menu surface `id`.
+    pub fn scale_factor(&self, id: Id) -> Option<f64> {
+        self.0.iter().find_map(|(_, shell_info, _)| {
+            shell_info
+                .as_ref()
+                .filter(|shell_info| shell_info.id == id || shell_info.menu.id == id)
+                .map(|shell_info| shell_info.scale_factor)
+        })
+    }
+
     /// Determine whether any tracked menu surface is currently visible.
     ///
     /// # Examples
@@ -498,6 +557,26 @@ impl Outputs {
         })
     }
 
+    /// Whether a menu of the given type is open on any output.
+    pub fn menu_type_is_open(&self, menu_type: &MenuType) -> bool {
+        self.0.iter().any(|(_, shell_info, _)| {
+            shell_info
+                .as_ref()
+                .and_then(|shell_info| shell_info.menu.menu_info.as_ref())
+                .is_some_and(|(open_type, _)| open_type == menu_type)
+        })
+    }
+
+    /// Open menus together with the name of the output showing them.
+    pub fn open_menus(&self) -> impl Iterator<Item = (Option<&str>, &MenuType)> {
+        self.0.iter().filter_map(|(name, shell_info, _)| {
+            shell_info
+                .as_ref()
+                .and_then(|shell_info| shell_info.menu.menu_info.as_ref())
+                .map(|(menu_type, _)| (name.as_deref(), menu_type))
+        })
+    }
+
     /// Get the animated opacity for a menu window.
     pub fn get_menu_opacity(&self, id: Id) -> f32 {
         self.0
@@ -514,6 +593,18 @@ impl Outputs {
             .unwrap_or(0.0)
     }
 
+    /// Complete every running menu fade at once, used while animations are
+    /// paused.
+    pub fn finish_menu_animations(&mut self) {
+        for shell_info in self
+            .0
+            .iter_mut()
+            .filter_map(|(_, shell_info, _)| shell_info.as_mut())
+        {
+            shell_info.menu.finish_animation();
+        }
+    }
+
     /// Update menu animations. Returns true if any menu is currently animating.
     pub fn tick_menu_animations(
         &mut self,
@@ -567,6 +658,7 @@ impl Outputs {
                     })
                     .collect::<Vec<_>>();
                 tasks.push(toggle_task);
+                tasks.push(self.sync_backdrops(config));
                 Task::batch(tasks)
             }
             _ => Task::none()
@@ -589,7 +681,10 @@ impl Outputs {
             shell_info.as_ref().map(|shell_info| shell_info.id) == Some(id)
                 || shell_info.as_ref().map(|shell_info| shell_info.menu.id) == Some(id)
         }) {
-            Some((_, Some(shell_info), _)) => shell_info.menu.close(config),
+            Some((_, Some(shell_info), _)) => {
+                let task = shell_info.menu.close(config);
+                Task::batch(vec![task, self.sync_backdrops(config)])
+            }
             _ => Task::none()
         }
     }
@@ -611,7 +706,10 @@ impl Outputs {
             shell_info.as_ref().map(|shell_info| shell_info.id) == Some(id)
                 || shell_info.as_ref().map(|shell_info| shell_info.menu.id) == Some(id)
         }) {
-            Some((_, Some(shell_info), _)) => shell_info.menu.close_if(menu_type, config),
+            Some((_, Some(shell_info), _)) => {
+                let task = shell_info.menu.close_if(menu_type, config);
+                Task::batch(vec![task, self.sync_backdrops(config)])
+            }
             _ => Task::none()
         }
     }
@@ -628,18 +726,20 @@ impl Outputs {
         menu_type: MenuType,
         config: &crate::config::Config
     ) -> Task<Message> {
-        Task::batch(
-            self.0
-                .iter_mut()
-                .map(|(_, shell_info, _)| {
-                    if let Some(shell_info) = shell_info {
-                        shell_info.menu.close_if(menu_type.clone(), config)
-                    } else {
-                        Task::none()
-                    }
-                })
-                .collect::<Vec<_>>()
-        )
+        let mut tasks = self
+            .0
+            .iter_mut()
+            .map(|(_, shell_info, _)| {
+                if let Some(shell_info) = shell_info {
+                    shell_info.menu.close_if(menu_type.clone(), config)
+                } else {
+                    Task::none()
+                }
+            })
+            .collect::<Vec<_>>();
+        tasks.push(self.sync_backdrops(config));
+
+        Task::batch(tasks)
     }
 
     /// Close every open menu regardless of its type.
@@ -658,22 +758,24 @@ impl Outputs {
         &mut self,
         config: &crate::config::Config
     ) -> Task<Message> {
-        Task::batch(
-            self.0
-                .iter_mut()
-                .map(|(_, shell_info, _)| {
-                    if let Some(shell_info) = shell_info {
-                        if shell_info.menu.menu_info.is_some() {
-                            shell_info.menu.close(config)
-                        } else {
-                            Task::none()
-                        }
+        let mut tasks = self
+            .0
+            .iter_mut()
+            .map(|(_, shell_info, _)| {
+                if let Some(shell_info) = shell_info {
+                    if shell_info.menu.menu_info.is_some() {
+                        shell_info.menu.close(config)
                     } else {
                         Task::none()
                     }
-                })
-                .collect::<Vec<_>>()
-        )
+                } else {
+                    Task::none()
+                }
+            })
+            .collect::<Vec<_>>();
+        tasks.push(self.sync_backdrops(config));
+
+        Task::batch(tasks)
     }
 
     /// Request keyboard focus for the menu associated with the identifier.
@@ -722,21 +824,185 @@ impl Outputs {
         }
     }
 
-    /// Returns the first main window Id if any outputs exist.
-    pub fn first_main_window_id(&self) -> Option<Id> {
-        self.0
+    /// Limit pointer input of the bar surface `id` to the provided module
+    /// bounds, given in surface-local logical coordinates.
+    ///
+    /// Identifiers that do not belong to a bar surface yield an empty
+    /// [`Task`].
+    pub fn set_input_region<Message: 'static>(
+        &self,
+        id: Id,
+        region: Vec<Rectangle>
+    ) -> Task<Message> {
+        if self
+            .0
             .iter()
-            .find_map(|(_, shell_info, _)| shell_info.as_ref().map(|s| s.id))
+            .any(|(_, shell_info, _)| shell_info.as_ref().is_some_and(|s| s.id == id))
+        {
+            set_input_region(id, Some(region))
+        } else {
+            Task::none()
+        }
+    }
+
+    /// Restore whole-surface pointer input on every bar surface.
+    pub fn reset_input_regions<Message: 'static>(&self) -> Task<Message> {
+        Task::batch(
+            self.0
+                .iter()
+                .filter_map(|(_, shell_info, _)| shell_info.as_ref())
+                .map(|shell_info| set_input_region(shell_info.id, None))
+                .collect::<Vec<_>>()
+        )
+    }
+
+    /// Destroy every bar, menu and backdrop surface, leaving no outputs
+    /// tracked. Used before the bar exits.
+    pub fn destroy_all<Message: 'static>(&mut self) -> Task<Message> {
+        let entries = std::mem::take(&mut self.0);
+
+        destroy_entries(&entries)
+    }
+
+    /// Returns the first primary bar window Id if any outputs exist.
+    pub fn first_main_window_id(&self) -> Option<Id> {
+        self.0.iter().find_map(|(_, shell_info, _)| {
+            shell_info.as_ref().filter(|s| s.bar == 0).map(|s| s.id)
+        })
+    }
+
+    /// Primary bar window on the output called `name`, falling back to the
+    /// first one when `name` is unknown or has no bar.
+    pub fn main_window_id_on(&self, name: Option<&str>) -> Option<Id> {
+        name.and_then(|name| {
+            self.0.iter().find_map(|(output, shell_info, _)| {
+                shell_info
+                    .as_ref()
+                    .filter(|s| s.bar == 0 && output.as_deref() == Some(name))
+                    .map(|s| s.id)
+            })
+        })
+        .or_else(|| self.first_main_window_id())
+    }
+
+    /// Create or destroy the screen-dimming surfaces so that one exists
+    /// behind every open menu while `backdrop_scope` is `Screen`.
+    fn sync_backdrops<Message: 'static>(
+        &mut self,
+        config: &crate::config::Config
+    ) -> Task<Message> {
+        let dim_screen = config.appearance.menu.backdrop_scope == BackdropScope::Screen;
+
+        Task::batch(
+            self.0
+                .iter_mut()
+                .filter_map(|(_, shell_info, wl_output)| {
+                    let shell_info = shell_info.as_mut()?;
+                    let menu_open = shell_info.menu.menu_info.is_some();
+
+                    match backdrop_change(dim_screen, menu_open, shell_info.backdrop)? {
+                        BackdropChange::Create => {
+                            let (id, task) = create_backdrop_surface(wl_output.clone());
+                            shell_info.backdrop = Some(id);
+                            Some(task)
+                        }
+                        BackdropChange::Destroy(id) => {
+                            shell_info.backdrop = None;
+                            Some(destroy_layer_surface(id))
+                        }
+                    }
+                })
+                .collect::<Vec<_>>()
+        )
+    }
+
+    /// Remove and return every entry matching the predicate, preserving the
+    /// order of the remaining entries.
+    fn take_entries(&mut self, predicate: impl Fn(&OutputEntry) -> bool) -> Vec<OutputEntry> {
+        let (taken, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.0)
+            .into_iter()
+            .partition(|entry| predicate(entry));
+        self.0 = kept;
+        taken
     }
 
     #[cfg(test)]
-    fn iter_internal(
-        &self
-    ) -> impl Iterator<Item = &(Option<String>, Option<ShellInfo>, Option<WlOutput>)> {
+    fn iter_internal(&self) -> impl Iterator<Item = &OutputEntry> {
         self.0.iter()
     }
 }
 
+/// Placement of the bar instance at `bar`, where `position` is the placement
+/// of the primary bar.
+fn bar_position(position: Position, config: &crate::config::Config, bar: usize) -> Position {
+    bar_positions(position, &config.auxiliary_bars)
+        .get(bar)
+        .copied()
+        .unwrap_or(position)
+}
+
+/// Create the layer-surfaces of every configured bar instance on one output.
+fn create_shells<Message: 'static>(
+    style: AppearanceStyle,
+    name: Option<&str>,
+    wl_output: Option<WlOutput>,
+    position: Position,
+    config: &crate::config::Config
+) -> (Vec<ShellInfo>, Task<Message>) {
+    let scale_factor = config.scale_factor_for(name);
+    let mut tasks = Vec::new();
+    let shells = bar_positions(position, &config.auxiliary_bars)
+        .into_iter()
+        .enumerate()
+        .map(|(bar, position)| {
+            let LayerSurfaceCreation {
+                main_id,
+                menu_id,
+                task
+            } = create_layer_surfaces(
+                style,
+                wl_output.clone(),
+                position,
+                config.menu_keyboard_focus,
+                scale_factor,
+                config.reserved_height,
+                config.layer
+            );
+            tasks.push(task);
+
+            ShellInfo {
+                id: main_id,
+                bar,
+                menu: Menu::new(menu_id),
+                position,
+                style,
+                scale_factor,
+                reserved_height: config.reserved_height,
+                layer: config.layer,
+                backdrop: None
+            }
+        })
+        .collect();
+
+    (shells, Task::batch(tasks))
+}
+
+fn destroy_entries<Message: 'static>(entries: &[OutputEntry]) -> Task<Message> {
+    Task::batch(
+        entries
+            .iter()
+            .filter_map(|(_, shell_info, _)| {
+                shell_info.as_ref().map(|shell_info| {
+                    Task::batch(
+                        std::iter::once(destroy_layer_surfaces(shell_info.id, shell_info.menu.id))
+                            .chain(shell_info.backdrop.map(destroy_layer_surface))
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+    )
+}
+
 // TODO: Fix broken tests
 #[cfg(all(test, feature = "enable-broken-tests"))]
 mod tests {
@@ -770,6 +1036,32 @@ mod tests {
         assert!(!outputs.menu_is_open());
     }
 
+    #[test]
+    fn screen_backdrop_follows_the_menu() {
+        let mut config = Config::default();
+        config.appearance.menu.backdrop_scope = BackdropScope::Screen;
+        let (mut outputs, _task) =
+            Outputs::new::<()>(config.appearance.style, config.position, &config);
+        let id = outputs.first_main_window_id().expect("main window");
+        let backdrop = |outputs: &Outputs| {
+            outputs
+                .iter_internal()
+                .next()
+                .and_then(|(_, shell_info, _)| shell_info.as_ref())
+                .and_then(|shell_info| shell_info.backdrop)
+        };
+
+        let button_ref = ButtonUIRef {
+            position: Point::new(0.0, 0.0),
+            viewport: (0., 0.)
+        };
+        let _ = outputs.toggle_menu::<()>(id, MenuType::Updates, button_ref, &config);
+        assert!(backdrop(&outputs).is_some());
+
+        let _ = outputs.close_menu::<()>(id, &config);
+        assert!(backdrop(&outputs).is_none());
+    }
+
     #[test]
     fn sync_updates_position_internally() {
         let config = Config::default();
@@ -787,7 +1079,9 @@ mod tests {
         let mut updated_config = config.clone();
         updated_config.position = match updated_config.position {
             Position::Top => Position::Bottom,
-            Position::Bottom => Position::Top
+            Position::Bottom => Position::Top,
+            Position::Left => Position::Right,
+            Position::Right => Position::Left
         };
 
         let _ = outputs.sync::<()>(
@@ -797,6 +1091,6 @@ mod tests {
             &updated_config
         );
 
-        assert!(matches!(outputs.has(id), Some(HasOutput::Main)));
+        assert!(matches!(outputs.has(id), Some(HasOutput::Main(0))));
     }
 }
@@ -1,7 +1,8 @@
 use iced::{
-    Task,
+    Rectangle, Task,
     platform_specific::shell::commands::layer_surface::{
-        Anchor, KeyboardInteractivity, Layer, destroy_layer_surface, get_layer_surface
+        Anchor, KeyboardInteractivity, Layer, destroy_layer_surface, get_layer_surface,
+        set_input_zone
     },
     runtime::platform_specific::wayland::layer_surface::{IcedOutput, SctkLayerSurfaceSettings},
     window::Id
@@ -10,7 +11,7 @@ use wayland_client::protocol::wl_output::WlOutput;
 
 use crate::{
     HEIGHT,
-    config::{AppearanceStyle, Position}
+    config::{AppearanceStyle, AuxiliaryBar, BarLayer, Position}
 };
 
 pub(crate) struct LayerSurfaceCreation<Message> {
@@ -28,12 +29,61 @@ pub(crate) fn layer_height(style: AppearanceStyle, scale_factor: f64) -> f64 {
         * scale_factor
 }
 
+/// Space reserved for the bar through the layer-shell exclusive zone.
+///
+/// Defaults to the bar height; an explicit `reserved_height` lets the bar
+/// overlap windows or keep extra room for compositor gaps.
+pub(crate) fn exclusive_zone(height: f64, reserved_height: Option<u32>) -> i32 {
+    reserved_height.map_or(height as i32, |reserved| reserved as i32)
+}
+
+/// Edges a bar surface is anchored to: its own edge plus the two edges it
+/// stretches between.
+pub(crate) fn bar_anchor(position: Position) -> Anchor {
+    match position {
+        Position::Top => Anchor::TOP | Anchor::LEFT | Anchor::RIGHT,
+        Position::Bottom => Anchor::BOTTOM | Anchor::LEFT | Anchor::RIGHT,
+        Position::Left => Anchor::LEFT | Anchor::TOP | Anchor::BOTTOM,
+        Position::Right => Anchor::RIGHT | Anchor::TOP | Anchor::BOTTOM
+    }
+}
+
+/// Requested surface size of a bar `thickness` pixels across. The length
+/// along the anchored edges is left to the compositor.
+pub(crate) fn bar_size(position: Position, thickness: f64) -> (Option<u32>, Option<u32>) {
+    if position.is_vertical() {
+        (Some(thickness as u32), None)
+    } else {
+        (None, Some(thickness as u32))
+    }
+}
+
+/// Placement of every bar surface created on one output: the primary bar at
+/// `position` first, then one for each auxiliary bar.
+pub(crate) fn bar_positions(position: Position, auxiliary_bars: &[AuxiliaryBar]) -> Vec<Position> {
+    std::iter::once(position)
+        .chain(auxiliary_bars.iter().map(|auxiliary| auxiliary.position))
+        .collect()
+}
+
+/// Map the configured bar layer onto the layer-shell protocol layer.
+pub(crate) fn shell_layer(layer: BarLayer) -> Layer {
+    match layer {
+        BarLayer::Background => Layer::Background,
+        BarLayer::Bottom => Layer::Bottom,
+        BarLayer::Top => Layer::Top,
+        BarLayer::Overlay => Layer::Overlay
+    }
+}
+
 pub(crate) fn create_layer_surfaces<Message: 'static>(
     style: AppearanceStyle,
     wl_output: Option<WlOutput>,
     position: Position,
     menu_keyboard_focus: bool,
-    scale_factor: f64
+    scale_factor: f64,
+    reserved_height: Option<u32>,
+    layer: BarLayer
 ) -> LayerSurfaceCreation<Message> {
     let main_id = Id::unique();
     let height = layer_height(style, scale_factor);
@@ -41,23 +91,19 @@ pub(crate) fn create_layer_surfaces<Message: 'static>(
     let main_task = get_layer_surface(SctkLayerSurfaceSettings {
         id: main_id,
         namespace: "hydebar-main-layer".to_string(),
-        size: Some((None, Some(height as u32))),
-        layer: Layer::Bottom,
+        size: Some(bar_size(position, height)),
+        layer: shell_layer(layer),
         pointer_interactivity: true,
         keyboard_interactivity: if menu_keyboard_focus {
             KeyboardInteractivity::OnDemand
         } else {
             KeyboardInteractivity::None
         },
-        exclusive_zone: height as i32,
+        exclusive_zone: exclusive_zone(height, reserved_height),
         output: wl_output
             .clone()
             .map_or(IcedOutput::Active, IcedOutput::Output),
-        anchor: match position {
-            Position::Top => Anchor::TOP,
-            Position::Bottom => Anchor::BOTTOM
-        } | Anchor::LEFT
-            | Anchor::RIGHT,
+        anchor: bar_anchor(position),
         ..Default::default()
     });
 
@@ -81,9 +127,164 @@ pub(crate) fn create_layer_surfaces<Message: 'static>(
     }
 }
 
+/// Change to an output's backdrop surface keeping one in place exactly while
+/// a menu is open and the backdrop dims the whole screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BackdropChange {
+    Create,
+    Destroy(Id)
+}
+
+/// Decide what happens to the `backdrop` surface of an output once its menu
+/// has opened or closed; `None` when it already matches.
+pub(crate) fn backdrop_change(
+    dim_screen: bool,
+    menu_open: bool,
+    backdrop: Option<Id>
+) -> Option<BackdropChange> {
+    match (dim_screen && menu_open, backdrop) {
+        (true, None) => Some(BackdropChange::Create),
+        (false, Some(id)) => Some(BackdropChange::Destroy(id)),
+        _ => None
+    }
+}
+
+/// Settings of the transient surface dimming a whole output behind an open
+/// menu.
+///
+/// It sits below the menu on the top layer, ignores exclusive zones so the
+/// bar is covered as well, and lets pointer input through.
+pub(crate) fn backdrop_surface_settings(
+    id: Id,
+    wl_output: Option<WlOutput>
+) -> SctkLayerSurfaceSettings {
+    SctkLayerSurfaceSettings {
+        id,
+        namespace: "hydebar-backdrop-layer".to_string(),
+        size: Some((None, None)),
+        layer: Layer::Top,
+        pointer_interactivity: false,
+        keyboard_interactivity: KeyboardInteractivity::None,
+        exclusive_zone: -1,
+        output: wl_output.map_or(IcedOutput::Active, IcedOutput::Output),
+        anchor: Anchor::TOP | Anchor::BOTTOM | Anchor::LEFT | Anchor::RIGHT,
+        ..Default::default()
+    }
+}
+
+/// Create the backdrop surface described by [`backdrop_surface_settings`].
+pub(crate) fn create_backdrop_surface<Message: 'static>(
+    wl_output: Option<WlOutput>
+) -> (Id, Task<Message>) {
+    let id = Id::unique();
+
+    (
+        id,
+        get_layer_surface(backdrop_surface_settings(id, wl_output))
+    )
+}
+
+/// Restrict pointer input of a layer-surface to `region`, or accept input on
+/// the whole surface when `region` is [`None`].
+pub(crate) fn set_input_region<Message: 'static>(
+    id: Id,
+    region: Option<Vec<Rectangle>>
+) -> Task<Message> {
+    set_input_zone(id, region)
+}
+
 pub(crate) fn destroy_layer_surfaces<Message: 'static>(main_id: Id, menu_id: Id) -> Task<Message> {
     Task::batch(vec![
         destroy_layer_surface(main_id),
         destroy_layer_surface(menu_id),
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn horizontal_bars_span_the_output_width() {
+        assert_eq!(
+            bar_anchor(Position::Top),
+            Anchor::TOP | Anchor::LEFT | Anchor::RIGHT
+        );
+        assert_eq!(
+            bar_anchor(Position::Bottom),
+            Anchor::BOTTOM | Anchor::LEFT | Anchor::RIGHT
+        );
+        assert_eq!(bar_size(Position::Top, 34.), (None, Some(34)));
+    }
+
+    #[test]
+    fn vertical_bars_span_the_output_height() {
+        assert_eq!(
+            bar_anchor(Position::Left),
+            Anchor::LEFT | Anchor::TOP | Anchor::BOTTOM
+        );
+        assert_eq!(
+            bar_anchor(Position::Right),
+            Anchor::RIGHT | Anchor::TOP | Anchor::BOTTOM
+        );
+        assert_eq!(bar_size(Position::Left, 34.), (Some(34), None));
+    }
+
+    #[test]
+    fn one_surface_per_auxiliary_bar() {
+        let auxiliary = |position| AuxiliaryBar {
+            position,
+            modules: Default::default()
+        };
+
+        assert_eq!(bar_positions(Position::Top, &[]), [Position::Top]);
+        assert_eq!(
+            bar_positions(
+                Position::Left,
+                &[auxiliary(Position::Bottom), auxiliary(Position::Right)]
+            ),
+            [Position::Left, Position::Bottom, Position::Right]
+        );
+    }
+
+    #[test]
+    fn backdrop_exists_only_while_a_menu_dims_the_screen() {
+        let id = Id::unique();
+
+        assert_eq!(
+            backdrop_change(true, true, None),
+            Some(BackdropChange::Create)
+        );
+        assert_eq!(backdrop_change(true, true, Some(id)), None);
+        assert_eq!(
+            backdrop_change(true, false, Some(id)),
+            Some(BackdropChange::Destroy(id))
+        );
+        assert_eq!(
+            backdrop_change(false, true, Some(id)),
+            Some(BackdropChange::Destroy(id))
+        );
+        assert_eq!(backdrop_change(false, true, None), None);
+    }
+
+    #[test]
+    fn backdrop_covers_the_output_without_taking_input() {
+        let id = Id::unique();
+        let settings = backdrop_surface_settings(id, None);
+
+        assert_eq!(settings.id, id);
+        assert!(matches!(settings.layer, Layer::Top));
+        assert_eq!(settings.exclusive_zone, -1);
+        assert!(!settings.pointer_interactivity);
+        assert_eq!(
+            settings.anchor,
+            Anchor::TOP | Anchor::BOTTOM | Anchor::LEFT | Anchor::RIGHT
+        );
+    }
+
+    #[test]
+    fn exclusive_zone_covers_the_bar_thickness() {
+        assert_eq!(exclusive_zone(34., None), 34);
+        assert_eq!(exclusive_zone(34., Some(40)), 40);
+    }
+}
@@ -6,6 +6,7 @@ use iced::{
 };
 
 pub mod audio;
+pub(crate) mod backoff;
 pub mod bluetooth;
 pub mod brightness;
 pub mod idle_inhibitor;
@@ -12,7 +12,7 @@ use libpulse_binding::{
     callbacks::ListResult,
     context::{
         self, Context, FlagSet,
-        introspect::{Introspector, SinkInfo, SourceInfo},
+        introspect::{Introspector, SinkInfo, SinkInputInfo, SourceInfo},
         subscribe::InterestMaskSet
     },
     def::{DevicePortType, PortAvailable, SinkState, SourceState},
@@ -25,7 +25,7 @@ use log::{debug, error, trace};
 use masterror::{AppError, AppResult};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
-use crate::services::audio::model::{AudioEvent, Device, DeviceType, Port, ServerInfo};
+use crate::services::audio::model::{AudioEvent, Device, DeviceType, Port, ServerInfo, SinkInput};
 
 /// Commands accepted by backend implementations.
 #[derive(Debug, Clone)]
@@ -34,6 +34,8 @@ pub enum BackendCommand {
     SourceMute(String, bool),
     SinkVolume(String, ChannelVolumes),
     SourceVolume(String, ChannelVolumes),
+    SinkInputVolume(u32, ChannelVolumes),
+    SinkInputMute(u32, bool),
     DefaultSink(String, String),
     DefaultSource(String, String)
 }
@@ -189,7 +191,8 @@ impl PulseAudioServer {
                     server.context.subscribe(
                         InterestMaskSet::SERVER
                             .union(InterestMaskSet::SINK)
-                            .union(InterestMaskSet::SOURCE),
+                            .union(InterestMaskSet::SOURCE)
+                            .union(InterestMaskSet::SINK_INPUT),
                         |result| {
                             if !result {
                                 error!("Audio subscription failed");
@@ -241,6 +244,24 @@ impl PulseAudioServer {
                         let _ = from_server_tx.send(BackendEvent::Error(err.to_string()));
                     }
 
+                    let sink_inputs = Rc::new(RefCell::new(Vec::new()));
+                    if let Err(err) =
+                        server.wait_for_response(server.introspector.get_sink_input_info_list({
+                            let tx = from_server_tx.clone();
+                            let sink_inputs = sink_inputs.clone();
+                            move |info| {
+                                Self::populate_and_send_sink_inputs(
+                                    info,
+                                    &tx,
+                                    &mut sink_inputs.borrow_mut()
+                                );
+                            }
+                        }))
+                    {
+                        error!("Failed to get sink input info: {err}");
+                        let _ = from_server_tx.send(BackendEvent::Error(err.to_string()));
+                    }
+
                     let introspector = server.context.introspect();
                     let from_server_tx_clone = from_server_tx.clone();
                     server.context.set_subscribe_callback(Some(Box::new(
@@ -276,6 +297,18 @@ impl PulseAudioServer {
                                     );
                                 }
                             });
+                            introspector.get_sink_input_info_list({
+                                let tx = from_server_tx_clone.clone();
+                                let sink_inputs = sink_inputs.clone();
+
+                                move |info| {
+                                    Self::populate_and_send_sink_inputs(
+                                        info,
+                                        &tx,
+                                        &mut sink_inputs.borrow_mut()
+                                    );
+                                }
+                            });
                         }
                     )));
 
@@ -329,6 +362,12 @@ impl PulseAudioServer {
                                 BackendCommand::SourceVolume(name, volume) => {
                                     server.set_source_volume(&name, &volume)
                                 }
+                                BackendCommand::SinkInputVolume(index, volume) => {
+                                    server.set_sink_input_volume(index, &volume)
+                                }
+                                BackendCommand::SinkInputMute(index, mute) => {
+                                    server.set_sink_input_mute(index, mute)
+                                }
                                 BackendCommand::DefaultSink(name, port) => {
                                     server.set_default_sink(&name, &port)
                                 }
@@ -434,6 +473,32 @@ impl PulseAudioServer {
         }
     }
 
+    fn populate_and_send_sink_inputs(
+        info: ListResult<&SinkInputInfo<'_>>,
+        tx: &UnboundedSender<BackendEvent>,
+        sink_inputs: &mut Vec<SinkInput>
+    ) {
+        match info {
+            ListResult::Item(data) => {
+                trace!("Received sink input data: {data:?}");
+
+                // Volume-less streams such as passthrough audio cannot be
+                // adjusted, so there is nothing to show for them.
+                if data.has_volume {
+                    sink_inputs.push(data.into());
+                }
+            }
+            ListResult::End => {
+                debug!("New sink input list {sink_inputs:?}");
+                let _ = tx.send(BackendEvent::Update(AudioEvent::SinkInputs(
+                    sink_inputs.clone()
+                )));
+                sink_inputs.clear();
+            }
+            ListResult::Error => error!("Error during sink input list population")
+        }
+    }
+
     fn set_sink_mute(&mut self, name: &str, mute: bool) -> AppResult<()> {
         let op = self.introspector.set_sink_mute_by_name(name, mute, None);
         self.wait_for_response(op)
@@ -458,6 +523,16 @@ impl PulseAudioServer {
         self.wait_for_response(op)
     }
 
+    fn set_sink_input_volume(&mut self, index: u32, volume: &ChannelVolumes) -> AppResult<()> {
+        let op = self.introspector.set_sink_input_volume(index, volume, None);
+        self.wait_for_response(op)
+    }
+
+    fn set_sink_input_mute(&mut self, index: u32, mute: bool) -> AppResult<()> {
+        let op = self.introspector.set_sink_input_mute(index, mute, None);
+        self.wait_for_response(op)
+    }
+
     fn set_default_sink(&mut self, name: &str, port: &str) -> AppResult<()> {
         let op = self.context.set_default_sink(name, |_| {});
         self.wait_for_response(op)?;
@@ -583,3 +658,18 @@ impl From<&SourceInfo<'_>> for Device {
         }
     }
 }
+
+impl From<&SinkInputInfo<'_>> for SinkInput {
+    fn from(value: &SinkInputInfo<'_>) -> Self {
+        Self {
+            index:  value.index,
+            name:   value
+                .proplist
+                .get_str(APPLICATION_NAME)
+                .or_else(|| value.name.as_ref().map(ToString::to_string))
+                .unwrap_or_default(),
+            volume: value.volume,
+            muted:  value.mute
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use libpulse_binding::volume::ChannelVolumes;
 
-use crate::components::icons::Icons;
+use crate::{components::icons::Icons, config::VolumeIconsConfig};
 
 /// Describes a single audio device (sink or source).
 ///
@@ -47,6 +47,16 @@ impl DeviceType {
     }
 }
 
+/// An application stream playing into a sink, e.g. a browser tab or a music
+/// player.
+#[derive(Debug, Clone)]
+pub struct SinkInput {
+    pub index:  u32,
+    pub name:   String,
+    pub volume: ChannelVolumes,
+    pub muted:  bool
+}
+
 /// Server level metadata tracked by the audio service.
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct ServerInfo {
@@ -77,14 +87,66 @@ impl Volume for ChannelVolumes {
     }
 }
 
+/// Speaker indicator state derived from mute flag and volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeLevel {
+    Muted,
+    Silent,
+    Low,
+    Medium,
+    High
+}
+
+impl VolumeLevel {
+    /// Classifies `volume` (a fraction of normal) using the configured
+    /// percentage breakpoints.
+    pub fn new(is_mute: bool, volume: f64, config: &VolumeIconsConfig) -> Self {
+        let percent = volume * 100.0;
+
+        if is_mute {
+            VolumeLevel::Muted
+        } else if percent > f64::from(config.high_above) {
+            VolumeLevel::High
+        } else if percent > f64::from(config.medium_above) {
+            VolumeLevel::Medium
+        } else if volume > 0.000_001 {
+            VolumeLevel::Low
+        } else {
+            VolumeLevel::Silent
+        }
+    }
+
+    /// Built-in glyph for the level.
+    pub fn icon(self) -> Icons {
+        match self {
+            VolumeLevel::Muted => Icons::SpeakerMuted,
+            VolumeLevel::Silent => Icons::Speaker0,
+            VolumeLevel::Low => Icons::Speaker1,
+            VolumeLevel::Medium => Icons::Speaker2,
+            VolumeLevel::High => Icons::Speaker3
+        }
+    }
+
+    /// Glyph override configured for the level, if any.
+    pub fn glyph(self, config: &VolumeIconsConfig) -> Option<&str> {
+        match self {
+            VolumeLevel::Muted => config.muted.as_deref(),
+            VolumeLevel::Silent => config.silent.as_deref(),
+            VolumeLevel::Low => config.low.as_deref(),
+            VolumeLevel::Medium => config.medium.as_deref(),
+            VolumeLevel::High => config.high.as_deref()
+        }
+    }
+}
+
 /// Convenience helpers for sink collections.
 pub trait Sinks {
-    /// Computes the icon for the default sink.
-    fn get_icon(&self, default_sink: &str) -> Icons;
+    /// Computes the volume level of the default sink.
+    fn volume_level(&self, default_sink: &str, config: &VolumeIconsConfig) -> VolumeLevel;
 }
 
 impl Sinks for Vec<Device> {
-    fn get_icon(&self, default_sink: &str) -> Icons {
+    fn volume_level(&self, default_sink: &str, config: &VolumeIconsConfig) -> VolumeLevel {
         match self.iter().find_map(|sink| {
             if sink.ports.iter().any(|port| port.active) && sink.name == default_sink {
                 Some((sink.is_mute, sink.volume.get_volume()))
@@ -92,19 +154,8 @@ impl Sinks for Vec<Device> {
                 None
             }
         }) {
-            Some((true, _)) => Icons::Speaker0,
-            Some((false, volume)) => {
-                if volume > 0.66 {
-                    Icons::Speaker3
-                } else if volume > 0.33 {
-                    Icons::Speaker2
-                } else if volume > 0.000_001 {
-                    Icons::Speaker1
-                } else {
-                    Icons::Speaker0
-                }
-            }
-            None => Icons::Speaker0
+            Some((is_mute, volume)) => VolumeLevel::new(is_mute, volume, config),
+            None => VolumeLevel::Silent
         }
     }
 }
@@ -115,6 +166,7 @@ pub struct AudioData {
     pub server_info:       ServerInfo,
     pub sinks:             Vec<Device>,
     pub sources:           Vec<Device>,
+    pub sink_inputs:       Vec<SinkInput>,
     pub cur_sink_volume:   i32,
     pub cur_source_volume: i32
 }
@@ -124,6 +176,7 @@ pub struct AudioData {
 pub enum AudioEvent {
     Sinks(Vec<Device>),
     Sources(Vec<Device>),
+    SinkInputs(Vec<SinkInput>),
     ServerInfo(ServerInfo)
 }
 
@@ -155,7 +208,31 @@ mod tests {
             }]
         }];
 
-        assert_eq!(sinks.get_icon("default"), Icons::Speaker0);
+        assert_eq!(
+            sinks.volume_level("default", &VolumeIconsConfig::default()),
+            VolumeLevel::Muted
+        );
+    }
+
+    #[test]
+    fn volume_level_follows_configured_breakpoints() {
+        let defaults = VolumeIconsConfig::default();
+        assert_eq!(VolumeLevel::new(false, 0.0, &defaults), VolumeLevel::Silent);
+        assert_eq!(VolumeLevel::new(false, 0.2, &defaults), VolumeLevel::Low);
+        assert_eq!(VolumeLevel::new(false, 0.5, &defaults), VolumeLevel::Medium);
+        assert_eq!(VolumeLevel::new(false, 0.9, &defaults), VolumeLevel::High);
+        assert_eq!(VolumeLevel::new(true, 0.9, &defaults), VolumeLevel::Muted);
+
+        let custom = VolumeIconsConfig {
+            medium_above: 10,
+            high_above: 40,
+            high: Some("H".into()),
+            ..VolumeIconsConfig::default()
+        };
+        let level = VolumeLevel::new(false, 0.5, &custom);
+        assert_eq!(level, VolumeLevel::High);
+        assert_eq!(level.glyph(&custom), Some("H"));
+        assert_eq!(VolumeLevel::Low.glyph(&custom), None);
     }
 
     #[test]
@@ -174,7 +251,10 @@ mod tests {
             }]
         }];
 
-        assert_eq!(sinks.get_icon("default"), Icons::Speaker0);
+        assert_eq!(
+            sinks.volume_level("default", &VolumeIconsConfig::default()),
+            VolumeLevel::Silent
+        );
     }
 
     #[test]
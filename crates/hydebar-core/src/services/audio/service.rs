@@ -26,6 +26,9 @@ pub enum AudioCommand {
     ToggleSourceMute,
     SinkVolume(i32),
     SourceVolume(i32),
+    /// Sets the volume of the sink input with the given index, in percent.
+    SinkInputVolume(u32, i32),
+    ToggleSinkInputMute(u32),
     DefaultSink(String, String),
     DefaultSource(String, String)
 }
@@ -107,6 +110,33 @@ impl AudioService {
                     self.send_backend_command(command);
                 }
             }
+            AudioCommand::SinkInputVolume(index, volume) => {
+                let command = self
+                    .data
+                    .sink_inputs
+                    .iter_mut()
+                    .find(|input| input.index == index)
+                    .and_then(|input| {
+                        input
+                            .volume
+                            .scale_volume(volume as f64 / 100.0)
+                            .map(|volume| BackendCommand::SinkInputVolume(index, *volume))
+                    });
+
+                if let Some(command) = command {
+                    self.send_backend_command(command);
+                }
+            }
+            AudioCommand::ToggleSinkInputMute(index) => {
+                if let Some(input) = self
+                    .data
+                    .sink_inputs
+                    .iter()
+                    .find(|input| input.index == index)
+                {
+                    self.send_backend_command(BackendCommand::SinkInputMute(index, !input.muted));
+                }
+            }
             AudioCommand::DefaultSink(name, port) => {
                 self.send_backend_command(BackendCommand::DefaultSink(name, port));
             }
@@ -153,14 +183,14 @@ impl AudioService {
                 }
                 Err(err) => {
                     error!("Failed to initialise audio backend: {err}");
-                    let _ = publisher.send(ServiceEvent::Error(())).await;
+                    let _ = publisher.send(ServiceEvent::Error(err.to_string())).await;
                     State::Error
                 }
             },
             State::Active(mut handle) => match handle.recv().await {
                 Some(BackendEvent::Error(err)) => {
                     error!("Audio backend error: {err}");
-                    let _ = publisher.send(ServiceEvent::Error(())).await;
+                    let _ = publisher.send(ServiceEvent::Error(err.to_string())).await;
                     State::Error
                 }
                 Some(BackendEvent::Update(event)) => {
@@ -169,7 +199,11 @@ impl AudioService {
                 }
                 None => {
                     warn!("Audio backend closed event stream");
-                    let _ = publisher.send(ServiceEvent::Error(())).await;
+                    let _ = publisher
+                        .send(ServiceEvent::Error(
+                            "audio backend closed its event stream".to_owned()
+                        ))
+                        .await;
                     State::Error
                 }
             },
@@ -196,6 +230,9 @@ impl AudioService {
                     &self.data.server_info.default_source
                 );
             }
+            AudioEvent::SinkInputs(sink_inputs) => {
+                self.data.sink_inputs = sink_inputs;
+            }
             AudioEvent::ServerInfo(info) => {
                 self.data.server_info = info;
                 self.data.cur_sink_volume = Self::active_device_volume(
@@ -257,7 +294,8 @@ impl DerefMut for AudioService {
 
 impl ReadOnlyService for AudioService {
     type UpdateEvent = AudioEvent;
-    type Error = ();
+    /// Why the backend failed, shown in the settings menu.
+    type Error = String;
 
     fn update(&mut self, event: Self::UpdateEvent) {
         self.update_from_event(event);
@@ -340,6 +378,7 @@ mod tests {
                         active:      true
                     }]
                 }],
+                sink_inputs:       Vec::new(),
                 cur_sink_volume:   0,
                 cur_source_volume: 0
             },
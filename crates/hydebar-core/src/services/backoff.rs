@@ -0,0 +1,70 @@
+//! Reconnect policy for services backed by the system bus.
+//!
+//! Without a bus (for example inside containers) every reconnect attempt
+//! fails immediately, so retries are spaced out exponentially and eventually
+//! abandoned instead of logging the same error forever.
+
+use std::time::Duration;
+
+/// Delay before the first reconnect attempt.
+const INITIAL_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound for the delay between two attempts.
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Consecutive failures after which a service stops retrying.
+const MAX_ATTEMPTS: u32 = 8;
+
+/// Tracks consecutive reconnect failures of a single service.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Backoff {
+    attempts: u32
+}
+
+impl Backoff {
+    /// Delay to wait before the next attempt, doubling after every failure up
+    /// to [`MAX_DELAY`]. Returns `None` once the retry budget is exhausted.
+    pub(crate) fn next_delay(&mut self) -> Option<Duration> {
+        if self.attempts >= MAX_ATTEMPTS {
+            return None;
+        }
+
+        let delay = INITIAL_DELAY
+            .saturating_mul(1 << self.attempts.min(16))
+            .min(MAX_DELAY);
+        self.attempts += 1;
+
+        Some(delay)
+    }
+
+    /// Number of reconnect attempts made so far.
+    pub(crate) fn attempts(&self) -> u32 {
+        self.attempts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delays_double_until_capped() {
+        let mut backoff = Backoff::default();
+        let delays: Vec<u64> = std::iter::from_fn(|| backoff.next_delay())
+            .map(|delay| delay.as_secs())
+            .collect();
+
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(backoff.attempts(), MAX_ATTEMPTS);
+    }
+
+    #[test]
+    fn exhausted_backoff_stays_exhausted() {
+        let mut backoff = Backoff {
+            attempts: MAX_ATTEMPTS
+        };
+
+        assert_eq!(backoff.next_delay(), None);
+        assert_eq!(backoff.next_delay(), None);
+    }
+}
@@ -88,7 +88,20 @@ impl BluetoothService {
         })
     }
 
-    async fn events(conn: &zbus::Connection) -> AppResult<impl Stream<Item = ()> + use<>> {
+    /// Paths of the devices currently reporting a battery level.
+    fn battery_devices(devices: &[BluetoothDevice]) -> Vec<OwnedObjectPath> {
+        devices
+            .iter()
+            .filter(|device| device.battery.is_some())
+            .map(|device| device.path.clone())
+            .collect()
+    }
+
+    /// Stream of changes worth a refresh, along with the devices whose
+    /// battery level it watches.
+    async fn events(
+        conn: &zbus::Connection
+    ) -> AppResult<(impl Stream<Item = ()> + use<>, Vec<OwnedObjectPath>)> {
         let bluetooth = BluetoothDbus::new(conn).await?;
 
         let interface_changed = stream_select!(
@@ -113,16 +126,16 @@ impl BluetoothService {
         )
         .boxed();
 
-        let combined = match bluetooth.adapter.as_ref() {
+        let (combined, watched) = match bluetooth.adapter.as_ref() {
             Some(adapter) => {
                 let powered = adapter.receive_powered_changed().await.map(|_| {});
                 let rfkill = BluetoothService::listen_rfkill_soft_block_changes().await?;
-                let devices = bluetooth.devices().await?;
+                let watched = Self::battery_devices(&bluetooth.devices().await?);
 
-                let mut batteries = Vec::with_capacity(devices.len());
-                for device in devices {
+                let mut batteries = Vec::with_capacity(watched.len());
+                for path in &watched {
                     let battery = BatteryProxy::builder(bluetooth.bluez.inner().connection())
-                        .path(device.path)
+                        .path(path.clone())
                         .map_err(|e| {
                             AppError::internal(format!("Failed to set battery path: {}", e))
                         })?
@@ -134,12 +147,16 @@ impl BluetoothService {
                     batteries.push(battery.receive_percentage_changed().await.map(|_| {}));
                 }
 
-                stream_select!(interface_changed, powered, rfkill, select_all(batteries)).boxed()
+                (
+                    stream_select!(interface_changed, powered, rfkill, select_all(batteries))
+                        .boxed(),
+                    watched
+                )
             }
-            _ => interface_changed
+            _ => (interface_changed, Vec::new())
         };
 
-        Ok(combined)
+        Ok((combined, watched))
     }
 
     async fn start_listening<P>(state: State, publisher: &mut P) -> State
@@ -166,6 +183,7 @@ impl BluetoothService {
                         }
                         Err(err) => {
                             error!("Failed to initialize bluetooth service: {err}");
+                            let _ = publisher.send(ServiceEvent::Error(err.to_string())).await;
 
                             State::Error
                         }
@@ -173,6 +191,11 @@ impl BluetoothService {
                 }
                 Err(err) => {
                     error!("Failed to connect to system bus: {err}");
+                    let _ = publisher
+                        .send(ServiceEvent::Error(format!(
+                            "system bus unavailable: {err}"
+                        )))
+                        .await;
 
                     State::Error
                 }
@@ -181,10 +204,17 @@ impl BluetoothService {
                 info!("Listening for bluetooth events");
 
                 match BluetoothService::events(&conn).await {
-                    Ok(mut events) => {
+                    Ok((mut events, watched)) => {
                         while events.next().await.is_some() {
                             if let Ok(data) = BluetoothService::initialize_data(&conn).await {
+                                let batteries = Self::battery_devices(&data.devices);
                                 let _ = publisher.send(ServiceEvent::Update(data)).await;
+
+                                // A headset only exposes its battery once
+                                // connected, so subscribe again to follow it.
+                                if batteries != watched {
+                                    break;
+                                }
                             }
                         }
 
@@ -192,6 +222,8 @@ impl BluetoothService {
                     }
                     Err(err) => {
                         error!("Failed to listen for bluetooth events: {err}");
+                        let _ = publisher.send(ServiceEvent::Error(err.to_string())).await;
+
                         State::Error
                     }
                 }
@@ -294,7 +326,8 @@ impl BluetoothService {
 
 impl ReadOnlyService for BluetoothService {
     type UpdateEvent = BluetoothData;
-    type Error = ();
+    /// Why BlueZ could not be reached, shown next to the retry button.
+    type Error = String;
 
     fn update(&mut self, event: Self::UpdateEvent) {
         self.data = event;
@@ -129,9 +129,7 @@ impl BluetoothDbus<'_> {
                             AppError::internal(format!("Failed to build BatteryProxy: {}", e))
                         })?;
 
-                    Some(battery_proxy.percentage().await.map_err(|e| {
-                        AppError::internal(format!("Failed to get battery percentage: {}", e))
-                    })?)
+                    battery_level(battery_proxy.percentage().await)
                 } else {
                     None
                 };
@@ -231,8 +229,36 @@ trait Device {
     fn disconnect(&self) -> zbus::Result<()>;
 }
 
+/// Map the `Percentage` byte of `org.bluez.Battery1` to the device model.
+///
+/// BlueZ documents the range as 0-100, so anything above is clamped. A
+/// device whose battery cannot be read is shown without the indicator
+/// rather than failing the whole device list.
+fn battery_level(percentage: zbus::Result<u8>) -> Option<u8> {
+    match percentage {
+        Ok(percentage) => Some(percentage.min(100)),
+        Err(err) => {
+            log::warn!("Failed to get battery percentage: {err}");
+            None
+        }
+    }
+}
+
 #[proxy(default_service = "org.bluez", interface = "org.bluez.Battery1")]
 pub trait Battery {
     #[zbus(property)]
     fn percentage(&self) -> zbus::Result<u8>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::battery_level;
+
+    #[test]
+    fn maps_percentage_byte_into_battery_level() {
+        assert_eq!(battery_level(Ok(0)), Some(0));
+        assert_eq!(battery_level(Ok(87)), Some(87));
+        assert_eq!(battery_level(Ok(u8::MAX)), Some(100));
+        assert_eq!(battery_level(Err(zbus::Error::InterfaceNotFound)), None);
+    }
+}
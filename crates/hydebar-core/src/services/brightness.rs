@@ -2,7 +2,8 @@ use std::{
     any::TypeId,
     fs,
     ops::Deref,
-    path::{Path, PathBuf}
+    path::{Path, PathBuf},
+    time::Duration
 };
 
 use iced::{
@@ -11,7 +12,10 @@ use iced::{
     stream::channel
 };
 use log::{debug, error, info, warn};
-use tokio::io::{Interest, unix::AsyncFd};
+use tokio::{
+    io::{Interest, unix::AsyncFd},
+    time::{MissedTickBehavior, interval}
+};
 use zbus::proxy;
 
 use super::{ReadOnlyService, Service, ServiceEvent, ServiceEventPublisher};
@@ -21,17 +25,42 @@ mod error;
 
 pub use error::BrightnessError;
 
+/// Where keyboard backlights appear in sysfs.
+const LEDS_DIR: &str = "/sys/class/leds";
+
+/// How often the levels are re-read between udev events.
+///
+/// Auto-brightness daemons writing to sysfs directly do not raise a uevent,
+/// so without polling their changes would never reach the bar.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 #[derive(Debug, Clone, Default)]
 pub struct BrightnessData {
+    pub current:             u32,
+    pub max:                 u32,
+    /// Level of the keyboard backlight, `None` when there is none.
+    pub keyboard_brightness: Option<KeyboardBrightness>
+}
+
+/// Current and maximum level of a keyboard backlight.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyboardBrightness {
     pub current: u32,
     pub max:     u32
 }
 
+/// Sysfs directories of the devices whose brightness the service controls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Backlights {
+    display:  PathBuf,
+    keyboard: Option<PathBuf>
+}
+
 #[derive(Debug, Clone)]
 pub struct BrightnessService {
-    data:        BrightnessData,
-    device_path: PathBuf,
-    conn:        zbus::Connection
+    data:       BrightnessData,
+    backlights: Backlights,
+    conn:       zbus::Connection
 }
 
 impl Deref for BrightnessService {
@@ -67,23 +96,84 @@ impl BrightnessService {
         Ok(value)
     }
 
-    async fn initialize_data(device_path: &Path) -> Result<BrightnessData, BrightnessError> {
-        let max_brightness = Self::get_max_brightness(device_path).await?;
-        let actual_brightness = Self::get_actual_brightness(device_path).await?;
+    /// Level of the LED device at `device_path`, which has no
+    /// `actual_brightness` file.
+    fn get_led_brightness(device_path: &Path) -> Result<u32, BrightnessError> {
+        let path = device_path.join("brightness");
+        let contents = fs::read_to_string(&path)
+            .map_err(|err| BrightnessError::filesystem(format!("{}: {err}", path.display())))?;
+
+        contents
+            .trim()
+            .parse::<u32>()
+            .map_err(|err| BrightnessError::parse(format!("{}: {err}", path.display())))
+    }
+
+    async fn initialize_data(backlights: &Backlights) -> Result<BrightnessData, BrightnessError> {
+        let max_brightness = Self::get_max_brightness(&backlights.display).await?;
+        let actual_brightness = Self::get_actual_brightness(&backlights.display).await?;
 
         debug!("Max brightness: {max_brightness}, current brightness: {actual_brightness}");
 
+        // A keyboard backlight that cannot be read only hides its slider.
+        let keyboard_brightness = match backlights.keyboard.as_deref() {
+            Some(path) => match Self::keyboard_brightness(path).await {
+                Ok(brightness) => Some(brightness),
+                Err(err) => {
+                    warn!("Ignoring keyboard backlight: {err}");
+                    None
+                }
+            },
+            None => None
+        };
+
         Ok(BrightnessData {
             current: actual_brightness,
-            max:     max_brightness
+            max: max_brightness,
+            keyboard_brightness
         })
     }
 
+    async fn keyboard_brightness(
+        device_path: &Path
+    ) -> Result<KeyboardBrightness, BrightnessError> {
+        Ok(KeyboardBrightness {
+            current: Self::get_led_brightness(device_path)?,
+            max:     Self::get_max_brightness(device_path).await?
+        })
+    }
+
+    /// First LED under `leds_dir` named like a keyboard backlight, such as
+    /// `tpacpi::kbd_backlight`, that exposes a usable `max_brightness`.
+    fn find_keyboard_backlight(leds_dir: &Path) -> Option<PathBuf> {
+        let mut candidates: Vec<PathBuf> = fs::read_dir(leds_dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .contains("kbd_backlight")
+            })
+            .map(|entry| entry.path())
+            .filter(|path| {
+                fs::read_to_string(path.join("max_brightness"))
+                    .ok()
+                    .and_then(|max| max.trim().parse::<u32>().ok())
+                    .is_some_and(|max| max > 0)
+            })
+            .collect();
+
+        // Directory order is arbitrary, so pick the same device every time.
+        candidates.sort();
+        candidates.into_iter().next()
+    }
+
     fn resolve_device_path(device_path: Option<PathBuf>) -> Result<PathBuf, BrightnessError> {
         device_path.ok_or(BrightnessError::MissingDevice)
     }
 
-    async fn init_service() -> Result<(zbus::Connection, PathBuf), BrightnessError> {
+    async fn init_service() -> Result<(zbus::Connection, Backlights), BrightnessError> {
         let backlight_devices = Self::backlight_enumerate()?;
         let candidate = backlight_devices
             .iter()
@@ -98,11 +188,22 @@ impl BrightnessService {
                 Err(err) => return Err(err)
             };
 
+        let keyboard = Self::find_keyboard_backlight(Path::new(LEDS_DIR));
+        if let Some(keyboard) = &keyboard {
+            debug!("Keyboard backlight: {}", keyboard.display());
+        }
+
         let conn = zbus::Connection::system()
             .await
             .map_err(BrightnessError::from)?;
 
-        Ok((conn, device_path))
+        Ok((
+            conn,
+            Backlights {
+                display: device_path,
+                keyboard
+            }
+        ))
     }
 
     pub async fn backlight_monitor_listener()
@@ -110,6 +211,7 @@ impl BrightnessService {
         let builder = udev::MonitorBuilder::new().map_err(BrightnessError::from)?;
         let builder = builder
             .match_subsystem("backlight")
+            .and_then(|builder| builder.match_subsystem("leds"))
             .map_err(BrightnessError::from)?;
         let socket = builder.listen().map_err(BrightnessError::from)?;
 
@@ -135,55 +237,98 @@ impl BrightnessService {
     {
         match state {
             State::Init => {
-                let (conn, device_path) = Self::init_service().await?;
-                let data = Self::initialize_data(&device_path).await?;
+                let (conn, backlights) = Self::init_service().await?;
+                let data = Self::initialize_data(&backlights).await?;
                 let service = BrightnessService {
                     data,
-                    device_path: device_path.clone(),
+                    backlights: backlights.clone(),
                     conn
                 };
                 let _ = publisher.send(ServiceEvent::Init(service)).await;
 
-                Ok(State::Active(device_path))
+                Ok(State::Active(backlights))
             }
-            State::Active(device_path) => {
+            State::Active(backlights) => {
                 info!("Listening for brightness events");
-                let mut current_value = Self::get_actual_brightness(&device_path).await?;
+                let device_path = &backlights.display;
+                let keyboard_name = backlights
+                    .keyboard
+                    .as_deref()
+                    .and_then(Path::file_name)
+                    .map(ToOwned::to_owned);
+                let mut current_value = Self::get_actual_brightness(device_path).await?;
+                let mut keyboard_value = backlights
+                    .keyboard
+                    .as_deref()
+                    .and_then(|path| Self::get_led_brightness(path).ok());
                 let mut socket = Self::backlight_monitor_listener().await?;
+                let mut poll = interval(POLL_INTERVAL);
+                poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
                 loop {
-                    let mut guard = socket.writable_mut().await.map_err(BrightnessError::from)?;
+                    let (display_changed, keyboard_changed) = tokio::select! {
+                        guard = socket.writable_mut() => {
+                            let mut guard = guard.map_err(BrightnessError::from)?;
+                            let mut changed = (false, false);
 
-                    for evt in guard.get_inner().iter() {
-                        debug!("{:?}: {:?}", evt.event_type(), evt.device());
+                            for evt in guard.get_inner().iter() {
+                                debug!("{:?}: {:?}", evt.event_type(), evt.device());
 
-                        if evt.device().subsystem().and_then(|s| s.to_str()) != Some("backlight") {
-                            continue;
-                        }
-
-                        match evt.event_type() {
-                            udev::EventType::Change => {
-                                debug!("Changed backlight device: {:?}", evt.syspath());
-                                let new_value = Self::get_actual_brightness(&device_path).await?;
+                                if evt.event_type() != udev::EventType::Change {
+                                    debug!("Unhandled event type: {:?}", evt.event_type());
+                                    continue;
+                                }
 
-                                if new_value != current_value {
-                                    current_value = new_value;
-                                    let _ = publisher
-                                        .send(ServiceEvent::Update(BrightnessEvent(new_value)))
-                                        .await;
+                                match evt.device().subsystem().and_then(|s| s.to_str()) {
+                                    Some("backlight") => {
+                                        debug!("Changed backlight device: {:?}", evt.syspath());
+                                        changed.0 = true;
+                                    }
+                                    Some("leds")
+                                        if Some(evt.sysname()) == keyboard_name.as_deref() =>
+                                    {
+                                        changed.1 = true;
+                                    }
+                                    _ => {}
                                 }
                             }
-                            other => {
-                                debug!("Unhandled event type: {other:?}");
-                            }
+
+                            guard.clear_ready();
+                            changed
+                        }
+                        _ = poll.tick() => (true, true)
+                    };
+
+                    if display_changed {
+                        let new_value = Self::get_actual_brightness(device_path).await?;
+
+                        if new_value != current_value {
+                            current_value = new_value;
+                            let _ = publisher
+                                .send(ServiceEvent::Update(BrightnessEvent::Display(new_value)))
+                                .await;
                         }
                     }
 
-                    guard.clear_ready();
+                    if keyboard_changed {
+                        let new_value = backlights
+                            .keyboard
+                            .as_deref()
+                            .and_then(|path| Self::get_led_brightness(path).ok());
+
+                        if let Some(value) = new_value
+                            && new_value != keyboard_value
+                        {
+                            keyboard_value = new_value;
+                            let _ = publisher
+                                .send(ServiceEvent::Update(BrightnessEvent::Keyboard(value)))
+                                .await;
+                        }
+                    }
                 }
 
                 #[allow(unreachable_code)]
-                Ok(State::Active(device_path))
+                Ok(State::Active(backlights))
             }
             State::Error => {
                 error!("Brightness service error");
@@ -193,8 +338,11 @@ impl BrightnessService {
         }
     }
 
+    /// Ask logind to set the brightness of the `subsystem` device at
+    /// `device_path`, which works without write access to sysfs.
     async fn set_brightness(
         conn: &zbus::Connection,
+        subsystem: &str,
         device_path: &Path,
         value: u32
     ) -> Result<(), BrightnessError> {
@@ -212,7 +360,7 @@ impl BrightnessService {
             })?;
 
         brightness_ctrl
-            .set_brightness("backlight", device_name, value)
+            .set_brightness(subsystem, device_name, value)
             .await
             .map_err(BrightnessError::from)?;
 
@@ -244,36 +392,63 @@ impl BrightnessService {
     pub async fn run_command(self, command: BrightnessCommand) -> ServiceEvent<Self> {
         match command {
             BrightnessCommand::Set(value) => {
-                match Self::set_brightness(&self.conn, &self.device_path, value).await {
-                    Ok(()) => ServiceEvent::Update(BrightnessEvent(value)),
+                match Self::set_brightness(
+                    &self.conn,
+                    "backlight",
+                    &self.backlights.display,
+                    value
+                )
+                .await
+                {
+                    Ok(()) => ServiceEvent::Update(BrightnessEvent::Display(value)),
                     Err(err) => ServiceEvent::Error(err)
                 }
             }
-            BrightnessCommand::Refresh => {
-                match Self::get_actual_brightness(&self.device_path).await {
-                    Ok(value) => ServiceEvent::Update(BrightnessEvent(value)),
+            BrightnessCommand::SetKeyboard(value) => {
+                let Some(keyboard) = self.backlights.keyboard.as_deref() else {
+                    return ServiceEvent::Error(BrightnessError::MissingDevice);
+                };
+
+                match Self::set_brightness(&self.conn, "leds", keyboard, value).await {
+                    Ok(()) => ServiceEvent::Update(BrightnessEvent::Keyboard(value)),
                     Err(err) => ServiceEvent::Error(err)
                 }
             }
+            BrightnessCommand::Refresh => match Self::initialize_data(&self.backlights).await {
+                Ok(data) => ServiceEvent::Update(BrightnessEvent::Refreshed(data)),
+                Err(err) => ServiceEvent::Error(err)
+            }
         }
     }
 }
 
 enum State {
     Init,
-    Active(PathBuf),
+    Active(Backlights),
     Error
 }
 
 #[derive(Debug, Clone)]
-pub struct BrightnessEvent(u32);
+pub enum BrightnessEvent {
+    Display(u32),
+    Keyboard(u32),
+    Refreshed(BrightnessData)
+}
 
 impl ReadOnlyService for BrightnessService {
     type UpdateEvent = BrightnessEvent;
     type Error = BrightnessError;
 
     fn update(&mut self, event: Self::UpdateEvent) {
-        self.data.current = event.0;
+        match event {
+            BrightnessEvent::Display(value) => self.data.current = value,
+            BrightnessEvent::Keyboard(value) => {
+                if let Some(keyboard) = self.data.keyboard_brightness.as_mut() {
+                    keyboard.current = value;
+                }
+            }
+            BrightnessEvent::Refreshed(data) => self.data = data
+        }
     }
 
     fn subscribe() -> Subscription<ServiceEvent<Self>> {
@@ -291,6 +466,8 @@ impl ReadOnlyService for BrightnessService {
 #[derive(Debug, Clone)]
 pub enum BrightnessCommand {
     Set(u32),
+    /// Set the keyboard backlight to the given raw level.
+    SetKeyboard(u32),
     Refresh
 }
 
@@ -318,11 +495,53 @@ trait BrightnessCtrl {
 
 #[cfg(test)]
 mod tests {
+    use std::{fs, path::Path};
+
+    use tempfile::TempDir;
+
     use super::{BrightnessError, BrightnessService};
 
+    fn led(leds: &Path, name: &str, max: &str) {
+        let dir = leds.join(name);
+        fs::create_dir(&dir).expect("create led dir");
+        fs::write(dir.join("max_brightness"), max).expect("write max_brightness");
+        fs::write(dir.join("brightness"), "1\n").expect("write brightness");
+    }
+
     #[test]
     fn resolve_device_path_without_device_fails() {
         let result = BrightnessService::resolve_device_path(None);
         assert!(matches!(result, Err(BrightnessError::MissingDevice)));
     }
+
+    #[test]
+    fn finds_keyboard_backlight_among_leds() {
+        let leds = TempDir::new().expect("temp dir");
+        led(leds.path(), "input3::capslock", "1\n");
+        led(leds.path(), "tpacpi::kbd_backlight", "2\n");
+        led(leds.path(), "asus::kbd_backlight", "0\n");
+
+        let found = BrightnessService::find_keyboard_backlight(leds.path());
+
+        assert_eq!(found, Some(leds.path().join("tpacpi::kbd_backlight")));
+        assert_eq!(
+            BrightnessService::get_led_brightness(&leds.path().join("tpacpi::kbd_backlight")),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn no_keyboard_backlight_without_matching_led() {
+        let leds = TempDir::new().expect("temp dir");
+        led(leds.path(), "input3::numlock", "1\n");
+
+        assert_eq!(
+            BrightnessService::find_keyboard_backlight(leds.path()),
+            None
+        );
+        assert_eq!(
+            BrightnessService::find_keyboard_backlight(&leds.path().join("missing")),
+            None
+        );
+    }
 }
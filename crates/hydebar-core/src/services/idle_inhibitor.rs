@@ -16,6 +16,16 @@ use wayland_protocols::wp::idle_inhibit::zv1::client::{
     zwp_idle_inhibitor_v1::ZwpIdleInhibitorV1
 };
 
+/// Keeps the session from going idle while enabled.
+pub trait IdleInhibit: Send {
+    /// Whether idle is currently inhibited.
+    fn is_inhibited(&self) -> bool;
+
+    /// Switch between inhibiting idle and letting the session go idle.
+    fn toggle(&mut self);
+}
+
+/// [`IdleInhibit`] over the Wayland idle inhibit protocol.
 pub struct IdleInhibitorManager {
     _connection: Connection,
     _display:    WlDisplay,
@@ -64,22 +74,6 @@ impl IdleInhibitorManager {
             .map_err(IdleInhibitorError::from)
     }
 
-    pub fn is_inhibited(&self) -> bool {
-        self.data.idle_inhibitor_state.is_some()
-    }
-
-    pub fn toggle(&mut self) {
-        let res = if self.is_inhibited() {
-            self.set_inhibit_idle(false)
-        } else {
-            self.set_inhibit_idle(true)
-        };
-
-        if let Err(err) = res {
-            warn!("Failed to toggle idle inhibitor: {err}");
-        }
-    }
-
     fn set_inhibit_idle(&mut self, inhibit_idle: bool) -> Result<(), IdleInhibitorError> {
         let data = &self.data;
         let (idle_manager, _) = data
@@ -137,6 +131,24 @@ impl IdleInhibitorManager {
     }
 }
 
+impl IdleInhibit for IdleInhibitorManager {
+    fn is_inhibited(&self) -> bool {
+        self.data.idle_inhibitor_state.is_some()
+    }
+
+    fn toggle(&mut self) {
+        let res = if self.is_inhibited() {
+            self.set_inhibit_idle(false)
+        } else {
+            self.set_inhibit_idle(true)
+        };
+
+        if let Err(err) = res {
+            warn!("Failed to toggle idle inhibitor: {err}");
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 struct IdleInhibitorInitState {
     has_compositor:   bool,
@@ -1,7 +1,7 @@
-use std::{future::Future, ops::Deref, pin::Pin};
+use std::{future::Future, ops::Deref, pin::Pin, time::Duration};
 
 use commands::{execute_player_command, module_error};
-use futures::StreamExt;
+use futures::{StreamExt, future::join_all};
 use iced::{Subscription, Task};
 use log::{debug, error, info};
 use zbus::Connection;
@@ -15,8 +15,10 @@ mod dbus;
 mod ipc;
 
 pub use commands::{MprisPlayerCommand, PlayerCommand};
-pub use data::{MprisPlayerData, MprisPlayerEvent, MprisPlayerMetadata, PlaybackStatus};
-use ipc::{IpcEvent, build_event_stream, collect_players};
+pub use data::{
+    LoopStatus, MprisPlayerData, MprisPlayerEvent, MprisPlayerMetadata, PlaybackStatus
+};
+use ipc::{IpcEvent, build_event_stream, collect_players, fetch_position};
 
 /// Service storing the currently discovered MPRIS players and their cached
 /// state.
@@ -74,6 +76,21 @@ impl ReadOnlyService for MprisPlayerService {
                     entry.state = state;
                 }
             }
+            MprisPlayerEvent::Position(service, position) => {
+                if let Some(entry) = self.data.iter_mut().find(|d| d.service == service) {
+                    entry.position = position;
+                }
+            }
+            MprisPlayerEvent::Shuffle(service, shuffle) => {
+                if let Some(entry) = self.data.iter_mut().find(|d| d.service == service) {
+                    entry.shuffle = shuffle;
+                }
+            }
+            MprisPlayerEvent::LoopStatus(service, loop_status) => {
+                if let Some(entry) = self.data.iter_mut().find(|d| d.service == service) {
+                    entry.loop_status = loop_status;
+                }
+            }
         }
     }
 
@@ -180,6 +197,27 @@ impl MprisPlayerService {
                                         )))
                                         .await?;
                                 }
+                                IpcEvent::Shuffle(service, shuffle) => {
+                                    debug!(
+                                        "MPRIS player service {service} shuffle changed: {shuffle:?}"
+                                    );
+                                    publisher
+                                        .send(ServiceEvent::Update(MprisPlayerEvent::Shuffle(
+                                            service, shuffle
+                                        )))
+                                        .await?;
+                                }
+                                IpcEvent::LoopStatus(service, loop_status) => {
+                                    debug!(
+                                        "MPRIS player service {service} loop status changed: {loop_status:?}"
+                                    );
+                                    publisher
+                                        .send(ServiceEvent::Update(MprisPlayerEvent::LoopStatus(
+                                            service,
+                                            loop_status
+                                        )))
+                                        .await?;
+                                }
                             }
                         }
 
@@ -234,6 +272,21 @@ impl MprisPlayerService {
 
         execute_player_command(&service.conn, &service.data, command).await
     }
+
+    /// Polls the playback position of every cached player.
+    pub(crate) async fn fetch_positions(
+        service: Option<MprisPlayerService>
+    ) -> Result<Vec<(String, Option<Duration>)>, ModuleError> {
+        let service = service
+            .ok_or_else(|| ModuleError::registration("MPRIS player service is not initialised"))?;
+
+        Ok(join_all(
+            service.data.iter().map(|entry| async {
+                (entry.service.clone(), fetch_position(&entry.proxy).await)
+            })
+        )
+        .await)
+    }
 }
 
 impl Service for MprisPlayerService {
@@ -2,7 +2,11 @@ use std::{fmt::Display, future::Future, pin::Pin};
 
 use zbus::Connection;
 
-use super::{data::MprisPlayerData, dbus::MprisPlayerProxy, ipc};
+use super::{
+    data::{LoopStatus, MprisPlayerData},
+    dbus::MprisPlayerProxy,
+    ipc
+};
 use crate::modules::ModuleError;
 
 /// Helper that converts lower-level errors into [`ModuleError`] values.
@@ -49,7 +53,13 @@ pub enum PlayerCommand {
     /// Jump to the next item in the playlist.
     Next,
     /// Adjust the playback volume to a percentage in the range `[0, 100]`.
-    Volume(f64)
+    Volume(f64),
+    /// Stop playback.
+    Stop,
+    /// Enable or disable shuffle.
+    Shuffle(bool),
+    /// Switch to the given repeat mode.
+    Loop(LoopStatus)
 }
 
 /// Trait describing how player actions are executed for a proxy implementation.
@@ -83,7 +93,19 @@ impl PlayerCommandExecutor for MprisPlayerProxy<'static> {
                 PlayerCommand::Volume(volume) => self
                     .set_volume(volume / 100.0)
                     .await
-                    .map_err(|err| module_error("failed to execute volume command", err))
+                    .map_err(|err| module_error("failed to execute volume command", err)),
+                PlayerCommand::Stop => self
+                    .stop()
+                    .await
+                    .map_err(|err| module_error("failed to execute stop command", err)),
+                PlayerCommand::Shuffle(shuffle) => self
+                    .set_shuffle(*shuffle)
+                    .await
+                    .map_err(|err| module_error("failed to set shuffle", err)),
+                PlayerCommand::Loop(status) => self
+                    .set_loop_status(status.as_str())
+                    .await
+                    .map_err(|err| module_error("failed to set loop status", err))
             }
         })
     }
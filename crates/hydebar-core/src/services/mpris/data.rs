@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
-    fmt::{Display, Formatter, Result as FmtResult}
+    fmt::{Display, Formatter, Result as FmtResult},
+    time::Duration
 };
 
 use zbus::zvariant::OwnedValue;
@@ -47,6 +48,49 @@ impl From<String> for PlaybackStatus {
     }
 }
 
+/// Repeat mode exposed through the MPRIS `LoopStatus` property.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopStatus {
+    /// Playback stops at the end of the playlist.
+    #[default]
+    None,
+    /// The current track repeats.
+    Track,
+    /// The whole playlist repeats.
+    Playlist
+}
+
+impl LoopStatus {
+    /// Returns the value expected by the `LoopStatus` property.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LoopStatus::None => "None",
+            LoopStatus::Track => "Track",
+            LoopStatus::Playlist => "Playlist"
+        }
+    }
+
+    /// Cycles through the modes in the order most players use for their
+    /// repeat button: off, playlist, track.
+    pub fn next(self) -> Self {
+        match self {
+            LoopStatus::None => LoopStatus::Playlist,
+            LoopStatus::Playlist => LoopStatus::Track,
+            LoopStatus::Track => LoopStatus::None
+        }
+    }
+}
+
+impl From<String> for LoopStatus {
+    fn from(loop_status: String) -> LoopStatus {
+        match loop_status.as_str() {
+            "Track" => LoopStatus::Track,
+            "Playlist" => LoopStatus::Playlist,
+            _ => LoopStatus::None
+        }
+    }
+}
+
 /// Song metadata exposed by an MPRIS-compatible player.
 ///
 /// # Examples
@@ -69,7 +113,9 @@ pub struct MprisPlayerMetadata {
     /// List of artists contributing to the current track.
     pub artists: Option<Vec<String>>,
     /// Title of the currently playing track.
-    pub title:   Option<String>
+    pub title:   Option<String>,
+    /// Track length taken from `mpris:length`.
+    pub length:  Option<Duration>
 }
 
 impl Display for MprisPlayerMetadata {
@@ -98,13 +144,27 @@ impl From<HashMap<String, OwnedValue>> for MprisPlayerMetadata {
             None => None
         };
 
+        // Players disagree on whether the length is signed or unsigned.
+        let length = value.get("mpris:length").and_then(|entry| {
+            i64::try_from(entry.clone())
+                .ok()
+                .and_then(duration_from_micros)
+                .or_else(|| u64::try_from(entry.clone()).ok().map(Duration::from_micros))
+        });
+
         Self {
             artists,
-            title
+            title,
+            length
         }
     }
 }
 
+/// Converts an MPRIS microsecond value, rejecting negative offsets.
+pub(crate) fn duration_from_micros(micros: i64) -> Option<Duration> {
+    u64::try_from(micros).ok().map(Duration::from_micros)
+}
+
 /// Representation of a single MPRIS player instance known to the service.
 #[derive(Debug, Clone)]
 pub struct MprisPlayerData {
@@ -116,6 +176,14 @@ pub struct MprisPlayerData {
     pub volume:       Option<f64>,
     /// Current playback status as reported by the player.
     pub state:        PlaybackStatus,
+    /// Playback position at the time of the last poll.
+    pub position:     Option<Duration>,
+    /// Whether the player accepts transport commands at all.
+    pub can_control:  bool,
+    /// Shuffle state, `None` when the player does not expose it.
+    pub shuffle:      Option<bool>,
+    /// Repeat mode, `None` when the player does not expose it.
+    pub loop_status:  Option<LoopStatus>,
     pub(crate) proxy: MprisPlayerProxy<'static>
 }
 
@@ -129,5 +197,38 @@ pub enum MprisPlayerEvent {
     /// Volume for a specific service changed.
     Volume(String, Option<f64>),
     /// Playback state for a specific service changed.
-    State(String, PlaybackStatus)
+    State(String, PlaybackStatus),
+    /// Polled playback position for a specific service.
+    Position(String, Option<Duration>),
+    /// Shuffle state for a specific service changed.
+    Shuffle(String, Option<bool>),
+    /// Repeat mode for a specific service changed.
+    LoopStatus(String, Option<LoopStatus>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loop_status_cycles_back_to_none() {
+        let mut status = LoopStatus::None;
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            status = status.next();
+            seen.push(status);
+        }
+        assert_eq!(
+            seen,
+            [LoopStatus::Playlist, LoopStatus::Track, LoopStatus::None]
+        );
+    }
+
+    #[test]
+    fn loop_status_round_trips_property_values() {
+        for status in [LoopStatus::None, LoopStatus::Track, LoopStatus::Playlist] {
+            assert_eq!(LoopStatus::from(status.as_str().to_string()), status);
+        }
+        assert_eq!(LoopStatus::from("bogus".to_string()), LoopStatus::None);
+    }
 }
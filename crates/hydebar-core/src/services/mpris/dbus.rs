@@ -21,6 +21,7 @@ pub trait MprisPlayer {
     fn next(&self) -> Result<()>;
     fn play_pause(&self) -> Result<()>;
     fn previous(&self) -> Result<()>;
+    fn stop(&self) -> Result<()>;
 
     #[zbus(property)]
     fn playback_status(&self) -> Result<String>;
@@ -32,4 +33,14 @@ pub trait MprisPlayer {
     fn volume(&self) -> Result<f64>;
     #[zbus(property)]
     fn can_control(&self) -> Result<bool>;
+    #[zbus(property)]
+    fn shuffle(&self) -> Result<bool>;
+    #[zbus(property)]
+    fn set_shuffle(&self, shuffle: bool) -> Result<()>;
+    #[zbus(property)]
+    fn loop_status(&self) -> Result<String>;
+    #[zbus(property)]
+    fn set_loop_status(&self, status: &str) -> Result<()>;
+    #[zbus(property(emits_changed_signal = "false"))]
+    fn position(&self) -> Result<i64>;
 }
@@ -1,11 +1,13 @@
-use std::{pin::Pin, sync::Arc};
+use std::{pin::Pin, sync::Arc, time::Duration};
 
 use futures::{Stream, StreamExt, future::join_all, stream::SelectAll};
 use masterror::{AppError, AppResult};
 use zbus::{Connection, fdo::DBusProxy};
 
 use super::{
-    data::{MprisPlayerData, MprisPlayerMetadata, PlaybackStatus},
+    data::{
+        LoopStatus, MprisPlayerData, MprisPlayerMetadata, PlaybackStatus, duration_from_micros
+    },
     dbus::MprisPlayerProxy
 };
 
@@ -23,7 +25,11 @@ pub(crate) enum IpcEvent {
     /// Volume for `service` changed.
     Volume(String, Option<f64>),
     /// Playback state for `service` changed.
-    State(String, PlaybackStatus)
+    State(String, PlaybackStatus),
+    /// Shuffle state for `service` changed.
+    Shuffle(String, Option<bool>),
+    /// Repeat mode for `service` changed.
+    LoopStatus(String, Option<LoopStatus>)
 }
 
 /// Combined event stream type returned by [`build_event_stream`].
@@ -68,12 +74,20 @@ pub(crate) async fn fetch_players(conn: &Connection, names: &[String]) -> Vec<Mp
                     .await
                     .map(PlaybackStatus::from)
                     .unwrap_or_default();
+                let position = fetch_position(&proxy).await;
+                let can_control = proxy.can_control().await.unwrap_or_default();
+                let shuffle = proxy.shuffle().await.ok();
+                let loop_status = proxy.loop_status().await.map(LoopStatus::from).ok();
 
                 Some(MprisPlayerData {
                     service: service.to_string(),
                     metadata,
                     volume,
                     state,
+                    position,
+                    can_control,
+                    shuffle,
+                    loop_status,
                     proxy
                 })
             }
@@ -86,6 +100,12 @@ pub(crate) async fn fetch_players(conn: &Connection, names: &[String]) -> Vec<Mp
     .collect()
 }
 
+/// Reads the current playback position; `Position` is never signalled, so
+/// callers have to poll it.
+pub(crate) async fn fetch_position(proxy: &MprisPlayerProxy<'static>) -> Option<Duration> {
+    proxy.position().await.ok().and_then(duration_from_micros)
+}
+
 /// Builds a stream that emits [`IpcEvent`] values for all active players.
 pub(crate) async fn build_event_stream(conn: &Connection) -> AppResult<EventStream> {
     let dbus = DBusProxy::new(conn)
@@ -191,6 +211,58 @@ pub(crate) async fn build_event_stream(conn: &Connection) -> AppResult<EventStre
         ) as Pin<Box<dyn Stream<Item = IpcEvent> + Send>>);
     }
 
+    for entry in &data {
+        let service = entry.service.clone();
+        let shuffle = entry.shuffle;
+
+        combined.push(
+            Box::pin(
+                entry
+                    .proxy
+                    .receive_shuffle_changed()
+                    .await
+                    .filter_map(move |signal| {
+                        let service = service.clone();
+
+                        async move {
+                            let new_shuffle = signal.get().await.ok();
+                            if new_shuffle == shuffle {
+                                None
+                            } else {
+                                Some(IpcEvent::Shuffle(service, new_shuffle))
+                            }
+                        }
+                    })
+            ) as Pin<Box<dyn Stream<Item = IpcEvent> + Send>>
+        );
+    }
+
+    for entry in &data {
+        let service = entry.service.clone();
+        let loop_status = entry.loop_status;
+
+        combined.push(
+            Box::pin(
+                entry
+                    .proxy
+                    .receive_loop_status_changed()
+                    .await
+                    .filter_map(move |signal| {
+                        let service = service.clone();
+
+                        async move {
+                            let new_status = signal.get().await.map(LoopStatus::from).ok();
+                            if new_status == loop_status {
+                                None
+                            } else {
+                                Some(IpcEvent::LoopStatus(service, new_status))
+                            }
+                        }
+                    })
+            ) as Pin<Box<dyn Stream<Item = IpcEvent> + Send>>
+        );
+    }
+
     Ok(combined)
 }
 
@@ -1,9 +1,15 @@
 mod backend;
 mod data;
 mod service;
+mod throughput;
 
-pub use backend::{NetworkBackend, iwd::IwdDbus, network_manager::NetworkDbus};
+pub use backend::{
+    NetworkBackend,
+    iwd::IwdDbus,
+    network_manager::{DEFAULT_SCAN_CACHE_TTL, NetworkDbus, ScanCache}
+};
 pub use service::{
     AccessPoint, ActiveConnectionInfo, ConnectivityState, DeviceState, KnownConnection,
-    NetworkCommand, NetworkData, NetworkEvent, NetworkService, NetworkServiceError, Vpn
+    NetworkCommand, NetworkData, NetworkEvent, NetworkService, NetworkServiceError, Vpn, VpnKind
 };
+pub use throughput::Throughput;
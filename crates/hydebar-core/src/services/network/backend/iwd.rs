@@ -101,6 +101,8 @@ impl NetworkBackend for IwdDbus<'_> {
             wireless_access_points,
             known_connections,
             scanning_nearby_wifi: is_scanning,
+            throughput: None,
+            refresh_failed: false,
             last_error: None
         })
     }
@@ -740,12 +742,27 @@ impl IwdDbus<'_> {
             info.push(ActiveConnectionInfo::WiFi {
                 id:       ssid.clone(),
                 name:     ssid,
-                strength: (s / 100 + 100) as u8
+                strength: (s / 100 + 100) as u8,
+                iface:    self.network_interface(&net).await
             });
         }
         Ok(info)
     }
 
+    /// Kernel interface name of the device `net` is reached through.
+    async fn network_interface(&self, net: &NetworkProxy<'_>) -> Option<String> {
+        let device = DeviceProxy::builder(self.inner().connection())
+            .destination("net.connman.iwd")
+            .ok()?
+            .path(net.device().await.ok()?)
+            .ok()?
+            .build()
+            .await
+            .ok()?;
+
+        device.name().await.ok()
+    }
+
     /// List all wireless (station-mode) devices
     pub async fn wireless_devices(&self) -> AppResult<Vec<DeviceProxy>> {
         let devices = self.devices().await?;
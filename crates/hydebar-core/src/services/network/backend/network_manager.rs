@@ -1,4 +1,6 @@
-use std::{collections::HashMap, ops::Deref};
+mod scan_cache;
+
+use std::{collections::HashMap, ops::Deref, sync::Arc};
 
 use iced::futures::{
     Stream, StreamExt,
@@ -7,6 +9,7 @@ use iced::futures::{
 use itertools::Itertools;
 use log::{debug, warn};
 use masterror::{AppError, AppResult};
+pub use scan_cache::{DEFAULT_SCAN_CACHE_TTL, ScanCache};
 use tokio::process::Command;
 use zbus::{
     Result, proxy,
@@ -18,12 +21,12 @@ use crate::services::{
     bluetooth::BluetoothService,
     network::{
         AccessPoint, ActiveConnectionInfo, ConnectivityState, DeviceState, KnownConnection,
-        NetworkBackend, NetworkData, NetworkEvent, Vpn
+        NetworkBackend, NetworkData, NetworkEvent, Vpn, VpnKind
     }
 };
 
 #[derive(Clone)]
-pub struct NetworkDbus<'a>(NetworkManagerProxy<'a>);
+pub struct NetworkDbus<'a>(NetworkManagerProxy<'a>, Arc<ScanCache>);
 
 impl NetworkBackend for NetworkDbus<'_> {
     async fn initialize_data(&self) -> AppResult<NetworkData> {
@@ -62,6 +65,8 @@ impl NetworkBackend for NetworkDbus<'_> {
             wireless_access_points,
             known_connections,
             scanning_nearby_wifi: false,
+            throughput: None,
+            refresh_failed: false,
             last_error: None
         })
     }
@@ -79,7 +84,7 @@ impl NetworkBackend for NetworkDbus<'_> {
             debug!("Bluetooth rfkill set successfully");
         }
 
-        let nm = NetworkDbus::new(self.0.inner().connection()).await?;
+        let nm = NetworkDbus::new(self.0.inner().connection(), Arc::clone(&self.1)).await?;
         nm.set_wireless_enabled(!enable)
             .await
             .map_err(|e| AppError::internal(format!("Failed to set wireless enabled: {}", e)))?;
@@ -88,29 +93,7 @@ impl NetworkBackend for NetworkDbus<'_> {
     }
 
     async fn scan_nearby_wifi(&self) -> AppResult<()> {
-        for device_path in self
-            .wireless_access_points()
-            .await?
-            .iter()
-            .map(|ap| ap.path.clone())
-        {
-            let device = WirelessDeviceProxy::builder(self.0.inner().connection())
-                .path(device_path)
-                .map_err(|e| {
-                    AppError::internal(format!("Failed to set WirelessDeviceProxy path: {}", e))
-                })?
-                .build()
-                .await
-                .map_err(|e| {
-                    AppError::internal(format!("Failed to build WirelessDeviceProxy: {}", e))
-                })?;
-
-            device
-                .request_scan(HashMap::new())
-                .await
-                .map_err(|e| AppError::internal(format!("Failed to request WiFi scan: {}", e)))?;
-        }
-
+        self.scan_wireless_access_points(true).await?;
         Ok(())
     }
 
@@ -260,12 +243,13 @@ impl<'a> Deref for NetworkDbus<'a> {
 }
 
 impl<'a> NetworkDbus<'a> {
-    pub async fn new(conn: &zbus::Connection) -> AppResult<Self> {
+    /// Connects to NetworkManager, keeping scan results in `scan_cache`.
+    pub async fn new(conn: &zbus::Connection, scan_cache: Arc<ScanCache>) -> AppResult<Self> {
         let nm = NetworkManagerProxy::new(conn).await.map_err(|e| {
             AppError::internal(format!("Failed to create NetworkManagerProxy: {}", e))
         })?;
 
-        Ok(Self(nm))
+        Ok(Self(nm, scan_cache))
     }
 }
 
@@ -343,6 +327,7 @@ impl<'a> NetworkDbus<'a> {
                     async move {
                         let current_devices = backend.wireless_devices().await?;
                         if current_devices != devices {
+                            backend.1.clear();
                             let wifi_present = backend.wifi_device_present().await?;
                             let wireless_access_points =
                                 backend.wireless_access_points().await?;
@@ -430,9 +415,12 @@ impl<'a> NetworkDbus<'a> {
                     .await
                     .then({
                         let backend = self.clone();
+                        let device_path = access_point.device_path.clone();
                         move |_| {
                             let backend = backend.clone();
+                            let device_path = device_path.clone();
                             async move {
+                                backend.1.invalidate(&device_path);
                                 let wireless_access_points =
                                     backend.wireless_access_points().await?;
                                 debug!("access_points_changed {wireless_access_points:?}");
@@ -629,7 +617,8 @@ impl<'a> NetworkDbus<'a> {
                                     "Failed to get wired device speed: {}",
                                     e
                                 ))
-                            })?
+                            })?,
+                            iface: device.interface().await.ok()
                         });
                     }
                     Some(DeviceType::Wifi) => {
@@ -686,7 +675,8 @@ impl<'a> NetworkDbus<'a> {
                                     })?
                                 )
                                 .into_owned(),
-                                strength: access_point.strength().await.unwrap_or_default()
+                                strength: access_point.strength().await.unwrap_or_default(),
+                                iface:    device.interface().await.ok()
                             });
                         }
                     }
@@ -753,34 +743,14 @@ impl<'a> NetworkDbus<'a> {
                 continue;
             };
 
-            let wifi = s.get("802-11-wireless");
-
-            if wifi.is_some() {
-                let ssid =
-                    s.get("connection")
-                        .and_then(|c| c.get("id"))
-                        .map(|s| match s.deref() {
-                            Value::Str(v) => v.to_string(),
-                            _ => "".to_string()
-                        });
-
-                if let Some(cur_ssid) = ssid {
-                    known_ssid.push(cur_ssid);
-                }
-            } else if s.contains_key("vpn") {
-                let id = s
-                    .get("connection")
-                    .and_then(|c| c.get("id"))
-                    .map(|v| match v.deref() {
-                        Value::Str(v) => v.to_string(),
-                        _ => "".to_string()
-                    });
-
-                if let Some(id) = id {
-                    known_vpn.push(Vpn {
-                        name: id, path: c
-                    });
-                }
+            match SavedConnection::from_settings(&s) {
+                Some(SavedConnection::WiFi(ssid)) => known_ssid.push(ssid),
+                Some(SavedConnection::Vpn(name, kind)) => known_vpn.push(Vpn {
+                    name,
+                    path: c,
+                    kind
+                }),
+                None => {}
             }
         }
         let known_connections: Vec<_> = wireless_access_points
@@ -823,11 +793,21 @@ impl<'a> NetworkDbus<'a> {
         Ok(wireless_devices)
     }
 
+    /// Access points of every wireless device, reusing results of a scan
+    /// still held by the scan cache.
     pub async fn wireless_access_points(&self) -> AppResult<Vec<AccessPoint>> {
+        self.scan_wireless_access_points(false).await
+    }
+
+    async fn scan_wireless_access_points(&self, force: bool) -> AppResult<Vec<AccessPoint>> {
         let wireless_devices = self.wireless_devices().await?;
         let wireless_access_point_futures: Vec<_> = wireless_devices
             .into_iter()
             .map(|path| async move {
+                if !force && let Some(access_points) = self.1.get(&path) {
+                    return Ok(access_points);
+                }
+
                 let device = DeviceProxy::builder(self.0.inner().connection())
                     .path(&path)
                     .map_err(|e| {
@@ -923,11 +903,12 @@ impl<'a> NetworkDbus<'a> {
                     );
                 }
 
-                let aps = aps
+                let aps: Vec<_> = aps
                     .into_values()
                     .sorted_by(|a, b| b.strength.cmp(&a.strength))
                     .collect();
 
+                self.1.insert(path, aps.clone());
                 Ok(aps)
             })
             .collect();
@@ -1086,6 +1067,9 @@ pub trait Device {
     #[zbus(property)]
     fn device_type(&self) -> Result<u32>;
 
+    #[zbus(property)]
+    fn interface(&self) -> Result<String>;
+
     #[zbus(property)]
     fn available_connections(&self) -> Result<Vec<OwnedObjectPath>>;
 
@@ -1189,6 +1173,40 @@ trait ConnectionSettings {
     fn get_settings(&self) -> Result<HashMap<String, HashMap<String, OwnedValue>>>;
 }
 
+/// What a saved connection offers to the settings menu.
+#[derive(Debug, PartialEq, Eq)]
+enum SavedConnection {
+    /// A Wi-Fi network, by SSID.
+    WiFi(String),
+    /// A tunnel that can be toggled from the VPN submenu.
+    Vpn(String, VpnKind)
+}
+
+impl SavedConnection {
+    /// Classify a connection from the settings NetworkManager stores for it.
+    /// WireGuard tunnels are plain connections rather than VPN plugins, but
+    /// activate and deactivate the same way.
+    fn from_settings(settings: &HashMap<String, HashMap<String, OwnedValue>>) -> Option<Self> {
+        let id = settings
+            .get("connection")
+            .and_then(|c| c.get("id"))
+            .map(|v| match v.deref() {
+                Value::Str(v) => v.to_string(),
+                _ => "".to_string()
+            })?;
+
+        if settings.contains_key("802-11-wireless") {
+            Some(Self::WiFi(id))
+        } else if settings.contains_key("vpn") {
+            Some(Self::Vpn(id, VpnKind::Plugin))
+        } else if settings.contains_key("wireguard") {
+            Some(Self::Vpn(id, VpnKind::WireGuard))
+        } else {
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1211,4 +1229,39 @@ mod tests {
 
         assert_eq!(ConnectivityState::from(states), ConnectivityState::Full);
     }
+
+    fn settings(kind: &str, id: &str) -> HashMap<String, HashMap<String, OwnedValue>> {
+        HashMap::from([
+            (
+                "connection".to_owned(),
+                HashMap::from([(
+                    "id".to_owned(),
+                    Value::from(id)
+                        .try_to_owned()
+                        .expect("string values convert")
+                )])
+            ),
+            (kind.to_owned(), HashMap::new())
+        ])
+    }
+
+    #[test]
+    fn saved_connection_classifies_settings() {
+        assert_eq!(
+            SavedConnection::from_settings(&settings("802-11-wireless", "home")),
+            Some(SavedConnection::WiFi("home".to_owned()))
+        );
+        assert_eq!(
+            SavedConnection::from_settings(&settings("vpn", "work")),
+            Some(SavedConnection::Vpn("work".to_owned(), VpnKind::Plugin))
+        );
+        assert_eq!(
+            SavedConnection::from_settings(&settings("wireguard", "wg0")),
+            Some(SavedConnection::Vpn("wg0".to_owned(), VpnKind::WireGuard))
+        );
+        assert_eq!(
+            SavedConnection::from_settings(&settings("802-3-ethernet", "wired")),
+            None
+        );
+    }
 }
@@ -0,0 +1,124 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant}
+};
+
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::services::network::AccessPoint;
+
+/// How long a device's scan results are reused before scanning again.
+pub const DEFAULT_SCAN_CACHE_TTL: Duration = Duration::from_secs(10);
+
+type Entries = HashMap<OwnedObjectPath, (Instant, Vec<AccessPoint>)>;
+
+/// Access points found by the last scan of each wireless device.
+///
+/// Scanning blocks until the device reports a new scan, so results are kept
+/// for a short while and shared by every [`NetworkDbus`](super::NetworkDbus)
+/// built from the same cache.
+#[derive(Debug)]
+pub struct ScanCache {
+    ttl:     Duration,
+    entries: Mutex<Entries>
+}
+
+impl Default for ScanCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_SCAN_CACHE_TTL)
+    }
+}
+
+impl ScanCache {
+    /// Creates an empty cache whose entries expire after `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::default()
+        }
+    }
+
+    /// Access points of `device` if it was scanned less than the TTL ago.
+    pub fn get(&self, device: &OwnedObjectPath) -> Option<Vec<AccessPoint>> {
+        self.get_at(device, Instant::now())
+    }
+
+    fn get_at(&self, device: &OwnedObjectPath, now: Instant) -> Option<Vec<AccessPoint>> {
+        self.entries()
+            .get(device)
+            .filter(|(scanned_at, _)| now.saturating_duration_since(*scanned_at) < self.ttl)
+            .map(|(_, access_points)| access_points.clone())
+    }
+
+    /// Stores the result of scanning `device` now.
+    pub fn insert(&self, device: OwnedObjectPath, access_points: Vec<AccessPoint>) {
+        self.insert_at(device, access_points, Instant::now());
+    }
+
+    fn insert_at(&self, device: OwnedObjectPath, access_points: Vec<AccessPoint>, now: Instant) {
+        self.entries().insert(device, (now, access_points));
+    }
+
+    /// Drops the entry of `device` so the next lookup scans it again.
+    pub fn invalidate(&self, device: &OwnedObjectPath) {
+        self.entries().remove(device);
+    }
+
+    /// Drops every entry, e.g. after wireless devices were added or removed.
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Entries> {
+        // Entries are replaced whole, so a poisoned map is still consistent.
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::network::DeviceState;
+
+    fn path(path: &str) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(path).expect("valid object path")
+    }
+
+    fn access_point(ssid: &str) -> AccessPoint {
+        AccessPoint {
+            ssid:        ssid.to_owned(),
+            strength:    70,
+            state:       DeviceState::Activated,
+            public:      false,
+            working:     false,
+            path:        path("/org/freedesktop/NetworkManager/AccessPoint/1"),
+            device_path: path("/org/freedesktop/NetworkManager/Devices/3")
+        }
+    }
+
+    #[test]
+    fn reuses_results_until_the_ttl_expires() {
+        let cache = ScanCache::new(Duration::from_secs(10));
+        let start = Instant::now();
+        let device = path("/org/freedesktop/NetworkManager/Devices/3");
+        let other = path("/org/freedesktop/NetworkManager/Devices/4");
+
+        cache.insert_at(device.clone(), vec![access_point("home")], start);
+        assert_eq!(
+            cache.get_at(&device, start),
+            Some(vec![access_point("home")])
+        );
+        assert_eq!(cache.get_at(&other, start), None);
+
+        let later = start + Duration::from_secs(9);
+        assert!(cache.get_at(&device, later).is_some());
+
+        let expired = start + Duration::from_secs(10);
+        assert_eq!(cache.get_at(&device, expired), None);
+
+        cache.insert_at(device.clone(), Vec::new(), expired);
+        cache.invalidate(&device);
+        assert_eq!(cache.get_at(&device, expired), None);
+    }
+}
@@ -1,5 +1,7 @@
 use zbus::zvariant::OwnedObjectPath;
 
+use super::throughput::Throughput;
+
 /// Describes network-related events emitted by the [`NetworkService`].
 ///
 /// # Examples
@@ -34,7 +36,11 @@ pub enum NetworkEvent {
     /// Requests a password for the given SSID.
     RequestPasswordForSSID(String),
     /// Indicates that the backend is scanning for Wi-Fi networks.
-    ScanningNearbyWifi
+    ScanningNearbyWifi,
+    /// Carries the full state re-read from the backend.
+    Snapshot(NetworkData),
+    /// Reports that re-reading the backend state failed.
+    RefreshFailed(NetworkServiceError)
 }
 
 /// Commands accepted by the [`NetworkService`].
@@ -71,7 +77,10 @@ pub enum NetworkCommand {
     /// Request connection to an access point.
     SelectAccessPoint((AccessPoint, Option<String>)),
     /// Toggle a VPN connection.
-    ToggleVpn(Vpn)
+    ToggleVpn(Vpn),
+    /// Re-read the whole state from the backend, for when a change was
+    /// missed or has not been signalled yet.
+    RefreshState
 }
 
 /// Collection of data maintained by the [`NetworkService`].
@@ -101,6 +110,11 @@ pub struct NetworkData {
     pub connectivity:           ConnectivityState,
     /// Whether the backend is scanning for Wi-Fi.
     pub scanning_nearby_wifi:   bool,
+    /// Traffic rates of the active interface, while they are sampled.
+    pub throughput:             Option<Throughput>,
+    /// Whether the last refresh of the backend state failed; cleared by the
+    /// next successful one.
+    pub refresh_failed:         bool,
     /// The last error encountered by the service, if any.
     pub last_error:             Option<NetworkServiceError>
 }
@@ -142,19 +156,31 @@ pub struct AccessPoint {
 /// ```
 /// use std::convert::TryFrom;
 ///
-/// use hydebar_core::services::network::Vpn;
+/// use hydebar_core::services::network::{Vpn, VpnKind};
 /// use zbus::zvariant::OwnedObjectPath;
 ///
 /// let vpn = Vpn {
 ///     name: "work".into(),
-///     path: OwnedObjectPath::try_from("/").unwrap()
+///     path: OwnedObjectPath::try_from("/").unwrap(),
+///     kind: VpnKind::WireGuard
 /// };
 /// assert_eq!(vpn.name, "work");
 /// ```
 #[derive(Debug, Clone)]
 pub struct Vpn {
     pub name: String,
-    pub path: OwnedObjectPath
+    pub path: OwnedObjectPath,
+    pub kind: VpnKind
+}
+
+/// Tunnel technology behind a [`Vpn`] entry.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum VpnKind {
+    /// Connection handled by a NetworkManager VPN plugin such as OpenVPN.
+    #[default]
+    Plugin,
+    /// WireGuard interface configured directly in NetworkManager.
+    WireGuard
 }
 
 /// Known connections stored by the backend.
@@ -203,12 +229,16 @@ pub enum KnownConnection {
 pub enum ActiveConnectionInfo {
     Wired {
         name:  String,
-        speed: u32
+        speed: u32,
+        /// Kernel interface name, when the backend reports it.
+        iface: Option<String>
     },
     WiFi {
         id:       String,
         name:     String,
-        strength: u8
+        strength: u8,
+        /// Kernel interface name, when the backend reports it.
+        iface:    Option<String>
     },
     Vpn {
         name:        String,
@@ -244,6 +274,35 @@ impl ActiveConnectionInfo {
             } => name.clone()
         }
     }
+
+    /// Kernel interface carrying the connection, `None` for VPNs and when
+    /// the backend did not report it.
+    ///
+    /// # Examples
+    /// ```
+    /// use hydebar_core::services::network::ActiveConnectionInfo;
+    ///
+    /// let info = ActiveConnectionInfo::Wired {
+    ///     name:  "Wired connection 1".into(),
+    ///     speed: 1000,
+    ///     iface: Some("eth0".into())
+    /// };
+    /// assert_eq!(info.iface(), Some("eth0"));
+    /// ```
+    #[must_use]
+    pub fn iface(&self) -> Option<&str> {
+        match self {
+            Self::Wired {
+                iface, ..
+            }
+            | Self::WiFi {
+                iface, ..
+            } => iface.as_deref(),
+            Self::Vpn {
+                ..
+            } => None
+        }
+    }
 }
 
 /// Errors surfaced by the [`NetworkService`].
@@ -1,21 +1,30 @@
-use std::{any::TypeId, ops::Deref, time::Duration};
+use std::{any::TypeId, ops::Deref, sync::Arc};
 
 use iced::{
     Subscription, Task,
     futures::{Stream, StreamExt, TryFutureExt},
     stream::channel
 };
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use masterror::{AppError, AppResult};
 use tokio::time::sleep;
 use zbus::zvariant::OwnedObjectPath;
 
-use super::backend::{NetworkBackend, iwd::IwdDbus, network_manager::NetworkDbus};
 pub use super::data::{
     AccessPoint, ActiveConnectionInfo, ConnectivityState, DeviceState, KnownConnection,
-    NetworkCommand, NetworkData, NetworkEvent, NetworkServiceError, Vpn
+    NetworkCommand, NetworkData, NetworkEvent, NetworkServiceError, Vpn, VpnKind
+};
+use super::{
+    backend::{
+        NetworkBackend,
+        iwd::IwdDbus,
+        network_manager::{NetworkDbus, ScanCache}
+    },
+    throughput::ThroughputSampler
+};
+use crate::services::{
+    ReadOnlyService, Service, ServiceEvent, ServiceEventPublisher, backoff::Backoff
 };
-use crate::services::{ReadOnlyService, Service, ServiceEvent, ServiceEventPublisher};
 
 #[derive(Debug, Clone)]
 /// Reactive service responsible for keeping track of the system network state.
@@ -30,7 +39,10 @@ use crate::services::{ReadOnlyService, Service, ServiceEvent, ServiceEventPublis
 pub struct NetworkService {
     data:           NetworkData,
     conn:           zbus::Connection,
-    backend_choice: BackendChoice
+    backend_choice: BackendChoice,
+    throughput:     ThroughputSampler,
+    /// Wi-Fi scan results shared by the listener and every command.
+    scan_cache:     Arc<ScanCache>
 }
 
 impl Deref for NetworkService {
@@ -42,9 +54,11 @@ impl Deref for NetworkService {
 }
 
 enum State {
-    Init,
+    Init(Backoff),
     Active(zbus::Connection, BackendChoice),
-    Error
+    Error(Backoff),
+    /// Retries were exhausted; the service stays unavailable.
+    Disabled
 }
 
 impl ReadOnlyService for NetworkService {
@@ -106,6 +120,19 @@ impl ReadOnlyService for NetworkService {
                 self.data.wireless_access_points = wireless_access_points;
             }
             NetworkEvent::RequestPasswordForSSID(_) => {}
+            NetworkEvent::Snapshot(data) => {
+                // A scan requested alongside the refresh finishes with its own
+                // event, which the snapshot must not pre-empt.
+                let scanning_nearby_wifi = self.data.scanning_nearby_wifi;
+                let throughput = self.data.throughput;
+                self.data = data;
+                self.data.scanning_nearby_wifi |= scanning_nearby_wifi;
+                self.data.throughput = throughput;
+            }
+            NetworkEvent::RefreshFailed(error) => {
+                self.data.refresh_failed = true;
+                self.data.last_error = Some(error);
+            }
         }
     }
 
@@ -115,7 +142,7 @@ impl ReadOnlyService for NetworkService {
         Subscription::run_with_id(
             id,
             channel(50, async |mut output| {
-                NetworkService::listen(&mut output).await;
+                NetworkService::listen(&mut output, Arc::default()).await;
             })
         )
     }
@@ -128,25 +155,35 @@ enum BackendChoice {
 }
 
 impl BackendChoice {
-    fn with_connection(self, conn: zbus::Connection) -> BackendChoiceWithConnection {
+    fn with_connection(
+        self,
+        conn: zbus::Connection,
+        scan_cache: Arc<ScanCache>
+    ) -> BackendChoiceWithConnection {
         BackendChoiceWithConnection {
             choice: self,
-            conn
+            conn,
+            scan_cache
         }
     }
 }
 
 struct BackendChoiceWithConnection {
-    choice: BackendChoice,
-    conn:   zbus::Connection
+    choice:     BackendChoice,
+    conn:       zbus::Connection,
+    scan_cache: Arc<ScanCache>
+}
+
+impl BackendChoiceWithConnection {
+    async fn network_manager(&self) -> AppResult<NetworkDbus<'static>> {
+        NetworkDbus::new(&self.conn, Arc::clone(&self.scan_cache)).await
+    }
 }
 
 impl NetworkBackend for BackendChoiceWithConnection {
     async fn initialize_data(&self) -> AppResult<NetworkData> {
         match self.choice {
-            BackendChoice::NetworkManager => {
-                NetworkDbus::new(&self.conn).await?.initialize_data().await
-            }
+            BackendChoice::NetworkManager => self.network_manager().await?.initialize_data().await,
             BackendChoice::Iwd => IwdDbus::new(&self.conn).await?.initialize_data().await
         }
     }
@@ -154,7 +191,7 @@ impl NetworkBackend for BackendChoiceWithConnection {
     async fn set_airplane_mode(&self, enable: bool) -> AppResult<()> {
         match self.choice {
             BackendChoice::NetworkManager => {
-                NetworkDbus::new(&self.conn)
+                self.network_manager()
                     .await?
                     .set_airplane_mode(enable)
                     .await
@@ -171,7 +208,7 @@ impl NetworkBackend for BackendChoiceWithConnection {
     async fn scan_nearby_wifi(&self) -> AppResult<()> {
         match self.choice {
             BackendChoice::NetworkManager => {
-                NetworkDbus::new(&self.conn).await?.scan_nearby_wifi().await
+                self.network_manager().await?.scan_nearby_wifi().await
             }
             BackendChoice::Iwd => IwdDbus::new(&self.conn).await?.scan_nearby_wifi().await
         }
@@ -180,10 +217,7 @@ impl NetworkBackend for BackendChoiceWithConnection {
     async fn set_wifi_enabled(&self, enable: bool) -> AppResult<()> {
         match self.choice {
             BackendChoice::NetworkManager => {
-                NetworkDbus::new(&self.conn)
-                    .await?
-                    .set_wifi_enabled(enable)
-                    .await
+                self.network_manager().await?.set_wifi_enabled(enable).await
             }
             BackendChoice::Iwd => {
                 IwdDbus::new(&self.conn)
@@ -201,7 +235,7 @@ impl NetworkBackend for BackendChoiceWithConnection {
     ) -> AppResult<()> {
         match self.choice {
             BackendChoice::NetworkManager => {
-                NetworkDbus::new(&self.conn)
+                self.network_manager()
                     .await?
                     .select_access_point(ap, password)
                     .await
@@ -222,7 +256,7 @@ impl NetworkBackend for BackendChoiceWithConnection {
     ) -> AppResult<Vec<KnownConnection>> {
         match self.choice {
             BackendChoice::NetworkManager => {
-                NetworkDbus::new(&self.conn)
+                self.network_manager()
                     .await?
                     .set_vpn(connection_path, enable)
                     .await
@@ -235,10 +269,7 @@ impl NetworkBackend for BackendChoiceWithConnection {
     async fn known_connections(&self) -> AppResult<Vec<KnownConnection>> {
         match self.choice {
             BackendChoice::NetworkManager => {
-                NetworkDbus::new(&self.conn)
-                    .await?
-                    .known_connections()
-                    .await
+                self.network_manager().await?.known_connections().await
             }
             BackendChoice::Iwd => IwdDbus::new(&self.conn).await?.known_connections().await
         }
@@ -267,6 +298,17 @@ impl NetworkService {
         self.data.last_error = Some(error);
     }
 
+    /// Updates the traffic rates from the counters of the first wired or
+    /// Wi-Fi connection's interface.
+    pub fn sample_throughput(&mut self) {
+        let iface = self
+            .data
+            .active_connections
+            .iter()
+            .find_map(ActiveConnectionInfo::iface);
+        self.data.throughput = self.throughput.sample(iface);
+    }
+
     async fn consume_network_events<S, P>(mut events: S, publisher: &mut P) -> AppResult<()>
     where
         S: Stream<Item = AppResult<NetworkEvent>> + Unpin,
@@ -291,16 +333,20 @@ impl NetworkService {
         Ok(())
     }
 
-    async fn start_listening<P>(state: State, publisher: &mut P) -> State
+    async fn start_listening<P>(
+        state: State,
+        publisher: &mut P,
+        scan_cache: &Arc<ScanCache>
+    ) -> State
     where
         P: ServiceEventPublisher<Self> + Send
     {
         match state {
-            State::Init => match zbus::Connection::system().await {
+            State::Init(backoff) => match zbus::Connection::system().await {
                 Ok(conn) => {
                     info!("Connecting to backend");
                     let maybe_backend: Result<(NetworkData, BackendChoice), _> =
-                        match NetworkDbus::new(&conn)
+                        match NetworkDbus::new(&conn, Arc::clone(scan_cache))
                             .and_then(|nm| async move { nm.initialize_data().await })
                             .await
                         {
@@ -336,7 +382,9 @@ impl NetworkService {
                                 .send(ServiceEvent::Init(NetworkService {
                                     data,
                                     conn: conn.clone(),
-                                    backend_choice: choice
+                                    backend_choice: choice,
+                                    throughput: ThroughputSampler::default(),
+                                    scan_cache: Arc::clone(scan_cache)
                                 }))
                                 .await;
                             State::Active(conn, choice)
@@ -349,7 +397,7 @@ impl NetworkService {
                             }
                             let error = NetworkServiceError::from(err);
                             let _ = publisher.send(ServiceEvent::Error(error)).await;
-                            State::Error
+                            State::Error(backoff)
                         }
                     }
                 }
@@ -360,7 +408,7 @@ impl NetworkService {
                     ));
                     let _ = publisher.send(ServiceEvent::Error(error)).await;
 
-                    State::Error
+                    State::Error(backoff)
                 }
             },
             State::Active(conn, choice) => {
@@ -368,13 +416,13 @@ impl NetworkService {
 
                 match choice {
                     BackendChoice::NetworkManager => {
-                        let nm = match NetworkDbus::new(&conn).await {
+                        let nm = match NetworkDbus::new(&conn, Arc::clone(scan_cache)).await {
                             Ok(nm) => nm,
                             Err(e) => {
                                 error!("Failed to create NetworkDbus: {e}");
                                 let error = NetworkServiceError::from(e);
                                 let _ = publisher.send(ServiceEvent::Error(error)).await;
-                                return State::Error;
+                                return State::Error(Backoff::default());
                             }
                         };
 
@@ -389,7 +437,7 @@ impl NetworkService {
                                         error!("Network event stream error: {err}");
                                         let error = NetworkServiceError::from(err);
                                         let _ = publisher.send(ServiceEvent::Error(error)).await;
-                                        State::Error
+                                        State::Error(Backoff::default())
                                     }
                                 }
                             }
@@ -398,7 +446,7 @@ impl NetworkService {
                                 let error = NetworkServiceError::from(err);
                                 let _ = publisher.send(ServiceEvent::Error(error)).await;
 
-                                State::Error
+                                State::Error(Backoff::default())
                             }
                         }
                     }
@@ -409,7 +457,7 @@ impl NetworkService {
                                 error!("Failed to create IwdDbus: {err}");
                                 let error = NetworkServiceError::from(err);
                                 let _ = publisher.send(ServiceEvent::Error(error)).await;
-                                return State::Error;
+                                return State::Error(Backoff::default());
                             }
                         };
                         match iwd.subscribe_events().await {
@@ -429,39 +477,68 @@ impl NetworkService {
                                 let error = NetworkServiceError::from(err);
                                 let _ = publisher.send(ServiceEvent::Error(error)).await;
 
-                                State::Error
+                                State::Error(Backoff::default())
                             }
                         }
                     }
                 }
             }
-            State::Error => {
-                error!("Network service error");
+            State::Error(mut backoff) => match backoff.next_delay() {
+                Some(delay) => {
+                    debug!("Retrying network service in {delay:?}");
+                    sleep(delay).await;
 
-                sleep(Duration::from_secs(1)).await;
+                    State::Init(backoff)
+                }
+                None => {
+                    warn!(
+                        "Network service unavailable after {} attempts, giving up",
+                        backoff.attempts()
+                    );
 
-                State::Init
-            }
+                    State::Disabled
+                }
+            },
+            State::Disabled => State::Disabled
         }
     }
 
-    pub async fn listen<P>(publisher: &mut P)
+    /// Follows the network state until the backend gives up, keeping Wi-Fi
+    /// scan results in `scan_cache`.
+    pub async fn listen<P>(publisher: &mut P, scan_cache: Arc<ScanCache>)
     where
         P: ServiceEventPublisher<Self> + Send
     {
-        let mut state = State::Init;
+        let mut state = State::Init(Backoff::default());
 
-        loop {
-            state = Self::start_listening(state, publisher).await;
+        while !matches!(state, State::Disabled) {
+            state = Self::start_listening(state, publisher, &scan_cache).await;
         }
     }
 
     pub async fn run_command(self, command: NetworkCommand) -> ServiceEvent<Self> {
-        let mut bc = self.backend_choice.with_connection(self.conn.clone());
+        let mut backend = self
+            .backend_choice
+            .with_connection(self.conn.clone(), Arc::clone(&self.scan_cache));
 
+        Self::execute(&self.data, &mut backend, command).await
+    }
+
+    /// Runs `command` against `bc`, with `data` as the state it applies to.
+    ///
+    /// Commands only talk to the backend and never touch the event stream of
+    /// [`NetworkService::listen`], which keeps running throughout.
+    async fn execute<B>(
+        data: &NetworkData,
+        bc: &mut B,
+        command: NetworkCommand
+    ) -> ServiceEvent<Self>
+    where
+        B: NetworkBackend
+    {
         match command {
             NetworkCommand::ToggleAirplaneMode => {
-                let airplane_mode = self.airplane_mode;
+                let airplane_mode = data.airplane_mode;
                 debug!("Toggling airplane mode to: {}", !airplane_mode);
                 let result = bc.set_airplane_mode(!airplane_mode).await;
                 let new_state = if result.is_ok() {
@@ -477,7 +554,7 @@ impl NetworkService {
                 ServiceEvent::Update(NetworkEvent::ScanningNearbyWifi)
             }
             NetworkCommand::ToggleWiFi => {
-                let wifi_enabled = self.wifi_enabled;
+                let wifi_enabled = data.wifi_enabled;
                 debug!("Toggling wifi to: {}", !wifi_enabled);
                 let result = bc.set_wifi_enabled(!wifi_enabled).await;
                 let new_state = if result.is_ok() {
@@ -497,7 +574,7 @@ impl NetworkService {
                 ServiceEvent::Update(NetworkEvent::KnownConnections(known_connections))
             }
             NetworkCommand::ToggleVpn(vpn) => {
-                let mut active_vpn = self.active_connections.iter().find_map(|kc| match kc {
+                let mut active_vpn = data.active_connections.iter().find_map(|kc| match kc {
                     ActiveConnectionInfo::Vpn {
                         name,
                         object_path
@@ -516,6 +593,13 @@ impl NetworkService {
 
                 ServiceEvent::Update(NetworkEvent::KnownConnections(known_connections))
             }
+            NetworkCommand::RefreshState => match bc.initialize_data().await {
+                Ok(data) => ServiceEvent::Update(NetworkEvent::Snapshot(data)),
+                Err(err) => {
+                    warn!("Failed to refresh network state: {err}");
+                    ServiceEvent::Update(NetworkEvent::RefreshFailed(err.into()))
+                }
+            }
         }
     }
 }
@@ -536,12 +620,104 @@ impl Service for NetworkService {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use iced::futures::{StreamExt, channel::mpsc, stream};
     use masterror::AppError;
     use tokio::time::timeout;
 
     use super::*;
 
+    /// Backend whose state reads return a fixed result.
+    struct MockBackend {
+        data: Option<NetworkData>
+    }
+
+    impl NetworkBackend for MockBackend {
+        async fn initialize_data(&self) -> AppResult<NetworkData> {
+            self.data
+                .clone()
+                .ok_or_else(|| AppError::internal("backend unreachable"))
+        }
+
+        async fn set_airplane_mode(&self, _: bool) -> AppResult<()> {
+            Ok(())
+        }
+
+        async fn scan_nearby_wifi(&self) -> AppResult<()> {
+            Ok(())
+        }
+
+        async fn set_wifi_enabled(&self, _: bool) -> AppResult<()> {
+            Ok(())
+        }
+
+        async fn select_access_point(
+            &mut self,
+            _: &AccessPoint,
+            _: Option<String>
+        ) -> AppResult<()> {
+            Ok(())
+        }
+
+        async fn known_connections(&self) -> AppResult<Vec<KnownConnection>> {
+            Ok(Vec::new())
+        }
+
+        async fn set_vpn(&self, _: OwnedObjectPath, _: bool) -> AppResult<Vec<KnownConnection>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_state_publishes_backend_snapshot() {
+        let mut backend = MockBackend {
+            data: Some(NetworkData {
+                wifi_present: true,
+                wifi_enabled: true,
+                connectivity: ConnectivityState::Full,
+                ..Default::default()
+            })
+        };
+
+        let event = NetworkService::execute(
+            &NetworkData::default(),
+            &mut backend,
+            NetworkCommand::RefreshState
+        )
+        .await;
+
+        assert!(
+            matches!(
+                &event,
+                ServiceEvent::Update(NetworkEvent::Snapshot(data))
+                    if data.wifi_present
+                        && data.wifi_enabled
+                        && data.connectivity == ConnectivityState::Full
+            ),
+            "unexpected event: {event:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_state_reports_backend_errors() {
+        let mut backend = MockBackend {
+            data: None
+        };
+
+        let event = NetworkService::execute(
+            &NetworkData::default(),
+            &mut backend,
+            NetworkCommand::RefreshState
+        )
+        .await;
+
+        assert!(
+            matches!(&event, ServiceEvent::Update(NetworkEvent::RefreshFailed(_))),
+            "unexpected event: {event:?}"
+        );
+    }
+
     #[tokio::test]
     async fn consume_network_events_stops_on_error() {
         let (mut sender, mut receiver) = mpsc::channel(4);
@@ -577,10 +753,14 @@ mod tests {
 
         let state = timeout(
             Duration::from_secs(2),
-            NetworkService::start_listening(State::Error, &mut sender)
+            NetworkService::start_listening(
+                State::Error(Backoff::default()),
+                &mut sender,
+                &Arc::default()
+            )
         )
         .await
         .expect("network listener should complete after delay");
-        assert!(matches!(state, State::Init));
+        assert!(matches!(state, State::Init(_)));
     }
 }
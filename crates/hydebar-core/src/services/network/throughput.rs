@@ -0,0 +1,146 @@
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, Instant}
+};
+
+/// Where the kernel publishes the traffic counters of every interface.
+const SYS_CLASS_NET: &str = "/sys/class/net";
+
+/// Receive and transmit rates of the active interface, in bytes per second.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Throughput {
+    pub rx: u64,
+    pub tx: u64
+}
+
+/// Bytes per second moved by `delta` bytes over `elapsed`.
+fn rate(delta: u64, elapsed: Duration) -> u64 {
+    if elapsed.is_zero() {
+        return 0;
+    }
+
+    (delta as f64 / elapsed.as_secs_f64()).round() as u64
+}
+
+/// Cumulative byte counters of one interface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Counters {
+    iface: String,
+    rx:    u64,
+    tx:    u64,
+    at:    Instant
+}
+
+fn read_counters(iface: &str) -> io::Result<Counters> {
+    let statistics = Path::new(SYS_CLASS_NET).join(iface).join("statistics");
+    let read = |file: &str| -> io::Result<u64> {
+        fs::read_to_string(statistics.join(file))?
+            .trim()
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    };
+
+    Ok(Counters {
+        iface: iface.to_owned(),
+        rx:    read("rx_bytes")?,
+        tx:    read("tx_bytes")?,
+        at:    Instant::now()
+    })
+}
+
+/// Turns successive counter readings of the active interface into rates.
+#[derive(Debug, Clone, Default)]
+pub struct ThroughputSampler {
+    previous: Option<Counters>
+}
+
+impl ThroughputSampler {
+    /// Rates of `iface` since the previous call. `None` for the first
+    /// reading of an interface, and whenever there is no interface or its
+    /// counters are gone, which also forgets the previous reading.
+    pub fn sample(&mut self, iface: Option<&str>) -> Option<Throughput> {
+        match iface.map(read_counters) {
+            Some(Ok(counters)) => self.record(counters),
+            Some(Err(_)) | None => {
+                self.previous = None;
+                None
+            }
+        }
+    }
+
+    fn record(&mut self, current: Counters) -> Option<Throughput> {
+        // Counters of a renamed or different interface cannot be diffed.
+        let throughput = self
+            .previous
+            .as_ref()
+            .filter(|previous| previous.iface == current.iface)
+            .map(|previous| {
+                let elapsed = current.at.duration_since(previous.at);
+
+                Throughput {
+                    rx: rate(current.rx.saturating_sub(previous.rx), elapsed),
+                    tx: rate(current.tx.saturating_sub(previous.tx), elapsed)
+                }
+            });
+
+        self.previous = Some(current);
+        throughput
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counters(iface: &str, rx: u64, tx: u64, at: Instant) -> Counters {
+        Counters {
+            iface: iface.to_owned(),
+            rx,
+            tx,
+            at
+        }
+    }
+
+    #[test]
+    fn byte_deltas_become_rates_per_second() {
+        let start = Instant::now();
+        let mut sampler = ThroughputSampler::default();
+
+        assert_eq!(sampler.record(counters("wlan0", 1000, 500, start)), None);
+        assert_eq!(
+            sampler.record(counters(
+                "wlan0",
+                2_401_000,
+                600_500,
+                start + Duration::from_secs(2)
+            )),
+            Some(Throughput {
+                rx: 1_200_000,
+                tx: 300_000
+            })
+        );
+    }
+
+    #[test]
+    fn switching_interfaces_resets_the_counters() {
+        let start = Instant::now();
+        let mut sampler = ThroughputSampler::default();
+
+        sampler.record(counters("wlan0", 1000, 500, start));
+        let switched = counters("eth0", 9000, 9000, start + Duration::from_secs(1));
+
+        assert_eq!(sampler.record(switched), None);
+        assert_eq!(
+            sampler.record(counters(
+                "eth0",
+                10_000,
+                9500,
+                start + Duration::from_secs(2)
+            )),
+            Some(Throughput {
+                rx: 1000, tx: 500
+            })
+        );
+    }
+}
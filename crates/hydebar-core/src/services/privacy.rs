@@ -1,5 +1,6 @@
 use super::{ReadOnlyService, ServiceEvent};
 pub mod error;
+pub mod geoclue;
 pub mod inotify;
 pub mod pipewire;
 pub mod publisher;
@@ -17,6 +18,7 @@ pub use publisher::PrivacyEventPublisher;
 use tokio::sync::mpsc::UnboundedReceiver;
 
 use self::{
+    geoclue::{LocationEventSource, LocationWatcher},
     inotify::{WebcamEventSource, WebcamWatcher},
     pipewire::{PipewireEventSource, PipewireListener}
 };
@@ -47,21 +49,23 @@ pub struct ApplicationNode {
 /// Aggregated privacy information exposed to UI consumers.
 #[derive(Debug, Clone)]
 pub struct PrivacyData {
-    nodes:         Vec<ApplicationNode>,
-    webcam_access: i32
+    nodes:           Vec<ApplicationNode>,
+    webcam_access:   i32,
+    location_access: bool
 }
 
 impl PrivacyData {
     fn new() -> Self {
         Self {
-            nodes:         Vec::new(),
-            webcam_access: is_device_in_use(WEBCAM_DEVICE_PATH)
+            nodes:           Vec::new(),
+            webcam_access:   is_device_in_use(WEBCAM_DEVICE_PATH),
+            location_access: false
         }
     }
 
     /// Returns `true` when no privacy-sensitive resources are currently in use.
     pub fn no_access(&self) -> bool {
-        self.nodes.is_empty() && self.webcam_access == 0
+        self.nodes.is_empty() && self.webcam_access == 0 && !self.location_access
     }
 
     /// Returns `true` when an audio input node is active.
@@ -79,6 +83,11 @@ impl PrivacyData {
     pub fn screenshare_access(&self) -> bool {
         self.nodes.iter().any(|node| node.media == Media::Video)
     }
+
+    /// Returns `true` while GeoClue reports a client using the location.
+    pub fn location_access(&self) -> bool {
+        self.location_access
+    }
 }
 
 /// Service exposing read-only privacy state to interested modules.
@@ -106,28 +115,34 @@ impl PrivacyService {
         publisher.send(event).await
     }
 
+    /// Run one step of the listener state machine. GeoClue is only queried
+    /// when `location` is set.
     pub(crate) async fn start_listening<P>(
         state: State,
-        publisher: &mut P
+        publisher: &mut P,
+        location: bool
     ) -> Result<State, PrivacyError>
     where
         P: PrivacyEventPublisher + Send
     {
         let pipewire = PipewireListener;
         let webcam = WebcamWatcher::new(Path::new(WEBCAM_DEVICE_PATH));
-        Self::start_listening_with_sources(state, publisher, &pipewire, &webcam).await
+        let location = LocationWatcher::new(location);
+        Self::start_listening_with_sources(state, publisher, &pipewire, &webcam, &location).await
     }
 
-    async fn start_listening_with_sources<P, Pipewire, Webcam>(
+    async fn start_listening_with_sources<P, Pipewire, Webcam, Location>(
         state: State,
         publisher: &mut P,
         pipewire_source: &Pipewire,
-        webcam_source: &Webcam
+        webcam_source: &Webcam,
+        location_source: &Location
     ) -> Result<State, PrivacyError>
     where
         P: PrivacyEventPublisher,
         Pipewire: PipewireEventSource,
-        Webcam: WebcamEventSource
+        Webcam: WebcamEventSource,
+        Location: LocationEventSource
     {
         match state {
             State::Init => {
@@ -140,6 +155,18 @@ impl PrivacyService {
                     }
                     Err(err) => return Err(err)
                 };
+                let location = match location_source.subscribe().await {
+                    Ok(stream) => stream,
+                    Err(
+                        err @ PrivacyError::GeoclueUnavailable {
+                            ..
+                        }
+                    ) => {
+                        warn!("{err}");
+                        pending::<PrivacyEvent>().boxed()
+                    }
+                    Err(err) => return Err(err)
+                };
 
                 let data = PrivacyData::new();
                 Self::emit_event(
@@ -152,17 +179,21 @@ impl PrivacyService {
 
                 Ok(State::Active {
                     pipewire,
-                    webcam
+                    webcam,
+                    location
                 })
             }
             State::Active {
                 mut pipewire,
-                mut webcam
+                mut webcam,
+                mut location
             } => {
                 info!("Listening for privacy events");
 
                 let mut webcam_pin = webcam.as_mut();
                 let mut webcam_future = webcam_pin.next().fuse();
+                let mut location_pin = location.as_mut();
+                let mut location_future = location_pin.next().fuse();
 
                 select! {
                     value = pipewire.recv().fuse() => {
@@ -191,11 +222,25 @@ impl PrivacyService {
                             }
                         }
                     }
+                    value = location_future => {
+                        match value {
+                            Some(event) => {
+                                Self::emit_event(publisher, ServiceEvent::Update(event)).await?;
+                            }
+                            None => {
+                                error!("GeoClue listener exited unexpectedly");
+                                return Err(PrivacyError::channel(
+                                    "geoclue listener closed unexpectedly",
+                                ));
+                            }
+                        }
+                    }
                 };
 
                 Ok(State::Active {
                     pipewire,
-                    webcam
+                    webcam,
+                    location
                 })
             }
         }
@@ -206,7 +251,8 @@ pub(crate) enum State {
     Init,
     Active {
         pipewire: UnboundedReceiver<PrivacyEvent>,
-        webcam:   PrivacyStream
+        webcam:   PrivacyStream,
+        location: PrivacyStream
     }
 }
 
@@ -220,7 +266,9 @@ pub enum PrivacyEvent {
     /// The webcam device has been opened by an application.
     WebcamOpen,
     /// The webcam device has been closed by an application.
-    WebcamClose
+    WebcamClose,
+    /// GeoClue started or stopped serving a location to some client.
+    Location(bool)
 }
 
 impl ReadOnlyService for PrivacyService {
@@ -243,6 +291,9 @@ impl ReadOnlyService for PrivacyService {
                 self.data.webcam_access = i32::max(self.data.webcam_access - 1, 0);
                 debug!("Webcam closed {}", self.data.webcam_access);
             }
+            PrivacyEvent::Location(in_use) => {
+                self.data.location_access = in_use;
+            }
         }
     }
 
@@ -255,7 +306,7 @@ impl ReadOnlyService for PrivacyService {
                 let mut state = State::Init;
 
                 loop {
-                    match PrivacyService::start_listening(state, &mut output).await {
+                    match PrivacyService::start_listening(state, &mut output, false).await {
                         Ok(next_state) => {
                             state = next_state;
                         }
@@ -317,10 +368,15 @@ mod tests {
     use tokio::{sync::mpsc::unbounded_channel, time::timeout};
 
     use super::{
-        ApplicationNode, Media, PrivacyEvent, PrivacyService, ServiceEvent, State,
+        ApplicationNode, Media, PrivacyData, PrivacyEvent, PrivacyService, ServiceEvent, State,
         error::PrivacyError
     };
-    use crate::services::privacy::{inotify::WebcamEventSource, pipewire::PipewireEventSource};
+    use crate::services::{
+        ReadOnlyService,
+        privacy::{
+            geoclue::LocationWatcher, inotify::WebcamEventSource, pipewire::PipewireEventSource
+        }
+    };
 
     #[derive(Default)]
     struct TestPipewireSource {
@@ -422,7 +478,8 @@ mod tests {
             state,
             &mut output_tx,
             &pipewire_source,
-            &webcam_source
+            &webcam_source,
+            &LocationWatcher::new(false)
         )
         .await
         .expect("initialisation should succeed");
@@ -434,6 +491,24 @@ mod tests {
         assert!(matches!(event, Ok(Some(ServiceEvent::Init(_)))));
     }
 
+    #[test]
+    fn location_events_toggle_location_access() {
+        let mut service = PrivacyService {
+            data: PrivacyData {
+                nodes:           Vec::new(),
+                webcam_access:   0,
+                location_access: false
+            }
+        };
+
+        service.update(PrivacyEvent::Location(true));
+        assert!(service.location_access());
+        assert!(!service.no_access());
+
+        service.update(PrivacyEvent::Location(false));
+        assert!(service.no_access());
+    }
+
     #[tokio::test]
     async fn init_reports_pipewire_failure() {
         let pipewire_source = TestPipewireSource::failing(PrivacyError::pipewire_mainloop("boom"));
@@ -444,7 +519,8 @@ mod tests {
             State::Init,
             &mut output_tx,
             &pipewire_source,
-            &webcam_source
+            &webcam_source,
+            &LocationWatcher::new(false)
         )
         .await;
         assert!(matches!(result, Err(PrivacyError::PipewireMainloop { .. })));
@@ -463,7 +539,8 @@ mod tests {
             State::Init,
             &mut output_tx,
             &pipewire_source,
-            &webcam_source
+            &webcam_source,
+            &LocationWatcher::new(false)
         )
         .await
         .expect("initialisation should succeed with webcam fallback");
@@ -488,7 +565,8 @@ mod tests {
             State::Init,
             &mut output_tx,
             &pipewire_source,
-            &webcam_source
+            &webcam_source,
+            &LocationWatcher::new(false)
         )
         .await;
         assert!(matches!(result, Err(PrivacyError::Channel { .. })));
@@ -506,7 +584,8 @@ mod tests {
             State::Init,
             &mut output_tx,
             &pipewire_source,
-            &webcam_source
+            &webcam_source,
+            &LocationWatcher::new(false)
         )
         .await
         .expect("initialisation should succeed");
@@ -514,10 +593,12 @@ mod tests {
         let state = match state {
             State::Active {
                 pipewire,
-                webcam
+                webcam,
+                location
             } => State::Active {
                 pipewire,
-                webcam
+                webcam,
+                location
             },
             State::Init => panic!("expected active state")
         };
@@ -540,7 +621,8 @@ mod tests {
                     state,
                     &mut output_tx_clone,
                     &pipewire_source_clone,
-                    &webcam_source_clone
+                    &webcam_source_clone,
+                    &LocationWatcher::new(false)
                 )
             )
             .await;
@@ -576,7 +658,8 @@ mod tests {
             State::Init,
             &mut output_tx,
             &pipewire_source,
-            &webcam_source
+            &webcam_source,
+            &LocationWatcher::new(false)
         )
         .await
         .expect("initialisation should succeed");
@@ -584,10 +667,12 @@ mod tests {
         let state = match state {
             State::Active {
                 pipewire,
-                webcam
+                webcam,
+                location
             } => State::Active {
                 pipewire,
-                webcam
+                webcam,
+                location
             },
             State::Init => panic!("expected active state")
         };
@@ -603,7 +688,8 @@ mod tests {
                     state,
                     &mut output_tx_clone,
                     &pipewire_source_clone,
-                    &webcam_source_clone
+                    &webcam_source_clone,
+                    &LocationWatcher::new(false)
                 )
             )
             .await;
@@ -25,7 +25,10 @@ pub enum PrivacyError {
     Channel { context: Arc<str> },
 
     /// The webcam device is not present on the system.
-    WebcamUnavailable
+    WebcamUnavailable,
+
+    /// GeoClue could not be reached on the system bus.
+    GeoclueUnavailable { context: Arc<str> }
 }
 
 impl std::fmt::Display for PrivacyError {
@@ -69,6 +72,11 @@ impl std::fmt::Display for PrivacyError {
             Self::WebcamUnavailable => {
                 write!(f, "webcam device is unavailable")
             }
+            Self::GeoclueUnavailable {
+                context
+            } => {
+                write!(f, "GeoClue is unavailable: {}", context)
+            }
         }
     }
 }
@@ -122,6 +130,13 @@ impl PrivacyError {
         }
     }
 
+    /// Create a new GeoClue availability error with additional context.
+    pub fn geoclue(context: impl Into<String>) -> Self {
+        Self::GeoclueUnavailable {
+            context: Self::arc_from(context)
+        }
+    }
+
     /// Create a new channel error with contextual information.
     pub fn channel(context: impl Into<String>) -> Self {
         Self::Channel {
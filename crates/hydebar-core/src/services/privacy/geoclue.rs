@@ -0,0 +1,92 @@
+use std::{future::Future, pin::Pin};
+
+use iced::futures::{
+    StreamExt,
+    stream::{self, pending}
+};
+use zbus::proxy;
+
+use crate::services::privacy::{PrivacyError, PrivacyEvent, PrivacyStream};
+
+/// Provides location usage updates.
+pub(crate) trait LocationEventSource {
+    /// Future returned when subscribing to location usage notifications.
+    type Future<'a>: Future<Output = Result<PrivacyStream, PrivacyError>> + Send + 'a
+    where
+        Self: 'a;
+
+    /// Subscribe to location usage notifications.
+    fn subscribe(&self) -> Self::Future<'_>;
+}
+
+#[proxy(
+    interface = "org.freedesktop.GeoClue2.Manager",
+    default_service = "org.freedesktop.GeoClue2",
+    default_path = "/org/freedesktop/GeoClue2/Manager"
+)]
+trait Manager {
+    /// Whether any client is currently using the service.
+    #[zbus(property)]
+    fn in_use(&self) -> zbus::Result<bool>;
+}
+
+/// Follows the `InUse` property of the GeoClue2 manager on the system bus.
+///
+/// When disabled the watcher yields a stream that never produces events, so
+/// systems without GeoClue do not need to run it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LocationWatcher {
+    enabled: bool
+}
+
+impl LocationWatcher {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled
+        }
+    }
+
+    async fn create_stream(&self) -> Result<PrivacyStream, PrivacyError> {
+        if !self.enabled {
+            return Ok(pending::<PrivacyEvent>().boxed());
+        }
+
+        let conn = zbus::Connection::system()
+            .await
+            .map_err(|err| PrivacyError::geoclue(err.to_string()))?;
+        let manager = ManagerProxy::new(&conn)
+            .await
+            .map_err(|err| PrivacyError::geoclue(err.to_string()))?;
+        let in_use = manager
+            .in_use()
+            .await
+            .map_err(|err| PrivacyError::geoclue(err.to_string()))?;
+        let changes = manager.receive_in_use_changed().await;
+
+        // The connection lives as long as the proxy, which the stream owns.
+        let stream = stream::once(async move { PrivacyEvent::Location(in_use) })
+            .chain(changes.filter_map(|change| async move {
+                match change.get().await {
+                    Ok(in_use) => Some(PrivacyEvent::Location(in_use)),
+                    Err(error) => {
+                        log::warn!("Failed to read GeoClue usage: {error}");
+                        None
+                    }
+                }
+            }))
+            .boxed();
+
+        Ok(stream)
+    }
+}
+
+impl LocationEventSource for LocationWatcher {
+    type Future<'a>
+        = Pin<Box<dyn Future<Output = Result<PrivacyStream, PrivacyError>> + Send + 'a>>
+    where
+        Self: 'a;
+
+    fn subscribe(&self) -> Self::Future<'_> {
+        Box::pin(self.create_stream())
+    }
+}
@@ -1,4 +1,4 @@
-use std::{future::Future, ops::Deref, pin::Pin};
+use std::{future::Future, ops::Deref, pin::Pin, time::Duration};
 
 use dbus::{DBusMenuProxy, Layout, StatusNotifierItemProxy};
 use iced::{
@@ -140,7 +140,8 @@ impl TrayService {
     /// Start listening for tray events using the underlying D-Bus watcher.
     ///
     /// The provided `publisher` receives service lifecycle events as they are
-    /// produced by the watcher loop.
+    /// produced by the watcher loop. With a `poll_interval` the registered
+    /// items are also re-read periodically, for watchers that miss signals.
     ///
     /// # Examples
     ///
@@ -148,15 +149,15 @@ impl TrayService {
     /// use hydebar_core::services::{ServiceEvent, tray::TrayService};
     ///
     /// async fn listen() {
-    ///     TrayService::start_listening(|_event: ServiceEvent<TrayService>| async {}).await;
+    ///     TrayService::start_listening(None, |_event: ServiceEvent<TrayService>| async {}).await;
     /// }
     /// ```
-    pub async fn start_listening<F, Fut>(publisher: F)
+    pub async fn start_listening<F, Fut>(poll_interval: Option<Duration>, publisher: F)
     where
         F: FnMut(ServiceEvent<Self>) -> Fut + Send,
         Fut: Future<Output = ()> + Send
     {
-        watcher::start_listening(publisher).await;
+        watcher::start_listening(poll_interval, publisher).await;
     }
 
     pub async fn menu_voice_selected(
@@ -184,6 +185,36 @@ impl TrayService {
         Ok(layout)
     }
 
+    /// Forward a scroll gesture to the item. Items that do not implement
+    /// `Scroll` reject the call, which is expected and only logged.
+    pub async fn scroll(
+        item_proxy: &StatusNotifierItemProxy<'_>,
+        delta: i32,
+        axis: ScrollAxis
+    ) -> AppResult<()> {
+        item_proxy
+            .scroll(delta, axis.as_str())
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to scroll tray item: {}", e)))
+    }
+
+    /// Ask the item to show its own context menu or run its secondary
+    /// action. Wayland hides global pointer coordinates, so the origin is
+    /// passed and the item picks a position.
+    pub async fn activate(item: &impl ItemActivate, activation: ItemActivation) -> AppResult<()> {
+        match activation {
+            ItemActivation::ContextMenu => item.context_menu(0, 0).await,
+            ItemActivation::Secondary => item.secondary_activate(0, 0).await
+        }
+        .map_err(|e| {
+            AppError::internal(format!(
+                "Failed to call {} on tray item: {}",
+                activation.method_name(),
+                e
+            ))
+        })
+    }
+
     pub fn prepare_command(&self, command: TrayCommand) -> Option<TrayCommandFuture> {
         match command {
             TrayCommand::MenuSelected(name, id) => {
@@ -204,6 +235,30 @@ impl TrayService {
                     }
                 }))
             }
+            TrayCommand::Scroll(name, delta, axis) => {
+                let item = self.data.iter().find(|item| item.name == name)?;
+                let proxy = item.item_proxy.clone();
+
+                Some(Box::pin(async move {
+                    if let Err(err) = TrayService::scroll(&proxy, delta, axis).await {
+                        debug!("Tray item {name} ignored scroll: {err}");
+                    }
+                    ServiceEvent::Update(TrayEvent::None)
+                }))
+            }
+            TrayCommand::Activate(name, activation) => {
+                let item = self.data.iter().find(|item| item.name == name)?;
+                let proxy = item.item_proxy.clone();
+
+                Some(Box::pin(async move {
+                    // Items without the method reject the call, which leaves
+                    // the click without effect.
+                    if let Err(err) = TrayService::activate(&proxy, activation).await {
+                        debug!("Tray item {name} ignored activation: {err}");
+                    }
+                    ServiceEvent::Update(TrayEvent::None)
+                }))
+            }
         }
     }
 }
@@ -252,9 +307,67 @@ impl ReadOnlyService for TrayService {
     }
 }
 
+/// Axis of a scroll gesture forwarded to a tray item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollAxis {
+    Vertical,
+    Horizontal
+}
+
+impl ScrollAxis {
+    /// Orientation string expected by the `StatusNotifierItem.Scroll` method.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Vertical => "vertical",
+            Self::Horizontal => "horizontal"
+        }
+    }
+}
+
+/// Item method triggered by a click other than the primary one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemActivation {
+    /// `ContextMenu`, bound to the right button.
+    ContextMenu,
+    /// `SecondaryActivate`, bound to the middle button.
+    Secondary
+}
+
+impl ItemActivation {
+    /// Name of the `StatusNotifierItem` method this activation calls.
+    pub fn method_name(self) -> &'static str {
+        match self {
+            Self::ContextMenu => "ContextMenu",
+            Self::Secondary => "SecondaryActivate"
+        }
+    }
+}
+
+/// Click methods of a `StatusNotifierItem`.
+pub trait ItemActivate {
+    /// Call `ContextMenu` at the given position.
+    fn context_menu(&self, x: i32, y: i32) -> impl Future<Output = zbus::Result<()>> + Send;
+
+    /// Call `SecondaryActivate` at the given position.
+    fn secondary_activate(&self, x: i32, y: i32) -> impl Future<Output = zbus::Result<()>> + Send;
+}
+
+/// [`ItemActivate`] over the item's D-Bus proxy.
+impl ItemActivate for StatusNotifierItemProxy<'_> {
+    fn context_menu(&self, x: i32, y: i32) -> impl Future<Output = zbus::Result<()>> + Send {
+        StatusNotifierItemProxy::context_menu(self, x, y)
+    }
+
+    fn secondary_activate(&self, x: i32, y: i32) -> impl Future<Output = zbus::Result<()>> + Send {
+        StatusNotifierItemProxy::secondary_activate(self, x, y)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum TrayCommand {
-    MenuSelected(String, i32)
+    MenuSelected(String, i32),
+    Scroll(String, i32, ScrollAxis),
+    Activate(String, ItemActivation)
 }
 
 type TrayCommandFuture = Pin<Box<dyn Future<Output = ServiceEvent<TrayService>> + Send + 'static>>;
@@ -268,3 +381,54 @@ impl Service for TrayService {
             .unwrap_or_else(Task::none)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Mutex, PoisonError};
+
+    use super::{ItemActivate, ItemActivation, TrayService};
+
+    /// Records the item methods called on it.
+    #[derive(Default)]
+    struct FakeItem {
+        calls: Mutex<Vec<(&'static str, i32, i32)>>
+    }
+
+    impl FakeItem {
+        fn record(&self, method: &'static str, x: i32, y: i32) {
+            self.calls
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push((method, x, y));
+        }
+    }
+
+    impl ItemActivate for FakeItem {
+        async fn context_menu(&self, x: i32, y: i32) -> zbus::Result<()> {
+            self.record("ContextMenu", x, y);
+            Ok(())
+        }
+
+        async fn secondary_activate(&self, x: i32, y: i32) -> zbus::Result<()> {
+            self.record("SecondaryActivate", x, y);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn activation_calls_the_matching_item_method_at_the_origin() {
+        let item = FakeItem::default();
+
+        TrayService::activate(&item, ItemActivation::Secondary)
+            .await
+            .expect("secondary activation");
+        TrayService::activate(&item, ItemActivation::ContextMenu)
+            .await
+            .expect("context menu");
+
+        assert_eq!(
+            *item.calls.lock().unwrap_or_else(PoisonError::into_inner),
+            [("SecondaryActivate", 0, 0), ("ContextMenu", 0, 0)]
+        );
+    }
+}
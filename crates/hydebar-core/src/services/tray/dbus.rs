@@ -106,6 +106,28 @@ impl StatusNotifierWatcher {
 
         Ok(connection)
     }
+
+    /// Register hydebar as the tray host with whichever watcher owns the
+    /// bus name, so watchers run by another program hand their items over.
+    pub async fn register_host(connection: &Connection) -> AppResult<()> {
+        let host = format!("org.kde.StatusNotifierHost-{}", std::process::id());
+        connection
+            .request_name(host.as_str())
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to request host name: {}", e)))?;
+
+        StatusNotifierWatcherProxy::new(connection)
+            .await
+            .map_err(|e| {
+                AppError::internal(format!(
+                    "Failed to create StatusNotifierWatcherProxy: {}",
+                    e
+                ))
+            })?
+            .register_status_notifier_host(&host)
+            .await
+            .map_err(|e| AppError::internal(format!("Failed to register tray host: {}", e)))
+    }
 }
 
 #[interface(
@@ -189,6 +211,12 @@ pub trait StatusNotifierItem {
 
     #[zbus(property)]
     fn menu(&self) -> zbus::Result<OwnedObjectPath>;
+
+    fn scroll(&self, delta: i32, orientation: &str) -> zbus::Result<()>;
+
+    fn context_menu(&self, x: i32, y: i32) -> zbus::Result<()>;
+
+    fn secondary_activate(&self, x: i32, y: i32) -> zbus::Result<()>;
 }
 
 #[derive(Clone, Debug, Type)]
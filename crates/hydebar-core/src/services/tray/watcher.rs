@@ -1,9 +1,10 @@
-use std::{future::Future, pin::Pin};
+use std::{future::Future, pin::Pin, time::Duration};
 
 use futures::future::pending;
 use iced::futures::{Stream, StreamExt, stream::select_all, stream_select};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use masterror::AppError;
+use tokio::time::{Instant, interval_at};
 
 use super::{
     StatusNotifierItem, TrayData, TrayEvent, TrayService,
@@ -224,7 +225,49 @@ pub(crate) async fn events(conn: &zbus::Connection) -> Result<TrayEventStream, T
     .boxed())
 }
 
-pub(crate) async fn start_listening<F, Fut>(mut publisher: F)
+/// Names of the items the watcher currently lists.
+async fn registered_items(conn: &zbus::Connection) -> Result<Vec<String>, TrayWatcherError> {
+    let watcher = StatusNotifierWatcherProxy::new(conn).await.map_err(|err| {
+        TrayWatcherError::EventStream(AppError::internal(format!(
+            "Failed to create StatusNotifierWatcherProxy: {}",
+            err
+        )))
+    })?;
+
+    watcher
+        .registered_status_notifier_items()
+        .await
+        .map_err(|err| {
+            TrayWatcherError::EventStream(AppError::internal(format!(
+                "Failed to get registered status notifier items: {}",
+                err
+            )))
+        })
+}
+
+/// Publish the items that appeared in `items` or vanished from `known`.
+async fn publish_item_changes<F, Fut>(
+    conn: &zbus::Connection,
+    known: &[String],
+    items: &[String],
+    publisher: &mut F
+) where
+    F: FnMut(ServiceEvent<TrayService>) -> Fut + Send,
+    Fut: Future<Output = ()> + Send
+{
+    for name in known.iter().filter(|name| !items.contains(name)) {
+        publisher(ServiceEvent::Update(TrayEvent::Unregistered(name.clone()))).await;
+    }
+
+    for name in items.iter().filter(|name| !known.contains(name)) {
+        match StatusNotifierItem::new(conn, name.clone()).await {
+            Ok(item) => publisher(ServiceEvent::Update(TrayEvent::Registered(item))).await,
+            Err(err) => warn!("Failed to read polled tray item {name}: {err}")
+        }
+    }
+}
+
+pub(crate) async fn start_listening<F, Fut>(poll_interval: Option<Duration>, mut publisher: F)
 where
     F: FnMut(ServiceEvent<TrayService>) -> Fut + Send,
     Fut: Future<Output = ()> + Send
@@ -232,7 +275,7 @@ where
     let mut state = State::Init;
 
     loop {
-        state = drive_state(state, &mut publisher).await;
+        state = drive_state(state, poll_interval, &mut publisher).await;
     }
 }
 
@@ -242,7 +285,11 @@ enum State {
     Error
 }
 
-async fn drive_state<F, Fut>(state: State, publisher: &mut F) -> State
+async fn drive_state<F, Fut>(
+    state: State,
+    poll_interval: Option<Duration>,
+    publisher: &mut F
+) -> State
 where
     F: FnMut(ServiceEvent<TrayService>) -> Fut + Send,
     Fut: Future<Output = ()> + Send
@@ -253,6 +300,11 @@ where
                 Ok(data) => {
                     info!("Tray service initialized");
 
+                    // Only needed when another program owns the watcher name.
+                    if let Err(err) = StatusNotifierWatcher::register_host(&conn).await {
+                        warn!("{err}");
+                    }
+
                     publisher(ServiceEvent::Init(TrayService {
                         data,
                         _conn: conn.clone()
@@ -268,24 +320,57 @@ where
         State::Active(conn) => {
             info!("Listening for tray events");
 
-            match events(&conn).await {
-                Ok(mut stream) => {
-                    while let Some(event) = stream.next().await {
-                        debug!("tray data {event:?}");
-
-                        let reload_events = matches!(event, TrayEvent::Registered(_));
-
-                        publisher(ServiceEvent::Update(event)).await;
+            let mut stream = match events(&conn).await {
+                Ok(stream) => stream,
+                Err(err) => return transition_to_error(err)
+            };
+            let mut known = match poll_interval {
+                Some(_) => registered_items(&conn).await.unwrap_or_default(),
+                None => Vec::new()
+            };
+            let mut poll =
+                poll_interval.map(|period| interval_at(Instant::now() + period, period));
+
+            loop {
+                let event = match poll.as_mut() {
+                    Some(poll) => tokio::select! {
+                        event = stream.next() => event,
+                        _ = poll.tick() => {
+                            let Ok(items) = registered_items(&conn).await else {
+                                continue;
+                            };
+
+                            if items == known {
+                                continue;
+                            }
 
-                        if reload_events {
+                            debug!("tray items changed without a signal: {items:?}");
+                            publish_item_changes(&conn, &known, &items, publisher).await;
+                            // New items need their own change streams.
                             break;
                         }
-                    }
+                    },
+                    None => stream.next().await
+                };
+                let Some(event) = event else {
+                    break;
+                };
+
+                debug!("tray data {event:?}");
+
+                let reload_events = matches!(event, TrayEvent::Registered(_));
+                if let TrayEvent::Unregistered(name) = &event {
+                    known.retain(|item| item != name);
+                }
 
-                    State::Active(conn)
+                publisher(ServiceEvent::Update(event)).await;
+
+                if reload_events {
+                    break;
                 }
-                Err(err) => transition_to_error(err)
             }
+
+            State::Active(conn)
         }
         State::Error => {
             error!("Tray service error");
@@ -5,17 +5,18 @@ use iced::{
     Subscription,
     futures::{
         Stream, StreamExt,
-        stream::{once, pending, select_all},
+        stream::{once, select_all},
         stream_select
     },
     stream::channel
 };
-use log::{error, warn};
+use log::{debug, error, warn};
 use masterror::{AppError, AppResult};
+use tokio::time::sleep;
 use zbus::zvariant::ObjectPath;
 
-use super::{ReadOnlyService, Service, ServiceEvent, ServiceEventPublisher};
-use crate::{components::icons::Icons, utils::IndicatorState};
+use super::{ReadOnlyService, Service, ServiceEvent, ServiceEventPublisher, backoff::Backoff};
+use crate::{components::icons::Icons, config::BatteryLevels, utils::IndicatorState};
 
 mod dbus;
 
@@ -26,7 +27,9 @@ pub struct BatteryData {
 }
 
 impl BatteryData {
-    pub fn get_indicator_state(&self) -> IndicatorState {
+    /// State of a discharging battery under the configured `levels`;
+    /// charging always counts as success.
+    pub fn get_indicator_state(&self, levels: &BatteryLevels) -> IndicatorState {
         match self {
             BatteryData {
                 status: BatteryStatus::Charging(_),
@@ -35,7 +38,11 @@ impl BatteryData {
             BatteryData {
                 status: BatteryStatus::Discharging(_),
                 capacity
-            } if *capacity < 20 => IndicatorState::Danger,
+            } if *capacity <= i64::from(levels.danger) => IndicatorState::Danger,
+            BatteryData {
+                status: BatteryStatus::Discharging(_),
+                capacity
+            } if *capacity <= i64::from(levels.warning) => IndicatorState::Warning,
             _ => IndicatorState::Normal
         }
     }
@@ -120,14 +127,17 @@ pub struct UPowerService {
 }
 
 pub(crate) enum State {
-    Init,
+    Init(Backoff),
     Active(zbus::Connection, Option<Vec<ObjectPath<'static>>>),
-    Error
+    Error(Backoff),
+    /// Retries were exhausted; the service stays unavailable.
+    Disabled
 }
 
 impl ReadOnlyService for UPowerService {
     type UpdateEvent = UPowerEvent;
-    type Error = ();
+    /// Why UPower could not be reached, shown in the settings menu.
+    type Error = String;
 
     fn update(&mut self, event: Self::UpdateEvent) {
         match event {
@@ -306,7 +316,7 @@ impl UPowerService {
         P: ServiceEventPublisher<Self> + Send
     {
         match state {
-            State::Init => match zbus::Connection::system()
+            State::Init(backoff) => match zbus::Connection::system()
                 .await
                 .map_err(|e| AppError::internal(format!("Failed to connect to system bus: {}", e)))
             {
@@ -319,8 +329,9 @@ impl UPowerService {
                             Ok((None, power_profile)) => (None, None, power_profile),
                             Err(err) => {
                                 error!("Failed to initialize upower service: {err}");
+                                let _ = publisher.send(ServiceEvent::Error(err.to_string())).await;
 
-                                return State::Error;
+                                return State::Error(backoff);
                             }
                         };
 
@@ -335,7 +346,9 @@ impl UPowerService {
                 }
                 Err(err) => {
                     error!("Failed to connect to system bus for upower: {err}");
-                    State::Error
+                    let _ = publisher.send(ServiceEvent::Error(err.to_string())).await;
+
+                    State::Error(backoff)
                 }
             },
             State::Active(conn, battery_devices) => {
@@ -349,16 +362,29 @@ impl UPowerService {
                     }
                     Err(err) => {
                         error!("Failed to listen for upower events: {err}");
+                        let _ = publisher.send(ServiceEvent::Error(err.to_string())).await;
 
-                        State::Error
+                        State::Error(Backoff::default())
                     }
                 }
             }
-            State::Error => {
-                let _ = pending::<u8>().next().await;
+            State::Error(mut backoff) => match backoff.next_delay() {
+                Some(delay) => {
+                    debug!("Retrying upower service in {delay:?}");
+                    sleep(delay).await;
 
-                State::Error
-            }
+                    State::Init(backoff)
+                }
+                None => {
+                    warn!(
+                        "UPower service unavailable after {} attempts, giving up",
+                        backoff.attempts()
+                    );
+
+                    State::Disabled
+                }
+            },
+            State::Disabled => State::Disabled
         }
     }
 
@@ -366,9 +392,9 @@ impl UPowerService {
     where
         P: ServiceEventPublisher<Self> + Send
     {
-        let mut state = State::Init;
+        let mut state = State::Init(Backoff::default());
 
-        loop {
+        while !matches!(state, State::Disabled) {
             state = Self::start_listening(state, publisher).await;
         }
     }
@@ -384,57 +410,33 @@ impl UPowerService {
             Ok(proxy) => proxy,
             Err(err) => {
                 error!("Failed to create PowerProfilesProxy: {err}");
-                return ServiceEvent::Error(());
+                return ServiceEvent::Error(err.to_string());
             }
         };
 
         let next_profile = match command {
             PowerProfileCommand::Toggle => match power_profile {
                 PowerProfile::Balanced => {
-                    if powerprofiles
-                        .set_active_profile("performance")
-                        .await
-                        .map_err(|e| {
-                            AppError::internal(format!(
-                                "Failed to set power profile to performance: {}",
-                                e
-                            ))
-                        })
-                        .is_err()
-                    {
-                        return ServiceEvent::Error(());
+                    if let Err(err) = powerprofiles.set_active_profile("performance").await {
+                        return ServiceEvent::Error(format!(
+                            "Failed to set power profile to performance: {}: {err}"
+                        ));
                     }
                     PowerProfile::Performance
                 }
                 PowerProfile::Performance => {
-                    if powerprofiles
-                        .set_active_profile("power-saver")
-                        .await
-                        .map_err(|e| {
-                            AppError::internal(format!(
-                                "Failed to set power profile to power-saver: {}",
-                                e
-                            ))
-                        })
-                        .is_err()
-                    {
-                        return ServiceEvent::Error(());
+                    if let Err(err) = powerprofiles.set_active_profile("power-saver").await {
+                        return ServiceEvent::Error(format!(
+                            "Failed to set power profile to power-saver: {}: {err}"
+                        ));
                     }
                     PowerProfile::PowerSaver
                 }
                 PowerProfile::PowerSaver => {
-                    if powerprofiles
-                        .set_active_profile("balanced")
-                        .await
-                        .map_err(|e| {
-                            AppError::internal(format!(
-                                "Failed to set power profile to balanced: {}",
-                                e
-                            ))
-                        })
-                        .is_err()
-                    {
-                        return ServiceEvent::Error(());
+                    if let Err(err) = powerprofiles.set_active_profile("balanced").await {
+                        return ServiceEvent::Error(format!(
+                            "Failed to set power profile to balanced: {}: {err}"
+                        ));
                     }
                     PowerProfile::Balanced
                 }
@@ -462,3 +464,45 @@ impl Service for UPowerService {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn discharging(capacity: i64) -> BatteryData {
+        BatteryData {
+            capacity,
+            status: BatteryStatus::Discharging(Duration::ZERO)
+        }
+    }
+
+    #[test]
+    fn indicator_state_follows_configured_levels() {
+        let levels = BatteryLevels {
+            warning: 40,
+            danger:  25
+        };
+
+        assert_eq!(
+            discharging(50).get_indicator_state(&levels),
+            IndicatorState::Normal
+        );
+        assert_eq!(
+            discharging(40).get_indicator_state(&levels),
+            IndicatorState::Warning
+        );
+        assert_eq!(
+            discharging(25).get_indicator_state(&levels),
+            IndicatorState::Danger
+        );
+
+        let charging = BatteryData {
+            capacity: 5,
+            status:   BatteryStatus::Charging(Duration::ZERO)
+        };
+        assert_eq!(
+            charging.get_indicator_state(&levels),
+            IndicatorState::Success
+        );
+    }
+}
@@ -9,8 +9,9 @@ use std::sync::{
 
 use hydebar_proto::ports::hyprland::{
     HyprlandError, HyprlandEventStream, HyprlandKeyboardEvent, HyprlandKeyboardState,
-    HyprlandMonitorInfo, HyprlandMonitorSelector, HyprlandPort, HyprlandWindowEvent,
-    HyprlandWindowInfo, HyprlandWorkspaceEvent, HyprlandWorkspaceInfo, HyprlandWorkspaceSelector,
+    HyprlandLayoutState, HyprlandLockState, HyprlandMonitorInfo, HyprlandMonitorSelector,
+    HyprlandPort, HyprlandWindowEvent, HyprlandWindowInfo, HyprlandWindowState,
+    HyprlandWorkspaceEvent, HyprlandWorkspaceInfo, HyprlandWorkspaceSelector,
     HyprlandWorkspaceSnapshot
 };
 use tokio_stream;
@@ -20,6 +21,8 @@ pub struct MockHyprlandPort {
     pub active_window:          Mutex<Option<HyprlandWindowInfo>>,
     pub workspace_snapshot:     Mutex<HyprlandWorkspaceSnapshot>,
     pub keyboard_state:         Mutex<HyprlandKeyboardState>,
+    pub lock_state:             Mutex<HyprlandLockState>,
+    pub layout_state:           Mutex<HyprlandLayoutState>,
     pub change_workspace_calls: AtomicUsize,
     pub toggle_special_calls:   AtomicUsize,
     pub switch_layout_calls:    AtomicUsize
@@ -30,13 +33,15 @@ impl Default for MockHyprlandPort {
         Self {
             active_window:          Mutex::new(Some(HyprlandWindowInfo {
                 title: "Mock Window".into(),
-                class: "MockClass".into()
+                class: "MockClass".into(),
+                state: HyprlandWindowState::Tiled
             })),
             workspace_snapshot:     Mutex::new(HyprlandWorkspaceSnapshot {
                 monitors:            vec![HyprlandMonitorInfo {
                     id:                   0,
                     name:                 "MockMonitor".into(),
-                    special_workspace_id: None
+                    special_workspace_id: None,
+                    focused:              true
                 }],
                 workspaces:          vec![HyprlandWorkspaceInfo {
                     id:           1,
@@ -52,6 +57,16 @@ impl Default for MockHyprlandPort {
                 has_multiple_layouts: true,
                 active_submap:        Some("resize".into())
             }),
+            lock_state:             Mutex::new(HyprlandLockState {
+                caps_lock:   false,
+                num_lock:    true,
+                scroll_lock: false
+            }),
+            layout_state:           Mutex::new(HyprlandLayoutState {
+                layout:   "dwindle".into(),
+                gaps_in:  "5".into(),
+                gaps_out: "20".into()
+            }),
             change_workspace_calls: AtomicUsize::new(0),
             toggle_special_calls:   AtomicUsize::new(0),
             switch_layout_calls:    AtomicUsize::new(0)
@@ -67,7 +82,8 @@ impl MockHyprlandPort {
             .lock()
             .expect("poisoned active window lock") = Some(HyprlandWindowInfo {
             title: title.into(),
-            class: class.into()
+            class: class.into(),
+            state: HyprlandWindowState::Tiled
         });
         port
     }
@@ -140,8 +156,30 @@ impl HyprlandPort for MockHyprlandPort {
             .clone())
     }
 
+    fn lock_state(&self) -> Result<HyprlandLockState, HyprlandError> {
+        Ok(*self.lock_state.lock().expect("poisoned lock state lock"))
+    }
+
     fn switch_keyboard_layout(&self) -> Result<(), HyprlandError> {
         self.switch_layout_calls.fetch_add(1, Ordering::SeqCst);
         Ok(())
     }
+
+    fn layout_state(&self) -> Result<HyprlandLayoutState, HyprlandError> {
+        Ok(self
+            .layout_state
+            .lock()
+            .expect("poisoned layout state lock")
+            .clone())
+    }
+
+    fn set_gaps(&self, gaps_in: &str, gaps_out: &str) -> Result<(), HyprlandError> {
+        let mut state = self
+            .layout_state
+            .lock()
+            .expect("poisoned layout state lock");
+        state.gaps_in = gaps_in.into();
+        state.gaps_out = gaps_out.into();
+        Ok(())
+    }
 }
@@ -1,7 +1,13 @@
 use std::time::Duration;
 
+use iced::mouse::ScrollDelta;
+
+pub mod font;
 pub mod launcher;
+pub mod locale;
+pub mod throughput;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IndicatorState {
     Normal,
     Success,
@@ -9,13 +15,55 @@ pub enum IndicatorState {
     Danger
 }
 
+/// Formats a time estimate as `2h 15m`, `45m`, or `<1m` below a minute.
 pub fn format_duration(duration: &Duration) -> String {
     let h = duration.as_secs() / 60 / 60;
     let m = duration.as_secs() / 60 % 60;
     if h > 0 {
-        format!("{h}h {m:>2}m")
+        format!("{h}h {m}m")
+    } else if m > 0 {
+        format!("{m}m")
+    } else {
+        "<1m".to_owned()
+    }
+}
+
+/// Reverses the direction of a scroll gesture on both axes.
+pub fn invert_scroll(delta: ScrollDelta) -> ScrollDelta {
+    match delta {
+        ScrollDelta::Lines {
+            x,
+            y
+        } => ScrollDelta::Lines {
+            x: -x, y: -y
+        },
+        ScrollDelta::Pixels {
+            x,
+            y
+        } => ScrollDelta::Pixels {
+            x: -x, y: -y
+        }
+    }
+}
+
+/// Rounds a slider `value` to the nearest multiple of `step`, leaving `max`
+/// reachable when it is not a multiple itself. A zero `step` keeps the value.
+pub fn snap_to_step(value: u32, step: u32, max: u32) -> u32 {
+    if step == 0 || value >= max {
+        return value;
+    }
+
+    ((value + step / 2) / step * step).min(max)
+}
+
+/// Formats a media timestamp as `m:ss`, or `h:mm:ss` once it reaches an hour.
+pub fn format_timestamp(duration: &Duration) -> String {
+    let secs = duration.as_secs();
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
     } else {
-        format!("{m:>2}m")
+        format!("{m}:{s:02}")
     }
 }
 
@@ -31,3 +79,64 @@ pub fn truncate_text(value: &str, max_length: u32) -> String {
         value.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverted_scroll_flips_both_axes() {
+        assert_eq!(
+            invert_scroll(ScrollDelta::Lines {
+                x: 1., y: -2.
+            }),
+            ScrollDelta::Lines {
+                x: -1., y: 2.
+            }
+        );
+        assert_eq!(
+            invert_scroll(ScrollDelta::Pixels {
+                x: 3., y: 12.5
+            }),
+            ScrollDelta::Pixels {
+                x: -3., y: -12.5
+            }
+        );
+    }
+
+    #[test]
+    fn slider_values_snap_to_the_nearest_step() {
+        assert_eq!(snap_to_step(37, 5, 100), 35);
+        assert_eq!(snap_to_step(38, 5, 100), 40);
+        assert_eq!(snap_to_step(37, 0, 100), 37);
+        assert_eq!(snap_to_step(98, 30, 100), 90);
+        assert_eq!(snap_to_step(100, 30, 100), 100);
+    }
+
+    #[test]
+    fn duration_rounds_seconds_down_to_minutes() {
+        assert_eq!(format_duration(&Duration::from_secs(45)), "<1m");
+        assert_eq!(format_duration(&Duration::from_secs(60)), "1m");
+        assert_eq!(format_duration(&Duration::from_secs(119)), "1m");
+    }
+
+    #[test]
+    fn duration_adds_hours_from_sixty_minutes() {
+        assert_eq!(format_duration(&Duration::from_secs(3599)), "59m");
+        assert_eq!(format_duration(&Duration::from_secs(3600)), "1h 0m");
+        assert_eq!(format_duration(&Duration::from_secs(8100)), "2h 15m");
+    }
+
+    #[test]
+    fn timestamp_uses_minutes_below_an_hour() {
+        assert_eq!(format_timestamp(&Duration::from_secs(0)), "0:00");
+        assert_eq!(format_timestamp(&Duration::from_secs(83)), "1:23");
+        assert_eq!(format_timestamp(&Duration::from_millis(296_900)), "4:56");
+    }
+
+    #[test]
+    fn timestamp_adds_hours_for_long_media() {
+        assert_eq!(format_timestamp(&Duration::from_secs(3600)), "1:00:00");
+        assert_eq!(format_timestamp(&Duration::from_secs(7384)), "2:03:04");
+    }
+}
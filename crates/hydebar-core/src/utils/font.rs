@@ -0,0 +1,56 @@
+use std::{
+    collections::HashSet,
+    sync::{Mutex, OnceLock}
+};
+
+use iced::Font;
+
+/// Family names handed out so far. iced only accepts `'static` names, so each
+/// distinct name is leaked exactly once and reused on every later lookup.
+static FAMILIES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+/// Font for the family called `name`.
+///
+/// Installed system fonts are discovered by the renderer, so any family known
+/// to fontconfig can be used without registering it first.
+pub fn named(name: &str) -> Font {
+    let mut families = FAMILIES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let family = match families.get(name) {
+        Some(family) => *family,
+        None => {
+            let family: &'static str = Box::leak(name.to_owned().into_boxed_str());
+            families.insert(family);
+            family
+        }
+    };
+
+    Font::with_name(family)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_lookups_reuse_the_family_name() {
+        let first = named("JetBrains Mono");
+        let second = named("JetBrains Mono");
+
+        assert_eq!(first, second);
+        assert_eq!(
+            FAMILIES
+                .get()
+                .expect("families initialized")
+                .lock()
+                .expect("families lock")
+                .iter()
+                .filter(|family| **family == "JetBrains Mono")
+                .count(),
+            1
+        );
+    }
+}
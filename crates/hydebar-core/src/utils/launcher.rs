@@ -1,10 +1,247 @@
 use std::{
-    process::{ExitStatus, Output},
-    sync::Arc
+    path::{Path, PathBuf},
+    process::{ExitStatus, Output, Stdio},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant}
 };
 
-use log::error;
-use tokio::process::Command;
+use chrono::NaiveDate;
+use log::{debug, error, warn};
+use tokio::{process::Command, time::timeout};
+
+/// How long [`launch`] waits for a command to fail before leaving it to run
+/// in the background.
+const LAUNCH_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// Window within which [`launch`] ignores a command it just started, unless
+/// `command_debounce_ms` says otherwise.
+pub const DEFAULT_COMMAND_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Where a command was launched from, handed to it as `HYDEBAR_*`
+/// environment variables so scripts can react to it, and the directory it
+/// should run in.
+///
+/// # Examples
+///
+/// ```
+/// use hydebar_core::utils::launcher::CommandContext;
+///
+/// let context = CommandContext::module("Clock").with_output(Some("DP-1"));
+/// assert_eq!(context.output.as_deref(), Some("DP-1"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandContext {
+    /// Module the command belongs to, exported as `HYDEBAR_MODULE`.
+    pub module:   Option<String>,
+    /// Output the module was used on, exported as `HYDEBAR_OUTPUT`.
+    pub output:   Option<String>,
+    /// Date the clock shows, exported as `HYDEBAR_DATE` in `YYYY-MM-DD` form.
+    pub date:     Option<NaiveDate>,
+    /// Working directory overriding `command_cwd`.
+    pub cwd:      Option<PathBuf>,
+    /// Shell, default working directory and debounce window the command
+    /// runs with.
+    pub settings: CommandSettings
+}
+
+impl CommandContext {
+    /// Context of a command belonging to the module named as in the layout,
+    /// such as `Clock` or the name of a custom module.
+    pub fn module(name: impl Into<String>) -> Self {
+        Self {
+            module: Some(name.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Set the output, left unset when it is not known.
+    pub fn with_output(mut self, output: Option<&str>) -> Self {
+        self.output = output.map(str::to_owned);
+        self
+    }
+
+    /// Set the date the clock shows.
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    /// Run in `cwd` instead of the default working directory when set.
+    pub fn with_cwd(mut self, cwd: Option<&Path>) -> Self {
+        self.cwd = cwd.map(Path::to_path_buf);
+        self
+    }
+
+    /// Run with the shell, working directory and debounce window configured
+    /// in `settings`.
+    pub fn with_settings(mut self, settings: CommandSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Build a process running `command` through the configured shell with
+    /// this context applied.
+    pub fn shell_command(&self, command: &str) -> Command {
+        let mut process = self.settings.shell_command(command);
+        self.apply(&mut process);
+        process
+    }
+
+    fn apply(&self, process: &mut Command) {
+        if let Some(module) = &self.module {
+            process.env("HYDEBAR_MODULE", module);
+        }
+        if let Some(output) = &self.output {
+            process.env("HYDEBAR_OUTPUT", output);
+        }
+        if let Some(date) = self.date {
+            process.env("HYDEBAR_DATE", date.format("%Y-%m-%d").to_string());
+        }
+        if let Some(cwd) = &self.cwd {
+            process.current_dir(expand_home(cwd));
+        }
+    }
+}
+
+/// How launched commands are run, taken from the `command_*` options.
+///
+/// The application hands these to modules through the
+/// [`ModuleContext`](crate::ModuleContext) when they register, so a config
+/// reload applies to the next command.
+///
+/// # Examples
+///
+/// ```
+/// use hydebar_core::utils::launcher::CommandSettings;
+///
+/// let settings = CommandSettings::default().with_shell(Some("zsh -lc"));
+/// let process = settings.shell_command("true");
+/// assert_eq!(process.as_std().get_program(), "zsh");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandSettings {
+    /// Program and leading arguments of `command_shell`, empty while
+    /// commands run through the default `bash -c`.
+    shell:    Vec<String>,
+    /// Directory of `command_cwd`, `None` while commands inherit the working
+    /// directory of hydebar.
+    cwd:      Option<PathBuf>,
+    /// Window of `command_debounce_ms`.
+    debounce: Duration
+}
+
+impl Default for CommandSettings {
+    fn default() -> Self {
+        Self {
+            shell:    Vec::new(),
+            cwd:      None,
+            debounce: DEFAULT_COMMAND_DEBOUNCE
+        }
+    }
+}
+
+impl CommandSettings {
+    /// Run commands through `shell`, a program followed by the arguments
+    /// that precede the command such as `zsh -lc`. `None` keeps `bash -c`.
+    pub fn with_shell(mut self, shell: Option<&str>) -> Self {
+        let words: Vec<String> = shell
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_owned)
+            .collect();
+
+        if shell.is_some() && words.is_empty() {
+            warn!("command_shell is empty, running commands through bash -c");
+        }
+
+        self.shell = words;
+        self
+    }
+
+    /// Start commands in `cwd`, where a leading `~` stands for the home
+    /// directory. `None` leaves them in the working directory of hydebar.
+    pub fn with_cwd(mut self, cwd: Option<&Path>) -> Self {
+        self.cwd = cwd.map(Path::to_path_buf);
+        self
+    }
+
+    /// Ignore a command launched again within `window` of its last start.
+    /// [`Duration::ZERO`] lets every launch through.
+    pub fn with_debounce(mut self, window: Duration) -> Self {
+        self.debounce = window;
+        self
+    }
+
+    /// Whether `command` may start now, i.e. it was not started within the
+    /// debounce window. Records the start when it may.
+    ///
+    /// [`launch`] checks this itself; actions spawning processes on their
+    /// own call it with a key naming the action.
+    pub fn should_launch(&self, command: &str) -> bool {
+        RECENT_LAUNCHES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .admit(command, self.debounce, Instant::now())
+    }
+
+    /// Build a process running `command` through the configured shell, in
+    /// the configured working directory.
+    pub fn shell_command(&self, command: &str) -> Command {
+        let mut process = match self.shell.split_first() {
+            Some((program, args)) => {
+                let mut process = Command::new(program);
+                process.args(args);
+                process
+            }
+            None => {
+                let mut process = Command::new("bash");
+                process.arg("-c");
+                process
+            }
+        };
+        process.arg(command);
+
+        if let Some(cwd) = &self.cwd {
+            process.current_dir(expand_home(cwd));
+        }
+
+        process
+    }
+}
+
+/// Launches of the whole process, so the same command started from two
+/// modules is debounced as well.
+static RECENT_LAUNCHES: Mutex<RecentLaunches> = Mutex::new(RecentLaunches(Vec::new()));
+
+/// Commands started within the debounce window, oldest first. Only a
+/// handful fit in a window this short, so a list beats a map.
+#[derive(Debug, Default)]
+struct RecentLaunches(Vec<(String, Instant)>);
+
+impl RecentLaunches {
+    /// Record `command` as started at `now` unless it already was within
+    /// `window`, returning whether it may run.
+    fn admit(&mut self, command: &str, window: Duration, now: Instant) -> bool {
+        self.0
+            .retain(|(_, started)| now.saturating_duration_since(*started) < window);
+
+        if self.0.iter().any(|(recent, _)| recent == command) {
+            return false;
+        }
+
+        if !window.is_zero() {
+            self.0.push((command.to_owned(), now));
+        }
+        true
+    }
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf()
+    }
+}
 
 /// Error type emitted when launching shell commands fails.
 ///
@@ -43,7 +280,9 @@ pub enum LauncherError {
         /// The attempted command string.
         command: Arc<str>,
         /// The exit status returned by the process.
-        status:  ExitStatus
+        status:  ExitStatus,
+        /// What the command wrote to stderr, trimmed.
+        stderr:  Arc<str>
     }
 }
 
@@ -58,9 +297,14 @@ impl std::fmt::Display for LauncherError {
             }
             Self::NonZeroExit {
                 command,
-                status
+                status,
+                stderr
             } => {
-                write!(f, "command `{}` exited with status {}", command, status)
+                write!(f, "command `{}` exited with status {}", command, status)?;
+                if !stderr.is_empty() {
+                    write!(f, ": {}", stderr)?;
+                }
+                Ok(())
             }
         }
     }
@@ -76,10 +320,11 @@ impl LauncherError {
         }
     }
 
-    fn exit_error(command: Arc<str>, status: ExitStatus) -> Self {
+    fn exit_error(command: Arc<str>, output: &Output) -> Self {
         Self::NonZeroExit {
             command,
-            status
+            status: output.status,
+            stderr: Arc::from(String::from_utf8_lossy(&output.stderr).trim())
         }
     }
 }
@@ -91,10 +336,15 @@ impl LauncherError {
 /// Returns [`LauncherError::Spawn`] if the process cannot be created or
 /// [`LauncherError::NonZeroExit`] when the command finishes unsuccessfully.
 pub async fn run_shell_command_with_output(command: &Arc<str>) -> Result<Output, LauncherError> {
-    let mut process = Command::new("bash");
-    process.arg("-c").arg(command.as_ref());
+    run_in_context(command, &CommandContext::default()).await
+}
 
-    let output = process
+async fn run_in_context(
+    command: &Arc<str>,
+    context: &CommandContext
+) -> Result<Output, LauncherError> {
+    let output = context
+        .shell_command(command)
         .output()
         .await
         .map_err(|error| LauncherError::spawn_error(command.clone(), error))?;
@@ -102,70 +352,127 @@ pub async fn run_shell_command_with_output(command: &Arc<str>) -> Result<Output,
     if output.status.success() {
         Ok(output)
     } else {
-        Err(LauncherError::exit_error(command.clone(), output.status))
+        Err(LauncherError::exit_error(command.clone(), &output))
     }
 }
 
-fn spawn_and_log(command: String, context: &'static str) {
-    tokio::spawn(async move {
-        let command_arc: Arc<str> = Arc::from(command);
-        match run_shell_command_with_output(&command_arc).await {
-            Ok(output) => {
-                if !output.stderr.is_empty() {
-                    error!(
-                        "{context} command produced stderr: {}",
-                        String::from_utf8_lossy(&output.stderr)
-                    );
-                }
-            }
-            Err(error) => {
-                error!("{context} command failed: {error}");
+/// Start `command` and report whether it failed right away.
+///
+/// Commands still running after a short grace period, such as launched
+/// applications, are left in the background and a later failure is only
+/// logged. Their stdout is discarded while stderr is kept for the error.
+/// A command started moments ago is
+/// [debounced](CommandSettings::should_launch) and not run again.
+///
+/// # Errors
+///
+/// Returns [`LauncherError::Spawn`] if the process cannot be created or
+/// [`LauncherError::NonZeroExit`] when it exits unsuccessfully within the
+/// grace period.
+pub async fn launch(command: String, context: CommandContext) -> Result<(), LauncherError> {
+    if !context.settings.should_launch(&command) {
+        debug!("Not launching `{command}` again so soon");
+        return Ok(());
+    }
+
+    let command: Arc<str> = Arc::from(command);
+
+    let child = context
+        .shell_command(&command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| LauncherError::spawn_error(command.clone(), error))?;
+
+    // The task keeps draining stderr, so a detached command never blocks on
+    // a full pipe.
+    let mut finished = tokio::spawn(child.wait_with_output());
+
+    let Ok(joined) = timeout(LAUNCH_GRACE_PERIOD, &mut finished).await else {
+        tokio::spawn(async move {
+            if let Ok(Ok(output)) = finished.await
+                && !output.status.success()
+            {
+                error!("{}", LauncherError::exit_error(command, &output));
             }
+        });
+        return Ok(());
+    };
+
+    match joined {
+        Ok(Ok(output)) if output.status.success() => Ok(()),
+        Ok(Ok(output)) => Err(LauncherError::exit_error(command, &output)),
+        Ok(Err(error)) => Err(LauncherError::spawn_error(command, error)),
+        Err(error) => {
+            error!("Waiting for `{command}` failed: {error}");
+            Ok(())
+        }
+    }
+}
+
+fn spawn_and_log(command: String, kind: &'static str, context: CommandContext) {
+    tokio::spawn(async move {
+        if let Err(error) = launch(command, context).await {
+            error!("{kind} command failed: {error}");
         }
     });
 }
 
 /// Execute an arbitrary shell command without awaiting its completion.
 ///
-/// The command is executed in a background Tokio task to preserve the
-/// fire-and-forget semantics used throughout the UI.
+/// The command is [launched](launch) in a background Tokio task to preserve
+/// the fire-and-forget semantics used throughout the UI, so failures are only
+/// logged. `context` is exported to it as environment variables.
 ///
 /// # Examples
 ///
 /// ```no_run
-/// use hydebar::utils::launcher;
+/// use hydebar::utils::launcher::{self, CommandContext};
 ///
-/// launcher::execute_command("notify-send hydebar 'Hello'".to_owned());
+/// launcher::execute_command(
+///     "notify-send hydebar \"$HYDEBAR_MODULE\"".to_owned(),
+///     CommandContext::module("AppLauncher")
+/// );
 /// ```
-pub fn execute_command(command: String) {
-    spawn_and_log(command, "launcher");
+pub fn execute_command(command: String, context: CommandContext) {
+    spawn_and_log(command, "launcher", context);
 }
 
 /// Execute the configured suspend command in the background.
-pub fn suspend(command: String) {
-    spawn_and_log(command, "suspend");
+pub fn suspend(command: String, context: CommandContext) {
+    spawn_and_log(command, "suspend", context);
 }
 
 /// Execute the configured shutdown command in the background.
-pub fn shutdown(command: String) {
-    spawn_and_log(command, "shutdown");
+pub fn shutdown(command: String, context: CommandContext) {
+    spawn_and_log(command, "shutdown", context);
 }
 
 /// Execute the configured reboot command in the background.
-pub fn reboot(command: String) {
-    spawn_and_log(command, "reboot");
+pub fn reboot(command: String, context: CommandContext) {
+    spawn_and_log(command, "reboot", context);
 }
 
 /// Execute the configured logout command in the background.
-pub fn logout(command: String) {
-    spawn_and_log(command, "logout");
+pub fn logout(command: String, context: CommandContext) {
+    spawn_and_log(command, "logout", context);
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{sync::Arc, time::Duration};
+    use std::{
+        path::Path,
+        sync::Arc,
+        time::{Duration, Instant}
+    };
+
+    use chrono::NaiveDate;
 
-    use super::{LauncherError, run_shell_command_with_output};
+    use super::{
+        CommandContext, CommandSettings, LauncherError, RecentLaunches, expand_home, launch,
+        run_in_context, run_shell_command_with_output
+    };
 
     #[tokio::test]
     async fn reports_successful_status() -> Result<(), Box<dyn std::error::Error>> {
@@ -222,4 +529,141 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn launch_reports_early_failure_with_stderr() {
+        let outcome = launch(
+            "echo oops >&2; exit 3".to_owned(),
+            CommandContext::default()
+        )
+        .await;
+
+        match outcome {
+            Err(LauncherError::NonZeroExit {
+                status,
+                stderr,
+                ..
+            }) => {
+                assert_eq!(status.code(), Some(3));
+                assert_eq!(&*stderr, "oops");
+            }
+            other => panic!("unexpected outcome: {other:?}")
+        }
+    }
+
+    #[tokio::test]
+    async fn launch_detaches_long_running_commands() {
+        let outcome = tokio::time::timeout(
+            Duration::from_secs(5),
+            launch("sleep 3".to_owned(), CommandContext::default())
+        )
+        .await;
+
+        assert_eq!(outcome, Ok(Ok(())));
+    }
+
+    #[test]
+    fn ignores_repeated_commands_within_the_window() {
+        let mut recent = RecentLaunches::default();
+        let window = Duration::from_millis(300);
+        let start = Instant::now();
+
+        assert!(recent.admit("firefox", window, start));
+        assert!(!recent.admit("firefox", window, start + Duration::from_millis(100)));
+        assert!(recent.admit("kitty", window, start + Duration::from_millis(100)));
+        assert!(recent.admit("firefox", window, start + window));
+
+        assert!(recent.admit("kitty", Duration::ZERO, start));
+        assert!(recent.admit("kitty", Duration::ZERO, start));
+    }
+
+    #[test]
+    fn debounce_follows_the_settings() {
+        let settings = CommandSettings::default();
+        assert!(settings.should_launch("debounce_follows_the_settings"));
+        assert!(!settings.should_launch("debounce_follows_the_settings"));
+
+        let settings = settings.with_debounce(Duration::ZERO);
+        assert!(settings.should_launch("debounce_follows_the_settings zero"));
+        assert!(settings.should_launch("debounce_follows_the_settings zero"));
+    }
+
+    #[test]
+    fn runs_commands_through_configured_shell() {
+        let context = CommandContext::default()
+            .with_settings(CommandSettings::default().with_shell(Some("sh  -c")));
+        let process = context.shell_command("echo hi");
+
+        let process = process.as_std();
+        assert_eq!(process.get_program(), "sh");
+        assert_eq!(process.get_args().collect::<Vec<_>>(), ["-c", "echo hi"]);
+    }
+
+    #[test]
+    fn blank_shell_falls_back_to_bash() {
+        let process = CommandSettings::default()
+            .with_shell(Some("  "))
+            .shell_command("echo hi");
+
+        let process = process.as_std();
+        assert_eq!(process.get_program(), "bash");
+        assert_eq!(process.get_args().collect::<Vec<_>>(), ["-c", "echo hi"]);
+    }
+
+    #[test]
+    fn working_directory_is_only_set_when_configured() {
+        let process = CommandSettings::default().shell_command("pwd");
+        assert_eq!(process.as_std().get_current_dir(), None);
+
+        let process = CommandSettings::default()
+            .with_cwd(Some(Path::new("/tmp")))
+            .shell_command("pwd");
+        assert_eq!(process.as_std().get_current_dir(), Some(Path::new("/tmp")));
+    }
+
+    #[tokio::test]
+    async fn runs_in_the_context_working_directory() -> Result<(), Box<dyn std::error::Error>> {
+        let command = Arc::from("pwd");
+        let cwd = std::env::temp_dir().canonicalize()?;
+        let context = CommandContext::default().with_cwd(Some(&cwd));
+
+        let output =
+            tokio::time::timeout(Duration::from_secs(5), run_in_context(&command, &context))
+                .await??;
+
+        assert_eq!(
+            String::from_utf8(output.stdout)?.trim_end(),
+            cwd.to_string_lossy()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn expands_leading_tilde_to_home() {
+        let Some(home) = dirs::home_dir() else {
+            return;
+        };
+
+        assert_eq!(expand_home(Path::new("~/bin")), home.join("bin"));
+        assert_eq!(expand_home(Path::new("/tmp/~")), Path::new("/tmp/~"));
+    }
+
+    #[tokio::test]
+    async fn exports_context_to_the_command() -> Result<(), Box<dyn std::error::Error>> {
+        let command = Arc::from(
+            "printf '%s %s %s' \"$HYDEBAR_MODULE\" \"$HYDEBAR_OUTPUT\" \"$HYDEBAR_DATE\""
+        );
+        let context = CommandContext::module("Clock")
+            .with_output(Some("DP-1"))
+            .with_date(NaiveDate::from_ymd_opt(2024, 5, 1).ok_or("valid date")?);
+
+        let output =
+            tokio::time::timeout(Duration::from_secs(5), run_in_context(&command, &context))
+                .await??;
+
+        assert_eq!(output.stdout, b"Clock DP-1 2024-05-01");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,41 @@
+use chrono::Locale;
+use log::warn;
+
+/// Resolve a POSIX-style locale name such as `de_DE`, `de-DE` or
+/// `ar_EG.UTF-8` into a chrono [`Locale`].
+///
+/// Codeset and modifier suffixes are ignored since chrono only ships the
+/// language tables. Unknown names are logged and yield `None`, in which case
+/// callers keep their English output.
+pub fn parse(name: &str) -> Option<Locale> {
+    let name = name
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .replace('-', "_");
+
+    match Locale::try_from(name.as_str()) {
+        Ok(locale) => Some(locale),
+        Err(_) => {
+            warn!("unknown locale `{name}`, falling back to English formatting");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_with_codeset_and_dash() {
+        assert_eq!(parse("de_DE"), Some(Locale::de_DE));
+        assert_eq!(parse("de-DE"), Some(Locale::de_DE));
+        assert_eq!(parse("ar_EG.UTF-8"), Some(Locale::ar_EG));
+    }
+
+    #[test]
+    fn unknown_names_are_rejected() {
+        assert_eq!(parse("xx_YY"), None);
+    }
+}
@@ -0,0 +1,96 @@
+use hydebar_proto::config::{ThroughputFormat, ThroughputScale, ThroughputUnit};
+
+const BYTE_UNITS: [&str; 3] = ["KB/s", "MB/s", "GB/s"];
+const BIT_UNITS: [&str; 3] = ["Kb/s", "Mb/s", "Gb/s"];
+
+/// Widest integer part produced by automatic scaling before switching to the
+/// next unit.
+const AUTO_INTEGER_DIGITS: usize = 3;
+
+/// Format a throughput given in bytes per second according to `config`,
+/// returning the number and its unit separately.
+pub fn format_throughput(
+    bytes_per_second: u64,
+    config: &ThroughputFormat
+) -> (String, &'static str) {
+    let (base, units) = match config.unit {
+        ThroughputUnit::Bytes => (bytes_per_second as f64, BYTE_UNITS),
+        ThroughputUnit::Bits => (bytes_per_second as f64 * 8., BIT_UNITS)
+    };
+
+    let exponent = match config.scale {
+        ThroughputScale::Auto => {
+            let mut exponent = 0;
+            while exponent + 1 < units.len() && base >= 1000_f64.powi(exponent as i32 + 2) {
+                exponent += 1;
+            }
+            exponent
+        }
+        ThroughputScale::Kilo => 0,
+        ThroughputScale::Mega => 1,
+        ThroughputScale::Giga => 2
+    };
+
+    let value = base / 1000_f64.powi(exponent as i32 + 1);
+    let precision = usize::from(config.precision);
+    let formatted = if config.fixed_width {
+        let width = AUTO_INTEGER_DIGITS + if precision > 0 { precision + 1 } else { 0 };
+        format!("{value:>width$.precision$}")
+    } else {
+        format!("{value:.precision$}")
+    };
+
+    (formatted, units[exponent])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_scale_switches_units_at_thousands() {
+        let config = ThroughputFormat::default();
+
+        assert_eq!(
+            format_throughput(512_000, &config),
+            ("512".to_string(), "KB/s")
+        );
+        assert_eq!(
+            format_throughput(2_048_000, &config),
+            ("2".to_string(), "MB/s")
+        );
+        assert_eq!(
+            format_throughput(3_000_000_000, &config),
+            ("3".to_string(), "GB/s")
+        );
+    }
+
+    #[test]
+    fn bits_multiply_by_eight() {
+        let config = ThroughputFormat {
+            unit: ThroughputUnit::Bits,
+            precision: 1,
+            ..ThroughputFormat::default()
+        };
+
+        assert_eq!(
+            format_throughput(250_000, &config),
+            ("2.0".to_string(), "Mb/s")
+        );
+    }
+
+    #[test]
+    fn fixed_scale_and_width_pad_values() {
+        let config = ThroughputFormat {
+            scale: ThroughputScale::Mega,
+            precision: 2,
+            fixed_width: true,
+            ..ThroughputFormat::default()
+        };
+
+        assert_eq!(
+            format_throughput(1_500_000, &config),
+            ("  1.50".to_string(), "MB/s")
+        );
+    }
+}
@@ -1,8 +1,13 @@
 mod bus;
+mod debug;
 mod micro_ticker;
 mod modules;
+mod restart;
+mod resume_detector;
+mod signals;
 mod state;
 mod update;
 mod view;
 
+pub use restart::restart_requested;
 pub use state::{App, Message};
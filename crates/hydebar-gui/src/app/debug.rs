@@ -0,0 +1,113 @@
+//! Diagnostics menu enabled by the `debug_menu` config flag.
+
+use hydebar_core::{
+    components::icons::{Icons, icon},
+    menu::MenuType,
+    modules::settings::ServiceStatus,
+    position_button::position_button,
+    style::module_button_style
+};
+use iced::{
+    Alignment, Element, Length,
+    widget::{Column, column, container, horizontal_rule, row, text}
+};
+
+use super::state::{App, Message};
+
+impl App {
+    /// Bar button toggling the debug menu on the output `id`.
+    pub(super) fn debug_button(&self, id: iced::window::Id) -> Element<'_, Message> {
+        position_button(
+            container(icon(Icons::Bug))
+                .align_y(Alignment::Center)
+                .height(Length::Fill)
+        )
+        .padding([2, 8])
+        .height(Length::Fill)
+        .style(module_button_style(
+            self.config.appearance.style,
+            self.config.appearance.opacity,
+            false,
+            false
+        ))
+        .on_press_with_position(move |button_ui_ref| {
+            Message::ToggleMenu(MenuType::Debug, id, button_ui_ref)
+        })
+        .into()
+    }
+
+    /// Live snapshot of the bus, tick cadence, services and open menus.
+    pub(super) fn debug_menu_view(&self) -> Element<'_, Message> {
+        let bus_depth = match self.bus_receiver.lock() {
+            Ok(receiver) => match receiver.depth() {
+                Ok(depth) => format!("{depth} / {}", receiver.capacity()),
+                Err(err) => err.to_string()
+            },
+            Err(_) => "receiver poisoned".to_owned()
+        };
+
+        let bus_totals = match self.bus_receiver.lock() {
+            Ok(receiver) => receiver.metrics().to_string(),
+            Err(_) => "receiver poisoned".to_owned()
+        };
+
+        let cadence = format!(
+            "{} ms{}",
+            self.micro_ticker.interval().as_millis(),
+            if self.micro_ticker.is_idle() {
+                " (idle)"
+            } else {
+                ""
+            }
+        );
+
+        let services =
+            Column::with_children(self.settings.service_statuses().map(|(name, status)| {
+                let status = match status {
+                    ServiceStatus::Initializing => "initializing".to_owned(),
+                    ServiceStatus::Ready => "ready".to_owned(),
+                    ServiceStatus::Unavailable(reason) => format!("unavailable: {reason}")
+                };
+
+                entry(name, status)
+            }))
+            .spacing(4);
+
+        let menus = Column::with_children(
+            self.outputs
+                .open_menus()
+                .map(|(output, menu_type)| {
+                    entry(
+                        output.unwrap_or("fallback output"),
+                        format!("{menu_type:?}")
+                    )
+                })
+                .collect::<Vec<_>>()
+        )
+        .spacing(4);
+
+        column![
+            text("Diagnostics").size(20),
+            horizontal_rule(1),
+            entry("Event bus depth", bus_depth),
+            entry("Event bus totals", bus_totals),
+            entry("Tick interval", cadence),
+            entry("Frozen", if self.frozen { "yes" } else { "no" }.to_owned()),
+            horizontal_rule(1),
+            text("Services").size(16),
+            services,
+            horizontal_rule(1),
+            text("Open menus").size(16),
+            menus
+        ]
+        .spacing(8)
+        .into()
+    }
+}
+
+fn entry<'a>(label: &'a str, value: String) -> Element<'a, Message> {
+    row!(text(label).width(Length::Fill), text(value))
+        .align_y(Alignment::Center)
+        .spacing(8)
+        .into()
+}
@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use hydebar_proto::config::RefreshConfig;
+
 #[derive(Debug, Clone)]
 pub(super) struct MicroTicker {
     fast_interval:    Duration,
@@ -28,6 +30,11 @@ impl MicroTicker {
         self.current_interval
     }
 
+    /// Whether enough idle ticks passed to fall back to the slow interval.
+    pub(super) fn is_idle(&self) -> bool {
+        self.idle_ticks >= self.idle_threshold
+    }
+
     pub(super) fn record_activity(&mut self) {
         self.idle_ticks = 0;
         self.current_interval = self.fast_interval;
@@ -42,10 +49,67 @@ impl MicroTicker {
             self.current_interval = self.slow_interval;
         }
     }
+
+    /// Ticker with the cadence of `appearance.refresh`. A zero interval is
+    /// raised to one millisecond and the slow interval to the fast one.
+    pub(super) fn from_config(config: &RefreshConfig) -> Self {
+        let fast_ms = config.fast_ms.max(1);
+
+        Self::new(
+            Duration::from_millis(fast_ms),
+            Duration::from_millis(config.slow_ms.max(fast_ms)),
+            config.idle_threshold
+        )
+    }
 }
 
 impl Default for MicroTicker {
     fn default() -> Self {
-        Self::new(Duration::from_millis(100), Duration::from_millis(500), 10)
+        Self::from_config(&RefreshConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refresh(fast_ms: u64, slow_ms: u64, idle_threshold: u8) -> RefreshConfig {
+        RefreshConfig {
+            fast_ms,
+            slow_ms,
+            idle_threshold
+        }
+    }
+
+    #[test]
+    fn defaults_match_the_previous_cadence() {
+        let ticker = MicroTicker::default();
+
+        assert_eq!(ticker.interval(), Duration::from_millis(100));
+        assert!(!ticker.is_idle());
+    }
+
+    #[test]
+    fn goes_slow_after_configured_idle_ticks() {
+        let mut ticker = MicroTicker::from_config(&refresh(16, 33, 3));
+
+        ticker.record_idle();
+        ticker.record_idle();
+        assert_eq!(ticker.interval(), Duration::from_millis(16));
+
+        ticker.record_idle();
+        assert!(ticker.is_idle());
+        assert_eq!(ticker.interval(), Duration::from_millis(33));
+
+        ticker.record_activity();
+        assert_eq!(ticker.interval(), Duration::from_millis(16));
+    }
+
+    #[test]
+    fn slow_interval_is_raised_to_fast() {
+        let mut ticker = MicroTicker::from_config(&refresh(200, 50, 1));
+
+        ticker.record_idle();
+        assert_eq!(ticker.interval(), Duration::from_millis(200));
     }
 }
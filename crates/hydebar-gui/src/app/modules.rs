@@ -1,13 +1,16 @@
 /// Module rendering implementation for App - GUI layer only
+use std::{collections::HashSet, time::Instant};
+
 use hydebar_core::{
     config::{AppearanceStyle, ModuleDef, ModuleName},
-    modules::OnModulePress,
-    position_button::position_button,
+    menu::MenuType,
+    modules::{OnModulePress, settings::IndicatorOptions},
+    position_button::{ButtonUIRef, PositionButton, position_button},
     style::module_button_style
 };
 use iced::{
     Alignment, Border, Color, Element, Length, Subscription,
-    widget::{Row, container, row},
+    widget::{Row, container, mouse_area, row},
     window::Id
 };
 use log::error;
@@ -46,9 +49,14 @@ impl App {
         None
     }
 
-    pub fn modules_section(
+    /// Render one section of a bar layout.
+    ///
+    /// `rendered` is shared by the sections of a layout so a module that
+    /// keeps a single state is only rendered where it is first listed.
+    pub fn modules_section<'a>(
         &self,
-        modules_def: &[ModuleDef],
+        modules_def: &'a [ModuleDef],
+        rendered: &mut HashSet<&'a ModuleName>,
         id: Id,
         opacity: f32
     ) -> Element<'_, Message> {
@@ -58,9 +66,18 @@ impl App {
             .spacing(4);
 
         for module_def in modules_def {
-            row = row.push_maybe(match module_def {
-                ModuleDef::Single(module) => self.single_module_wrapper(module, id, opacity),
-                ModuleDef::Group(group) => self.group_module_wrapper(group, id, opacity)
+            let modules = module_def
+                .modules()
+                .iter()
+                .filter(|module| module.allows_duplicates() || rendered.insert(*module))
+                .collect::<Vec<_>>();
+
+            row = row.push_maybe(match (module_def, modules.as_slice()) {
+                (_, []) => None,
+                (ModuleDef::Single(_), [module]) => {
+                    self.single_module_wrapper(module, id, opacity)
+                }
+                (_, group) => self.group_module_wrapper(group, id, opacity)
             });
         }
 
@@ -114,15 +131,7 @@ impl App {
                     false
                 ));
 
-                match action {
-                    OnModulePress::Action(action) => button.on_press(*action),
-                    OnModulePress::ToggleMenu(menu_type) => {
-                        button.on_press_with_position(move |button_ui_ref| {
-                            Message::ToggleMenu(menu_type.clone(), id, button_ui_ref)
-                        })
-                    }
-                }
-                .into()
+                bind_press(button, action, id)
             }
             _ => {
                 let container = container(content)
@@ -156,7 +165,7 @@ impl App {
 
     fn group_module_wrapper(
         &self,
-        group: &[ModuleName],
+        group: &[&ModuleName],
         id: Id,
         opacity: f32
     ) -> Option<Element<'_, Message>> {
@@ -188,18 +197,7 @@ impl App {
                                     false
                                 ));
 
-                                match action {
-                                    OnModulePress::Action(action) => button.on_press(*action),
-                                    OnModulePress::ToggleMenu(menu_type) => button
-                                        .on_press_with_position(move |button_ui_ref| {
-                                            Message::ToggleMenu(
-                                                menu_type.clone(),
-                                                id,
-                                                button_ui_ref
-                                            )
-                                        })
-                                }
-                                .into()
+                                bind_press(button, action, id)
                             }
                             _ => container(content)
                                 .padding([2, 8])
@@ -243,19 +241,55 @@ impl App {
         use hydebar_core::modules::Module;
 
         match module_name {
-            ModuleName::AppLauncher => self.app_launcher.view(&self.config.app_launcher_cmd),
+            ModuleName::AppLauncher => self
+                .app_launcher
+                .view((&self.config.app_launcher_cmd, &self.config.app_launcher))
+                .map(|(content, _)| {
+                    (
+                        content,
+                        Some(OnModulePress::Action(Box::new(Message::OpenLauncher(id))))
+                    )
+                }),
             ModuleName::Custom(name) => self
                 .config
                 .custom_modules
                 .iter()
                 .find(|m| &m.name == name)
-                .and_then(|mc| self.custom.get(name).map(|cm| cm.view(mc)))
+                .and_then(|mc| {
+                    self.custom.get(name).map(|cm| {
+                        cm.view(mc).map(|(content, _)| {
+                            let context = self
+                                .module_context
+                                .command_context(name.as_str())
+                                .with_output(self.outputs.get_monitor_name(id))
+                                .with_cwd(mc.cwd.as_deref());
+
+                            (
+                                content,
+                                Some(OnModulePress::Action(Box::new(Message::LaunchCommand(
+                                    mc.command.clone(),
+                                    context
+                                ))))
+                            )
+                        })
+                    })
+                })
                 .unwrap_or_else(|| {
                     error!("Custom module `{name}` not found");
                     None
                 }),
             ModuleName::Updates => self.updates.view(&self.config.updates),
-            ModuleName::Clipboard => self.clipboard.view(&self.config.clipboard_cmd),
+            ModuleName::CommandOutput => self.command_output.view(()),
+            ModuleName::Clipboard => {
+                self.clipboard
+                    .view(&self.config.clipboard_cmd)
+                    .map(|(content, _)| {
+                        (
+                            content,
+                            Some(OnModulePress::Action(Box::new(Message::OpenClipboard(id))))
+                        )
+                    })
+            }
             ModuleName::Workspaces => self.workspaces.view((
                 &self.outputs,
                 id,
@@ -263,23 +297,89 @@ impl App {
                 &self.config.appearance.workspace_colors,
                 self.config.appearance.special_workspace_colors.as_deref()
             )),
-            ModuleName::WindowTitle => self.window_title.view(()),
+            ModuleName::WindowTitle => self.window_title.view(&self.config.window_title),
             ModuleName::SystemInfo => self.system_info.view(&self.config.system),
             ModuleName::KeyboardLayout => self.keyboard_layout.view(&self.config.keyboard_layout),
             ModuleName::KeyboardSubmap => self.keyboard_submap.view(()),
-            ModuleName::Tray => self.tray.view((id, opacity)),
-            ModuleName::Clock => self.clock.view(&self.config.clock.format),
-            ModuleName::Battery => self.battery.data().map(|data| {
+            ModuleName::LockKeys => self.lock_keys.view(&self.config.lock_keys),
+            ModuleName::TilingLayout => self.tiling_layout.view(()).map(|(content, _)| {
                 (
-                    crate::views::battery::render_battery(data, &self.config.battery),
-                    None
+                    content,
+                    Some(OnModulePress::Action(Box::new(Message::TilingLayout(
+                        hydebar_core::modules::tiling_layout::Message::ToggleGaps
+                    ))))
                 )
             }),
-            ModuleName::Privacy => self.privacy.view(()),
-            ModuleName::Settings => self.settings.view(()),
+            ModuleName::Tray => {
+                crate::views::tray::render_tray(&self.tray, &self.config.tray, id, opacity)
+                    .map(|view| (view, None))
+            }
+            ModuleName::Clock => self.clock.view(&self.config.clock),
+            ModuleName::Battery => {
+                let hide = self
+                    .config
+                    .battery
+                    .hide_when_unavailable
+                    .unwrap_or(self.config.hide_when_unavailable);
+
+                if hide && !self.battery.is_available() {
+                    return None;
+                }
+
+                self.battery.data().map(|data| {
+                    let animate = self.config.appearance.animations.enabled && !self.frozen;
+                    let glyph = animate
+                        .then(|| self.battery.charging_frame(Instant::now()))
+                        .flatten()
+                        .unwrap_or_else(|| data.icon.into());
+
+                    (
+                        crate::views::battery::render_battery(data, glyph, &self.config.battery),
+                        None
+                    )
+                })
+            }
+            ModuleName::Privacy => self.privacy.view((
+                self.config.privacy.screenshare_cmd.clone().map(|command| {
+                    Message::LaunchCommand(
+                        command,
+                        self.module_context
+                            .command_context("Privacy")
+                            .with_output(self.outputs.get_monitor_name(id))
+                    )
+                }),
+                self.config.privacy.display
+            )),
+            ModuleName::Settings => self.settings.view(IndicatorOptions {
+                hide_unavailable: self
+                    .config
+                    .settings
+                    .hide_when_unavailable
+                    .unwrap_or(self.config.hide_when_unavailable),
+                volume_icons:     &self.config.settings.volume_icons,
+                volume_label:     self.config.settings.volume.as_ref(),
+                icon_position:    self.config.settings.icon_position,
+                battery:          &self.config.battery,
+                throughput:       &self.config.settings.network.format
+            }),
             ModuleName::MediaPlayer => self.media_player.view(&self.config.media_player),
+            ModuleName::Mic => self.mic.view((
+                Message::Settings(hydebar_core::modules::settings::Message::Audio(
+                    hydebar_core::modules::settings::AudioMessage::ToggleSourceMute
+                )),
+                &self.config.mic,
+                self.settings.audio()
+            )),
             ModuleName::Notifications => self.notifications.view(()),
-            ModuleName::Screenshot => self.screenshot.view(())
+            ModuleName::Screenshot => self.screenshot.view(()),
+            ModuleName::IdleInhibitor => self.settings.idle_inhibited().map(|inhibited| {
+                (
+                    crate::views::idle_inhibitor::render_idle_inhibitor(inhibited),
+                    Some(OnModulePress::Action(Box::new(Message::Settings(
+                        hydebar_core::modules::settings::Message::ToggleInhibitIdle
+                    ))))
+                )
+            })
         }
     }
 
@@ -307,20 +407,49 @@ impl App {
                 }
             }
             ModuleName::Updates => self.updates.subscription(),
+            ModuleName::CommandOutput => self.command_output.subscription(),
             ModuleName::Clipboard => self.clipboard.subscription(),
             ModuleName::Workspaces => self.workspaces.subscription(),
             ModuleName::WindowTitle => self.window_title.subscription(),
             ModuleName::SystemInfo => self.system_info.subscription(),
             ModuleName::KeyboardLayout => self.keyboard_layout.subscription(),
             ModuleName::KeyboardSubmap => self.keyboard_submap.subscription(),
+            ModuleName::LockKeys => self.lock_keys.subscription(),
+            ModuleName::TilingLayout => self.tiling_layout.subscription(),
             ModuleName::Tray => self.tray.subscription(),
             ModuleName::Clock => None,
             ModuleName::Battery => None,
             ModuleName::Privacy => self.privacy.subscription(),
             ModuleName::Settings => self.settings.subscription(),
             ModuleName::MediaPlayer => self.media_player.subscription(),
+            ModuleName::Mic => None,
             ModuleName::Notifications => self.notifications.subscription(),
-            ModuleName::Screenshot => self.screenshot.subscription()
+            ModuleName::Screenshot => self.screenshot.subscription(),
+            ModuleName::IdleInhibitor => None
+        }
+    }
+}
+
+/// Attach the press action of a module to its bar button. A right-click
+/// action wraps the button, which keeps handling the left click.
+fn bind_press<'a>(
+    button: PositionButton<'a, Message>,
+    action: OnModulePress<Message>,
+    id: Id
+) -> Element<'a, Message> {
+    let toggle = |menu_type: MenuType| {
+        move |button_ui_ref: ButtonUIRef| Message::ToggleMenu(menu_type.clone(), id, button_ui_ref)
+    };
+
+    match action {
+        OnModulePress::Action(action) => button.on_press(*action).into(),
+        OnModulePress::ToggleMenu(menu_type) => {
+            button.on_press_with_position(toggle(menu_type)).into()
+        }
+        OnModulePress::ToggleMenuOrAction(menu_type, action) => {
+            mouse_area(button.on_press_with_position(toggle(menu_type)))
+                .on_right_press(*action)
+                .into()
         }
     }
 }
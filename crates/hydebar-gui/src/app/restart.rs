@@ -0,0 +1,15 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once the user asked for a restart; read by the binary after the event
+/// loop has exited.
+static RESTART_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+pub(super) fn request_restart() {
+    RESTART_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Whether the bar exited because a restart was requested, in which case the
+/// caller should re-exec the binary.
+pub fn restart_requested() -> bool {
+    RESTART_REQUESTED.load(Ordering::SeqCst)
+}
@@ -0,0 +1,81 @@
+use std::time::{Duration, Instant, SystemTime};
+
+/// Wall-clock drift beyond the monotonic clock that is treated as a resume
+/// from suspend.
+const SUSPEND_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// Detects system suspend by comparing wall-clock and monotonic progress
+/// between micro ticks.
+///
+/// The monotonic clock stops while the machine sleeps whereas the wall clock
+/// keeps running, so a large gap between the two marks a resume.
+#[derive(Debug, Clone)]
+pub(super) struct ResumeDetector {
+    last_instant: Instant,
+    last_wall:    SystemTime
+}
+
+impl ResumeDetector {
+    pub(super) fn new(now: Instant, wall: SystemTime) -> Self {
+        Self {
+            last_instant: now,
+            last_wall:    wall
+        }
+    }
+
+    /// Record a tick and report whether the system resumed since the previous
+    /// one.
+    pub(super) fn observe(&mut self, now: Instant, wall: SystemTime) -> bool {
+        let monotonic = now.saturating_duration_since(self.last_instant);
+        let wall_clock = wall
+            .duration_since(self.last_wall)
+            .unwrap_or(Duration::ZERO);
+
+        self.last_instant = now;
+        self.last_wall = wall;
+
+        wall_clock.saturating_sub(monotonic) > SUSPEND_THRESHOLD
+    }
+}
+
+impl Default for ResumeDetector {
+    fn default() -> Self {
+        Self::new(Instant::now(), SystemTime::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regular_ticks_are_not_a_resume() {
+        let start = Instant::now();
+        let wall = SystemTime::now();
+        let mut detector = ResumeDetector::new(start, wall);
+
+        let tick = Duration::from_millis(500);
+        assert!(!detector.observe(start + tick, wall + tick));
+    }
+
+    #[test]
+    fn wall_clock_jump_is_a_resume() {
+        let start = Instant::now();
+        let wall = SystemTime::now();
+        let mut detector = ResumeDetector::new(start, wall);
+
+        let tick = Duration::from_millis(500);
+        assert!(detector.observe(start + tick, wall + Duration::from_secs(600)));
+        assert!(!detector.observe(start + tick * 2, wall + Duration::from_secs(600) + tick));
+    }
+
+    #[test]
+    fn stalled_event_loop_is_not_a_resume() {
+        let start = Instant::now();
+        let wall = SystemTime::now();
+        let mut detector = ResumeDetector::new(start, wall);
+
+        let stall = Duration::from_secs(45);
+        assert!(!detector.observe(start + stall, wall + stall));
+    }
+}
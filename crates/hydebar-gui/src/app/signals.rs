@@ -0,0 +1,115 @@
+use std::any::TypeId;
+
+use iced::{Subscription, futures::SinkExt, stream::channel};
+use log::{error, info};
+use tokio::signal::unix::{SignalKind, signal};
+
+use super::state::Message;
+
+/// Marker type identifying the refresh signal subscription.
+struct RefreshSignal;
+
+/// Marker type identifying the config dump signal subscription.
+struct ConfigDumpSignal;
+
+/// Marker type identifying the restart signal subscription.
+struct RestartSignal;
+
+/// Marker type identifying the freeze signal subscription.
+struct FreezeSignal;
+
+/// Marker type identifying the resume signal subscription.
+struct ResumeSignal;
+
+/// Emit [`Message::RefreshServices`] whenever the process receives `SIGUSR1`.
+///
+/// Compositor key bindings can run `pkill -USR1 hydebar` to recover stale
+/// services without restarting the bar.
+pub(super) fn refresh_signal() -> Subscription<Message> {
+    on_signal(
+        TypeId::of::<RefreshSignal>(),
+        SignalKind::user_defined1(),
+        "SIGUSR1",
+        Message::RefreshServices
+    )
+}
+
+/// Emit [`Message::CopyConfig`] whenever the process receives `SIGUSR2`.
+///
+/// `pkill -USR2 hydebar` logs the effective configuration with credentials
+/// redacted and copies it to the clipboard for pasting into bug reports.
+pub(super) fn config_dump_signal() -> Subscription<Message> {
+    on_signal(
+        TypeId::of::<ConfigDumpSignal>(),
+        SignalKind::user_defined2(),
+        "SIGUSR2",
+        Message::CopyConfig
+    )
+}
+
+/// Emit [`Message::Restart`] whenever the process receives `SIGRTMIN+1`.
+///
+/// `pkill -RTMIN+1 hydebar` re-executes the bar, which recovers from
+/// environment changes that a config reload cannot pick up. `SIGHUP` keeps
+/// its default action, so closing the launching terminal still ends the bar
+/// instead of restarting it.
+pub(super) fn restart_signal() -> Subscription<Message> {
+    on_signal(
+        TypeId::of::<RestartSignal>(),
+        SignalKind::from_raw(libc::SIGRTMIN() + 1),
+        "SIGRTMIN+1",
+        Message::Restart
+    )
+}
+
+/// Emit [`Message::Freeze`] whenever the process receives `SIGRTMIN+2`.
+///
+/// `pkill -RTMIN+2 hydebar` before a screen recording pauses animations, and
+/// `pkill -RTMIN+3 hydebar` resumes them. Realtime signals keep shell job
+/// control (`Ctrl+Z`, `fg`) working when the bar runs from a terminal.
+pub(super) fn freeze_signal() -> Subscription<Message> {
+    on_signal(
+        TypeId::of::<FreezeSignal>(),
+        SignalKind::from_raw(libc::SIGRTMIN() + 2),
+        "SIGRTMIN+2",
+        Message::Freeze
+    )
+}
+
+/// Emit [`Message::Resume`] whenever the process receives `SIGRTMIN+3`.
+pub(super) fn resume_signal() -> Subscription<Message> {
+    on_signal(
+        TypeId::of::<ResumeSignal>(),
+        SignalKind::from_raw(libc::SIGRTMIN() + 3),
+        "SIGRTMIN+3",
+        Message::Resume
+    )
+}
+
+fn on_signal(
+    id: TypeId,
+    kind: SignalKind,
+    name: &'static str,
+    message: Message
+) -> Subscription<Message> {
+    Subscription::run_with_id(
+        id,
+        channel(1, move |mut output| async move {
+            let mut signals = match signal(kind) {
+                Ok(signals) => signals,
+                Err(err) => {
+                    error!("failed to install {name} handler: {err}");
+                    return;
+                }
+            };
+
+            while signals.recv().await.is_some() {
+                info!("{name} received");
+
+                if output.send(message.clone()).await.is_err() {
+                    break;
+                }
+            }
+        })
+    )
+}
@@ -1,7 +1,8 @@
 use std::{
     collections::HashMap,
     path::PathBuf,
-    sync::{Arc, Mutex}
+    sync::{Arc, Mutex},
+    time::Instant
 };
 
 use flexi_logger::LoggerHandle;
@@ -16,15 +17,19 @@ use hydebar_core::{
         battery::Battery,
         clipboard::Clipboard,
         clock::Clock,
+        command_output::CommandOutput,
         custom_module::Custom,
         keyboard_layout::KeyboardLayout,
         keyboard_submap::KeyboardSubmap,
+        lock_keys::LockKeys,
         media_player::MediaPlayer,
+        mic::Mic,
         notifications::Notifications,
         privacy::Privacy,
         screenshot::Screenshot,
         settings::Settings,
         system_info::SystemInfo,
+        tiling_layout::TilingLayout,
         tray::{TrayMessage, TrayModule},
         updates::Updates,
         weather::Weather,
@@ -32,14 +37,15 @@ use hydebar_core::{
         workspaces::Workspaces
     },
     outputs::Outputs,
-    position_button::ButtonUIRef
+    position_button::ButtonUIRef,
+    utils::launcher::{CommandContext, LauncherError}
 };
 use hydebar_proto::{config::Config, ports::hyprland::HyprlandPort};
-use iced::{Task, event::wayland::OutputEvent, window::Id};
+use iced::{Rectangle, Task, event::wayland::OutputEvent, window::Id};
 use tokio::runtime::Handle;
 use wayland_client::protocol::wl_output::WlOutput;
 
-use super::{bus::BusFlushOutcome, micro_ticker::MicroTicker};
+use super::{bus::BusFlushOutcome, micro_ticker::MicroTicker, resume_detector::ResumeDetector};
 
 pub struct App {
     pub(super) config_path:         PathBuf,
@@ -48,6 +54,12 @@ pub struct App {
     pub(super) config_manager:      Arc<ConfigManager>,
     pub(super) bus_receiver:        Arc<Mutex<EventReceiver>>,
     pub(super) micro_ticker:        MicroTicker,
+    /// Set while a screen recording wants animations paused.
+    pub(super) frozen:              bool,
+    pub(super) resume_detector:     ResumeDetector,
+    /// Last command launched from the bar that failed right away, shown
+    /// until the instant stored with it.
+    pub(super) launch_failure:      Option<(LauncherError, Instant)>,
     pub(super) module_context:      ModuleContext,
     pub config:                     Arc<Config>,
     pub outputs:                    Outputs,
@@ -56,18 +68,22 @@ pub struct App {
     pub app_launcher:               AppLauncher,
     pub custom:                     HashMap<String, Custom>,
     pub updates:                    Updates,
+    pub command_output:             CommandOutput,
     pub clipboard:                  Clipboard,
     pub workspaces:                 Workspaces,
     pub window_title:               WindowTitle,
     pub system_info:                SystemInfo,
     pub keyboard_layout:            KeyboardLayout,
     pub keyboard_submap:            KeyboardSubmap,
+    pub lock_keys:                  LockKeys,
+    pub tiling_layout:              TilingLayout,
     pub tray:                       TrayModule,
     pub clock:                      Clock,
     pub battery:                    Battery,
     pub privacy:                    Privacy,
     pub settings:                   Settings,
     pub media_player:               MediaPlayer,
+    pub mic:                        Mic,
     pub notifications:              Notifications,
     pub screenshot:                 Screenshot,
     pub weather:                    Weather
@@ -78,26 +94,37 @@ pub enum Message {
     None,
     MicroTick,
     BusFlushed(BusFlushOutcome),
+    LogBusMetrics,
     ConfigChanged(ConfigApplied),
     ConfigDegraded(ConfigDegradation),
     ToggleMenu(MenuType, Id, ButtonUIRef),
     CloseMenu(Id),
     CloseAllMenus,
+    RefreshServices,
+    CopyConfig,
+    Restart,
+    Freeze,
+    Resume,
     ActivateNavigationMode,
     DeactivateNavigationMode,
+    /// A character key, resolved against `keybindings.menu`.
+    NavigationKey(String),
     NavigateUp,
     NavigateDown,
     NavigateLeft,
     NavigateRight,
     ActivateFocusedModule,
-    OpenLauncher,
-    OpenClipboard,
+    OpenLauncher(Id),
+    OpenClipboard(Id),
     Updates(modules::updates::Message),
+    CommandOutput(modules::command_output::Message),
     Workspaces(modules::workspaces::Message),
     WindowTitle(modules::window_title::Message),
     SystemInfo(modules::system_info::Message),
     KeyboardLayout(modules::keyboard_layout::Message),
     KeyboardSubmap(modules::keyboard_submap::Message),
+    LockKeys(modules::lock_keys::Message),
+    TilingLayout(modules::tiling_layout::Message),
     Tray(TrayMessage),
     Clock(modules::clock::Message),
     Battery(modules::battery::Message),
@@ -108,7 +135,9 @@ pub enum Message {
     Screenshot(modules::screenshot::ScreenshotMessage),
     Weather(modules::weather::Message),
     OutputEvent((OutputEvent, WlOutput)),
-    LaunchCommand(String),
+    InputRegionChanged(Id, Vec<Rectangle>),
+    LaunchCommand(String, CommandContext),
+    LaunchFailed(LauncherError),
     CustomUpdate(String, modules::custom_module::Message)
 }
 
@@ -210,7 +239,10 @@ impl App {
                 _hyprland: hyprland,
                 config_manager,
                 bus_receiver: Arc::new(Mutex::new(bus_receiver)),
-                micro_ticker: MicroTicker::default(),
+                micro_ticker: MicroTicker::from_config(&config.appearance.refresh),
+                frozen: false,
+                resume_detector: ResumeDetector::default(),
+                launch_failure: None,
                 module_context,
                 outputs,
                 navigation_mode: false,
@@ -218,18 +250,22 @@ impl App {
                 app_launcher: AppLauncher,
                 custom,
                 updates: Updates::default(),
+                command_output: CommandOutput::default(),
                 clipboard: Clipboard,
                 workspaces: Workspaces::new(Arc::clone(&hyprland_clone), &config.workspaces),
                 window_title: WindowTitle::new(Arc::clone(&hyprland_clone), &config.window_title),
                 system_info: SystemInfo::default(),
                 keyboard_layout: KeyboardLayout::new(Arc::clone(&hyprland_clone)),
-                keyboard_submap: KeyboardSubmap::new(hyprland_clone),
+                keyboard_submap: KeyboardSubmap::new(Arc::clone(&hyprland_clone)),
+                lock_keys: LockKeys::new(Arc::clone(&hyprland_clone)),
+                tiling_layout: TilingLayout::new(hyprland_clone),
                 tray: TrayModule::default(),
                 clock: Clock::default(),
                 battery: Battery::default(),
                 privacy: Privacy::default(),
                 settings: Settings::default(),
                 media_player: MediaPlayer::default(),
+                mic: Mic::default(),
                 notifications: Notifications::default(),
                 screenshot: Screenshot::default(),
                 weather: Weather::new(
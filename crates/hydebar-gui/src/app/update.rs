@@ -1,4 +1,8 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime}
+};
 
 #[allow(unused_imports)]
 use hydebar_core::modules::custom_module::Custom as _;
@@ -12,9 +16,12 @@ use hydebar_core::{
     },
     position_button::ButtonUIRef,
     services::{ServiceEvent, brightness::BrightnessCommand, tray::TrayEvent},
-    utils
+    utils::{
+        self,
+        launcher::{self, CommandContext, CommandSettings}
+    }
 };
-use hydebar_proto::config::{Config, ModuleName};
+use hydebar_proto::config::{Config, InputRegion, ModuleName};
 use iced::{
     Subscription, Task,
     event::{
@@ -27,23 +34,89 @@ use log::{debug, error, info, warn};
 
 use super::{
     bus::drain_bus,
+    micro_ticker::MicroTicker,
+    restart::request_restart,
+    signals::{config_dump_signal, freeze_signal, refresh_signal, restart_signal, resume_signal},
     state::{App, Message}
 };
 use crate::get_log_spec;
 
+/// How long a failed launch stays visible on the bar.
+const LAUNCH_FAILURE_DURATION: Duration = Duration::from_secs(5);
+
+/// How often the event bus counters are logged at debug level.
+const BUS_METRICS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Reverse the direction carried by scroll-driven messages.
+fn invert_scroll(message: Message) -> Message {
+    match message {
+        Message::Workspaces(modules::workspaces::Message::Scrolled(delta)) => Message::Workspaces(
+            modules::workspaces::Message::Scrolled(utils::invert_scroll(delta))
+        ),
+        Message::Tray(TrayMessage::Scrolled(name, delta)) => {
+            Message::Tray(TrayMessage::Scrolled(name, utils::invert_scroll(delta)))
+        }
+        message => message
+    }
+}
+
 impl App {
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        let task = self.handle(message);
+
+        // Menus open and close along many paths, so follow the system info
+        // menu after every message rather than in each of them.
+        self.system_info
+            .set_menu_open(self.outputs.menu_type_is_open(&MenuType::SystemInfo));
+
+        task
+    }
+
+    fn handle(&mut self, message: Message) -> Task<Message> {
+        let message = if self.config.invert_scroll {
+            invert_scroll(message)
+        } else {
+            message
+        };
+
         match message {
             Message::MicroTick => {
-                if self.outputs.menu_is_open() {
+                if self
+                    .launch_failure
+                    .as_ref()
+                    .is_some_and(|(_, until)| *until <= Instant::now())
+                {
+                    self.launch_failure = None;
+                }
+
+                if self.frozen {
+                    self.outputs.finish_menu_animations();
+                } else if self.outputs.menu_is_open() {
                     self.outputs
                         .tick_menu_animations(&self.config.appearance.animations);
                 }
 
-                Task::perform(
+                let drain = Task::perform(
                     drain_bus(Arc::clone(&self.bus_receiver)),
                     Message::BusFlushed
-                )
+                );
+
+                if self
+                    .resume_detector
+                    .observe(Instant::now(), SystemTime::now())
+                {
+                    info!("Resume from suspend detected, refreshing modules");
+                    Task::batch(vec![self.update(Message::RefreshServices), drain])
+                } else {
+                    drain
+                }
+            }
+            Message::LogBusMetrics => {
+                match self.bus_receiver.lock() {
+                    Ok(receiver) => debug!("Event bus: {}", receiver.metrics()),
+                    Err(err) => error!("event bus receiver poisoned: {err}")
+                }
+                Task::none()
             }
             Message::BusFlushed(outcome) => {
                 if outcome.had_error() {
@@ -61,9 +134,11 @@ impl App {
                         self.micro_ticker.record_activity();
                     }
 
+                    let freeze_readouts = self.frozen && self.config.freeze_readouts;
                     let tasks: Vec<_> = outcome
                         .into_events()
                         .into_iter()
+                        .filter(|event| !(freeze_readouts && is_volatile_readout(event)))
                         .filter_map(App::message_from_bus_event)
                         .map(|msg| self.update(msg))
                         .collect();
@@ -78,15 +153,24 @@ impl App {
                     impact
                 } = update;
 
-                info!("New config applied: {config:?}");
+                info!("New config applied");
+                let changes =
+                    config::diff::describe_changes(self.config.as_ref(), config.as_ref());
+                for change in changes {
+                    info!("  {change}");
+                }
                 debug!("Config impact: {impact:?}");
 
                 let mut tasks = Vec::new();
 
                 let outputs_need_sync = impact.outputs_changed
                     || impact.position_changed
+                    || impact.auxiliary_bars_changed
+                    || self.config.reserved_height != config.reserved_height
+                    || self.config.layer != config.layer
                     || self.config.appearance.style != config.appearance.style
-                    || self.config.appearance.scale_factor != config.appearance.scale_factor;
+                    || self.config.appearance.scale_factor != config.appearance.scale_factor
+                    || self.config.output_scale != config.output_scale;
 
                 if outputs_need_sync {
                     warn!("Outputs or layout changed, syncing");
@@ -98,10 +182,20 @@ impl App {
                     ));
                 }
 
+                if self.config.input_region != config.input_region
+                    && config.input_region == InputRegion::Bar
+                {
+                    tasks.push(self.outputs.reset_input_regions());
+                }
+
                 if impact.custom_modules_changed {
                     self.update_custom_modules(&config, &impact);
                 }
 
+                if self.config.appearance.refresh != config.appearance.refresh {
+                    self.micro_ticker = MicroTicker::from_config(&config.appearance.refresh);
+                }
+
                 self.config = config;
 
                 self.register_modules();
@@ -134,8 +228,6 @@ impl App {
                         }
                     }
                     MenuType::Settings => {
-                        self.settings.sub_menu = None;
-
                         if let Some(brightness) = self.settings.brightness.as_mut() {
                             use hydebar_core::services::Service;
                             cmd.push(brightness.command(BrightnessCommand::Refresh).map(
@@ -147,15 +239,44 @@ impl App {
                             ));
                         }
                     }
+                    MenuType::Calendar if !self.outputs.menu_type_is_open(&MenuType::Calendar) => {
+                        self.clock.select_today();
+                    }
                     _ => {}
                 };
+                let settings = menu_type == MenuType::Settings;
                 cmd.push(
                     self.outputs
                         .toggle_menu(id, menu_type, button_ui_ref, &self.config)
                 );
 
+                if settings && self.outputs.menu_type_is_open(&MenuType::Settings) {
+                    self.settings.reset_sub_menu(&self.config.settings);
+                }
+
                 Task::batch(cmd)
             }
+            Message::Freeze => {
+                info!("Freezing animations");
+                self.frozen = true;
+                Task::none()
+            }
+            Message::Resume => {
+                if !self.frozen {
+                    return Task::none();
+                }
+
+                info!("Resuming animations");
+                self.frozen = false;
+
+                // Dropped readout updates are not replayed; refresh so the
+                // frozen modules catch up immediately.
+                if self.config.freeze_readouts {
+                    self.update(Message::RefreshServices)
+                } else {
+                    Task::none()
+                }
+            }
             Message::CloseMenu(id) => self.outputs.close_menu(id, &self.config),
             Message::CloseAllMenus => {
                 if self.outputs.menu_is_open() {
@@ -164,6 +285,25 @@ impl App {
                     Task::none()
                 }
             }
+            Message::RefreshServices => {
+                info!("Refreshing all services");
+                self.register_modules();
+                Task::none()
+            }
+            Message::CopyConfig => {
+                let report = config::report::redacted_report(&self.config);
+                info!("Effective configuration:\n{report}");
+                iced::clipboard::write(report)
+            }
+            Message::Restart => {
+                info!("Restarting hydebar");
+                request_restart();
+                self.logger.flush();
+
+                // Tear the surfaces down before leaving the event loop so the
+                // re-executed bar never overlaps the old one.
+                self.outputs.destroy_all().chain(iced::exit())
+            }
             Message::ActivateNavigationMode => {
                 if !self.navigation_mode && self.config.keybindings.enabled {
                     info!("Activating navigation mode");
@@ -184,6 +324,22 @@ impl App {
                     Task::none()
                 }
             }
+            Message::NavigationKey(key) => {
+                let bindings = &self.config.keybindings.menu;
+                let message = if key == bindings.up {
+                    Message::NavigateUp
+                } else if key == bindings.down {
+                    Message::NavigateDown
+                } else if key == bindings.left {
+                    Message::NavigateLeft
+                } else if key == bindings.right {
+                    Message::NavigateRight
+                } else {
+                    return Task::none();
+                };
+                debug!("Navigation key {key}: {message:?}");
+                self.update(message)
+            }
             Message::NavigateUp | Message::NavigateDown => {
                 if !self.navigation_mode {
                     return Task::none();
@@ -225,7 +381,12 @@ impl App {
 
                 let index = self.focused_module_index.unwrap();
 
-                let main_window_id = if let Some(id) = self.outputs.first_main_window_id() {
+                // Not tied to a surface, so the menu opens on the monitor
+                // Hyprland has focused.
+                let main_window_id = if let Some(id) = self
+                    .outputs
+                    .main_window_id_on(self.workspaces.active_monitor())
+                {
                     id
                 } else {
                     return Task::none();
@@ -237,7 +398,8 @@ impl App {
                             info!("Activating module at index {} with action", index);
                             return self.update(*msg);
                         }
-                        OnModulePress::ToggleMenu(menu_type) => {
+                        OnModulePress::ToggleMenu(menu_type)
+                        | OnModulePress::ToggleMenuOrAction(menu_type, _) => {
                             info!("Activating module at index {} - opening menu {:?}", index, menu_type);
 
                             let center_button_ref = ButtonUIRef {
@@ -259,14 +421,23 @@ impl App {
                 }
                 Task::none()
             }
-            Message::OpenLauncher => {
-                if let Some(app_launcher_cmd) = self.config.app_launcher_cmd.as_ref() {
-                    utils::launcher::execute_command(app_launcher_cmd.to_string());
-                }
+            Message::CommandOutput(message) => {
+                self.command_output.update(message);
                 Task::none()
             }
-            Message::LaunchCommand(command) => {
-                utils::launcher::execute_command(command);
+            Message::OpenLauncher(id) => match self.config.app_launcher_cmd.as_ref() {
+                Some(app_launcher_cmd) => launch(
+                    app_launcher_cmd.to_string(),
+                    self.module_context
+                        .command_context("AppLauncher")
+                        .with_output(self.outputs.get_monitor_name(id))
+                ),
+                None => Task::none()
+            },
+            Message::LaunchCommand(command, context) => launch(command, context),
+            Message::LaunchFailed(error) => {
+                error!("{error}");
+                self.launch_failure = Some((error, Instant::now() + LAUNCH_FAILURE_DURATION));
                 Task::none()
             }
             Message::CustomUpdate(name, message) => {
@@ -276,12 +447,15 @@ impl App {
                 };
                 Task::none()
             }
-            Message::OpenClipboard => {
-                if let Some(clipboard_cmd) = self.config.clipboard_cmd.as_ref() {
-                    utils::launcher::execute_command(clipboard_cmd.to_string());
-                }
-                Task::none()
-            }
+            Message::OpenClipboard(id) => match self.config.clipboard_cmd.as_ref() {
+                Some(clipboard_cmd) => launch(
+                    clipboard_cmd.to_string(),
+                    self.module_context
+                        .command_context("Clipboard")
+                        .with_output(self.outputs.get_monitor_name(id))
+                ),
+                None => Task::none()
+            },
             Message::Workspaces(msg) => {
                 self.workspaces.update(msg, &self.config.workspaces);
 
@@ -303,6 +477,15 @@ impl App {
                 self.keyboard_submap.update(message);
                 Task::none()
             }
+            Message::LockKeys(message) => {
+                self.lock_keys.update(message);
+                Task::none()
+            }
+            Message::TilingLayout(message) => {
+                self.tiling_layout
+                    .update(message, &self.config.tiling_layout);
+                Task::none()
+            }
             Message::Tray(msg) => {
                 let close_tray = match &msg {
                     TrayMessage::Event(event) => {
@@ -320,6 +503,9 @@ impl App {
                 self.tray.update(msg);
                 close_tray
             }
+            Message::Clock(modules::clock::Message::CopyTimestamp) => {
+                iced::clipboard::write(self.clock.timestamp(&self.config.clock.copy_format))
+            }
             Message::Clock(message) => {
                 self.clock.update(message);
                 Task::none()
@@ -382,6 +568,20 @@ impl App {
                 }
                 _ => Task::none()
             },
+            Message::InputRegionChanged(id, regions) => {
+                if self.config.input_region != InputRegion::Modules {
+                    return Task::none();
+                }
+
+                let scale_factor = self.scale_factor(id) as f32;
+                self.outputs.set_input_region(
+                    id,
+                    regions
+                        .into_iter()
+                        .map(|region| region * scale_factor)
+                        .collect()
+                )
+            }
             Message::MediaPlayer(msg) => {
                 self.media_player.update(msg);
                 Task::none()
@@ -408,6 +608,11 @@ impl App {
                     ConfigEvent::Degraded(degradation) => Message::ConfigDegraded(degradation)
                 }
             ),
+            refresh_signal(),
+            config_dump_signal(),
+            restart_signal(),
+            freeze_signal(),
+            resume_signal(),
             listen_with(|evt, _, _| match evt {
                 iced::Event::PlatformSpecific(iced::event::PlatformSpecific::Wayland(
                     WaylandEvent::Output(event, wl_output)
@@ -440,19 +645,7 @@ impl App {
                             return Some(Message::ActivateNavigationMode);
                         }
 
-                        if ch_str == "k" {
-                            debug!("Navigate up: k");
-                            return Some(Message::NavigateUp);
-                        } else if ch_str == "j" {
-                            debug!("Navigate down: j");
-                            return Some(Message::NavigateDown);
-                        } else if ch_str == "h" {
-                            debug!("Navigate left: h");
-                            return Some(Message::NavigateLeft);
-                        } else if ch_str == "l" {
-                            debug!("Navigate right: l");
-                            return Some(Message::NavigateRight);
-                        }
+                        return Some(Message::NavigationKey(ch_str.to_owned()));
                     }
 
                     None
@@ -461,14 +654,45 @@ impl App {
             }),
         ];
 
-        subscriptions.extend(self.modules_subscriptions(&self.config.modules.left));
-        subscriptions.extend(self.modules_subscriptions(&self.config.modules.center));
-        subscriptions.extend(self.modules_subscriptions(&self.config.modules.right));
+        if log::log_enabled!(log::Level::Debug) {
+            subscriptions.push(time::every(BUS_METRICS_INTERVAL).map(|_| Message::LogBusMetrics));
+        }
+
+        if self.outputs.menu_type_is_open(&MenuType::Settings) {
+            subscriptions.push(
+                self.settings
+                    .menu_subscription(&self.config.settings)
+                    .map(Message::Settings)
+            );
+        }
+
+        if self.outputs.menu_type_is_open(&MenuType::MediaPlayer) {
+            subscriptions.push(
+                self.media_player
+                    .menu_subscription()
+                    .map(Message::MediaPlayer)
+            );
+        }
+
+        for modules in self.config.layouts() {
+            subscriptions.extend(self.modules_subscriptions(&modules.left));
+            subscriptions.extend(self.modules_subscriptions(&modules.center));
+            subscriptions.extend(self.modules_subscriptions(&modules.right));
+        }
 
         Subscription::batch(subscriptions)
     }
 
     pub(crate) fn register_modules(&mut self) {
+        // Custom module listeners start while registering, so the shell and
+        // working directory have to be in place first.
+        self.module_context.set_command_settings(
+            CommandSettings::default()
+                .with_shell(self.config.command_shell.as_deref())
+                .with_cwd(self.config.command_cwd.as_deref())
+                .with_debounce(Duration::from_millis(self.config.command_debounce_ms))
+        );
+
         let ctx = &self.module_context;
         let register = |name: &str, result: Result<(), modules::ModuleError>| {
             if let Err(err) = result {
@@ -484,7 +708,9 @@ impl App {
             "clipboard",
             modules::Module::<Message>::register(&mut self.clipboard, ctx, ())
         );
-        self.clock.register(ctx, &self.config.clock.format);
+        self.clock.set_locale(self.config.locale.as_deref());
+        self.clock.set_alarms(&self.config.clock.alarms);
+        self.clock.register(ctx, &self.config.clock);
         self.weather.register(ctx);
         register(
             "updates",
@@ -494,6 +720,14 @@ impl App {
                 self.config.updates.as_ref()
             )
         );
+        register(
+            "command-output",
+            modules::Module::<Message>::register(
+                &mut self.command_output,
+                ctx,
+                self.config.command_output.as_ref()
+            )
+        );
         register(
             "workspaces",
             modules::Module::<Message>::register(
@@ -508,7 +742,7 @@ impl App {
         );
         register(
             "system-info",
-            modules::Module::<Message>::register(&mut self.system_info, ctx, ())
+            modules::Module::<Message>::register(&mut self.system_info, ctx, &self.config.system)
         );
         register(
             "keyboard-layout",
@@ -518,18 +752,26 @@ impl App {
             "keyboard-submap",
             modules::Module::<Message>::register(&mut self.keyboard_submap, ctx, ())
         );
+        register(
+            "lock-keys",
+            modules::Module::<Message>::register(&mut self.lock_keys, ctx, &self.config.lock_keys)
+        );
+        register(
+            "tiling-layout",
+            modules::Module::<Message>::register(&mut self.tiling_layout, ctx, ())
+        );
         register(
             "tray",
-            modules::Module::<Message>::register(&mut self.tray, ctx, ())
+            modules::Module::<Message>::register(&mut self.tray, ctx, &self.config.tray)
         );
-        self.battery.register(ctx);
+        self.battery.register(ctx, &self.config.battery);
         register(
             "privacy",
-            modules::Module::<Message>::register(&mut self.privacy, ctx, ())
+            modules::Module::<Message>::register(&mut self.privacy, ctx, &self.config.privacy)
         );
         register(
             "settings",
-            modules::Module::<Message>::register(&mut self.settings, ctx, ())
+            modules::Module::<Message>::register(&mut self.settings, ctx, &self.config.settings)
         );
         register(
             "media-player",
@@ -607,11 +849,14 @@ impl App {
     fn message_from_module_event(event: ModuleEvent) -> Option<Message> {
         match event {
             ModuleEvent::Updates(message) => Some(Message::Updates(message)),
+            ModuleEvent::CommandOutput(message) => Some(Message::CommandOutput(message)),
             ModuleEvent::Workspaces(message) => Some(Message::Workspaces(message)),
             ModuleEvent::WindowTitle(message) => Some(Message::WindowTitle(message)),
             ModuleEvent::SystemInfo(message) => Some(Message::SystemInfo(message)),
             ModuleEvent::KeyboardLayout(message) => Some(Message::KeyboardLayout(message)),
             ModuleEvent::KeyboardSubmap(message) => Some(Message::KeyboardSubmap(message)),
+            ModuleEvent::LockKeys(message) => Some(Message::LockKeys(message)),
+            ModuleEvent::TilingLayout(message) => Some(Message::TilingLayout(message)),
             ModuleEvent::Tray(message) => Some(Message::Tray(message)),
             ModuleEvent::Clock(message) => Some(Message::Clock(message)),
             ModuleEvent::Weather(message) => Some(Message::Weather(message)),
@@ -628,3 +873,24 @@ impl App {
         }
     }
 }
+
+/// Start `command` in the background, reporting a failure back to the bar.
+fn launch(command: String, context: CommandContext) -> Task<Message> {
+    Task::perform(launcher::launch(command, context), |result| {
+        result.map_or_else(Message::LaunchFailed, |()| Message::None)
+    })
+}
+
+/// Bus events carrying readouts that change on their own, held back while the
+/// bar is frozen with `freeze_readouts` enabled.
+fn is_volatile_readout(event: &BusEvent) -> bool {
+    matches!(
+        event,
+        BusEvent::Module(
+            ModuleEvent::Clock(_)
+                | ModuleEvent::SystemInfo(_)
+                | ModuleEvent::MediaPlayer(_)
+                | ModuleEvent::Weather(_)
+        )
+    )
+}
@@ -1,24 +1,33 @@
-use std::f32::consts::PI;
+use std::{collections::HashSet, f32::consts::PI};
 
 use hydebar_core::{
     HEIGHT,
-    menu::{MenuSize, MenuType, menu_wrapper},
+    components::icons::{Icons, icon},
+    menu::{KeyHints, MenuSize, MenuType, menu_wrapper},
     modules::settings::SettingsViewExt,
     outputs::HasOutput,
-    style::{backdrop_color, darken_color, hydebar_theme}
+    style::{backdrop_color, darken_color, hydebar_theme, menu_backdrop_style},
+    utils::truncate_text
 };
-use hydebar_proto::config::{AppearanceStyle, Position};
+use hydebar_proto::config::{AppearanceStyle, BackdropScope, InputRegion, Position};
 use iced::{
     Alignment, Color, Element, Gradient, Length, Radians, Theme,
     daemon::Appearance,
     gradient::Linear,
-    widget::{Row, container},
+    widget::{Row, Space, container, row, text},
     window::Id
 };
 
 use super::state::{App, Message};
 use crate::centerbox;
 
+/// Opacity multiplier applied to bars on monitors without input focus when
+/// `dim_inactive_monitors` is enabled.
+const INACTIVE_MONITOR_OPACITY: f32 = 0.5;
+
+/// Longest launch failure shown on the bar, in characters.
+const LAUNCH_FAILURE_LENGTH: u32 = 60;
+
 impl App {
     pub fn title(&self, _id: Id) -> String {
         String::from("hydebar")
@@ -36,33 +45,95 @@ impl App {
         }
     }
 
-    pub fn scale_factor(&self, _id: Id) -> f64 {
-        self.config.appearance.scale_factor
+    pub fn scale_factor(&self, id: Id) -> f64 {
+        self.outputs
+            .scale_factor(id)
+            .unwrap_or(self.config.appearance.scale_factor)
+    }
+
+    /// Alert glyph and message of a command that failed right after being
+    /// launched from the bar.
+    fn launch_failure_notice(&self) -> Option<Element<'_, Message>> {
+        let (error, _) = self.launch_failure.as_ref()?;
+
+        Some(
+            container(
+                row![
+                    icon(Icons::Alert),
+                    text(truncate_text(&error.to_string(), LAUNCH_FAILURE_LENGTH)).size(12)
+                ]
+                .align_y(Alignment::Center)
+                .spacing(4)
+            )
+            .style(|theme: &Theme| container::Style {
+                text_color: Some(theme.palette().danger),
+                ..Default::default()
+            })
+            .into()
+        )
+    }
+
+    fn is_dimmed(&self, id: Id) -> bool {
+        if !self.config.dim_inactive_monitors {
+            return false;
+        }
+
+        match (
+            self.workspaces.active_monitor(),
+            self.outputs.get_monitor_name(id)
+        ) {
+            (Some(active), Some(name)) => active != name,
+            _ => false
+        }
     }
 
     pub fn view(&self, id: Id) -> Element<'_, Message> {
+        let position = self.outputs.position(id).unwrap_or(self.config.position);
+        let backdrop = self.config.appearance.menu.backdrop;
+        // With a screen-wide backdrop the dimming surface covers the bar and
+        // the area around the menu, so neither darkens on its own.
+        let bar_backdrop = match self.config.appearance.menu.backdrop_scope {
+            BackdropScope::Bar => backdrop,
+            BackdropScope::Screen => 0.
+        };
+
         match self.outputs.has(id) {
-            Some(HasOutput::Main) => {
-                let left = self.modules_section(
-                    &self.config.modules.left,
-                    id,
-                    self.config.appearance.opacity
-                );
-                let center = self.modules_section(
-                    &self.config.modules.center,
-                    id,
-                    self.config.appearance.opacity
-                );
-                let right = self.modules_section(
-                    &self.config.modules.right,
-                    id,
+            Some(HasOutput::Main(bar)) => {
+                let Some(modules) = self.config.bar_modules(bar) else {
+                    return Row::new().into();
+                };
+
+                let opacity = if self.is_dimmed(id) {
+                    self.config.appearance.opacity * INACTIVE_MONITOR_OPACITY
+                } else {
                     self.config.appearance.opacity
-                );
+                };
 
-                let centerbox = centerbox::Centerbox::new([left, center, right])
+                let mut rendered = HashSet::new();
+                let left = self.modules_section(&modules.left, &mut rendered, id, opacity);
+                let center = self.modules_section(&modules.center, &mut rendered, id, opacity);
+                let mut right = self.modules_section(&modules.right, &mut rendered, id, opacity);
+                if let Some(notice) = self.launch_failure_notice() {
+                    right = row![notice, right]
+                        .height(Length::Shrink)
+                        .align_y(Alignment::Center)
+                        .spacing(8)
+                        .into();
+                }
+                if self.config.debug_menu && bar == 0 {
+                    right = row![right, self.debug_button(id)]
+                        .height(Length::Shrink)
+                        .align_y(Alignment::Center)
+                        .spacing(4)
+                        .into();
+                }
+
+                let mut centerbox = centerbox::Centerbox::new([left, center, right])
                     .spacing(4)
                     .width(Length::Fill)
                     .align_items(Alignment::Center)
+                    .separators(self.config.appearance.section_separator)
+                    .empty_sections(self.config.appearance.empty_sections)
                     .height(
                         if self.config.appearance.style == AppearanceStyle::Islands {
                             HEIGHT
@@ -78,23 +149,28 @@ impl App {
                         }
                     );
 
+                if self.config.input_region == InputRegion::Modules {
+                    centerbox = centerbox.on_regions_change(move |regions| {
+                        Message::InputRegionChanged(id, regions)
+                    });
+                }
+
+                let darken = self.outputs.menu_is_open() && bar_backdrop > 0.;
+
                 container(centerbox)
-                    .style(|t| container::Style {
+                    .style(move |t| container::Style {
                         background: match self.config.appearance.style {
                             AppearanceStyle::Gradient => Some({
-                                let start_color = t
-                                    .palette()
-                                    .background
-                                    .scale_alpha(self.config.appearance.opacity);
+                                let start_color = t.palette().background.scale_alpha(opacity);
 
-                                let start_color = if self.outputs.menu_is_open() {
-                                    darken_color(start_color, self.config.appearance.menu.backdrop)
+                                let start_color = if darken {
+                                    darken_color(start_color, bar_backdrop)
                                 } else {
                                     start_color
                                 };
 
-                                let end_color = if self.outputs.menu_is_open() {
-                                    backdrop_color(self.config.appearance.menu.backdrop)
+                                let end_color = if darken {
+                                    backdrop_color(bar_backdrop)
                                 } else {
                                     Color::TRANSPARENT
                                 };
@@ -103,39 +179,37 @@ impl App {
                                     Linear::new(Radians(PI))
                                         .add_stop(
                                             0.0,
-                                            match self.config.position {
-                                                Position::Top => start_color,
-                                                Position::Bottom => end_color
+                                            match position {
+                                                Position::Bottom => end_color,
+                                                Position::Top
+                                                | Position::Left
+                                                | Position::Right => start_color
                                             }
                                         )
                                         .add_stop(
                                             1.0,
-                                            match self.config.position {
-                                                Position::Top => end_color,
-                                                Position::Bottom => start_color
+                                            match position {
+                                                Position::Bottom => start_color,
+                                                Position::Top
+                                                | Position::Left
+                                                | Position::Right => end_color
                                             }
                                         )
                                 )
                                 .into()
                             }),
                             AppearanceStyle::Solid => Some({
-                                let bg = t
-                                    .palette()
-                                    .background
-                                    .scale_alpha(self.config.appearance.opacity);
-                                if self.outputs.menu_is_open() {
-                                    darken_color(bg, self.config.appearance.menu.backdrop)
+                                let bg = t.palette().background.scale_alpha(opacity);
+                                if darken {
+                                    darken_color(bg, bar_backdrop)
                                 } else {
                                     bg
                                 }
                                 .into()
                             }),
                             AppearanceStyle::Islands => {
-                                if self.outputs.menu_is_open() {
-                                    Some(
-                                        backdrop_color(self.config.appearance.menu.backdrop)
-                                            .into()
-                                    )
+                                if darken {
+                                    Some(backdrop_color(bar_backdrop).into())
                                 } else {
                                     None
                                 }
@@ -147,6 +221,19 @@ impl App {
             }
             Some(HasOutput::Menu(menu_info)) => {
                 let animated_opacity = self.outputs.get_menu_opacity(id);
+                let key_hints = match menu_info {
+                    Some((menu_type, _))
+                        if self.config.keybindings.show_hints
+                            && menu_type.keyboard_focus(&self.config) =>
+                    {
+                        if self.navigation_mode {
+                            KeyHints::Navigation(&self.config.keybindings.menu)
+                        } else {
+                            KeyHints::Menu
+                        }
+                    }
+                    _ => KeyHints::Hidden
+                };
                 match menu_info {
                     Some((MenuType::Updates, button_ui_ref)) => menu_wrapper(
                         id,
@@ -155,10 +242,12 @@ impl App {
                             .map(Message::Updates),
                         MenuSize::Small,
                         *button_ui_ref,
-                        self.config.position,
+                        position,
                         self.config.appearance.style,
+                        self.config.appearance.menu.margin,
                         animated_opacity,
-                        self.config.appearance.menu.backdrop,
+                        bar_backdrop,
+                        key_hints,
                         Message::None,
                         Message::CloseMenu(id)
                     ),
@@ -169,10 +258,32 @@ impl App {
                             .map(Message::Tray),
                         MenuSize::Small,
                         *button_ui_ref,
-                        self.config.position,
+                        position,
                         self.config.appearance.style,
+                        self.config.appearance.menu.margin,
                         animated_opacity,
-                        self.config.appearance.menu.backdrop,
+                        bar_backdrop,
+                        key_hints,
+                        Message::None,
+                        Message::CloseMenu(id)
+                    ),
+                    Some((MenuType::TrayOverflow, button_ui_ref)) => menu_wrapper(
+                        id,
+                        crate::views::tray::render_tray_overflow(
+                            &self.tray,
+                            &self.config.tray,
+                            id,
+                            *button_ui_ref,
+                            animated_opacity
+                        ),
+                        MenuSize::Small,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        self.config.appearance.menu.margin,
+                        animated_opacity,
+                        bar_backdrop,
+                        key_hints,
                         Message::None,
                         Message::CloseMenu(id)
                     ),
@@ -182,16 +293,23 @@ impl App {
                             .menu_view(
                                 id,
                                 &self.config.settings,
+                                &self.config.battery,
+                                self.config
+                                    .settings
+                                    .hide_when_unavailable
+                                    .unwrap_or(self.config.hide_when_unavailable),
                                 animated_opacity,
-                                self.config.position
+                                position
                             )
                             .map(Message::Settings),
                         MenuSize::Medium,
                         *button_ui_ref,
-                        self.config.position,
+                        position,
                         self.config.appearance.style,
+                        self.config.appearance.menu.margin,
                         animated_opacity,
-                        self.config.appearance.menu.backdrop,
+                        bar_backdrop,
+                        key_hints,
                         Message::None,
                         Message::CloseMenu(id)
                     ),
@@ -202,22 +320,28 @@ impl App {
                             .map(Message::MediaPlayer),
                         MenuSize::Large,
                         *button_ui_ref,
-                        self.config.position,
+                        position,
                         self.config.appearance.style,
+                        self.config.appearance.menu.margin,
                         animated_opacity,
-                        self.config.appearance.menu.backdrop,
+                        bar_backdrop,
+                        key_hints,
                         Message::None,
                         Message::CloseMenu(id)
                     ),
                     Some((MenuType::SystemInfo, button_ui_ref)) => menu_wrapper(
                         id,
-                        self.system_info.menu_view().map(Message::SystemInfo),
+                        self.system_info
+                            .menu_view(&self.config.system)
+                            .map(Message::SystemInfo),
                         MenuSize::Medium,
                         *button_ui_ref,
-                        self.config.position,
+                        position,
                         self.config.appearance.style,
+                        self.config.appearance.menu.margin,
                         animated_opacity,
-                        self.config.appearance.menu.backdrop,
+                        bar_backdrop,
+                        key_hints,
                         Message::None,
                         Message::CloseMenu(id)
                     ),
@@ -228,10 +352,12 @@ impl App {
                             .map(Message::Notifications),
                         MenuSize::Medium,
                         *button_ui_ref,
-                        self.config.position,
+                        position,
                         self.config.appearance.style,
+                        self.config.appearance.menu.margin,
                         animated_opacity,
-                        self.config.appearance.menu.backdrop,
+                        bar_backdrop,
+                        key_hints,
                         Message::None,
                         Message::CloseMenu(id)
                     ),
@@ -242,28 +368,51 @@ impl App {
                             .map(Message::Screenshot),
                         MenuSize::Small,
                         *button_ui_ref,
-                        self.config.position,
+                        position,
                         self.config.appearance.style,
+                        self.config.appearance.menu.margin,
                         animated_opacity,
-                        self.config.appearance.menu.backdrop,
+                        bar_backdrop,
+                        key_hints,
                         Message::None,
                         Message::CloseMenu(id)
                     ),
                     Some((MenuType::Calendar, button_ui_ref)) => menu_wrapper(
                         id,
-                        self.clock.menu_view().map(Message::Clock),
+                        self.clock
+                            .menu_view(self.config.clock.today)
+                            .map(Message::Clock),
+                        MenuSize::Medium,
+                        *button_ui_ref,
+                        position,
+                        self.config.appearance.style,
+                        self.config.appearance.menu.margin,
+                        animated_opacity,
+                        bar_backdrop,
+                        key_hints,
+                        Message::None,
+                        Message::CloseMenu(id)
+                    ),
+                    Some((MenuType::Debug, button_ui_ref)) => menu_wrapper(
+                        id,
+                        self.debug_menu_view(),
                         MenuSize::Medium,
                         *button_ui_ref,
-                        self.config.position,
+                        position,
                         self.config.appearance.style,
+                        self.config.appearance.menu.margin,
                         animated_opacity,
-                        self.config.appearance.menu.backdrop,
+                        bar_backdrop,
+                        key_hints,
                         Message::None,
                         Message::CloseMenu(id)
                     ),
                     None => Row::new().into()
                 }
             }
+            Some(HasOutput::Backdrop) => container(Space::new(Length::Fill, Length::Fill))
+                .style(menu_backdrop_style(backdrop))
+                .into(),
             None => Row::new().into()
         }
     }
@@ -1,24 +1,41 @@
 //! Distribute content horizontally.
+use hydebar_proto::config::EmptySections;
 use iced::{
     Alignment, Element, Event, Length, Padding, Pixels, Point, Rectangle, Size, Vector,
     advanced::{
         Clipboard, Shell, Widget,
         layout::{self, Layout, Limits, Node},
         mouse, overlay, renderer,
-        widget::{Operation, Tree}
+        widget::{Operation, Tree, tree}
     },
-    event
+    event, window
 };
 
 /// A container that distributes its contents horizontally.
 #[allow(missing_debug_implementations)]
 pub struct Centerbox<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer> {
-    spacing:     f32,
-    padding:     Padding,
-    width:       Length,
-    height:      Length,
-    align_items: Alignment,
-    children:    [Element<'a, Message, Theme, Renderer>; 3]
+    spacing:        f32,
+    padding:        Padding,
+    width:          Length,
+    height:         Length,
+    align_items:    Alignment,
+    children:       [Element<'a, Message, Theme, Renderer>; 3],
+    on_regions:     Option<Box<dyn Fn(Vec<Rectangle>) -> Message + 'a>>,
+    separators:     bool,
+    empty_sections: EmptySections
+}
+
+/// Thickness of a section separator line.
+const SEPARATOR_WIDTH: f32 = 1.0;
+
+/// Share of the bar height covered by a section separator line.
+const SEPARATOR_HEIGHT_RATIO: f32 = 0.6;
+
+/// Module bounds reported during the last redraw; `None` until the first
+/// redraw, so even an empty first layout is published.
+#[derive(Default)]
+struct State {
+    regions: Option<Vec<Rectangle>>
 }
 
 impl<'a, Message, Theme, Renderer> Centerbox<'a, Message, Theme, Renderer>
@@ -33,7 +50,10 @@ where
             width: Length::Shrink,
             height: Length::Shrink,
             align_items: Alignment::Start,
-            children
+            children,
+            on_regions: None,
+            separators: false,
+            empty_sections: EmptySections::default()
         }
     }
 
@@ -70,6 +90,86 @@ where
         self.align_items = align;
         self
     }
+
+    /// Draws a thin line between every two adjacent non-empty sections.
+    pub fn separators(mut self, separators: bool) -> Self {
+        self.separators = separators;
+        self
+    }
+
+    /// Sets how empty sections affect the placement of the others.
+    pub fn empty_sections(mut self, mode: EmptySections) -> Self {
+        self.empty_sections = mode;
+        self
+    }
+
+    /// Sets the message produced when the bounds of the modules inside the
+    /// [`Centerbox`] change.
+    ///
+    /// Each module of every section contributes one rectangle, so the
+    /// regions describe exactly the parts of the bar that render content.
+    pub fn on_regions_change(mut self, f: impl Fn(Vec<Rectangle>) -> Message + 'a) -> Self {
+        self.on_regions = Some(Box::new(f));
+        self
+    }
+}
+
+/// Horizontal centres of the separators between consecutive non-empty
+/// sections, so an empty section never leaves a dangling line.
+fn separator_positions(sections: &[Rectangle]) -> Vec<f32> {
+    let visible: Vec<_> = sections
+        .iter()
+        .filter(|section| section.width > 0.0)
+        .collect();
+
+    visible
+        .windows(2)
+        .map(|pair| (pair[0].x + pair[0].width + pair[1].x) / 2.0)
+        .collect()
+}
+
+/// Horizontal centre of the middle section for the given section `widths`,
+/// bar `width` (padding excluded), `spacing` and `padding`.
+fn center_position(
+    mode: EmptySections,
+    widths: [f32; 3],
+    width: f32,
+    spacing: f32,
+    padding: Padding
+) -> f32 {
+    let [left, center, right] = widths;
+
+    match mode {
+        EmptySections::Collapse => {
+            let gap = |section: f32| if section > 0.0 { spacing } else { 0.0 };
+            let start = padding.left + left + gap(left);
+            let end = padding.left + width - right - gap(right);
+
+            (start + end) / 2.0
+        }
+        EmptySections::Centered | EmptySections::Proportional => {
+            let available = width - spacing * 2.0;
+            let half_available = available / 2.0;
+            let half_center_width = center / 2.0;
+            let pushed = half_available - left < half_center_width
+                || half_available - right < half_center_width;
+
+            // Proportional sections never exceed their third, so only the
+            // default mode lets a long side push the center off the middle.
+            if mode == EmptySections::Centered && pushed {
+                padding.left + spacing + left + (available - left - right) / 2.0
+            } else {
+                width / 2. + padding.horizontal() / 2.0
+            }
+        }
+    }
+}
+
+fn module_regions(layout: Layout<'_>) -> Vec<Rectangle> {
+    layout
+        .children()
+        .flat_map(|section| section.children().map(|module| module.bounds()))
+        .collect()
 }
 
 impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
@@ -77,6 +177,14 @@ impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
 where
     Renderer: iced::advanced::Renderer
 {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
     fn children(&self) -> Vec<Tree> {
         self.children.iter().map(Tree::new).collect()
     }
@@ -119,6 +227,10 @@ where
             Length::Shrink => 0.0,
             _ => available.max(0.0)
         };
+        let section_width = match self.empty_sections {
+            EmptySections::Proportional => remaining / 3.0,
+            EmptySections::Centered | EmptySections::Collapse => f32::INFINITY
+        };
 
         let mut calculate_edge_layout =
             |i: usize, (child, tree): (&Element<'a, Message, Theme, Renderer>, &mut Tree)| {
@@ -129,7 +241,7 @@ where
                 };
 
                 let (max_width, max_height) = (
-                    remaining,
+                    remaining.min(section_width),
                     if fill_cross_factor != 0 {
                         cross
                     } else {
@@ -160,29 +272,33 @@ where
         ));
         nodes[2].align_mut(Alignment::End, self.align_items, Size::new(0.0, cross));
 
-        let half_available = available / 2.0;
-        let half_center_width = nodes[1].size().width / 2.0;
-
-        if half_available - nodes[0].size().width < half_center_width
-            || half_available - nodes[2].size().width < half_center_width
-        {
-            nodes[1].move_to_mut(Point::new(
-                self.padding.left
-                    + self.spacing
-                    + nodes[0].size().width
-                    + (available - nodes[0].size().width - nodes[2].size().width) / 2.0,
-                self.padding.top
-            ));
-        } else {
-            nodes[1].move_to_mut(Point::new(
-                limits.max().width / 2. + self.padding.horizontal() / 2.0,
-                self.padding.top
-            ));
-        }
+        let widths = [
+            nodes[0].size().width,
+            nodes[1].size().width,
+            nodes[2].size().width
+        ];
+
+        nodes[1].move_to_mut(Point::new(
+            center_position(
+                self.empty_sections,
+                widths,
+                limits.max().width,
+                self.spacing,
+                self.padding
+            ),
+            self.padding.top
+        ));
         nodes[1].align_mut(Alignment::Center, self.align_items, Size::new(0.0, cross));
 
-        let main =
-            nodes[0].size().width + nodes[1].size().width + nodes[2].size().width + total_spacing;
+        let spacing = match self.empty_sections {
+            EmptySections::Collapse => {
+                let filled = widths.iter().filter(|width| **width > 0.0).count();
+
+                self.spacing * filled.saturating_sub(1) as f32
+            }
+            EmptySections::Centered | EmptySections::Proportional => total_spacing
+        };
+        let main = widths.iter().sum::<f32>() + spacing;
 
         let (intrinsic_width, intrinsic_height) = (main, cross);
         let size = limits.resolve(
@@ -225,6 +341,18 @@ where
         shell: &mut Shell<'_, Message>,
         viewport: &Rectangle
     ) -> event::Status {
+        if let Some(on_regions) = &self.on_regions
+            && let Event::Window(window::Event::RedrawRequested(_)) = event
+        {
+            let state = tree.state.downcast_mut::<State>();
+            let regions = module_regions(layout);
+
+            if state.regions.as_ref() != Some(&regions) {
+                state.regions = Some(regions.clone());
+                shell.publish(on_regions(regions));
+            }
+        }
+
         self.children
             .iter_mut()
             .zip(&mut tree.children)
@@ -286,6 +414,27 @@ where
                     .as_widget()
                     .draw(state, renderer, theme, style, layout, cursor, &viewport);
             }
+
+            if self.separators {
+                let bounds = layout.bounds();
+                let sections: Vec<_> = layout.children().map(|section| section.bounds()).collect();
+                let height = bounds.height * SEPARATOR_HEIGHT_RATIO;
+
+                for x in separator_positions(&sections) {
+                    renderer.fill_quad(
+                        renderer::Quad {
+                            bounds: Rectangle {
+                                x: x - SEPARATOR_WIDTH / 2.0,
+                                y: bounds.y + (bounds.height - height) / 2.0,
+                                width: SEPARATOR_WIDTH,
+                                height
+                            },
+                            ..renderer::Quad::default()
+                        },
+                        style.text_color.scale_alpha(0.3)
+                    );
+                }
+            }
         }
     }
 
@@ -311,3 +460,78 @@ where
         Self::new(row)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(x: f32, width: f32) -> Rectangle {
+        Rectangle {
+            x,
+            y: 0.0,
+            width,
+            height: 30.0
+        }
+    }
+
+    #[test]
+    fn collapse_centers_between_non_empty_sections() {
+        let widths = [0.0, 100.0, 200.0];
+
+        assert_eq!(
+            center_position(EmptySections::Centered, widths, 1000.0, 4.0, Padding::ZERO),
+            500.0
+        );
+        assert_eq!(
+            center_position(EmptySections::Collapse, widths, 1000.0, 4.0, Padding::ZERO),
+            398.0
+        );
+    }
+
+    #[test]
+    fn centered_mode_lets_a_long_side_push_the_center() {
+        let widths = [700.0, 100.0, 0.0];
+
+        assert_eq!(
+            center_position(EmptySections::Centered, widths, 1000.0, 4.0, Padding::ZERO),
+            850.0
+        );
+        assert_eq!(
+            center_position(
+                EmptySections::Proportional,
+                widths,
+                1000.0,
+                4.0,
+                Padding::ZERO
+            ),
+            500.0
+        );
+    }
+
+    #[test]
+    fn separators_sit_between_adjacent_sections() {
+        let positions = separator_positions(&[
+            section(0.0, 100.0),
+            section(200.0, 50.0),
+            section(400.0, 80.0)
+        ]);
+
+        assert_eq!(positions, vec![150.0, 325.0]);
+    }
+
+    #[test]
+    fn empty_sections_leave_no_dangling_separator() {
+        assert_eq!(
+            separator_positions(&[
+                section(0.0, 100.0),
+                section(250.0, 0.0),
+                section(400.0, 80.0)
+            ]),
+            vec![250.0]
+        );
+        assert!(
+            separator_positions(&[section(0.0, 0.0), section(250.0, 0.0), section(400.0, 80.0)])
+                .is_empty()
+        );
+    }
+}
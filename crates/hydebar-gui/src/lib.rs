@@ -5,7 +5,7 @@ mod views;
 
 pub mod app;
 
-pub use app::{App, Message};
+pub use app::{App, Message, restart_requested};
 
 pub fn get_log_spec(log_level: &str) -> LogSpecification {
     LogSpecification::env_or_parse(log_level).unwrap_or_else(|err| {
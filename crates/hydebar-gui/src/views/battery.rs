@@ -1,6 +1,6 @@
 /// Battery module view layer - Pure rendering, no business logic
 use hydebar_core::{
-    components::icons::icon,
+    components::icons::{Icons, icon, icon_with_label},
     config::BatteryModuleConfig,
     modules::battery::{BatteryData, IndicatorState}
 };
@@ -11,28 +11,25 @@ use iced::{
 
 use crate::app::Message;
 
-/// Render battery indicator for the bar
+/// Render battery indicator for the bar using `glyph`, which differs from
+/// the data icon while the charging animation runs.
 pub fn render_battery_indicator(
     data: &BatteryData,
+    glyph: Icons,
     config: &BatteryModuleConfig
 ) -> Element<'static, Message> {
-    let mut content = row![icon(data.icon.into())]
+    let label = config
+        .show_percentage
+        .then(|| text(config.percent.format(data.capacity)));
+    let content = icon_with_label(icon(glyph), label, config.icon_position)
         .align_y(Alignment::Center)
         .spacing(4);
 
-    if config.show_percentage {
-        content = content.push(text(format!("{}%", data.capacity)));
-    }
-
-    let indicator_state = data.indicator_state;
+    let indicator_state = IndicatorState::from_level(data.capacity, data.charging, &config.levels);
+    let colors = config.colors;
     container(content)
         .style(move |theme: &Theme| container::Style {
-            text_color: Some(match indicator_state {
-                IndicatorState::Success => theme.palette().success,
-                IndicatorState::Warning => theme.extended_palette().danger.weak.color,
-                IndicatorState::Danger => theme.palette().danger,
-                IndicatorState::Normal => theme.palette().text
-            }),
+            text_color: Some(indicator_state.color(&colors, theme)),
             ..Default::default()
         })
         .into()
@@ -51,6 +48,7 @@ pub fn render_power_profile(data: &BatteryData) -> Element<'static, Message> {
 /// Render complete battery widget (indicator + profile)
 pub fn render_battery(
     data: &BatteryData,
+    glyph: Icons,
     config: &BatteryModuleConfig
 ) -> Element<'static, Message> {
     let mut segments = vec![];
@@ -59,7 +57,7 @@ pub fn render_battery(
         segments.push(render_power_profile(data));
     }
 
-    segments.push(render_battery_indicator(data, config));
+    segments.push(render_battery_indicator(data, glyph, config));
 
     row(segments).align_y(Alignment::Center).spacing(4).into()
 }
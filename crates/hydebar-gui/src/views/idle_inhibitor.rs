@@ -0,0 +1,15 @@
+/// Idle inhibitor view layer - Pure rendering, no business logic
+use hydebar_core::{components::icons::icon, modules::idle_inhibitor::indicator_icon};
+use iced::{Element, Theme, widget::container};
+
+use crate::app::Message;
+
+/// Render the idle inhibitor indicator, highlighted while idle is inhibited.
+pub fn render_idle_inhibitor(inhibited: bool) -> Element<'static, Message> {
+    container(icon(indicator_icon(inhibited)))
+        .style(move |theme: &Theme| container::Style {
+            text_color: inhibited.then(|| theme.palette().primary),
+            ..Default::default()
+        })
+        .into()
+}
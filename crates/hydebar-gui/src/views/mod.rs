@@ -1,4 +1,6 @@
 pub mod battery;
+pub mod idle_inhibitor;
+pub mod tray;
 
 // TODO: Add other module views here as we refactor them
 // pub mod workspaces;
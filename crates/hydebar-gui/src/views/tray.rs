@@ -0,0 +1,142 @@
+/// Tray module view layer - Pure rendering, no business logic
+use hydebar_core::{
+    components::icons::{Icons, icon},
+    config::TrayModuleConfig,
+    menu::MenuType,
+    modules::tray::{TrayMessage, TrayModule},
+    position_button::{ButtonUIRef, position_button},
+    services::tray::{ItemActivation, StatusNotifierItem, TrayIcon},
+    style::ghost_button_style
+};
+use iced::{
+    Alignment, Element, Length,
+    widget::{Column, Row, button, image, mouse_area, svg, text},
+    window::Id
+};
+
+use crate::app::Message;
+
+/// Items per row of the overflow popover grid.
+const OVERFLOW_COLUMNS: usize = 4;
+
+/// Render one button per visible tray item, followed by a "···" button
+/// opening the overflow popover when `tray.max_visible` hides some.
+///
+/// Pressing an item toggles its menu and, unless `tray.scroll` is off,
+/// scrolling over it is forwarded to the item, which volume and network
+/// applets use for quick adjustments.
+/// Right and middle clicks call the item's `ContextMenu` and
+/// `SecondaryActivate` methods.
+pub fn render_tray<'a>(
+    tray: &'a TrayModule,
+    config: &TrayModuleConfig,
+    id: Id,
+    opacity: f32
+) -> Option<Element<'a, Message>> {
+    let (visible, overflow) = tray.arranged_items(config);
+    let scroll = config.scroll;
+
+    if visible.is_empty() && overflow.is_empty() {
+        return None;
+    }
+
+    let overflow_button = (!overflow.is_empty()).then(|| {
+        position_button(text("···"))
+            .on_press_with_position(move |button_ui_ref| {
+                Message::ToggleMenu(MenuType::TrayOverflow, id, button_ui_ref)
+            })
+            .padding([2, 2])
+            .height(Length::Fill)
+            .style(ghost_button_style(opacity))
+    });
+
+    Some(
+        Row::with_children(
+            visible
+                .into_iter()
+                .map(|item| {
+                    let name = item.name.as_str();
+
+                    let area = mouse_area(
+                        position_button(item_icon(item))
+                            .on_press_with_position(move |button_ui_ref| {
+                                Message::ToggleMenu(
+                                    MenuType::Tray(name.to_owned()),
+                                    id,
+                                    button_ui_ref
+                                )
+                            })
+                            .padding([2, 2])
+                            .height(Length::Fill)
+                            .style(ghost_button_style(opacity))
+                    )
+                    .on_right_press(Message::Tray(TrayMessage::Activate(
+                        name.to_owned(),
+                        ItemActivation::ContextMenu
+                    )))
+                    .on_middle_press(Message::Tray(TrayMessage::Activate(
+                        name.to_owned(),
+                        ItemActivation::Secondary
+                    )));
+
+                    if scroll {
+                        area.on_scroll(move |delta| {
+                            Message::Tray(TrayMessage::Scrolled(name.to_owned(), delta))
+                        })
+                        .into()
+                    } else {
+                        area.into()
+                    }
+                })
+                .collect::<Vec<_>>()
+        )
+        .push_maybe(overflow_button)
+        .height(Length::Shrink)
+        .align_y(Alignment::Center)
+        .spacing(8)
+        .into()
+    )
+}
+
+/// Render the overflow popover as a grid of the hidden tray items.
+///
+/// Items open their own menu in place of the popover, anchored to the same
+/// "···" button.
+pub fn render_tray_overflow<'a>(
+    tray: &'a TrayModule,
+    config: &TrayModuleConfig,
+    id: Id,
+    button_ui_ref: ButtonUIRef,
+    opacity: f32
+) -> Element<'a, Message> {
+    let (_, overflow) = tray.arranged_items(config);
+
+    Column::with_children(overflow.chunks(OVERFLOW_COLUMNS).map(|chunk| {
+        Row::with_children(chunk.iter().map(|item| {
+            button(item_icon(item))
+                .on_press(Message::ToggleMenu(
+                    MenuType::Tray(item.name.clone()),
+                    id,
+                    button_ui_ref
+                ))
+                .padding([8, 8])
+                .style(ghost_button_style(opacity))
+                .into()
+        }))
+        .spacing(8)
+        .into()
+    }))
+    .spacing(8)
+    .into()
+}
+
+fn item_icon<'a>(item: &StatusNotifierItem) -> Element<'a, Message> {
+    match &item.icon {
+        Some(TrayIcon::Image(handle)) => image(handle.clone()).height(Length::Fixed(14.)).into(),
+        Some(TrayIcon::Svg(handle)) => svg(handle.clone())
+            .height(Length::Fixed(16.))
+            .width(Length::Fixed(16.))
+            .into(),
+        None => icon(Icons::Point).into()
+    }
+}
@@ -8,27 +8,66 @@ mod validation;
 #[cfg(test)]
 mod themes_tests;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 pub use appearance::{
-    AnimationConfig, Appearance, AppearanceColor, AppearanceStyle, MenuAppearance
+    AnimationConfig, Appearance, AppearanceColor, AppearanceStyle, BackdropScope, EmptySections,
+    MenuAppearance, RefreshConfig
 };
+use hex_color::HexColor;
 pub use keybindings::{GlobalKeybindings, Keybindings, MenuKeybindings};
-pub use modules::{ModuleDef, ModuleName, Modules, Outputs, Position};
+pub use modules::{AuxiliaryBar, ModuleDef, ModuleName, Modules, Outputs, Position};
 use serde::Deserialize;
-pub use serde_helpers::RegexCfg;
+pub use serde_helpers::{AlarmTime, RegexCfg};
 use serde_with::serde_as;
-pub use themes::PresetTheme;
-pub use validation::ConfigValidationError;
+pub use themes::{PresetTheme, deserialize_theme_or_appearance};
+pub use validation::{ConfigValidationError, ConfigWarning};
 
 pub const DEFAULT_CONFIG_FILE_PATH: &str = "~/.config/hydebar/config.toml";
 
+/// Layer-shell layer the bar surfaces are placed on.
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum BarLayer {
+    Background,
+    /// Below regular windows' popups but above the wallpaper.
+    #[default]
+    Bottom,
+    Top,
+    /// Above everything, including fullscreen windows.
+    Overlay
+}
+
+/// Part of the bar surface that accepts pointer input.
+#[derive(Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum InputRegion {
+    /// The whole bar surface receives pointer input.
+    #[default]
+    Bar,
+    /// Only the area covered by modules receives pointer input; clicks on
+    /// empty parts of the bar pass through to the windows below.
+    Modules
+}
+
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct UpdatesModuleConfig {
     pub check_cmd:  String,
     pub update_cmd: String
 }
 
+/// Command whose output the command output module shows.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CommandOutputModuleConfig {
+    /// Shell command run on startup and then every `interval` seconds.
+    pub command:  String,
+    /// Seconds between runs.
+    #[serde(default = "default_command_output_interval")]
+    pub interval: u64
+}
+
+fn default_command_output_interval() -> u64 {
+    10
+}
+
 #[derive(Deserialize, Clone, Default, PartialEq, Eq, Debug)]
 pub enum WorkspaceVisibilityMode {
     #[default]
@@ -42,7 +81,14 @@ pub struct WorkspacesModuleConfig {
     pub visibility_mode:          WorkspaceVisibilityMode,
     #[serde(default)]
     pub enable_workspace_filling: bool,
-    pub max_workspaces:           Option<u32>
+    pub max_workspaces:           Option<u32>,
+    /// Scrolling over the module switches to the previous or next workspace.
+    #[serde(default)]
+    pub enable_scroll:            bool,
+    /// Labels or glyphs keyed by workspace id, shown instead of the name
+    /// Hyprland reports.
+    #[serde(default)]
+    pub name_map:                 HashMap<String, String>
 }
 
 #[derive(Deserialize, Clone, Default, PartialEq, Eq, Debug)]
@@ -57,7 +103,13 @@ pub struct WindowTitleConfig {
     #[serde(default)]
     pub mode: WindowTitleMode,
     #[serde(default = "default_truncate_title_after_length")]
-    pub truncate_title_after_length: u32
+    pub truncate_title_after_length: u32,
+    /// Prefix the title with a glyph showing whether the window is tiled,
+    /// floating or fullscreen.
+    #[serde(default)]
+    pub show_state: bool,
+    /// Font family for the title, overriding `appearance.font_name`.
+    pub font_name: Option<String>
 }
 
 #[derive(Deserialize, Clone, Default, Debug, PartialEq, Eq)]
@@ -66,6 +118,142 @@ pub struct KeyboardLayoutModuleConfig {
     pub labels: HashMap<String, String>
 }
 
+/// Options for the caps lock / num lock indicator.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LockKeysModuleConfig {
+    /// Keep inactive lock glyphs visible, dimmed, instead of hiding them.
+    #[serde(default)]
+    pub always_show:      bool,
+    /// Milliseconds between reads of the lock key state.
+    #[serde(default = "default_lock_keys_poll_interval")]
+    pub poll_interval_ms: u64
+}
+
+impl Default for LockKeysModuleConfig {
+    fn default() -> Self {
+        Self {
+            always_show:      false,
+            poll_interval_ms: default_lock_keys_poll_interval()
+        }
+    }
+}
+
+fn default_lock_keys_poll_interval() -> u64 {
+    1000
+}
+
+/// Look of the app launcher button.
+#[derive(Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct AppLauncherModuleConfig {
+    /// Glyph replacing the default launcher icon, such as a distribution
+    /// logo. An empty string leaves only the label.
+    #[serde(default)]
+    pub icon:  Option<String>,
+    /// Text shown after the icon.
+    #[serde(default)]
+    pub label: Option<String>
+}
+
+/// Options for the microphone mute toggle.
+#[derive(Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct MicModuleConfig {
+    /// Only show the module while the default source is muted.
+    #[serde(default)]
+    pub hide_unmuted: bool
+}
+
+/// Order in which tray items are laid out on the bar.
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum TrayOrder {
+    /// Items keep the order they registered in.
+    #[default]
+    Oldest,
+    /// The most recently registered item comes first.
+    Newest
+}
+
+/// Layout of the system tray.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TrayModuleConfig {
+    /// Items shown on the bar before the rest move into an overflow
+    /// popover; every item is shown when unset.
+    pub max_visible:   Option<usize>,
+    #[serde(default)]
+    pub order:         TrayOrder,
+    /// Seconds between re-reads of the registered items, for watchers that
+    /// do not announce them; items are only picked up from signals when
+    /// unset.
+    pub poll_interval: Option<u64>,
+    /// Forward the mouse wheel over an item to the item itself.
+    #[serde(default = "default_tray_scroll")]
+    pub scroll:        bool
+}
+
+impl Default for TrayModuleConfig {
+    fn default() -> Self {
+        Self {
+            max_visible:   None,
+            order:         TrayOrder::default(),
+            poll_interval: None,
+            scroll:        default_tray_scroll()
+        }
+    }
+}
+
+fn default_tray_scroll() -> bool {
+    true
+}
+
+/// Gap preset restored when the tiling layout module is clicked while gaps
+/// are zero. Values use Hyprland's `general:gaps_in`/`gaps_out` syntax.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TilingLayoutModuleConfig {
+    #[serde(default = "default_gaps_in")]
+    pub gaps_in:  String,
+    #[serde(default = "default_gaps_out")]
+    pub gaps_out: String
+}
+
+fn default_gaps_in() -> String {
+    "5".to_owned()
+}
+
+fn default_gaps_out() -> String {
+    "20".to_owned()
+}
+
+impl Default for TilingLayoutModuleConfig {
+    fn default() -> Self {
+        Self {
+            gaps_in:  default_gaps_in(),
+            gaps_out: default_gaps_out()
+        }
+    }
+}
+
+/// How the privacy module shows the resources in use.
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivacyDisplay {
+    /// One glyph per resource: screen, camera, microphone and location.
+    #[default]
+    Separate,
+    /// A single shield coloured by the most sensitive active resource.
+    Combined
+}
+
+#[derive(Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct PrivacyModuleConfig {
+    /// Command run when the screenshare indicator is clicked, for example to
+    /// pick a different source to share.
+    pub screenshare_cmd: Option<String>,
+    /// Show an indicator while GeoClue serves the location to an app.
+    #[serde(default)]
+    pub location:        bool,
+    #[serde(default)]
+    pub display:         PrivacyDisplay
+}
+
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct SystemInfoCpu {
     #[serde(default = "default_cpu_warn_threshold")]
@@ -105,14 +293,23 @@ pub struct SystemInfoTemperature {
     #[serde(default = "default_temp_warn_threshold")]
     pub warn_threshold:  i32,
     #[serde(default = "default_temp_alert_threshold")]
-    pub alert_threshold: i32
+    pub alert_threshold: i32,
+    /// Polling interval in seconds, falling back to the module interval.
+    #[serde(default)]
+    pub interval:        Option<u64>,
+    /// Label of the hwmon sensor to read, either its own label such as
+    /// `"Tctl"` or prefixed with the chip name such as `"acpitz temp1"`.
+    #[serde(default = "default_temp_sensor")]
+    pub sensor:          String
 }
 
 impl Default for SystemInfoTemperature {
     fn default() -> Self {
         Self {
             warn_threshold:  default_temp_warn_threshold(),
-            alert_threshold: default_temp_alert_threshold()
+            alert_threshold: default_temp_alert_threshold(),
+            interval:        None,
+            sensor:          default_temp_sensor()
         }
     }
 }
@@ -122,18 +319,72 @@ pub struct SystemInfoDisk {
     #[serde(default = "default_disk_warn_threshold")]
     pub warn_threshold:  u32,
     #[serde(default = "default_disk_alert_threshold")]
-    pub alert_threshold: u32
+    pub alert_threshold: u32,
+    /// Polling interval in seconds, falling back to the module interval.
+    #[serde(default)]
+    pub interval:        Option<u64>,
+    /// Mount points listed in the menu; every local disk when empty.
+    #[serde(default)]
+    pub mounts:          Vec<String>
 }
 
 impl Default for SystemInfoDisk {
     fn default() -> Self {
         Self {
             warn_threshold:  default_disk_warn_threshold(),
-            alert_threshold: default_disk_alert_threshold()
+            alert_threshold: default_disk_alert_threshold(),
+            interval:        None,
+            mounts:          Vec::new()
         }
     }
 }
 
+/// Base unit used when displaying network throughput.
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum ThroughputUnit {
+    #[default]
+    Bytes,
+    Bits
+}
+
+/// Magnitude used when displaying network throughput.
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum ThroughputScale {
+    /// Pick kilo, mega or giga depending on the current value.
+    #[default]
+    Auto,
+    Kilo,
+    Mega,
+    Giga
+}
+
+/// How download and upload rates are formatted.
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct ThroughputFormat {
+    #[serde(default)]
+    pub unit:        ThroughputUnit,
+    #[serde(default)]
+    pub scale:       ThroughputScale,
+    /// Number of decimal places shown for throughput values.
+    #[serde(default)]
+    pub precision:   u8,
+    /// Pad throughput values to a constant width so neighbouring modules do
+    /// not shift as the number of digits changes.
+    #[serde(default)]
+    pub fixed_width: bool
+}
+
+#[derive(Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct SystemInfoNetwork {
+    /// Polling interval in seconds for IP address and throughput readings,
+    /// falling back to the module interval.
+    #[serde(default)]
+    pub interval: Option<u64>,
+    /// Read from `unit`, `scale`, `precision` and `fixed_width`.
+    #[serde(flatten)]
+    pub format:   ThroughputFormat
+}
+
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub enum SystemIndicator {
     Cpu,
@@ -157,7 +408,20 @@ pub struct SystemModuleConfig {
     #[serde(default)]
     pub temperature: SystemInfoTemperature,
     #[serde(default)]
-    pub disk:        SystemInfoDisk
+    pub disk:        SystemInfoDisk,
+    #[serde(default)]
+    pub network:     SystemInfoNetwork,
+    /// Polling interval in seconds for CPU and memory usage and the default
+    /// for the other metrics.
+    #[serde(default = "default_system_interval")]
+    pub interval:    u64,
+    /// Number of decimal places shown for CPU and memory percentages.
+    #[serde(default)]
+    pub decimals:    u8,
+    /// Approximate number of samples averaged into CPU and memory readings.
+    /// Values of one or less disable smoothing.
+    #[serde(default)]
+    pub smoothing:   Option<u32>
 }
 
 fn default_system_indicators() -> Vec<SystemIndicator> {
@@ -168,6 +432,10 @@ fn default_system_indicators() -> Vec<SystemIndicator> {
     ]
 }
 
+fn default_system_interval() -> u64 {
+    5
+}
+
 fn default_cpu_warn_threshold() -> u32 {
     60
 }
@@ -192,6 +460,10 @@ fn default_temp_alert_threshold() -> i32 {
     80
 }
 
+fn default_temp_sensor() -> String {
+    "acpitz temp1".to_string()
+}
+
 fn default_disk_warn_threshold() -> u32 {
     80
 }
@@ -207,7 +479,11 @@ impl Default for SystemModuleConfig {
             cpu:         SystemInfoCpu::default(),
             memory:      SystemInfoMemory::default(),
             temperature: SystemInfoTemperature::default(),
-            disk:        SystemInfoDisk::default()
+            disk:        SystemInfoDisk::default(),
+            network:     SystemInfoNetwork::default(),
+            interval:    default_system_interval(),
+            decimals:    0,
+            smoothing:   None
         }
     }
 }
@@ -221,8 +497,96 @@ pub struct BatteryModuleConfig {
     pub show_power_profile:     bool,
     #[serde(default = "default_open_settings_on_click")]
     pub open_settings_on_click: bool,
+    /// Overrides the global `hide_when_unavailable` for this module.
+    pub hide_when_unavailable:  Option<bool>,
+    /// Text around the percentage, read from `label` and `suffix`.
+    #[serde(flatten)]
+    pub percent:                PercentLabel,
+    /// Whether the glyph goes before or after the percentage.
+    #[serde(default)]
+    pub icon_position:          IconPosition,
+    #[serde(default)]
+    pub levels:                 BatteryLevels,
+    #[serde(default)]
+    pub colors:                 BatteryColors,
+    /// Charge levels that raise a desktop notification once when the
+    /// discharging battery drops to them.
     #[serde(default)]
-    pub show_when_unavailable:  bool
+    pub warn_thresholds:        Vec<u8>
+}
+
+/// Charge levels at or below which the battery indicator turns to the
+/// warning and danger colors.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BatteryLevels {
+    #[serde(default = "default_battery_warning_level")]
+    pub warning: u8,
+    #[serde(default = "default_battery_danger_level")]
+    pub danger:  u8
+}
+
+impl Default for BatteryLevels {
+    fn default() -> Self {
+        Self {
+            warning: default_battery_warning_level(),
+            danger:  default_battery_danger_level()
+        }
+    }
+}
+
+fn default_battery_warning_level() -> u8 {
+    20
+}
+
+fn default_battery_danger_level() -> u8 {
+    10
+}
+
+/// Battery indicator colors; unset entries follow the theme palette.
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct BatteryColors {
+    pub normal:   Option<HexColor>,
+    pub warning:  Option<HexColor>,
+    pub danger:   Option<HexColor>,
+    /// Used while charging or full, whatever the level.
+    pub charging: Option<HexColor>
+}
+
+/// Side of its text an indicator glyph is drawn on.
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IconPosition {
+    #[default]
+    Before,
+    After
+}
+
+/// Text placed before and after a percentage readout, e.g. `BAT 87%`.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PercentLabel {
+    #[serde(default)]
+    pub label:  String,
+    #[serde(default = "default_percent_suffix")]
+    pub suffix: String
+}
+
+impl PercentLabel {
+    pub fn format(&self, value: impl std::fmt::Display) -> String {
+        format!("{}{value}{}", self.label, self.suffix)
+    }
+}
+
+impl Default for PercentLabel {
+    fn default() -> Self {
+        Self {
+            label:  String::new(),
+            suffix: default_percent_suffix()
+        }
+    }
+}
+
+fn default_percent_suffix() -> String {
+    "%".to_string()
 }
 
 impl Default for BatteryModuleConfig {
@@ -231,7 +595,12 @@ impl Default for BatteryModuleConfig {
             show_percentage:        default_show_percentage(),
             show_power_profile:     default_show_power_profile(),
             open_settings_on_click: default_open_settings_on_click(),
-            show_when_unavailable:  false
+            hide_when_unavailable:  None,
+            percent:                PercentLabel::default(),
+            icon_position:          IconPosition::default(),
+            levels:                 BatteryLevels::default(),
+            colors:                 BatteryColors::default(),
+            warn_thresholds:        Vec::new()
         }
     }
 }
@@ -250,20 +619,88 @@ fn default_open_settings_on_click() -> bool {
 
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct ClockModuleConfig {
-    pub format:       String,
+    pub format:          String,
+    #[serde(default)]
+    pub show_weather:    bool,
+    /// Font family for the clock, overriding `appearance.font_name`.
+    pub font_name:       Option<String>,
+    /// Commands run once when the local time reaches the given minute.
     #[serde(default)]
-    pub show_weather: bool
+    pub alarms:          Vec<ClockAlarm>,
+    /// chrono format used by the calendar menu's copy button; `%s` gives a
+    /// Unix timestamp.
+    #[serde(default = "default_clock_copy_format")]
+    pub copy_format:     String,
+    /// IANA zone names shown after local time, e.g. `"Asia/Tokyo"`.
+    #[serde(default)]
+    pub extra_timezones: Vec<String>,
+    /// chrono format for the extra timezones; defaults to `format`.
+    pub timezone_format: Option<String>,
+    /// Locale for the clock's names and AM/PM marker, overriding the
+    /// top-level `locale`.
+    pub locale:          Option<String>,
+    /// How the calendar menu marks today.
+    #[serde(default)]
+    pub today:           CalendarTodayConfig,
+    /// Command listing the events of the day picked in the calendar, which
+    /// it receives as `HYDEBAR_DATE`. One event per line, either
+    /// `HH:MM title` or just a title for all-day events.
+    pub agenda_cmd:      Option<String>,
+    /// Command run when the clock is right-clicked; left-click still opens
+    /// the calendar.
+    pub right_click_cmd: Option<String>
+}
+
+/// Way the calendar draws today's date.
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TodayStyle {
+    /// A colored border around the day.
+    #[default]
+    Outline,
+    /// The day's background in the marker color.
+    Filled,
+    /// Only the number in the marker color.
+    Text
+}
+
+/// Marker of today's date in the calendar menu.
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct CalendarTodayConfig {
+    #[serde(default)]
+    pub style: TodayStyle,
+    /// Marker color; the theme's primary color when unset.
+    pub color: Option<HexColor>
 }
 
 impl Default for ClockModuleConfig {
     fn default() -> Self {
         Self {
-            format:       "%a %d %b %R".to_string(),
-            show_weather: false
+            format:          "%a %d %b %R".to_string(),
+            show_weather:    false,
+            font_name:       None,
+            alarms:          Vec::new(),
+            copy_format:     default_clock_copy_format(),
+            extra_timezones: Vec::new(),
+            timezone_format: None,
+            locale:          None,
+            today:           CalendarTodayConfig::default(),
+            agenda_cmd:      None,
+            right_click_cmd: None
         }
     }
 }
 
+fn default_clock_copy_format() -> String {
+    "%Y-%m-%dT%H:%M:%S%:z".to_string()
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ClockAlarm {
+    pub time:    AlarmTime,
+    pub command: String
+}
+
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct WeatherModuleConfig {
     #[serde(default = "default_weather_location")]
@@ -330,22 +767,161 @@ pub struct SettingsModuleConfig {
     pub wifi_more_cmd:          Option<String>,
     pub vpn_more_cmd:           Option<String>,
     pub bluetooth_more_cmd:     Option<String>,
+    /// Submenu expanded whenever the settings menu opens.
+    pub default_submenu:        Option<SettingsSubmenu>,
     #[serde(default)]
     pub remove_airplane_btn:    bool,
     #[serde(default)]
-    pub remove_idle_btn:        bool
+    pub remove_idle_btn:        bool,
+    /// Hides the retry button shown next to an unavailable service.
+    #[serde(default)]
+    pub remove_retry_btn:       bool,
+    /// Ask for confirmation before running a power menu action.
+    #[serde(default)]
+    pub confirm_power_actions:  bool,
+    /// Overrides the global `hide_when_unavailable` for the bar indicators
+    /// and the Bluetooth toggle.
+    pub hide_when_unavailable:  Option<bool>,
+    #[serde(default)]
+    pub volume_icons:           VolumeIconsConfig,
+    #[serde(default)]
+    pub audio:                  SettingsAudioConfig,
+    #[serde(default)]
+    pub network:                SettingsNetworkConfig,
+    /// Shows the sink volume next to the speaker indicator when set.
+    pub volume:                 Option<PercentLabel>,
+    /// Shows the brightness next to its menu slider when set.
+    pub brightness:             Option<PercentLabel>,
+    /// Percentage the volume and brightness sliders snap to; `0` keeps them
+    /// continuous.
+    #[serde(default)]
+    pub slider_step:            u32,
+    /// Glyph placement for the battery and volume indicators on the bar.
+    #[serde(default)]
+    pub icon_position:          IconPosition
+}
+
+/// Expandable section of the settings menu.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingsSubmenu {
+    Power,
+    Sinks,
+    Sources,
+    Wifi,
+    Vpn,
+    Bluetooth
+}
+
+/// Network indicator of the settings module.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SettingsNetworkConfig {
+    /// Shows the download and upload rates of the active interface next to
+    /// the connection indicator.
+    #[serde(default)]
+    pub show_throughput:     bool,
+    /// Read from `unit`, `scale`, `precision` and `fixed_width`, as in
+    /// `[system.network]`.
+    #[serde(flatten)]
+    pub format:              ThroughputFormat,
+    /// Processes listed in the Wi-Fi menu by current TCP traffic, read from
+    /// `ss` while the menu is open; `0` leaves the list and the tool off.
+    #[serde(default)]
+    pub top_talkers:         usize,
+    /// Seconds a Wi-Fi scan result is reused before the device is scanned
+    /// again.
+    #[serde(default = "default_scan_cache_ttl_secs")]
+    pub scan_cache_ttl_secs: u64,
+    /// Command opening the captive portal sign-in page, run from the notice
+    /// shown while the network requires a login.
+    pub portal_cmd:          Option<String>
+}
+
+impl Default for SettingsNetworkConfig {
+    fn default() -> Self {
+        Self {
+            show_throughput:     false,
+            format:              ThroughputFormat::default(),
+            top_talkers:         0,
+            scan_cache_ttl_secs: default_scan_cache_ttl_secs(),
+            portal_cmd:          None
+        }
+    }
+}
+
+fn default_scan_cache_ttl_secs() -> u64 {
+    10
+}
+
+/// Audio section of the settings menu.
+#[derive(Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct SettingsAudioConfig {
+    /// Keeps the sink and source lists open whenever there is more than one
+    /// device to pick from, instead of behind the arrow button.
+    #[serde(default)]
+    pub auto_expand_devices: bool
+}
+
+/// Breakpoints and glyph overrides for the speaker indicator. A level applies
+/// when the volume is strictly above its percentage; glyphs left unset use
+/// the built-in icons.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VolumeIconsConfig {
+    #[serde(default = "default_volume_medium_above")]
+    pub medium_above: u32,
+    #[serde(default = "default_volume_high_above")]
+    pub high_above:   u32,
+    pub muted:        Option<String>,
+    pub silent:       Option<String>,
+    pub low:          Option<String>,
+    pub medium:       Option<String>,
+    pub high:         Option<String>
+}
+
+fn default_volume_medium_above() -> u32 {
+    33
+}
+
+fn default_volume_high_above() -> u32 {
+    66
+}
+
+impl Default for VolumeIconsConfig {
+    fn default() -> Self {
+        Self {
+            medium_above: default_volume_medium_above(),
+            high_above:   default_volume_high_above(),
+            muted:        None,
+            silent:       None,
+            low:          None,
+            medium:       None,
+            high:         None
+        }
+    }
 }
 
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct MediaPlayerModuleConfig {
     #[serde(default = "default_media_player_max_title_length")]
-    pub max_title_length: u32
+    pub max_title_length: u32,
+    /// Adds a Stop button next to the transport controls.
+    #[serde(default)]
+    pub show_stop:        bool,
+    /// Adds a shuffle toggle for players exposing `Shuffle`.
+    #[serde(default)]
+    pub show_shuffle:     bool,
+    /// Adds a repeat mode button for players exposing `LoopStatus`.
+    #[serde(default)]
+    pub show_repeat:      bool
 }
 
 impl Default for MediaPlayerModuleConfig {
     fn default() -> Self {
         MediaPlayerModuleConfig {
-            max_title_length: default_media_player_max_title_length()
+            max_title_length: default_media_player_max_title_length(),
+            show_stop:        false,
+            show_shuffle:     false,
+            show_repeat:      false
         }
     }
 }
@@ -361,6 +937,10 @@ pub struct CustomModuleDef {
     pub command: String,
     #[serde(default)]
     pub icon:    Option<String>,
+    /// Directory `command` and `listen_cmd` start in, overriding
+    /// `command_cwd`.
+    #[serde(default)]
+    pub cwd:     Option<PathBuf>,
 
     /// yields json lines containing text, alt, (pot tooltip)
     pub listen_cmd: Option<String>,
@@ -370,52 +950,139 @@ pub struct CustomModuleDef {
     pub alert:      Option<RegexCfg> // .. appearance etc
 }
 
+/// Per-menu overrides of `menu_keyboard_focus`; unset entries fall back to
+/// the global setting.
+#[derive(Deserialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct MenuFocusConfig {
+    #[serde(default)]
+    pub updates:       Option<bool>,
+    #[serde(default)]
+    pub settings:      Option<bool>,
+    #[serde(default)]
+    pub tray:          Option<bool>,
+    #[serde(default)]
+    pub media_player:  Option<bool>,
+    #[serde(default)]
+    pub system_info:   Option<bool>,
+    #[serde(default)]
+    pub notifications: Option<bool>,
+    #[serde(default)]
+    pub screenshot:    Option<bool>,
+    #[serde(default)]
+    pub calendar:      Option<bool>
+}
+
 #[derive(Deserialize, Clone, Debug, PartialEq)]
 pub struct Config {
     #[serde(default = "default_log_level")]
-    pub log_level:           String,
+    pub log_level:             String,
+    #[serde(default)]
+    pub position:              Position,
     #[serde(default)]
-    pub position:            Position,
+    pub outputs:               Outputs,
     #[serde(default)]
-    pub outputs:             Outputs,
+    pub modules:               Modules,
     #[serde(default)]
-    pub modules:             Modules,
-    pub app_launcher_cmd:    Option<String>,
+    pub auxiliary_bars:        Vec<AuxiliaryBar>,
+    /// Exclusive zone reserved for the bar, independent of its drawn height.
+    /// Defaults to the bar height when unset.
+    pub reserved_height:       Option<u32>,
+    pub app_launcher_cmd:      Option<String>,
+    #[serde(default)]
+    pub app_launcher:          AppLauncherModuleConfig,
     #[serde(rename = "CustomModule", default)]
-    pub custom_modules:      Vec<CustomModuleDef>,
-    pub clipboard_cmd:       Option<String>,
+    pub custom_modules:        Vec<CustomModuleDef>,
+    pub clipboard_cmd:         Option<String>,
+    /// Shell and leading arguments every configured command is handed to,
+    /// such as `zsh -lc`. Commands run through `bash -c` when unset.
+    pub command_shell:         Option<String>,
+    /// Directory configured commands start in, the working directory of
+    /// hydebar when unset. A leading `~` stands for the home directory.
+    pub command_cwd:           Option<PathBuf>,
+    /// Milliseconds within which launching the same command again is
+    /// ignored, so a double click starts it once. `0` disables this.
+    #[serde(default = "default_command_debounce_ms")]
+    pub command_debounce_ms:   u64,
+    /// Locale such as `de_DE` used for month and day names. English when
+    /// unset.
+    pub locale:                Option<String>,
+    #[serde(default)]
+    pub updates:               Option<UpdatesModuleConfig>,
     #[serde(default)]
-    pub updates:             Option<UpdatesModuleConfig>,
+    pub command_output:        Option<CommandOutputModuleConfig>,
     #[serde(default)]
-    pub workspaces:          WorkspacesModuleConfig,
+    pub workspaces:            WorkspacesModuleConfig,
     #[serde(default)]
-    pub window_title:        WindowTitleConfig,
+    pub window_title:          WindowTitleConfig,
     #[serde(default)]
-    pub system:              SystemModuleConfig,
+    pub system:                SystemModuleConfig,
     #[serde(default)]
-    pub battery:             BatteryModuleConfig,
+    pub battery:               BatteryModuleConfig,
     #[serde(default)]
-    pub clock:               ClockModuleConfig,
+    pub clock:                 ClockModuleConfig,
     #[serde(default)]
-    pub settings:            SettingsModuleConfig,
+    pub settings:              SettingsModuleConfig,
     #[serde(default, deserialize_with = "themes::deserialize_theme_or_appearance")]
-    pub appearance:          Appearance,
+    pub appearance:            Appearance,
+    /// TOML or JSON file holding a preset name or a full appearance that
+    /// replaces `appearance`. Relative paths start at the config directory.
+    pub theme_file:            Option<PathBuf>,
+    #[serde(default)]
+    pub media_player:          MediaPlayerModuleConfig,
+    #[serde(default)]
+    pub keyboard_layout:       KeyboardLayoutModuleConfig,
+    #[serde(default)]
+    pub tiling_layout:         TilingLayoutModuleConfig,
+    #[serde(default)]
+    pub lock_keys:             LockKeysModuleConfig,
+    #[serde(default)]
+    pub mic:                   MicModuleConfig,
     #[serde(default)]
-    pub media_player:        MediaPlayerModuleConfig,
+    pub tray:                  TrayModuleConfig,
     #[serde(default)]
-    pub keyboard_layout:     KeyboardLayoutModuleConfig,
+    pub privacy:               PrivacyModuleConfig,
     #[serde(default)]
-    pub menu_keyboard_focus: bool,
+    pub menu_keyboard_focus:   bool,
     #[serde(default)]
-    pub keybindings:         Keybindings,
+    pub menu_focus:            MenuFocusConfig,
     #[serde(default)]
-    pub weather:             WeatherModuleConfig
+    pub dim_inactive_monitors: bool,
+    #[serde(default)]
+    pub input_region:          InputRegion,
+    #[serde(default)]
+    pub layer:                 BarLayer,
+    /// Hide modules and indicators whose backing service is unavailable
+    /// instead of showing stale data.
+    #[serde(default)]
+    pub hide_when_unavailable: bool,
+    #[serde(default)]
+    pub keybindings:           Keybindings,
+    /// Show a bug icon at the end of the bar opening a diagnostics menu.
+    #[serde(default)]
+    pub debug_menu:            bool,
+    /// Also stop clock, system info, media and weather updates while the bar
+    /// is frozen with `SIGRTMIN+2`.
+    #[serde(default)]
+    pub freeze_readouts:       bool,
+    /// Reverse every scroll action on the bar, for natural scrolling.
+    #[serde(default)]
+    pub invert_scroll:         bool,
+    /// Scale factors for individual outputs, keyed by output name. Outputs
+    /// without an entry use `appearance.scale_factor`.
+    #[serde(default, deserialize_with = "appearance::output_scale_deserializer")]
+    pub output_scale:          HashMap<String, f64>,
+    #[serde(default)]
+    pub weather:               WeatherModuleConfig
 }
 
 fn default_log_level() -> String {
     "warn".to_owned()
 }
 
+fn default_command_debounce_ms() -> u64 {
+    300
+}
+
 fn default_menu_keyboard_focus() -> bool {
     true
 }
@@ -427,26 +1094,105 @@ fn default_truncate_title_after_length() -> u32 {
 impl Default for Config {
     fn default() -> Self {
         Self {
-            log_level:           default_log_level(),
-            position:            Position::Top,
-            outputs:             Outputs::default(),
-            modules:             Modules::default(),
-            app_launcher_cmd:    None,
-            clipboard_cmd:       None,
-            updates:             None,
-            workspaces:          WorkspacesModuleConfig::default(),
-            window_title:        WindowTitleConfig::default(),
-            system:              SystemModuleConfig::default(),
-            battery:             BatteryModuleConfig::default(),
-            clock:               ClockModuleConfig::default(),
-            settings:            SettingsModuleConfig::default(),
-            appearance:          Appearance::default(),
-            media_player:        MediaPlayerModuleConfig::default(),
-            keyboard_layout:     KeyboardLayoutModuleConfig::default(),
-            custom_modules:      vec![],
-            menu_keyboard_focus: default_menu_keyboard_focus(),
-            keybindings:         Keybindings::default(),
-            weather:             WeatherModuleConfig::default()
+            log_level:             default_log_level(),
+            position:              Position::Top,
+            outputs:               Outputs::default(),
+            modules:               Modules::default(),
+            auxiliary_bars:        vec![],
+            reserved_height:       None,
+            app_launcher_cmd:      None,
+            app_launcher:          AppLauncherModuleConfig::default(),
+            clipboard_cmd:         None,
+            command_shell:         None,
+            command_cwd:           None,
+            command_debounce_ms:   default_command_debounce_ms(),
+            locale:                None,
+            updates:               None,
+            command_output:        None,
+            workspaces:            WorkspacesModuleConfig::default(),
+            window_title:          WindowTitleConfig::default(),
+            system:                SystemModuleConfig::default(),
+            battery:               BatteryModuleConfig::default(),
+            clock:                 ClockModuleConfig::default(),
+            settings:              SettingsModuleConfig::default(),
+            appearance:            Appearance::default(),
+            theme_file:            None,
+            media_player:          MediaPlayerModuleConfig::default(),
+            keyboard_layout:       KeyboardLayoutModuleConfig::default(),
+            tiling_layout:         TilingLayoutModuleConfig::default(),
+            lock_keys:             LockKeysModuleConfig::default(),
+            mic:                   MicModuleConfig::default(),
+            tray:                  TrayModuleConfig::default(),
+            privacy:               PrivacyModuleConfig::default(),
+            custom_modules:        vec![],
+            menu_keyboard_focus:   default_menu_keyboard_focus(),
+            menu_focus:            MenuFocusConfig::default(),
+            dim_inactive_monitors: false,
+            input_region:          InputRegion::default(),
+            layer:                 BarLayer::default(),
+            hide_when_unavailable: false,
+            keybindings:           Keybindings::default(),
+            debug_menu:            false,
+            freeze_readouts:       false,
+            invert_scroll:         false,
+            output_scale:          HashMap::new(),
+            weather:               WeatherModuleConfig::default()
         }
     }
 }
+
+impl Config {
+    /// Module layout of the bar at `index`, where `0` addresses the primary
+    /// bar and higher indices address [`Config::auxiliary_bars`] in order.
+    pub fn bar_modules(&self, index: usize) -> Option<&Modules> {
+        match index {
+            0 => Some(&self.modules),
+            index => self.auxiliary_bars.get(index - 1).map(|bar| &bar.modules)
+        }
+    }
+
+    /// Scale factor for the output called `output`, falling back to the
+    /// global `appearance.scale_factor`.
+    pub fn scale_factor_for(&self, output: Option<&str>) -> f64 {
+        output
+            .and_then(|output| self.output_scale.get(output))
+            .copied()
+            .unwrap_or(self.appearance.scale_factor)
+    }
+
+    /// Iterate over every configured module layout, primary bar first.
+    pub fn layouts(&self) -> impl Iterator<Item = &Modules> {
+        std::iter::once(&self.modules).chain(self.auxiliary_bars.iter().map(|bar| &bar.modules))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_label_defaults_to_a_bare_percentage() {
+        assert_eq!(PercentLabel::default().format(87), "87%");
+    }
+
+    #[test]
+    fn percent_label_wraps_the_value() {
+        let label = PercentLabel {
+            label:  "BAT ".to_owned(),
+            suffix: " pct".to_owned()
+        };
+
+        assert_eq!(label.format(87), "BAT 87 pct");
+        assert_eq!(label.format(4.5), "BAT 4.5 pct");
+    }
+
+    #[test]
+    fn percent_label_may_drop_the_suffix() {
+        let label = PercentLabel {
+            label:  String::new(),
+            suffix: String::new()
+        };
+
+        assert_eq!(label.format(100), "100");
+    }
+}
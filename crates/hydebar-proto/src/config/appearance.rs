@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use hex_color::HexColor;
 use iced::{Color, theme::palette};
 use serde::{Deserialize, Deserializer, de::Error as _};
@@ -91,24 +93,60 @@ pub enum AppearanceStyle {
     Gradient
 }
 
+/// How the left, center and right bar sections share space when some of them
+/// are empty.
+#[derive(Deserialize, Default, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum EmptySections {
+    /// Keep the center section at the middle of the bar.
+    #[default]
+    Centered,
+    /// Empty sections take no width or spacing, and the center section sits
+    /// halfway between the left and right ones.
+    Collapse,
+    /// Every section reserves a third of the bar, even when empty.
+    Proportional
+}
+
+/// Area darkened by the menu backdrop.
+#[derive(Deserialize, Default, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum BackdropScope {
+    /// Darken the bar and the menu surface.
+    #[default]
+    Bar,
+    /// Dim the whole output, bar included, through a dedicated surface
+    /// placed behind the menu.
+    Screen
+}
+
 /// Menu-specific appearance configuration.
 #[derive(Deserialize, Clone, Debug, PartialEq)]
 pub struct MenuAppearance {
     #[serde(deserialize_with = "opacity_deserializer", default = "default_opacity")]
-    pub opacity:  f32,
+    pub opacity:        f32,
     #[serde(default)]
-    pub backdrop: f32
+    pub backdrop:       f32,
+    #[serde(default)]
+    pub backdrop_scope: BackdropScope,
+    /// Smallest gap in pixels kept between a menu and the output edges.
+    #[serde(default = "default_menu_margin")]
+    pub margin:         f32
 }
 
 impl Default for MenuAppearance {
     fn default() -> Self {
         Self {
-            opacity:  default_opacity(),
-            backdrop: f32::default()
+            opacity:        default_opacity(),
+            backdrop:       f32::default(),
+            backdrop_scope: BackdropScope::default(),
+            margin:         default_menu_margin()
         }
     }
 }
 
+fn default_menu_margin() -> f32 {
+    8.
+}
+
 /// Animation configuration.
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct AnimationConfig {
@@ -130,6 +168,43 @@ impl Default for AnimationConfig {
     }
 }
 
+/// Cadence of the ticker that drains module events into the bar.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RefreshConfig {
+    /// Interval in milliseconds while events keep arriving.
+    #[serde(default = "default_refresh_fast_ms")]
+    pub fast_ms:        u64,
+    /// Interval in milliseconds once the bar went idle. Raised to `fast_ms`
+    /// when lower.
+    #[serde(default = "default_refresh_slow_ms")]
+    pub slow_ms:        u64,
+    /// Ticks without events before switching to the slow interval.
+    #[serde(default = "default_refresh_idle_threshold")]
+    pub idle_threshold: u8
+}
+
+impl Default for RefreshConfig {
+    fn default() -> Self {
+        Self {
+            fast_ms:        default_refresh_fast_ms(),
+            slow_ms:        default_refresh_slow_ms(),
+            idle_threshold: default_refresh_idle_threshold()
+        }
+    }
+}
+
+fn default_refresh_fast_ms() -> u64 {
+    100
+}
+
+fn default_refresh_slow_ms() -> u64 {
+    500
+}
+
+fn default_refresh_idle_threshold() -> u8 {
+    10
+}
+
 fn default_animations_enabled() -> bool {
     true
 }
@@ -160,6 +235,13 @@ pub struct Appearance {
     pub menu:                     MenuAppearance,
     #[serde(default)]
     pub animations:               AnimationConfig,
+    #[serde(default)]
+    pub refresh:                  RefreshConfig,
+    /// Draw a thin line between the left, center and right sections.
+    #[serde(default)]
+    pub section_separator:        bool,
+    #[serde(default)]
+    pub empty_sections:           EmptySections,
     #[serde(default = "default_background_color")]
     pub background_color:         AppearanceColor,
     #[serde(default = "default_primary_color")]
@@ -183,14 +265,34 @@ fn scale_factor_deserializer<'de, D>(deserializer: D) -> Result<f64, D::Error>
 where
     D: Deserializer<'de>
 {
-    let value = f64::deserialize(deserializer)?;
+    check_scale_factor(f64::deserialize(deserializer)?)
+}
 
+/// Per-output scale factors keyed by output name, each bounded like the
+/// global `scale_factor`.
+pub(super) fn output_scale_deserializer<'de, D>(
+    deserializer: D
+) -> Result<HashMap<String, f64>, D::Error>
+where
+    D: Deserializer<'de>
+{
+    let scales = HashMap::<String, f64>::deserialize(deserializer)?;
+
+    for (output, value) in &scales {
+        check_scale_factor::<D::Error>(*value)
+            .map_err(|err| D::Error::custom(format!("output '{output}': {err}")))?;
+    }
+
+    Ok(scales)
+}
+
+fn check_scale_factor<E: serde::de::Error>(value: f64) -> Result<f64, E> {
     if value <= 0.0 {
-        return Err(D::Error::custom("Scale factor must be greater than 0.0"));
+        return Err(E::custom("Scale factor must be greater than 0.0"));
     }
 
     if value > 2.0 {
-        return Err(D::Error::custom("Scale factor cannot be greater than 2.0"));
+        return Err(E::custom("Scale factor cannot be greater than 2.0"));
     }
 
     Ok(value)
@@ -282,6 +384,9 @@ impl Default for Appearance {
             opacity:                  default_opacity(),
             menu:                     MenuAppearance::default(),
             animations:               AnimationConfig::default(),
+            refresh:                  RefreshConfig::default(),
+            section_separator:        false,
+            empty_sections:           EmptySections::default(),
             background_color:         default_background_color(),
             primary_color:            default_primary_color(),
             secondary_color:          default_secondary_color(),
@@ -319,6 +424,19 @@ mod tests {
         assert!(err_large.to_string().contains("greater than 2.0"));
     }
 
+    #[test]
+    fn output_scale_deserializer_names_the_offending_output() {
+        #[derive(Debug, Deserialize)]
+        struct OutputScales {
+            #[serde(deserialize_with = "output_scale_deserializer")]
+            _scales: HashMap<String, f64>
+        }
+
+        let err = toml::from_str::<OutputScales>("_scales = { DP-1 = 1.5, HDMI-A-1 = 3.0 }")
+            .expect_err("scale factor > 2 should error");
+        assert!(err.to_string().contains("output 'HDMI-A-1'"));
+    }
+
     #[test]
     fn opacity_deserializer_rejects_invalid_values() {
         let err_negative: DeError = opacity_deserializer(F32Deserializer::<DeError>::new(-0.1))
@@ -4,19 +4,23 @@ use serde::Deserialize;
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Keybindings {
     #[serde(default = "default_enabled")]
-    pub enabled: bool,
+    pub enabled:    bool,
+    /// Show the available keys at the bottom of menus holding keyboard focus.
+    #[serde(default = "default_show_hints")]
+    pub show_hints: bool,
     #[serde(default)]
-    pub global:  GlobalKeybindings,
+    pub global:     GlobalKeybindings,
     #[serde(default)]
-    pub menu:    MenuKeybindings,
+    pub menu:       MenuKeybindings,
 }
 
 impl Default for Keybindings {
     fn default() -> Self {
         Self {
-            enabled: default_enabled(),
-            global:  GlobalKeybindings::default(),
-            menu:    MenuKeybindings::default(),
+            enabled:    default_enabled(),
+            show_hints: default_show_hints(),
+            global:     GlobalKeybindings::default(),
+            menu:       MenuKeybindings::default(),
         }
     }
 }
@@ -25,6 +29,10 @@ fn default_enabled() -> bool {
     true
 }
 
+fn default_show_hints() -> bool {
+    true
+}
+
 /// Global keybindings for hydebar navigation mode
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct GlobalKeybindings {
@@ -112,9 +120,10 @@ mod tests {
     #[test]
     fn keybindings_can_be_disabled() {
         let kb = Keybindings {
-            enabled: false,
-            global:  GlobalKeybindings::default(),
-            menu:    MenuKeybindings::default(),
+            enabled:    false,
+            show_hints: true,
+            global:     GlobalKeybindings::default(),
+            menu:       MenuKeybindings::default(),
         };
         assert!(!kb.enabled);
     }
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{collections::HashSet, fmt};
 
 use serde::{Deserialize, Deserializer, de::Error as _};
 
@@ -9,7 +9,18 @@ pub enum Position {
     #[default]
     Top,
     /// Render the bar at the bottom of the output.
-    Bottom
+    Bottom,
+    /// Render the bar as a vertical strip along the left edge.
+    Left,
+    /// Render the bar as a vertical strip along the right edge.
+    Right
+}
+
+impl Position {
+    /// Whether the bar runs along a side edge of the output.
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
 }
 
 /// Named module variants supported by the bar.
@@ -17,20 +28,25 @@ pub enum Position {
 pub enum ModuleName {
     AppLauncher,
     Updates,
+    CommandOutput,
     Clipboard,
     Workspaces,
     WindowTitle,
     SystemInfo,
     KeyboardLayout,
     KeyboardSubmap,
+    LockKeys,
+    TilingLayout,
     Tray,
     Clock,
     Battery,
     Privacy,
     Settings,
     MediaPlayer,
+    Mic,
     Notifications,
     Screenshot,
+    IdleInhibitor,
     Custom(String)
 }
 
@@ -55,20 +71,25 @@ impl<'de> Deserialize<'de> for ModuleName {
                 Ok(match value {
                     "AppLauncher" => ModuleName::AppLauncher,
                     "Updates" => ModuleName::Updates,
+                    "CommandOutput" => ModuleName::CommandOutput,
                     "Clipboard" => ModuleName::Clipboard,
                     "Workspaces" => ModuleName::Workspaces,
                     "WindowTitle" => ModuleName::WindowTitle,
                     "SystemInfo" => ModuleName::SystemInfo,
                     "KeyboardLayout" => ModuleName::KeyboardLayout,
                     "KeyboardSubmap" => ModuleName::KeyboardSubmap,
+                    "LockKeys" => ModuleName::LockKeys,
+                    "TilingLayout" => ModuleName::TilingLayout,
                     "Tray" => ModuleName::Tray,
                     "Clock" => ModuleName::Clock,
                     "Battery" => ModuleName::Battery,
                     "Privacy" => ModuleName::Privacy,
                     "Settings" => ModuleName::Settings,
                     "MediaPlayer" => ModuleName::MediaPlayer,
+                    "Mic" => ModuleName::Mic,
                     "Notifications" => ModuleName::Notifications,
                     "Screenshot" => ModuleName::Screenshot,
+                    "IdleInhibitor" => ModuleName::IdleInhibitor,
                     other => ModuleName::Custom(other.to_string())
                 })
             }
@@ -78,6 +99,17 @@ impl<'de> Deserialize<'de> for ModuleName {
     }
 }
 
+impl ModuleName {
+    /// Whether the module may be listed more than once in a layout.
+    ///
+    /// The launcher keeps no state and custom modules render the same output
+    /// wherever they are placed. Every other module owns a single state and
+    /// menu, so only its first occurrence is rendered.
+    pub fn allows_duplicates(&self) -> bool {
+        matches!(self, Self::AppLauncher | Self::Custom(_))
+    }
+}
+
 /// Layout definition describing which modules render in each region.
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(untagged)]
@@ -86,6 +118,16 @@ pub enum ModuleDef {
     Group(Vec<ModuleName>)
 }
 
+impl ModuleDef {
+    /// Modules of the definition in display order.
+    pub fn modules(&self) -> &[ModuleName] {
+        match self {
+            Self::Single(module) => std::slice::from_ref(module),
+            Self::Group(group) => group
+        }
+    }
+}
+
 /// Overall module layout configuration.
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Modules {
@@ -112,6 +154,34 @@ impl Default for Modules {
     }
 }
 
+impl Modules {
+    /// Modules listed again after their first occurrence anywhere in the
+    /// layout, skipping those that [allow
+    /// duplicates](ModuleName::allows_duplicates).
+    pub fn duplicates(&self) -> Vec<&ModuleName> {
+        let mut seen = HashSet::new();
+
+        self.left
+            .iter()
+            .chain(&self.center)
+            .chain(&self.right)
+            .flat_map(ModuleDef::modules)
+            .filter(|module| !module.allows_duplicates() && !seen.insert(*module))
+            .collect()
+    }
+}
+
+/// Additional bar rendered on every targeted output next to the primary bar.
+///
+/// Each auxiliary bar owns its placement and module layout as well as a
+/// dedicated menu surface, so menus opened from it anchor to that bar.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AuxiliaryBar {
+    #[serde(default)]
+    pub position: Position,
+    pub modules:  Modules
+}
+
 /// Output targeting configuration for module rendering.
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq, Default)]
 pub enum Outputs {
@@ -168,4 +238,23 @@ mod tests {
             .expect("custom variant");
         assert!(matches!(name, ModuleName::Custom(value) if value == "MyCustom"));
     }
+
+    #[test]
+    fn duplicates_skip_modules_that_may_repeat() {
+        let modules = Modules {
+            left:   vec![
+                ModuleDef::Single(ModuleName::Clock),
+                ModuleDef::Single(ModuleName::AppLauncher),
+            ],
+            center: vec![ModuleDef::Single(ModuleName::Custom("vpn".to_owned()))],
+            right:  vec![ModuleDef::Group(vec![
+                ModuleName::AppLauncher,
+                ModuleName::Custom("vpn".to_owned()),
+                ModuleName::Clock,
+                ModuleName::Battery,
+            ])]
+        };
+
+        assert_eq!(modules.duplicates(), [&ModuleName::Clock]);
+    }
 }
@@ -36,6 +36,34 @@ impl Deref for RegexCfg {
     }
 }
 
+/// Wall-clock minute written as `HH:MM` in 24-hour time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct AlarmTime {
+    pub hour:   u8,
+    pub minute: u8
+}
+
+impl TryFrom<String> for AlarmTime {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = || format!("invalid alarm time `{value}`, expected HH:MM");
+        let (hour, minute) = value.trim().split_once(':').ok_or_else(invalid)?;
+        let hour: u8 = hour.parse().map_err(|_| invalid())?;
+        let minute: u8 = minute.parse().map_err(|_| invalid())?;
+
+        if hour > 23 || minute > 59 {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            hour,
+            minute
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde::de::value::{Error as DeError, StrDeserializer};
@@ -62,4 +90,18 @@ mod tests {
 
         assert_eq!(hasher_a.finish(), hasher_b.finish());
     }
+
+    #[test]
+    fn alarm_time_parses_hours_and_minutes() {
+        let time = AlarmTime::try_from("07:30".to_string()).expect("valid time");
+        assert_eq!((time.hour, time.minute), (7, 30));
+        assert!(AlarmTime::try_from("9:05".to_string()).is_ok());
+    }
+
+    #[test]
+    fn alarm_time_rejects_out_of_range_values() {
+        for value in ["24:00", "12:60", "noon", "12"] {
+            assert!(AlarmTime::try_from(value.to_string()).is_err(), "{value}");
+        }
+    }
 }
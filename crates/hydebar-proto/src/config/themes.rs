@@ -2,7 +2,8 @@ use hex_color::HexColor;
 use serde::{Deserialize, Deserializer};
 
 use super::appearance::{
-    AnimationConfig, Appearance, AppearanceColor, AppearanceStyle, MenuAppearance
+    AnimationConfig, Appearance, AppearanceColor, AppearanceStyle, EmptySections, MenuAppearance,
+    RefreshConfig
 };
 
 #[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
@@ -18,7 +19,9 @@ pub enum PresetTheme {
     GruvboxLight,
     TokyoNight,
     TokyoNightStorm,
-    TokyoNightLight
+    TokyoNightLight,
+    SolarizedDark,
+    SolarizedLight
 }
 
 impl PresetTheme {
@@ -34,7 +37,9 @@ impl PresetTheme {
             Self::GruvboxLight => gruvbox_light(),
             Self::TokyoNight => tokyo_night(),
             Self::TokyoNightStorm => tokyo_night_storm(),
-            Self::TokyoNightLight => tokyo_night_light()
+            Self::TokyoNightLight => tokyo_night_light(),
+            Self::SolarizedDark => solarized_dark(),
+            Self::SolarizedLight => solarized_light()
         }
     }
 }
@@ -46,10 +51,14 @@ fn catppuccin_mocha() -> Appearance {
         style:                    AppearanceStyle::Islands,
         opacity:                  0.95,
         menu:                     MenuAppearance {
-            opacity:  0.95,
-            backdrop: 0.3
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
         },
         animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
         background_color:         AppearanceColor::Simple(HexColor::rgb(30, 30, 46)),
         primary_color:            AppearanceColor::Simple(HexColor::rgb(203, 166, 247)),
         secondary_color:          AppearanceColor::Simple(HexColor::rgb(137, 180, 250)),
@@ -81,10 +90,14 @@ fn catppuccin_macchiato() -> Appearance {
         style:                    AppearanceStyle::Islands,
         opacity:                  0.95,
         menu:                     MenuAppearance {
-            opacity:  0.95,
-            backdrop: 0.3
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
         },
         animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
         background_color:         AppearanceColor::Simple(HexColor::rgb(36, 39, 58)),
         primary_color:            AppearanceColor::Simple(HexColor::rgb(198, 160, 246)),
         secondary_color:          AppearanceColor::Simple(HexColor::rgb(138, 173, 244)),
@@ -116,10 +129,14 @@ fn catppuccin_frappe() -> Appearance {
         style:                    AppearanceStyle::Islands,
         opacity:                  0.95,
         menu:                     MenuAppearance {
-            opacity:  0.95,
-            backdrop: 0.3
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
         },
         animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
         background_color:         AppearanceColor::Simple(HexColor::rgb(48, 52, 70)),
         primary_color:            AppearanceColor::Simple(HexColor::rgb(202, 158, 230)),
         secondary_color:          AppearanceColor::Simple(HexColor::rgb(140, 170, 238)),
@@ -151,10 +168,14 @@ fn catppuccin_latte() -> Appearance {
         style:                    AppearanceStyle::Islands,
         opacity:                  0.95,
         menu:                     MenuAppearance {
-            opacity:  0.95,
-            backdrop: 0.3
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
         },
         animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
         background_color:         AppearanceColor::Simple(HexColor::rgb(239, 241, 245)),
         primary_color:            AppearanceColor::Simple(HexColor::rgb(136, 57, 239)),
         secondary_color:          AppearanceColor::Simple(HexColor::rgb(30, 102, 245)),
@@ -184,10 +205,14 @@ fn dracula() -> Appearance {
         style:                    AppearanceStyle::Islands,
         opacity:                  0.95,
         menu:                     MenuAppearance {
-            opacity:  0.95,
-            backdrop: 0.3
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
         },
         animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
         background_color:         AppearanceColor::Simple(HexColor::rgb(40, 42, 54)),
         primary_color:            AppearanceColor::Simple(HexColor::rgb(189, 147, 249)),
         secondary_color:          AppearanceColor::Simple(HexColor::rgb(139, 233, 253)),
@@ -213,10 +238,14 @@ fn nord() -> Appearance {
         style:                    AppearanceStyle::Islands,
         opacity:                  0.95,
         menu:                     MenuAppearance {
-            opacity:  0.95,
-            backdrop: 0.3
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
         },
         animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
         background_color:         AppearanceColor::Simple(HexColor::rgb(46, 52, 64)),
         primary_color:            AppearanceColor::Simple(HexColor::rgb(136, 192, 208)),
         secondary_color:          AppearanceColor::Simple(HexColor::rgb(129, 161, 193)),
@@ -242,10 +271,14 @@ fn gruvbox_dark() -> Appearance {
         style:                    AppearanceStyle::Islands,
         opacity:                  0.95,
         menu:                     MenuAppearance {
-            opacity:  0.95,
-            backdrop: 0.3
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
         },
         animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
         background_color:         AppearanceColor::Simple(HexColor::rgb(40, 40, 40)),
         primary_color:            AppearanceColor::Simple(HexColor::rgb(211, 134, 155)),
         secondary_color:          AppearanceColor::Simple(HexColor::rgb(131, 165, 152)),
@@ -271,10 +304,14 @@ fn gruvbox_light() -> Appearance {
         style:                    AppearanceStyle::Islands,
         opacity:                  0.95,
         menu:                     MenuAppearance {
-            opacity:  0.95,
-            backdrop: 0.3
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
         },
         animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
         background_color:         AppearanceColor::Simple(HexColor::rgb(251, 241, 199)),
         primary_color:            AppearanceColor::Simple(HexColor::rgb(157, 0, 6)),
         secondary_color:          AppearanceColor::Simple(HexColor::rgb(7, 102, 120)),
@@ -300,10 +337,14 @@ fn tokyo_night() -> Appearance {
         style:                    AppearanceStyle::Islands,
         opacity:                  0.95,
         menu:                     MenuAppearance {
-            opacity:  0.95,
-            backdrop: 0.3
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
         },
         animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
         background_color:         AppearanceColor::Simple(HexColor::rgb(26, 27, 38)),
         primary_color:            AppearanceColor::Simple(HexColor::rgb(187, 154, 247)),
         secondary_color:          AppearanceColor::Simple(HexColor::rgb(122, 162, 247)),
@@ -333,10 +374,14 @@ fn tokyo_night_storm() -> Appearance {
         style:                    AppearanceStyle::Islands,
         opacity:                  0.95,
         menu:                     MenuAppearance {
-            opacity:  0.95,
-            backdrop: 0.3
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
         },
         animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
         background_color:         AppearanceColor::Simple(HexColor::rgb(36, 40, 59)),
         primary_color:            AppearanceColor::Simple(HexColor::rgb(187, 154, 247)),
         secondary_color:          AppearanceColor::Simple(HexColor::rgb(122, 162, 247)),
@@ -366,10 +411,14 @@ fn tokyo_night_light() -> Appearance {
         style:                    AppearanceStyle::Islands,
         opacity:                  0.95,
         menu:                     MenuAppearance {
-            opacity:  0.95,
-            backdrop: 0.3
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
         },
         animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
         background_color:         AppearanceColor::Simple(HexColor::rgb(213, 214, 219)),
         primary_color:            AppearanceColor::Simple(HexColor::rgb(121, 94, 172)),
         secondary_color:          AppearanceColor::Simple(HexColor::rgb(52, 108, 197)),
@@ -390,6 +439,76 @@ fn tokyo_night_light() -> Appearance {
     }
 }
 
+fn solarized_dark() -> Appearance {
+    Appearance {
+        font_name:                None,
+        scale_factor:             1.0,
+        style:                    AppearanceStyle::Islands,
+        opacity:                  0.95,
+        menu:                     MenuAppearance {
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
+        },
+        animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
+        background_color:         AppearanceColor::Simple(HexColor::rgb(0, 43, 54)),
+        primary_color:            AppearanceColor::Simple(HexColor::rgb(38, 139, 210)),
+        secondary_color:          AppearanceColor::Simple(HexColor::rgb(42, 161, 152)),
+        success_color:            AppearanceColor::Simple(HexColor::rgb(133, 153, 0)),
+        danger_color:             AppearanceColor::Simple(HexColor::rgb(220, 50, 47)),
+        text_color:               AppearanceColor::Simple(HexColor::rgb(131, 148, 150)),
+        workspace_colors:         vec![
+            AppearanceColor::Simple(HexColor::rgb(38, 139, 210)),
+            AppearanceColor::Simple(HexColor::rgb(108, 113, 196)),
+            AppearanceColor::Simple(HexColor::rgb(211, 54, 130)),
+            AppearanceColor::Simple(HexColor::rgb(220, 50, 47)),
+            AppearanceColor::Simple(HexColor::rgb(203, 75, 22)),
+            AppearanceColor::Simple(HexColor::rgb(181, 137, 0)),
+            AppearanceColor::Simple(HexColor::rgb(133, 153, 0)),
+            AppearanceColor::Simple(HexColor::rgb(42, 161, 152)),
+        ],
+        special_workspace_colors: Some(vec![AppearanceColor::Simple(HexColor::rgb(203, 75, 22))])
+    }
+}
+
+fn solarized_light() -> Appearance {
+    Appearance {
+        font_name:                None,
+        scale_factor:             1.0,
+        style:                    AppearanceStyle::Islands,
+        opacity:                  0.95,
+        menu:                     MenuAppearance {
+            opacity: 0.95,
+            backdrop: 0.3,
+            ..MenuAppearance::default()
+        },
+        animations:               AnimationConfig::default(),
+        refresh:                  RefreshConfig::default(),
+        section_separator:        false,
+        empty_sections:           EmptySections::Centered,
+        background_color:         AppearanceColor::Simple(HexColor::rgb(253, 246, 227)),
+        primary_color:            AppearanceColor::Simple(HexColor::rgb(38, 139, 210)),
+        secondary_color:          AppearanceColor::Simple(HexColor::rgb(42, 161, 152)),
+        success_color:            AppearanceColor::Simple(HexColor::rgb(133, 153, 0)),
+        danger_color:             AppearanceColor::Simple(HexColor::rgb(220, 50, 47)),
+        text_color:               AppearanceColor::Simple(HexColor::rgb(101, 123, 131)),
+        workspace_colors:         vec![
+            AppearanceColor::Simple(HexColor::rgb(38, 139, 210)),
+            AppearanceColor::Simple(HexColor::rgb(108, 113, 196)),
+            AppearanceColor::Simple(HexColor::rgb(211, 54, 130)),
+            AppearanceColor::Simple(HexColor::rgb(220, 50, 47)),
+            AppearanceColor::Simple(HexColor::rgb(203, 75, 22)),
+            AppearanceColor::Simple(HexColor::rgb(181, 137, 0)),
+            AppearanceColor::Simple(HexColor::rgb(133, 153, 0)),
+            AppearanceColor::Simple(HexColor::rgb(42, 161, 152)),
+        ],
+        special_workspace_colors: Some(vec![AppearanceColor::Simple(HexColor::rgb(203, 75, 22))])
+    }
+}
+
 pub fn deserialize_theme_or_appearance<'de, D>(deserializer: D) -> Result<Appearance, D::Error>
 where
     D: Deserializer<'de>
@@ -95,6 +95,8 @@ fn all_themes_have_opacity() {
         PresetTheme::TokyoNight,
         PresetTheme::TokyoNightStorm,
         PresetTheme::TokyoNightLight,
+        PresetTheme::SolarizedDark,
+        PresetTheme::SolarizedLight,
     ];
 
     for theme in themes {
@@ -117,6 +119,8 @@ fn all_themes_have_menu_opacity() {
         PresetTheme::TokyoNight,
         PresetTheme::TokyoNightStorm,
         PresetTheme::TokyoNightLight,
+        PresetTheme::SolarizedDark,
+        PresetTheme::SolarizedLight,
     ];
 
     for theme in themes {
@@ -139,6 +143,8 @@ fn all_themes_have_scale_factor() {
         PresetTheme::TokyoNight,
         PresetTheme::TokyoNightStorm,
         PresetTheme::TokyoNightLight,
+        PresetTheme::SolarizedDark,
+        PresetTheme::SolarizedLight,
     ];
 
     for theme in themes {
@@ -264,6 +270,34 @@ fn tokyo_night_light_colors() {
     );
 }
 
+#[test]
+fn solarized_dark_has_correct_background() {
+    let appearance = PresetTheme::SolarizedDark.to_appearance();
+    assert_eq!(
+        appearance.background_color,
+        AppearanceColor::Simple(HexColor::rgb(0, 43, 54))
+    );
+}
+
+#[test]
+fn deserialize_solarized_by_kebab_case_name() {
+    assert_eq!(
+        ::toml::Value::String("solarized-dark".to_owned())
+            .try_into::<PresetTheme>()
+            .expect("known preset"),
+        PresetTheme::SolarizedDark
+    );
+}
+
+#[test]
+fn solarized_light_has_correct_background() {
+    let appearance = PresetTheme::SolarizedLight.to_appearance();
+    assert_eq!(
+        appearance.background_color,
+        AppearanceColor::Simple(HexColor::rgb(253, 246, 227))
+    );
+}
+
 #[test]
 fn all_themes_have_animations_enabled() {
     let themes = vec![
@@ -278,6 +312,8 @@ fn all_themes_have_animations_enabled() {
         PresetTheme::TokyoNight,
         PresetTheme::TokyoNightStorm,
         PresetTheme::TokyoNightLight,
+        PresetTheme::SolarizedDark,
+        PresetTheme::SolarizedLight,
     ];
 
     for theme in themes {
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use super::{Config, ModuleDef, ModuleName};
+use super::{Config, ModuleDef, ModuleName, Modules};
 
 /// Errors returned when validating a [`Config`].
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -35,6 +35,45 @@ impl std::fmt::Display for ConfigValidationError {
 
 impl std::error::Error for ConfigValidationError {}
 
+/// Problems that do not prevent a [`Config`] from being applied but are most
+/// likely not what the user intended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigWarning {
+    /// A module that keeps a single state is listed more than once in a bar
+    /// layout, so only its first occurrence is rendered.
+    DuplicateModule { name: ModuleName },
+
+    /// The idle refresh interval is shorter than the busy one and is raised
+    /// to match it.
+    SlowRefreshBelowFast { fast_ms: u64, slow_ms: u64 }
+}
+
+impl std::fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateModule {
+                name
+            } => {
+                write!(
+                    f,
+                    "module {:?} is listed more than once in a bar layout, only the first one is rendered",
+                    name
+                )
+            }
+            Self::SlowRefreshBelowFast {
+                fast_ms,
+                slow_ms
+            } => {
+                write!(
+                    f,
+                    "appearance.refresh.slow_ms ({}) is below fast_ms ({}), using {} for both",
+                    slow_ms, fast_ms, fast_ms
+                )
+            }
+        }
+    }
+}
+
 impl Config {
     /// Validates the configuration, ensuring module definitions are consistent.
     ///
@@ -72,13 +111,13 @@ impl Config {
             Ok(())
         };
 
-        for module_def in self
-            .modules
-            .left
-            .iter()
-            .chain(self.modules.center.iter())
-            .chain(self.modules.right.iter())
-        {
+        for module_def in self.layouts().flat_map(|modules| {
+            modules
+                .left
+                .iter()
+                .chain(modules.center.iter())
+                .chain(modules.right.iter())
+        }) {
             match module_def {
                 ModuleDef::Single(ModuleName::Custom(name)) => {
                     ensure_custom_module_exists(name)?;
@@ -96,18 +135,40 @@ impl Config {
 
         Ok(())
     }
+
+    /// Collects the [`ConfigWarning`]s of an otherwise valid configuration.
+    pub fn warnings(&self) -> Vec<ConfigWarning> {
+        let mut warnings: Vec<_> = self
+            .layouts()
+            .flat_map(Modules::duplicates)
+            .map(|name| ConfigWarning::DuplicateModule {
+                name: name.clone()
+            })
+            .collect();
+
+        let refresh = &self.appearance.refresh;
+        if refresh.slow_ms < refresh.fast_ms {
+            warnings.push(ConfigWarning::SlowRefreshBelowFast {
+                fast_ms: refresh.fast_ms,
+                slow_ms: refresh.slow_ms
+            });
+        }
+
+        warnings
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{super::CustomModuleDef, *};
-    use crate::config::Modules;
+    use crate::config::{AuxiliaryBar, Modules, Position};
 
     fn custom_module(name: &str) -> CustomModuleDef {
         CustomModuleDef {
             name:       name.to_owned(),
             command:    String::from("true"),
             icon:       None,
+            cwd:        None,
             listen_cmd: None,
             icons:      None,
             alert:      None
@@ -155,4 +216,68 @@ mod tests {
             ConfigValidationError::MissingCustomModule { ref name } if name == "bar"
         ));
     }
+
+    #[test]
+    fn validate_checks_auxiliary_bar_layouts() {
+        let config = Config {
+            auxiliary_bars: vec![AuxiliaryBar {
+                position: Position::Bottom,
+                modules:  Modules {
+                    left: vec![ModuleDef::Single(ModuleName::Custom("bar".to_owned()))],
+                    ..Default::default()
+                }
+            }],
+            ..Default::default()
+        };
+
+        let error = config
+            .validate()
+            .expect_err("expected missing module error");
+        assert!(matches!(
+            error,
+            ConfigValidationError::MissingCustomModule { ref name } if name == "bar"
+        ));
+    }
+
+    #[test]
+    fn warnings_report_duplicate_modules_per_layout() {
+        let config = Config {
+            modules: Modules {
+                left: vec![ModuleDef::Single(ModuleName::Clock)],
+                right: vec![ModuleDef::Single(ModuleName::Clock)],
+                ..Default::default()
+            },
+            // The default layout lists the clock too, but on another bar.
+            auxiliary_bars: vec![AuxiliaryBar {
+                position: Position::Bottom,
+                modules:  Modules::default()
+            }],
+            ..Default::default()
+        };
+
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.warnings(),
+            [ConfigWarning::DuplicateModule {
+                name: ModuleName::Clock
+            }]
+        );
+    }
+
+    #[test]
+    fn warnings_report_slow_refresh_below_fast() {
+        let mut config = Config::default();
+        assert!(config.warnings().is_empty());
+
+        config.appearance.refresh.fast_ms = 200;
+        config.appearance.refresh.slow_ms = 50;
+
+        assert_eq!(
+            config.warnings(),
+            [ConfigWarning::SlowRefreshBelowFast {
+                fast_ms: 200,
+                slow_ms: 50
+            }]
+        );
+    }
 }
@@ -141,7 +141,9 @@ pub struct HyprlandMonitorInfo {
     /// Human readable monitor name.
     pub name:                 String,
     /// ID of the special workspace focused on this monitor, if any.
-    pub special_workspace_id: Option<i32>
+    pub special_workspace_id: Option<i32>,
+    /// Whether the monitor currently has input focus.
+    pub focused:              bool
 }
 
 /// Metadata describing a Hyprland workspace.
@@ -159,13 +161,27 @@ pub struct HyprlandWorkspaceInfo {
     pub window_count: u16
 }
 
+/// How a Hyprland window is placed on its workspace.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HyprlandWindowState {
+    /// The window is managed by the tiling layout.
+    #[default]
+    Tiled,
+    /// The window floats above tiled windows.
+    Floating,
+    /// The window covers its monitor, maximized or fullscreen.
+    Fullscreen
+}
+
 /// Metadata describing the focused Hyprland window.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HyprlandWindowInfo {
     /// Window title provided by the client.
     pub title: String,
     /// Window class name.
-    pub class: String
+    pub class: String,
+    /// Placement of the window.
+    pub state: HyprlandWindowState
 }
 
 /// Snapshot of the keyboard state known to Hyprland.
@@ -179,6 +195,40 @@ pub struct HyprlandKeyboardState {
     pub active_submap:        Option<String>
 }
 
+/// Modifier locks engaged on the main keyboard.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HyprlandLockState {
+    pub caps_lock:   bool,
+    pub num_lock:    bool,
+    pub scroll_lock: bool
+}
+
+/// Snapshot of the tiling layout and gap settings known to Hyprland.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HyprlandLayoutState {
+    /// Active tiling layout, e.g. `dwindle` or `master`.
+    pub layout:   String,
+    /// Raw `general:gaps_in` value.
+    pub gaps_in:  String,
+    /// Raw `general:gaps_out` value.
+    pub gaps_out: String
+}
+
+impl HyprlandLayoutState {
+    /// Whether both inner and outer gaps are zero on every side.
+    ///
+    /// Gap values may list one number per side separated by spaces or commas;
+    /// values that fail to parse count as non-zero.
+    pub fn gaps_zero(&self) -> bool {
+        [&self.gaps_in, &self.gaps_out].into_iter().all(|value| {
+            value
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|side| !side.is_empty())
+                .all(|side| side.parse::<f64>().is_ok_and(|side| side == 0.))
+        })
+    }
+}
+
 /// Identifies a monitor for Hyprland dispatch calls.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum HyprlandMonitorSelector {
@@ -218,8 +268,9 @@ impl fmt::Display for HyprlandWorkspaceSelector {
 /// Events related to Hyprland windows.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum HyprlandWindowEvent {
-    /// The active window changed.
-    ActiveWindowChanged,
+    /// The active window changed, or the active window was floated, tiled or
+    /// made fullscreen. Carries the state of the now focused window, if any.
+    ActiveWindowChanged(Option<HyprlandWindowState>),
     /// A workspace focus change occurred.
     WorkspaceFocusChanged,
     /// A window was closed.
@@ -272,8 +323,8 @@ pub enum HyprlandKeyboardEvent {
 /// ```ignore
 /// use std::sync::Arc;
 /// use hydebar_proto::ports::hyprland::{
-///     HyprlandEventStream, HyprlandKeyboardEvent, HyprlandKeyboardState, HyprlandMonitorSelector,
-///     HyprlandPort, HyprlandWorkspaceEvent, HyprlandWorkspaceSelector, HyprlandWindowEvent,
+///     HyprlandEventStream, HyprlandKeyboardEvent, HyprlandKeyboardState, HyprlandLayoutState,
+///     HyprlandLockState, HyprlandMonitorSelector, HyprlandPort, HyprlandWorkspaceEvent, HyprlandWorkspaceSelector, HyprlandWindowEvent,
 /// };
 ///
 /// struct DummyPort;
@@ -322,9 +373,21 @@ pub enum HyprlandKeyboardEvent {
 ///         Err(HyprlandError::unsupported("keyboard_state"))
 ///     }
 ///
+///     fn lock_state(&self) -> Result<HyprlandLockState, HyprlandError> {
+///         Err(HyprlandError::unsupported("lock_state"))
+///     }
+///
 ///     fn switch_keyboard_layout(&self) -> Result<(), HyprlandError> {
 ///         Err(HyprlandError::unsupported("switch_keyboard_layout"))
 ///     }
+///
+///     fn layout_state(&self) -> Result<HyprlandLayoutState, HyprlandError> {
+///         Err(HyprlandError::unsupported("layout_state"))
+///     }
+///
+///     fn set_gaps(&self, _: &str, _: &str) -> Result<(), HyprlandError> {
+///         Err(HyprlandError::unsupported("set_gaps"))
+///     }
 /// }
 ///
 /// let port: Arc<dyn HyprlandPort> = Arc::new(DummyPort);
@@ -362,8 +425,23 @@ pub trait HyprlandPort: Send + Sync {
     /// Retrieve the current keyboard state, including layout metadata.
     fn keyboard_state(&self) -> Result<HyprlandKeyboardState, HyprlandError>;
 
+    /// Retrieve which modifier locks are engaged on the main keyboard.
+    fn lock_state(&self) -> Result<HyprlandLockState, HyprlandError>;
+
     /// Request Hyprland to switch to the next keyboard layout.
     fn switch_keyboard_layout(&self) -> Result<(), HyprlandError>;
+
+    /// Retrieve the active tiling layout and gap settings.
+    fn layout_state(&self) -> Result<HyprlandLayoutState, HyprlandError>;
+
+    /// Apply new inner and outer gap values.
+    fn set_gaps(&self, gaps_in: &str, gaps_out: &str) -> Result<(), HyprlandError>;
+
+    /// Whether Hyprland is running. Modules depending on it stay hidden and
+    /// start no listeners while it is not.
+    fn is_available(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -401,4 +479,17 @@ mod tests {
         let state_b = state_a.clone();
         assert_eq!(state_a, state_b);
     }
+
+    #[test]
+    fn layout_state_detects_zero_gaps() {
+        let mut state = HyprlandLayoutState {
+            layout:   "dwindle".into(),
+            gaps_in:  "0".into(),
+            gaps_out: "0 0 0 0".into()
+        };
+        assert!(state.gaps_zero());
+
+        state.gaps_out = "0,0,10,0".into();
+        assert!(!state.gaps_zero());
+    }
 }